@@ -0,0 +1,214 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use pyo3::exceptions::{PyRuntimeError, PyTypeError};
+use pyo3::prelude::*;
+
+use qoqo::convert_into_circuit;
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_backend::APIBackend;
+
+use crate::{convert_into_aws_device, AWSConfigWrapper, QuantumTaskWrapper, TaskMetadataWrapper};
+
+/// Converts an error of the backend into a Python RuntimeError.
+fn runtime_error(err: RoqoqoBackendError) -> PyErr {
+    PyRuntimeError::new_err(format!("{}", err))
+}
+
+/// Backend running qoqo circuits as quantum tasks on an AWS Braket device.
+///
+/// The methods ending in `_async` return awaitables running on a tokio runtime shared by all
+/// backends, so that asyncio applications can submit and await tasks without blocking the
+/// event loop. They have to be called from a running event loop.
+///
+/// Args:
+///     device: The AWS device the circuits are run on.
+///     config (AWSConfig): The configuration of the access to AWS.
+///
+/// Raises:
+///     TypeError: The device is not an AWS device.
+///     RuntimeError: The backend could not be started.
+#[pyclass(name = "APIBackend", module = "aws_backend")]
+#[derive(Debug)]
+pub struct APIBackendWrapper {
+    /// Internal storage of [roqoqo_for_braket_backend::APIBackend]
+    pub internal: Arc<APIBackend>,
+}
+
+#[pymethods]
+impl APIBackendWrapper {
+    /// Create a new APIBackend instance.
+    #[new]
+    #[pyo3(text_signature = "(device, config)")]
+    pub fn new(device: &Bound<PyAny>, config: AWSConfigWrapper) -> PyResult<Self> {
+        let device = convert_into_aws_device(device)?;
+        let internal = APIBackend::new(device, config.internal).map_err(runtime_error)?;
+        Ok(Self {
+            internal: Arc::new(internal),
+        })
+    }
+
+    /// Return the metadata of a quantum task.
+    ///
+    /// Args:
+    ///     task_arn (str): The ARN of the task.
+    ///
+    /// Returns:
+    ///     TaskMetadata: The status, timestamps, shots, device and failure reason of the task.
+    ///
+    /// Raises:
+    ///     RuntimeError: The metadata could not be requested.
+    #[pyo3(text_signature = "(task_arn)")]
+    pub fn task_metadata(&self, py: Python, task_arn: &str) -> PyResult<TaskMetadataWrapper> {
+        let internal = py
+            .allow_threads(|| self.internal.task_metadata(task_arn))
+            .map_err(runtime_error)?;
+        Ok(TaskMetadataWrapper { internal })
+    }
+
+    /// Submit a circuit as a quantum task without waiting for its results.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to run.
+    ///
+    /// Returns:
+    ///     Awaitable[QuantumTask]: The handle of the submitted task.
+    ///
+    /// Raises:
+    ///     TypeError: Input is not a Circuit.
+    ///     RuntimeError: The circuit cannot be run on the device or the task could not be created.
+    #[pyo3(text_signature = "(circuit)")]
+    pub fn submit_async<'py>(
+        &self,
+        py: Python<'py>,
+        circuit: &Bound<PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let circuit = convert_into_circuit(circuit)
+            .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+        let backend = Arc::clone(&self.internal);
+        pyo3_asyncio_0_21::tokio::future_into_py(py, async move {
+            let internal = backend
+                .submit_async(&circuit)
+                .await
+                .map_err(runtime_error)?;
+            Ok(QuantumTaskWrapper { internal })
+        })
+    }
+
+    /// Request the status of a quantum task.
+    ///
+    /// Args:
+    ///     task (QuantumTask): The submitted task.
+    ///
+    /// Returns:
+    ///     Awaitable[str]: The status of the task, e.g. QUEUED or COMPLETED.
+    ///
+    /// Raises:
+    ///     RuntimeError: The status could not be requested.
+    #[pyo3(text_signature = "(task)")]
+    pub fn status_async<'py>(
+        &self,
+        py: Python<'py>,
+        task: QuantumTaskWrapper,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let backend = Arc::clone(&self.internal);
+        pyo3_asyncio_0_21::tokio::future_into_py(py, async move {
+            let status = backend
+                .status_async(&task.internal)
+                .await
+                .map_err(runtime_error)?;
+            Ok(status.to_string())
+        })
+    }
+
+    /// Wait for a quantum task and return its measured registers.
+    ///
+    /// Args:
+    ///     task (QuantumTask): The submitted task.
+    ///
+    /// Returns:
+    ///     Awaitable[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]], Dict[str, List[List[complex]]]]]: The bit, float and complex readout registers.
+    ///
+    /// Raises:
+    ///     RuntimeError: The task failed, was cancelled or did not finish within the timeout of the backend, or its results could not be downloaded.
+    #[pyo3(text_signature = "(task)")]
+    pub fn results_async<'py>(
+        &self,
+        py: Python<'py>,
+        task: QuantumTaskWrapper,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let backend = Arc::clone(&self.internal);
+        pyo3_asyncio_0_21::tokio::future_into_py(py, async move {
+            backend
+                .results_async(&task.internal)
+                .await
+                .map_err(runtime_error)
+        })
+    }
+
+    /// Run a circuit as a quantum task and wait for its measured registers.
+    ///
+    /// Args:
+    ///     circuit (Circuit): The circuit to run.
+    ///
+    /// Returns:
+    ///     Awaitable[Tuple[Dict[str, List[List[bool]]], Dict[str, List[List[float]]], Dict[str, List[List[complex]]]]]: The bit, float and complex readout registers.
+    ///
+    /// Raises:
+    ///     TypeError: Input is not a Circuit.
+    ///     RuntimeError: The circuit could not be run.
+    #[pyo3(text_signature = "(circuit)")]
+    pub fn run_circuit_async<'py>(
+        &self,
+        py: Python<'py>,
+        circuit: &Bound<PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let circuit = convert_into_circuit(circuit)
+            .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+        let backend = Arc::clone(&self.internal);
+        pyo3_asyncio_0_21::tokio::future_into_py(py, async move {
+            let task = backend
+                .submit_async(&circuit)
+                .await
+                .map_err(runtime_error)?;
+            backend.results_async(&task).await.map_err(runtime_error)
+        })
+    }
+
+    /// Request the metadata of a quantum task.
+    ///
+    /// Args:
+    ///     task_arn (str): The ARN of the task.
+    ///
+    /// Returns:
+    ///     Awaitable[TaskMetadata]: The status, timestamps, shots, device and failure reason of the task.
+    ///
+    /// Raises:
+    ///     RuntimeError: The metadata could not be requested.
+    #[pyo3(text_signature = "(task_arn)")]
+    pub fn task_metadata_async<'py>(
+        &self,
+        py: Python<'py>,
+        task_arn: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let backend = Arc::clone(&self.internal);
+        pyo3_asyncio_0_21::tokio::future_into_py(py, async move {
+            let internal = backend
+                .task_metadata_async(&task_arn)
+                .await
+                .map_err(runtime_error)?;
+            Ok(TaskMetadataWrapper { internal })
+        })
+    }
+}
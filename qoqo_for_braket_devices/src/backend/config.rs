@@ -0,0 +1,154 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pyo3::prelude::*;
+
+use roqoqo_for_braket_backend::{AWSConfig, AssumeRole};
+
+/// Configuration of the access to AWS.
+///
+/// The credentials are resolved by the default AWS credential chain: environment variables, the
+/// shared config and credentials files (with the default profile or the configured one) and the
+/// container or instance metadata. When a role is set with `set_assume_role`, these credentials
+/// are only used to assume the role. The region defaults to the region of the device.
+///
+/// Args:
+///     s3_bucket (str): The S3 bucket the task results are written to.
+///     s3_prefix (str): The key prefix of the task results in the S3 bucket.
+///     profile (Optional[str]): The profile of the shared AWS config files, None for the default profile.
+///     region (Optional[str]): The AWS region, None for the region of the device.
+///     endpoint_url (Optional[str]): The URL all requests are sent to, None for the AWS endpoints.
+#[pyclass(name = "AWSConfig", module = "aws_backend")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AWSConfigWrapper {
+    /// Internal storage of [roqoqo_for_braket_backend::AWSConfig]
+    pub internal: AWSConfig,
+}
+
+#[pymethods]
+impl AWSConfigWrapper {
+    /// Create a new AWSConfig instance.
+    #[new]
+    #[pyo3(signature = (s3_bucket, s3_prefix, profile=None, region=None, endpoint_url=None))]
+    pub fn new(
+        s3_bucket: &str,
+        s3_prefix: &str,
+        profile: Option<&str>,
+        region: Option<&str>,
+        endpoint_url: Option<&str>,
+    ) -> Self {
+        let mut internal = AWSConfig::new(s3_bucket, s3_prefix);
+        internal.set_profile(profile);
+        internal.set_region(region);
+        internal.set_endpoint_url(endpoint_url);
+        Self { internal }
+    }
+
+    /// The S3 bucket the task results are written to.
+    ///
+    /// Returns:
+    ///     str: The name of the bucket.
+    pub fn s3_bucket(&self) -> &str {
+        self.internal.s3_bucket()
+    }
+
+    /// The key prefix of the task results in the S3 bucket.
+    ///
+    /// Returns:
+    ///     str: The key prefix.
+    pub fn s3_prefix(&self) -> &str {
+        self.internal.s3_prefix()
+    }
+
+    /// The profile of the shared AWS config files the credentials are read from.
+    ///
+    /// Returns:
+    ///     Optional[str]: The name of the profile, None for the default profile.
+    pub fn profile(&self) -> Option<&str> {
+        self.internal.profile()
+    }
+
+    /// Set the profile of the shared AWS config files the credentials are read from.
+    ///
+    /// Args:
+    ///     profile (Optional[str]): The name of the profile, None for the default profile.
+    #[pyo3(text_signature = "(profile)")]
+    pub fn set_profile(&mut self, profile: Option<&str>) {
+        self.internal.set_profile(profile);
+    }
+
+    /// The configured AWS region.
+    ///
+    /// Returns:
+    ///     Optional[str]: The region, None when the region of the device is used.
+    pub fn region(&self) -> Option<&str> {
+        self.internal.region()
+    }
+
+    /// Set the AWS region of the requests.
+    ///
+    /// Args:
+    ///     region (Optional[str]): The region, None for the region of the device.
+    #[pyo3(text_signature = "(region)")]
+    pub fn set_region(&mut self, region: Option<&str>) {
+        self.internal.set_region(region);
+    }
+
+    /// The URL all requests are sent to instead of the AWS endpoints.
+    ///
+    /// Returns:
+    ///     Optional[str]: The URL, None when the AWS endpoints are used.
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.internal.endpoint_url()
+    }
+
+    /// Set the URL all requests are sent to, e.g. of moto, localstack or a proxy.
+    ///
+    /// Args:
+    ///     endpoint_url (Optional[str]): The URL, None for the AWS endpoints.
+    #[pyo3(text_signature = "(endpoint_url)")]
+    pub fn set_endpoint_url(&mut self, endpoint_url: Option<&str>) {
+        self.internal.set_endpoint_url(endpoint_url);
+    }
+
+    /// The IAM role assumed for all requests.
+    ///
+    /// Returns:
+    ///     Optional[Tuple[str, Optional[str], str]]: The role ARN, external ID and session name, None when no role is assumed.
+    pub fn assume_role(&self) -> Option<(String, Option<String>, String)> {
+        self.internal.assume_role().map(|role| {
+            (
+                role.role_arn.clone(),
+                role.external_id.clone(),
+                role.session_name.clone(),
+            )
+        })
+    }
+
+    /// Set the IAM role assumed through AWS STS for all requests.
+    ///
+    /// Args:
+    ///     role_arn (Optional[str]): The ARN of the role, None to use the resolved credentials directly.
+    ///     external_id (Optional[str]): The external ID required by the trust policy of the role, if any.
+    ///     session_name (str): The name of the role session.
+    #[pyo3(signature = (role_arn, external_id=None, session_name="qoqo-for-braket"))]
+    pub fn set_assume_role(
+        &mut self,
+        role_arn: Option<&str>,
+        external_id: Option<&str>,
+        session_name: &str,
+    ) {
+        self.internal.set_assume_role(
+            role_arn.map(|role_arn| AssumeRole::new(role_arn, external_id, session_name)),
+        );
+    }
+}
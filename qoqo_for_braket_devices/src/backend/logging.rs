@@ -0,0 +1,56 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, Registry};
+
+/// Handle changing the level of the subscriber installed by [set_log_level].
+static LOG_LEVEL: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// Set the verbosity of the logs of the backends.
+///
+/// The submission, polling and download of quantum tasks, the retries of AWS requests and the
+/// fetching of calibrations are logged to stderr. Logging is off until the level is set. When
+/// the application has installed its own tracing subscriber, the logs are sent to that
+/// subscriber and the level only applies once this function is called.
+///
+/// Args:
+///     level (str): The most verbose level that is logged: off, error, warn, info, debug or trace.
+///
+/// Raises:
+///     ValueError: The level is unknown.
+///     RuntimeError: The level could not be changed.
+#[pyfunction]
+#[pyo3(text_signature = "(level)")]
+pub fn set_log_level(level: &str) -> PyResult<()> {
+    let level = LevelFilter::from_str(level)
+        .map_err(|_| PyValueError::new_err(format!("Unknown log level {}", level)))?;
+    let handle = LOG_LEVEL.get_or_init(|| {
+        let (filter, handle) = reload::Layer::new(LevelFilter::OFF);
+        // Fails when a subscriber has already been installed, which then keeps receiving the
+        // events
+        let _ = tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt::layer().with_writer(std::io::stderr))
+            .try_init();
+        handle
+    });
+    handle
+        .modify(|filter| *filter = level)
+        .map_err(|err| PyRuntimeError::new_err(format!("{}", err)))
+}
@@ -0,0 +1,36 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod api_backend;
+pub use api_backend::*;
+
+mod config;
+pub use config::*;
+
+mod logging;
+pub use logging::*;
+
+mod task;
+pub use task::*;
+
+use pyo3::prelude::*;
+
+/// AWS Braket backend
+#[pymodule]
+pub fn aws_backend(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<APIBackendWrapper>()?;
+    m.add_class::<AWSConfigWrapper>()?;
+    m.add_class::<QuantumTaskWrapper>()?;
+    m.add_class::<TaskMetadataWrapper>()?;
+    m.add_function(wrap_pyfunction!(set_log_level, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use pyo3::prelude::*;
+
+use roqoqo_for_braket_backend::{QuantumTask, TaskMetadata};
+
+/// Converts a point in time into seconds since the Unix epoch.
+fn timestamp(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+/// Handle of a quantum task submitted to AWS Braket.
+///
+/// Returned by `APIBackend.submit_async` and passed to the other awaitable methods of the
+/// backend to request the status and the results of the task.
+#[pyclass(name = "QuantumTask", module = "aws_backend")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantumTaskWrapper {
+    /// Internal storage of [roqoqo_for_braket_backend::QuantumTask]
+    pub internal: QuantumTask,
+}
+
+#[pymethods]
+impl QuantumTaskWrapper {
+    /// The ARN of the task.
+    ///
+    /// Returns:
+    ///     str: The ARN of the task.
+    pub fn task_arn(&self) -> &str {
+        self.internal.task_arn()
+    }
+
+    /// The number of shots of the task.
+    ///
+    /// Returns:
+    ///     int: The number of shots.
+    pub fn shots(&self) -> usize {
+        self.internal.shots()
+    }
+}
+
+/// Metadata of a Braket quantum task.
+///
+/// The timestamps are seconds since the Unix epoch, e.g. for `datetime.fromtimestamp`.
+#[pyclass(name = "TaskMetadata", module = "aws_backend")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskMetadataWrapper {
+    /// Internal storage of [roqoqo_for_braket_backend::TaskMetadata]
+    pub internal: TaskMetadata,
+}
+
+#[pymethods]
+impl TaskMetadataWrapper {
+    /// The ARN of the task.
+    ///
+    /// Returns:
+    ///     str: The ARN of the task.
+    pub fn task_arn(&self) -> &str {
+        &self.internal.task_arn
+    }
+
+    /// The status of the task.
+    ///
+    /// Returns:
+    ///     str: The Braket status, e.g. QUEUED or COMPLETED.
+    pub fn status(&self) -> String {
+        self.internal.status.to_string()
+    }
+
+    /// The ARN of the device the task runs on.
+    ///
+    /// Returns:
+    ///     str: The ARN of the device.
+    pub fn device_arn(&self) -> &str {
+        &self.internal.device_arn
+    }
+
+    /// The number of shots of the task.
+    ///
+    /// Returns:
+    ///     int: The number of shots.
+    pub fn shots(&self) -> usize {
+        self.internal.shots
+    }
+
+    /// The time the task was created.
+    ///
+    /// Returns:
+    ///     float: The creation time in seconds since the Unix epoch.
+    pub fn created_at(&self) -> f64 {
+        timestamp(self.internal.created_at)
+    }
+
+    /// The time the task finished.
+    ///
+    /// Returns:
+    ///     Optional[float]: The end time in seconds since the Unix epoch, None while the task has not finished.
+    pub fn ended_at(&self) -> Option<f64> {
+        self.internal.ended_at.map(timestamp)
+    }
+
+    /// The reason the task failed.
+    ///
+    /// Returns:
+    ///     Optional[str]: The failure reason, None unless the task failed.
+    pub fn failure_reason(&self) -> Option<&str> {
+        self.internal.failure_reason.as_deref()
+    }
+}
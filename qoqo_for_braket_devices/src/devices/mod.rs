@@ -10,6 +10,8 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod shared;
+
 mod ionq_aria1;
 pub use ionq_aria1::*;
 
@@ -24,7 +26,72 @@ pub use rigetti_aspenm3::*;
 
 use qoqo_iqm::GarnetDeviceWrapper;
 
+use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
+use roqoqo::devices::QoqoDevice;
+use roqoqo_for_braket_devices::AWSDevice;
+
+/// Converts any of the AWS device wrappers into an AWSDevice.
+///
+/// # Arguments
+///
+/// * `device` - The Python device.
+///
+/// # Returns
+///
+/// * `Ok(AWSDevice)` - The converted device.
+/// * `Err(PyTypeError)` - The object is not an AWS device.
+pub fn convert_into_aws_device(device: &Bound<PyAny>) -> PyResult<AWSDevice> {
+    if let Ok(device) = device.extract::<IonQAria1DeviceWrapper>() {
+        Ok(device.internal.into())
+    } else if let Ok(device) = device.extract::<IonQHarmonyDeviceWrapper>() {
+        Ok(device.internal.into())
+    } else if let Ok(device) = device.extract::<OQCLucyDeviceWrapper>() {
+        Ok(device.internal.into())
+    } else if let Ok(device) = device.extract::<RigettiAspenM3DeviceWrapper>() {
+        Ok(device.internal.into())
+    } else {
+        Err(PyTypeError::new_err(
+            "Device is not one of the AWS devices of qoqo_for_braket_devices",
+        ))
+    }
+}
+
+/// Returns the representation of a device shown by the `__repr__` of the device wrappers.
+///
+/// A device counts as calibrated when decoherence rates are set for any of its qubits, e.g. from
+/// the calibration data published by AWS.
+///
+/// # Arguments
+///
+/// * `class_name` - The name of the Python class of the device.
+/// * `device` - The device.
+///
+/// # Returns
+///
+/// The name, region, number of qubits, native gates and calibration state of the device.
+pub(crate) fn device_repr(class_name: &str, device: AWSDevice) -> String {
+    let mut single_qubit_gates = device.single_qubit_gate_names();
+    single_qubit_gates.sort();
+    let mut two_qubit_gates = device.two_qubit_gate_names();
+    two_qubit_gates.sort();
+    let calibrated = (0..device.number_qubits()).any(|qubit| {
+        device
+            .qubit_decoherence_rates(&qubit)
+            .is_some_and(|rates| rates.iter().any(|rate| *rate != 0.0))
+    });
+    format!(
+        "{}(name={:?}, region={:?}, number_qubits={}, single_qubit_gates={:?}, \
+         two_qubit_gates={:?}, calibrated={})",
+        class_name,
+        device.clone().name(),
+        device.clone().region(),
+        device.number_qubits(),
+        single_qubit_gates,
+        two_qubit_gates,
+        if calibrated { "True" } else { "False" }
+    )
+}
 
 /// AWS Devices
 #[pymodule]
@@ -0,0 +1,2296 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Python methods shared by the AWS device wrappers.
+
+/// Items used by the methods generated with [device_wrapper].
+pub(crate) mod prelude {
+    pub(crate) use std::collections::HashMap;
+
+    pub(crate) use ndarray::Array2;
+    pub(crate) use numpy::{PyArray2, PyReadonlyArray2, ToPyArray};
+    pub(crate) use pyo3::exceptions::{PyNotImplementedError, PyTypeError, PyValueError};
+    pub(crate) use pyo3::prelude::*;
+    pub(crate) use pyo3::pyclass::CompareOp;
+    pub(crate) use pyo3::types::PyDict;
+
+    pub(crate) use bincode::deserialize;
+
+    pub(crate) use qoqo::devices::GenericDeviceWrapper;
+    pub(crate) use qoqo::noise_models::{
+        ContinuousDecoherenceModelWrapper, DecoherenceOnGateModelWrapper,
+        ImperfectReadoutModelWrapper,
+    };
+    pub(crate) use qoqo::{convert_into_circuit, CircuitWrapper};
+    pub(crate) use roqoqo::devices::QoqoDevice;
+    pub(crate) use roqoqo::measurements::PauliZProductInput;
+    pub(crate) use roqoqo::noise_models::NoiseModel;
+    pub(crate) use roqoqo_for_braket_devices::braket_ir::{
+        add_openqasm3_result_types, braket_inputs, braket_shots, circuit_from_jaqcd,
+        circuit_from_openqasm3, circuit_to_jaqcd, circuit_to_openqasm3,
+        circuit_to_openqasm3_simulator, circuit_to_openqasm3_verbatim, free_parameters,
+        pauli_z_product_result_types, MeasurementMapping,
+    };
+    pub(crate) use roqoqo_for_braket_devices::cutting::cut_circuit;
+    pub(crate) use roqoqo_for_braket_devices::routing::{
+        initial_layout, remap_to_best_qubits, route_circuit,
+    };
+    pub(crate) use roqoqo_for_braket_devices::transpile::{
+        optimize_virtual_z, transpile_with_rules, DecompositionRegistry,
+    };
+    pub(crate) use roqoqo_for_braket_devices::{
+        check_equivalence, decorate_circuit_with_noise, gate_report, AWSDevice, EdgeWeight,
+    };
+
+    pub(crate) use super::super::device_repr;
+}
+
+/// Implements the Python methods shared by all AWS device wrappers.
+///
+/// The wrapper has to be a `#[pyclass]` struct storing the device in its `internal` field, and the
+/// items of [prelude] have to be in scope where the macro is used. pyo3 only allows one
+/// `#[pymethods]` block per class, so the device-specific methods are passed to the macro and
+/// added to the same block. The macro also implements `from_pyany` and [Default] for the wrapper.
+///
+/// # Arguments
+///
+/// * `$wrapper` - The wrapper struct.
+/// * `$device` - The wrapped device, also the name of its [AWSDevice](prelude::AWSDevice) variant.
+/// * `$name` - The name of the Python class, used in the docstrings and error messages.
+/// * `$methods` - The device-specific methods.
+macro_rules! device_wrapper {
+    ($wrapper:ident, $device:ident, $name:literal, { $($methods:tt)* }) => {
+        #[pymethods]
+        impl $wrapper {
+            #[doc = concat!(" Create a new ", $name, " instance.")]
+            #[new]
+            pub fn new() -> Self {
+                Self {
+                    internal: $device::new(),
+                }
+            }
+
+            /// AWS's identifier.
+            ///
+            /// Returns:
+            ///     str: The AWS's identifier of the Device.
+            pub fn name(&self) -> &str {
+                roqoqo_for_braket_devices::$device::name(&self.internal)
+            }
+
+            /// The device's region.
+            ///
+            /// Returns:
+            ///     str: The region the device is defined on.
+            pub fn region(&self) -> &str {
+                roqoqo_for_braket_devices::$device::region(&self.internal)
+            }
+
+            /// The range of the number of shots of a task accepted by the device.
+            ///
+            /// Returns:
+            ///     Tuple[int, int]: The minimum and maximum number of shots.
+            pub fn shots_range(&self) -> (usize, usize) {
+                self.internal.shots_range()
+            }
+
+            /// Check whether the device accepts a task with the given number of shots.
+            ///
+            /// Args:
+            ///     shots (int): The requested number of shots.
+            ///
+            /// Raises:
+            ///     ValueError: The number of shots is outside of the range accepted by the device.
+            #[pyo3(text_signature = "(shots)")]
+            pub fn validate_shots(&self, shots: usize) -> PyResult<()> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.validate_shots(shots).map_err(|err| {
+                    PyValueError::new_err(format!("Invalid number of shots: {}", err))
+                })
+            }
+
+            /// Return the largest number of shots per task that fits into a budget.
+            ///
+            /// Every task costs the price per task of the device plus the price per shot for each
+            /// of its shots. The number of shots is limited to the maximum accepted by the device.
+            ///
+            /// Args:
+            ///     budget_usd (float): The budget in USD for all tasks.
+            ///     number_tasks (int): The number of tasks, each run with the same number of shots.
+            ///
+            /// Returns:
+            ///     Optional[int]: The number of shots per task, None if the budget does not
+            ///         cover the minimum number of shots.
+            #[pyo3(text_signature = "(budget_usd, number_tasks)")]
+            pub fn max_shots_within_budget(
+                &self,
+                budget_usd: f64,
+                number_tasks: usize,
+            ) -> Option<usize> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.max_shots_within_budget(budget_usd, number_tasks)
+            }
+
+            /// Whether the device supports OpenQASM 3 delay instructions.
+            ///
+            /// Returns:
+            ///     bool: True if PragmaSleep operations can be run on the device.
+            pub fn supports_delay(&self) -> bool {
+                self.internal.supports_delay()
+            }
+
+            /// Whether the device supports mid-circuit measurements.
+            ///
+            /// Returns:
+            ///     bool: True if gates can act on qubits after they have been measured.
+            pub fn supports_mid_circuit_measurement(&self) -> bool {
+                self.internal.supports_mid_circuit_measurement()
+            }
+
+            /// Whether the device supports classical feedforward.
+            ///
+            /// Returns:
+            ///     bool: True if PragmaConditional operations can be run on the device.
+            pub fn supports_feedforward(&self) -> bool {
+                self.internal.supports_feedforward()
+            }
+
+            /// Returns the gate time of a single qubit operation if the single qubit operation is
+            /// available on device.
+            ///
+            /// Args:
+            ///     hqslang[str]: The hqslang name of a single qubit gate.
+            ///     qubit[int]: The qubit the gate acts on.
+            ///
+            /// Returns:
+            ///     Option[float]: None if gate is not available.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(gate, qubit)")]
+            pub fn single_qubit_gate_time(&self, hqslang: &str, qubit: usize) -> Option<f64> {
+                self.internal.single_qubit_gate_time(hqslang, &qubit)
+            }
+
+            /// Set the gate time of a single qubit gate.
+            ///
+            /// Args:
+            ///     gate (str): hqslang name of the single-qubit-gate.
+            ///     qubit (int): The qubit for which the gate time is set.
+            ///     gate_time (float): The gate time for the given gate.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(gate, qubit, gate_time)")]
+            pub fn set_single_qubit_gate_time(
+                &mut self,
+                gate: &str,
+                qubit: usize,
+                gate_time: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_single_qubit_gate_time(gate, qubit, gate_time)
+                    .map_err(|err| PyValueError::new_err(format!("{:?}", err)))
+            }
+
+            /// Returns the names of a single qubit operations available on the device.
+            ///
+            /// Returns:
+            ///     List[str]: The list of gate names.
+            pub fn single_qubit_gate_names(&self) -> Vec<String> {
+                self.internal.single_qubit_gate_names()
+            }
+
+            /// Returns the gate time of a two qubit operation if the two qubit operation is
+            /// available on device.
+            ///
+            /// Args:
+            ///     hqslang[str]: The hqslang name of a single qubit gate.
+            ///     control[int]: The control qubit the gate acts on.
+            ///     target[int]: The target qubit the gate acts on.
+            ///
+            /// Returns:
+            ///     Option[float]: None if gate is not available.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            ///
+            #[pyo3(text_signature = "(gate, control, target)")]
+            pub fn two_qubit_gate_time(
+                &self,
+                hqslang: &str,
+                control: usize,
+                target: usize,
+            ) -> Option<f64> {
+                self.internal
+                    .two_qubit_gate_time(hqslang, &control, &target)
+            }
+
+            /// Set the gate time of a two qubit gate.
+            ///
+            /// Args:
+            ///     gate (str): hqslang name of the single-qubit-gate.
+            ///     control (int): The control qubit for which the gate time is set.
+            ///     target (int): The control qubit for which the gate time is set.
+            ///     gate_time (float): The gate time for the given gate.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(gate, control, target, gate_time)")]
+            pub fn set_two_qubit_gate_time(
+                &mut self,
+                gate: &str,
+                control: usize,
+                target: usize,
+                gate_time: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_two_qubit_gate_time(gate, control, target, gate_time)
+                    .map_err(|err| PyValueError::new_err(format!("{:?}", err)))
+            }
+
+            /// Returns the names of a two qubit operations available on the device.
+            ///
+            /// Returns:
+            ///     List[str]: The list of gate names.
+            pub fn two_qubit_gate_names(&self) -> Vec<String> {
+                self.internal.two_qubit_gate_names()
+            }
+
+            /// Returns the gate time of a three qubit operation if the three qubit operation is
+            /// available on device.
+            ///
+            /// Args:
+            ///     hqslang[str]: The hqslang name of a single qubit gate.
+            ///     control_0[int]: The control_0 qubit the gate acts on.
+            ///     control_1[int]: The control_1 qubit the gate acts on.
+            ///     target[int]: The target qubit the gate acts on.
+            ///
+            /// Returns:
+            ///     Option[float]: None if gate is not available.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            ///
+            #[pyo3(text_signature = "(gate, control_0, control_1, target")]
+            pub fn three_qubit_gate_time(
+                &self,
+                hqslang: &str,
+                control_0: usize,
+                control_1: usize,
+                target: usize,
+            ) -> Option<f64> {
+                self.internal
+                    .three_qubit_gate_time(hqslang, &control_0, &control_1, &target)
+            }
+
+            /// Returns the gate time of a multi qubit operation if the multi qubit operation is
+            /// available on device.
+            ///
+            /// Args:
+            ///     hqslang[str]: The hqslang name of a multi qubit gate.
+            ///     qubits[List[int]]: The qubits the gate acts on.
+            ///
+            /// Returns:
+            ///     Option[float]: None if gate is not available.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(gate, qubits)")]
+            pub fn multi_qubit_gate_time(&self, hqslang: &str, qubits: Vec<usize>) -> Option<f64> {
+                self.internal.multi_qubit_gate_time(hqslang, &qubits)
+            }
+
+            /// Returns the names of a mutli qubit operations available on the device.
+            ///
+            /// The list of names also includes the three qubit gate operations.
+            ///
+            /// Returns:
+            ///     List[str]: The list of gate names.
+            ///
+            pub fn multi_qubit_gate_names(&self) -> Vec<String> {
+                self.internal.multi_qubit_gate_names()
+            }
+
+            /// Return the matrix of the decoherence rates of the Lindblad equation.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the rate matrix M is returned.
+            ///
+            /// Returns:
+            ///     numpy.array: 3 by 3 numpy array of decoherence rates.
+            ///
+            #[pyo3(text_signature = "(qubit)")]
+            fn qubit_decoherence_rates(&self, qubit: usize) -> Py<PyArray2<f64>> {
+                Python::with_gil(|py| -> Py<PyArray2<f64>> {
+                    match self.internal.qubit_decoherence_rates(&qubit) {
+                        Some(matrix) => matrix.to_pyarray_bound(py).unbind().to_owned(),
+                        None => {
+                            let matrix = Array2::<f64>::zeros((3, 3));
+                            matrix.to_pyarray_bound(py).unbind().to_owned()
+                        }
+                    }
+                })
+            }
+
+            /// Set the decoherence rates of a qubit, replacing any previously set rates.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the rates are set.
+            ///     rates (numpy.array): 3 by 3 numpy array of decoherence rates.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device or rates are not a 3 by 3 matrix.
+            #[pyo3(text_signature = "(qubit, rates)")]
+            pub fn set_qubit_decoherence_rates(
+                &mut self,
+                qubit: usize,
+                rates: PyReadonlyArray2<f64>,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_qubit_decoherence_rates(qubit, rates.as_array().to_owned())
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set decoherence: {}", err))
+                    })
+            }
+
+            /// Adds single qubit damping to noise rates.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the decoherence is added.
+            ///     damping (float): The damping rates.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(qubit, damping)")]
+            pub fn add_damping(&mut self, qubit: usize, damping: f64) -> PyResult<()> {
+                self.internal.add_damping(qubit, damping).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                })
+            }
+
+            /// Adds single qubit dephasing to noise rates.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the decoherence is added.
+            ///     dephasing (float): The dephasing rates.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(qubit, dephasing)")]
+            pub fn add_dephasing(&mut self, qubit: usize, dephasing: f64) -> PyResult<()> {
+                self.internal
+                    .add_dephasing(qubit, dephasing)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                    })
+            }
+
+            /// Set the crosstalk strength between two qubits.
+            ///
+            /// Args:
+            ///     qubit_i (int): The qubit causing the crosstalk.
+            ///     qubit_j (int): The qubit affected by the crosstalk.
+            ///     strength (float): The crosstalk strength.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device or both qubits are the same.
+            #[pyo3(text_signature = "(qubit_i, qubit_j, strength)")]
+            pub fn set_crosstalk(
+                &mut self,
+                qubit_i: usize,
+                qubit_j: usize,
+                strength: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_crosstalk(qubit_i, qubit_j, strength)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot set crosstalk: {}", err)))
+            }
+
+            /// Return the crosstalk strength between two qubits.
+            ///
+            /// Args:
+            ///     qubit_i (int): The qubit causing the crosstalk.
+            ///     qubit_j (int): The qubit affected by the crosstalk.
+            ///
+            /// Returns:
+            ///     Option[float]: None if no crosstalk is set or a qubit is not in the device.
+            #[pyo3(text_signature = "(qubit_i, qubit_j)")]
+            pub fn crosstalk(&self, qubit_i: usize, qubit_j: usize) -> Option<f64> {
+                self.internal.crosstalk(qubit_i, qubit_j)
+            }
+
+            /// Return the crosstalk matrix of the device.
+            ///
+            /// Returns:
+            ///     Option[numpy.array]: number_qubits by number_qubits numpy array of crosstalk
+            ///         strengths, None if no crosstalk is set.
+            pub fn crosstalk_matrix(&self) -> Option<Py<PyArray2<f64>>> {
+                Python::with_gil(|py| -> Option<Py<PyArray2<f64>>> {
+                    self.internal
+                        .crosstalk_matrix()
+                        .map(|matrix| matrix.to_pyarray_bound(py).unbind())
+                })
+            }
+
+            /// Set the error rate of the two-qubit gates acting on an edge.
+            ///
+            /// Args:
+            ///     control (int): The first qubit of the edge.
+            ///     target (int): The second qubit of the edge.
+            ///     error_rate (float): The depolarizing error probability of a two-qubit gate
+            ///         on the edge.
+            ///
+            /// Raises:
+            ///     ValueError: The qubits are not connected or the error rate is not a probability.
+            #[pyo3(text_signature = "(control, target, error_rate)")]
+            pub fn set_two_qubit_error_rate(
+                &mut self,
+                control: usize,
+                target: usize,
+                error_rate: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_two_qubit_error_rate(control, target, error_rate)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set two-qubit error rate: {}", err))
+                    })
+            }
+
+            /// Return the error rate of the two-qubit gates acting on an edge.
+            ///
+            /// Args:
+            ///     control (int): The first qubit of the edge.
+            ///     target (int): The second qubit of the edge.
+            ///
+            /// Returns:
+            ///     Option[float]: The depolarizing error probability, None if no error rate is set.
+            #[pyo3(text_signature = "(control, target)")]
+            pub fn two_qubit_error_rate(&self, control: usize, target: usize) -> Option<f64> {
+                self.internal.two_qubit_error_rate(control, target)
+            }
+
+            /// Set the readout assignment error of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the readout error is set.
+            ///     prob_detect_0_as_1 (float): The probability to measure 1 when the qubit is
+            ///         in state 0.
+            ///     prob_detect_1_as_0 (float): The probability to measure 0 when the qubit is
+            ///         in state 1.
+            ///
+            /// Raises:
+            ///     ValueError: The qubit is not in the device or a probability is not between 0
+            ///         and 1.
+            #[pyo3(text_signature = "(qubit, prob_detect_0_as_1, prob_detect_1_as_0)")]
+            pub fn set_readout_error(
+                &mut self,
+                qubit: usize,
+                prob_detect_0_as_1: f64,
+                prob_detect_1_as_0: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_readout_error(qubit, prob_detect_0_as_1, prob_detect_1_as_0)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set readout error: {}", err))
+                    })
+            }
+
+            /// Return the readout assignment error of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the readout error is returned.
+            ///
+            /// Returns:
+            ///     Option[(float, float)]: The probabilities to detect 0 as 1 and to detect 1 as 0,
+            ///                             None if no readout error is set.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn readout_error(&self, qubit: usize) -> Option<(f64, f64)> {
+                self.internal.readout_error(qubit)
+            }
+
+            /// Set the state preparation error of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the preparation error is set.
+            ///     error (float): The probability to prepare the qubit in state 1 instead of
+            ///         state 0.
+            ///
+            /// Raises:
+            ///     ValueError: The qubit is not in the device or the error is not between 0 and 1.
+            #[pyo3(text_signature = "(qubit, error)")]
+            pub fn set_preparation_error(&mut self, qubit: usize, error: f64) -> PyResult<()> {
+                self.internal
+                    .set_preparation_error(qubit, error)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set preparation error: {}", err))
+                    })
+            }
+
+            /// Return the state preparation error of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the preparation error is returned.
+            ///
+            /// Returns:
+            ///     Option[float]: The probability to prepare the qubit in state 1 instead of
+            ///         state 0, None if no preparation error is set.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn preparation_error(&self, qubit: usize) -> Option<f64> {
+                self.internal.preparation_error(qubit)
+            }
+
+            /// Adds the same qubit damping to the noise rates of all qubits.
+            ///
+            /// Args:
+            ///     damping (float): The damping rate added to every qubit.
+            #[pyo3(text_signature = "(damping)")]
+            pub fn add_damping_all(&mut self, damping: f64) -> PyResult<()> {
+                self.internal.add_damping_all(damping).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                })
+            }
+
+            /// Adds the same qubit dephasing to the noise rates of all qubits.
+            ///
+            /// Args:
+            ///     dephasing (float): The dephasing rate added to every qubit.
+            #[pyo3(text_signature = "(dephasing)")]
+            pub fn add_dephasing_all(&mut self, dephasing: f64) -> PyResult<()> {
+                self.internal.add_dephasing_all(dephasing).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                })
+            }
+
+            /// Adds qubit damping to the noise rates of several qubits.
+            ///
+            /// Args:
+            ///     dampings (Dict[int, float]): The damping rates added to the qubits, keyed by
+            ///         qubit.
+            ///
+            /// Raises:
+            ///     PyValueError: A qubit is not in device.
+            #[pyo3(text_signature = "(dampings)")]
+            pub fn add_damping_rates(&mut self, dampings: HashMap<usize, f64>) -> PyResult<()> {
+                self.internal.add_damping_rates(&dampings).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                })
+            }
+
+            /// Adds qubit dephasing to the noise rates of several qubits.
+            ///
+            /// Args:
+            ///     dephasings (Dict[int, float]): The dephasing rates added to the qubits,
+            ///         keyed by qubit.
+            ///
+            /// Raises:
+            ///     PyValueError: A qubit is not in device.
+            #[pyo3(text_signature = "(dephasings)")]
+            pub fn add_dephasing_rates(&mut self, dephasings: HashMap<usize, f64>) -> PyResult<()> {
+                self.internal
+                    .add_dephasing_rates(&dephasings)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot add decoherence: {}", err))
+                    })
+            }
+
+            /// Remove the decoherence rates of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the decoherence rates are removed.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn clear_decoherence(&mut self, qubit: usize) -> PyResult<()> {
+                self.internal.clear_decoherence(qubit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot clear decoherence: {}", err))
+                })
+            }
+
+            /// Remove all noise from the device, keeping the gate times.
+            pub fn clear_all_noise(&mut self) {
+                self.internal.clear_all_noise()
+            }
+
+            /// Set the leakage rate of a qubit.
+            ///
+            /// The leakage rate is informational only. Noise models have no leakage channel, so
+            /// the rate is not part of the exported noise models, the noise added by
+            /// decorate_circuit_with_noise or the noise of simulations.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the leakage rate is set.
+            ///     leakage_rate (float): The rate with which population escapes the
+            ///         computational subspace.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device or the rate is negative.
+            #[pyo3(text_signature = "(qubit, leakage_rate)")]
+            pub fn set_leakage_rate(&mut self, qubit: usize, leakage_rate: f64) -> PyResult<()> {
+                self.internal
+                    .set_leakage_rate(qubit, leakage_rate)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set leakage rate: {}", err))
+                    })
+            }
+
+            /// Return the leakage rate of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the leakage rate is returned.
+            ///
+            /// Returns:
+            ///     Option[float]: The leakage rate, None if no leakage rate is set.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn leakage_rate(&self, qubit: usize) -> Option<f64> {
+                self.internal.leakage_rate(qubit)
+            }
+
+            /// Set the thermal excited-state population of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the thermal population is set.
+            ///     population (float): The probability to find the qubit in state 1 after
+            ///         initialisation.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device or the population is not between 0 and 1.
+            #[pyo3(text_signature = "(qubit, population)")]
+            pub fn set_thermal_population(
+                &mut self,
+                qubit: usize,
+                population: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_thermal_population(qubit, population)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set thermal population: {}", err))
+                    })
+            }
+
+            /// Return the thermal excited-state population of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the thermal population is returned.
+            ///
+            /// Returns:
+            ///     Option[float]: The thermal population, None if no thermal population is set.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn thermal_population(&self, qubit: usize) -> Option<f64> {
+                self.internal.thermal_population(qubit)
+            }
+
+            /// Set the damping and dephasing rates of a qubit from its T1 and T2 times.
+            ///
+            /// The damping rate is 1/T1 and the dephasing rate is (1/T2 - 1/(2 T1)) / 2.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the rates are set.
+            ///     t1 (float): The energy relaxation time T1.
+            ///     t2 (float): The coherence time T2, at most 2 T1.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device or the times are unphysical.
+            #[pyo3(text_signature = "(qubit, t1, t2)")]
+            pub fn set_coherence_times(&mut self, qubit: usize, t1: f64, t2: f64) -> PyResult<()> {
+                self.internal
+                    .set_coherence_times(qubit, t1, t2)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set coherence times: {}", err))
+                    })
+            }
+
+            /// Mark a qubit as unusable.
+            ///
+            /// The qubit is removed from the edges of the device and no gate times are returned for
+            /// it.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit that is disabled.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubit is not in device.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn disable_qubit(&mut self, qubit: usize) -> PyResult<()> {
+                self.internal
+                    .disable_qubit(qubit)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot disable qubit: {}", err)))
+            }
+
+            /// Mark an edge as unusable.
+            ///
+            /// The edge is removed from the edges of the device and no two-qubit gate times are
+            /// returned for it.
+            ///
+            /// Args:
+            ///     control (int): The first qubit of the edge.
+            ///     target (int): The second qubit of the edge.
+            ///
+            /// Raises:
+            ///     PyValueError: The qubits are not connected in the device.
+            #[pyo3(text_signature = "(control, target)")]
+            pub fn disable_edge(&mut self, control: usize, target: usize) -> PyResult<()> {
+                self.internal
+                    .disable_edge(control, target)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot disable edge: {}", err)))
+            }
+
+            /// Add an edge to the device and set the gate time of a two-qubit gate on it.
+            ///
+            /// The gate time is set for both directions of the edge. Adding an edge that already
+            /// exists re-enables it when it was disabled.
+            ///
+            /// Args:
+            ///     gate (str): hqslang name of the two-qubit-gate.
+            ///     control (int): The first qubit of the edge.
+            ///     target (int): The second qubit of the edge.
+            ///     gate_time (float): Gate time for the given gate.
+            ///
+            /// Raises:
+            ///     PyValueError: Qubits are not in device, disabled or the gate is not a
+            ///         two-qubit gate of the device.
+            #[pyo3(text_signature = "(gate, control, target, gate_time)")]
+            pub fn add_edge(
+                &mut self,
+                gate: &str,
+                control: usize,
+                target: usize,
+                gate_time: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .add_edge(gate, control, target, gate_time)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot add edge: {}", err)))
+            }
+
+            /// Return the directed edges on which the native two-qubit gates are calibrated.
+            ///
+            /// Using a gate against the calibrated direction requires additional single-qubit
+            /// gates.
+            ///
+            /// Returns:
+            ///     List[Tuple[int, int]]: The (control, target) pairs of the calibrated gates.
+            pub fn two_qubit_edges_directed(&self) -> Vec<(usize, usize)> {
+                self.internal.two_qubit_edges_directed()
+            }
+
+            /// Return the layout coordinates of the qubits used to draw the device.
+            ///
+            /// Returns:
+            ///     List[Tuple[float, float]]: The (x, y) coordinates of every qubit.
+            pub fn qubit_positions(&self) -> Vec<(f64, f64)> {
+                self.internal.qubit_positions()
+            }
+
+            /// Set the duration of the measurement of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the measurement time is set.
+            ///     measurement_time (float): The duration of the measurement.
+            ///
+            /// Raises:
+            ///     ValueError: The qubit is not in the device or the time is negative.
+            #[pyo3(text_signature = "(qubit, measurement_time)")]
+            pub fn set_measurement_time(
+                &mut self,
+                qubit: usize,
+                measurement_time: f64,
+            ) -> PyResult<()> {
+                self.internal
+                    .set_measurement_time(qubit, measurement_time)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set measurement time: {}", err))
+                    })
+            }
+
+            /// Return the duration of the measurement of a qubit.
+            ///
+            /// Args:
+            ///     qubit (int): The qubit for which the measurement time is returned.
+            ///
+            /// Returns:
+            ///     Option[float]: The duration of the measurement, None if no measurement time
+            ///         is set.
+            #[pyo3(text_signature = "(qubit)")]
+            pub fn measurement_time(&self, qubit: usize) -> Option<f64> {
+                self.internal.measurement_time(qubit)
+            }
+
+            /// Return number of qubits in device.
+            ///
+            /// Returns:
+            ///     int: The number of qubits.
+            pub fn number_qubits(&self) -> usize {
+                self.internal.number_qubits()
+            }
+
+            /// Return a list of longest linear chains through the device.
+            ///
+            /// Returns at least one chain of qubits with linear connectivity in the device, that
+            /// has the maximum possible number of qubits with linear connectivity in the device.
+            /// Can return more that one of the possible chains but is not guaranteed to return all
+            /// possible chains. (For example for all-to-all connectivity only one chain will be
+            /// returned).
+            ///
+            /// Returns:
+            ///     List[List[usize]]: A list of the longest chains given by vectors of qubits
+            ///         in the chain.
+            ///
+            pub fn longest_chains(&self) -> Vec<Vec<usize>> {
+                self.internal.longest_chains()
+            }
+
+            /// Return a list of longest closed linear chains through the device.
+            ///
+            /// Returns at least one chain of qubits with linear connectivity in the device , that
+            /// has the maximum possible number of qubits with linear connectivity in the device.
+            /// The chain must be closed, the first qubit needs to be connected to the last qubit.
+            /// Can return more that one of the possible chains but is not guaranteed to return all
+            /// possible chains. (For example for all-to-all connectivity only one chain will be
+            /// returned).
+            ///
+            /// Returns:
+            ///     List[List[usize]]: A list of the longest closed chains given by vectors of
+            ///         qubits in the chain.
+            ///
+            pub fn longest_closed_chains(&self) -> Vec<Vec<usize>> {
+                self.internal.longest_closed_chains()
+            }
+
+            /// Return the list of pairs of qubits linked by a native two-qubit-gate in the device.
+            ///
+            /// A pair of qubits is considered linked by a native two-qubit-gate if the device
+            /// can implement a two-qubit-gate between the two qubits without decomposing it
+            /// into a sequence of gates that involves a third qubit of the device.
+            /// The two-qubit-gate also has to form a universal set together with the available
+            /// single qubit gates.
+            ///
+            /// The returned vectors is a simple, graph-library independent, representation of the
+            /// undirected connectivity graph of the device. It can be used to construct the
+            /// connectivity graph in a graph library of the user's choice from a list of edges and
+            /// can be used for applications like routing in quantum algorithms.
+            ///
+            /// Returns:
+            ///     List[(int, int)]: List of two qubit edges in the undirected connectivity graph.
+            ///
+            pub fn two_qubit_edges(&self) -> Vec<(usize, usize)> {
+                self.internal.two_qubit_edges()
+            }
+
+            /// Convert the device to a qoqo GenericDevice.
+            ///
+            /// Returns:
+            ///     GenericDevice: converted device.
+            ///
+            /// Raises:
+            ///     PyValueError: Could not convert the device to a qoqo GenericDevice.
+            pub fn to_generic_device(&self) -> PyResult<GenericDeviceWrapper> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(GenericDeviceWrapper {
+                    internal: aws_device.to_generic_device().map_err(|err| {
+                        PyValueError::new_err(format!(
+                            "Cannot convert device to generic device: {}",
+                            err
+                        ))
+                    })?,
+                })
+            }
+
+            /// Convert the decoherence rates of the device to a qoqo ContinuousDecoherenceModel.
+            ///
+            /// Returns:
+            ///     ContinuousDecoherenceModel: The noise model of the device.
+            ///
+            /// Raises:
+            ///     PyValueError: Could not convert the decoherence rates to a noise model.
+            pub fn to_continuous_decoherence_model(
+                &self,
+            ) -> PyResult<ContinuousDecoherenceModelWrapper> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(ContinuousDecoherenceModelWrapper {
+                    internal: aws_device
+                        .to_continuous_decoherence_model()
+                        .map_err(|err| {
+                            PyValueError::new_err(format!(
+                                "Cannot convert device to noise model: {}",
+                                err
+                            ))
+                        })?,
+                })
+            }
+
+            /// Convert the gate times and decoherence rates of the device to a qoqo
+            /// DecoherenceOnGateModel.
+            ///
+            /// Every native gate is assigned the decoherence its qubits undergo during the gate
+            /// time.
+            ///
+            /// Returns:
+            ///     DecoherenceOnGateModel: The noise model of the device.
+            ///
+            /// Raises:
+            ///     PyValueError: Could not convert the decoherence rates to a noise model.
+            pub fn to_error_on_gate_model(&self) -> PyResult<DecoherenceOnGateModelWrapper> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(DecoherenceOnGateModelWrapper {
+                    internal: aws_device.to_error_on_gate_model().map_err(|err| {
+                        PyValueError::new_err(format!(
+                            "Cannot convert device to noise model: {}",
+                            err
+                        ))
+                    })?,
+                })
+            }
+
+            /// Export the state preparation and measurement errors of the device as a qoqo
+            /// ImperfectReadoutModel.
+            ///
+            /// The preparation error and thermal population of a qubit are added to its probability
+            /// to detect 0 as 1.
+            ///
+            /// Returns:
+            ///     ImperfectReadoutModel: The readout noise model of the device.
+            ///
+            /// Raises:
+            ///     ValueError: The device cannot be converted to a noise model.
+            pub fn to_imperfect_readout_model(&self) -> PyResult<ImperfectReadoutModelWrapper> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(ImperfectReadoutModelWrapper {
+                    internal: aws_device.to_imperfect_readout_model().map_err(|err| {
+                        PyValueError::new_err(format!(
+                            "Cannot convert device to noise model: {}",
+                            err
+                        ))
+                    })?,
+                })
+            }
+
+            /// Export the device to a qoqo GenericDevice together with the noise the GenericDevice
+            /// cannot hold.
+            ///
+            /// The readout and preparation errors are returned as an ImperfectReadoutModel and the
+            /// two-qubit error rates as depolarising noise after the two-qubit gates in a
+            /// DecoherenceOnGateModel.
+            ///
+            /// Returns:
+            ///     (GenericDevice, ImperfectReadoutModel, DecoherenceOnGateModel): The
+            ///         converted device and noise models.
+            ///
+            /// Raises:
+            ///     ValueError: The device cannot be converted.
+            pub fn to_generic_device_with_noise(
+                &self,
+            ) -> PyResult<(
+                GenericDeviceWrapper,
+                ImperfectReadoutModelWrapper,
+                DecoherenceOnGateModelWrapper,
+            )> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let (generic_device, readout_model, gate_model) =
+                    aws_device.to_generic_device_with_noise().map_err(|err| {
+                        PyValueError::new_err(format!(
+                            "Cannot convert device to generic device: {}",
+                            err
+                        ))
+                    })?;
+                Ok((
+                    GenericDeviceWrapper {
+                        internal: generic_device,
+                    },
+                    ImperfectReadoutModelWrapper {
+                        internal: readout_model,
+                    },
+                    DecoherenceOnGateModelWrapper {
+                        internal: gate_model,
+                    },
+                ))
+            }
+
+            /// Export the noise of the device as a Braket SDK NoiseModel for the DM1 simulator.
+            ///
+            /// The returned JSON can be loaded with
+            /// `braket.circuits.noise_model.NoiseModel.from_dict(json.loads(noise_model))`.
+            ///
+            /// Returns:
+            ///     str: The JSON representation of the Braket NoiseModel.
+            ///
+            /// Raises:
+            ///     ValueError: The device cannot be converted to a Braket NoiseModel.
+            pub fn to_braket_noise_model(&self) -> PyResult<String> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.to_braket_noise_model().map_err(|err| {
+                    PyValueError::new_err(format!(
+                        "Cannot convert device to Braket NoiseModel: {}",
+                        err
+                    ))
+                })
+            }
+
+            /// Set the decoherence rates of the device from Braket standardized device properties.
+            ///
+            /// Args:
+            ///     properties (str): The JSON of the standardized properties of an AWS device.
+            ///
+            /// Raises:
+            ///     ValueError: The properties cannot be parsed or contain unphysical coherence
+            ///         times.
+            #[pyo3(text_signature = "(properties)")]
+            pub fn set_coherence_times_from_properties(
+                &mut self,
+                properties: &str,
+            ) -> PyResult<()> {
+                let mut aws_device: AWSDevice = self.internal.clone().into();
+                aws_device
+                    .set_coherence_times_from_properties(properties)
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot set coherence times: {}", err))
+                    })?;
+                if let AWSDevice::$device(device) = aws_device {
+                    self.internal = device;
+                }
+                Ok(())
+            }
+
+            /// Add the decoherence of the device to a circuit as noise pragmas.
+            ///
+            /// After every gate available on the device, the decoherence its qubits undergo during
+            /// the gate time is inserted as PragmaDamping and PragmaDephasing, or
+            /// PragmaGeneralNoise.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to decorate.
+            ///
+            /// Returns:
+            ///     Circuit: The circuit with the added noise pragmas.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn decorate_circuit_with_noise(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<CircuitWrapper> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(CircuitWrapper {
+                    internal: decorate_circuit_with_noise(&circuit, &aws_device),
+                })
+            }
+
+            /// Check whether a circuit can be run on the device as it is.
+            ///
+            /// The circuit must fit on the device, consist only of native gates acting on available
+            /// and connected qubits, and must not contain operations other than definitions and
+            /// measurements.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to validate.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be run on the device, listing all problems found.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn validate_circuit(&self, circuit: &Bound<PyAny>) -> PyResult<()> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.validate_circuit(&circuit).map_err(|errors| {
+                    PyValueError::new_err(
+                        errors
+                            .iter()
+                            .map(|error| error.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\n"),
+                    )
+                })
+            }
+
+            /// Estimate how long a circuit runs on the device.
+            ///
+            /// The circuit is scheduled greedily: every operation starts as soon as all its qubits
+            /// are free. Gates take their gate time, measurements the measurement times of the
+            /// qubits and PragmaSleep its sleep time.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to schedule.
+            ///
+            /// Returns:
+            ///     Tuple[List[float], float]: The duration per qubit and of the whole circuit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: A gate is not available on the device or a sleep time is symbolic.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn estimate_duration(&self, circuit: &Bound<PyAny>) -> PyResult<(Vec<f64>, f64)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let duration = aws_device.estimate_duration(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot estimate duration: {}", err))
+                })?;
+                Ok((duration.qubit_durations, duration.total))
+            }
+
+            /// Estimate the SWAP gates needed to route a circuit on the device.
+            ///
+            /// The SWAP gates are the ones inserted by `route`, without building the routed
+            /// circuit. Every SWAP gate is counted as three native two-qubit gates.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to route.
+            ///
+            /// Returns:
+            ///     Tuple[int, int, float]: The number of SWAP gates, the number of extra
+            ///         two-qubit gates and their duration.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be routed on the device.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn estimate_swap_overhead(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<(usize, usize, f64)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let overhead = aws_device.estimate_swap_overhead(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot estimate SWAP overhead: {}", err))
+                })?;
+                Ok((
+                    overhead.number_swaps,
+                    overhead.extra_two_qubit_gates,
+                    overhead.extra_duration,
+                ))
+            }
+
+            /// Collect statistics of the gates of a circuit on the device.
+            ///
+            /// Shows at a glance which gates of the circuit are not native to the device and how
+            /// the two-qubit gates are distributed over the qubit pairs.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to analyse.
+            ///
+            /// Returns:
+            ///     Tuple[Dict[str, int], Dict[str, int], int, Dict[Tuple[int, int], int], int]:
+            ///         The counts of native and non-native gates per name, the two-qubit gate
+            ///         depth, the number of two-qubit gates per qubit pair and the number of
+            ///         two-qubit gates on unconnected qubits.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            #[pyo3(text_signature = "(circuit)")]
+            #[allow(clippy::type_complexity)]
+            pub fn gate_report(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<(
+                HashMap<String, usize>,
+                HashMap<String, usize>,
+                usize,
+                HashMap<(usize, usize), usize>,
+                usize,
+            )> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let report = gate_report(&circuit, &aws_device);
+                Ok((
+                    report.native_gates,
+                    report.non_native_gates,
+                    report.two_qubit_depth,
+                    report.edge_usage,
+                    report.unconnected_gates,
+                ))
+            }
+
+            /// Estimate the probability that a circuit runs without error on the device.
+            ///
+            /// The preparation, gate, two-qubit and readout fidelities stored on the device are
+            /// multiplied along the circuit. Decoherence of idle qubits is not taken into account.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to estimate.
+            ///
+            /// Returns:
+            ///     Tuple[List[float], float]: The fidelity per qubit and of the whole circuit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: A gate is not available on the device.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn estimate_fidelity(&self, circuit: &Bound<PyAny>) -> PyResult<(Vec<f64>, f64)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let fidelity = aws_device.estimate_fidelity(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot estimate fidelity: {}", err))
+                })?;
+                Ok((fidelity.qubit_fidelities, fidelity.total))
+            }
+
+            /// Decompose a circuit into the native gates of the device with custom decompositions.
+            ///
+            /// Every gate with an entry in `rules` that is not native to the device is replaced by
+            /// the given circuit, whose qubits 0 to n-1 are mapped onto the qubits of the gate,
+            /// i.e. `(control, target)` for two-qubit gates. The replacement is transpiled in turn,
+            /// all other gates are decomposed like in `transpile`.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to transpile.
+            ///     rules (Dict[str, Circuit]): The circuits replacing gates, by the hqslang
+            ///         name of the gate.
+            ///
+            /// Returns:
+            ///     Circuit: The circuit consisting of native gates.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit or a rule is not a Circuit.
+            ///     ValueError: The circuit contains a gate that cannot be decomposed.
+            #[pyo3(text_signature = "(circuit, rules)")]
+            pub fn transpile_with_rules(
+                &self,
+                circuit: &Bound<PyAny>,
+                rules: HashMap<String, Bound<PyAny>>,
+            ) -> PyResult<CircuitWrapper> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let mut registry = DecompositionRegistry::new();
+                for (hqslang, template) in rules {
+                    let template = convert_into_circuit(&template).map_err(|_| {
+                        PyTypeError::new_err(format!("Rule for {} is not a Circuit", hqslang))
+                    })?;
+                    registry.add_template(&hqslang, template);
+                }
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let transpiled =
+                    transpile_with_rules(&circuit, &aws_device, &registry).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot transpile circuit: {}", err))
+                    })?;
+                Ok(CircuitWrapper {
+                    internal: transpiled,
+                })
+            }
+
+            /// Route a circuit onto the connectivity of the device by inserting SWAP gates.
+            ///
+            /// The circuit qubits are placed on the device and SWAP gates are inserted before every
+            /// two-qubit gate acting on unconnected qubits, preferring edges with a high fidelity.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to route.
+            ///
+            /// Returns:
+            ///     Tuple[Circuit, Dict[int, int]]: The routed circuit and the device qubit
+            ///         every circuit qubit ends up on.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be routed on the device.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn route(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<(CircuitWrapper, HashMap<usize, usize>)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let (routed, permutation) =
+                    route_circuit(&circuit, &aws_device).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot route circuit: {}", err))
+                    })?;
+                Ok((CircuitWrapper { internal: routed }, permutation))
+            }
+
+            /// Return the placement of a circuit on the device that `route` starts from.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to place.
+            ///
+            /// Returns:
+            ///     Dict[int, int]: The device qubit of every circuit qubit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The device does not have enough available qubits.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn initial_layout(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<HashMap<usize, usize>> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                initial_layout(&circuit, &aws_device)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot place circuit: {}", err)))
+            }
+
+            /// Check that a transpiled or routed circuit implements the same operation as the
+            /// original.
+            ///
+            /// Both circuits are simulated, small circuits are compared as unitaries and larger
+            /// circuits with random product states. The fidelity of larger circuits is the smallest
+            /// one found for the random states, an estimate that can miss differences. Measurements
+            /// are ignored. For routed circuits the layouts are the result of `initial_layout` and
+            /// the layout returned by `route`.
+            ///
+            /// Args:
+            ///     original (Circuit): The circuit before transpilation.
+            ///     transpiled (Circuit): The transpiled circuit.
+            ///     initial_layout (Optional[Dict[int, int]]): The device qubit of every circuit
+            ///         qubit at the start, the identity by default.
+            ///     final_layout (Optional[Dict[int, int]]): The device qubit of every circuit
+            ///         qubit at the end, the identity by default.
+            ///     number_samples (Optional[int]): The number of random states of larger
+            ///         circuits, 16 by default.
+            ///
+            /// Returns:
+            ///     Tuple[float, bool]: The (estimated) fidelity between the circuits and
+            ///         whether they were compared as unitaries.
+            ///
+            /// Raises:
+            ///     TypeError: An input is not a Circuit.
+            ///     ValueError: The circuits cannot be simulated or number_samples is zero.
+            #[staticmethod]
+            #[pyo3(signature = (
+                original,
+                transpiled,
+                initial_layout=None,
+                final_layout=None,
+                number_samples=None
+            ))]
+            pub fn check_equivalence(
+                original: &Bound<PyAny>,
+                transpiled: &Bound<PyAny>,
+                initial_layout: Option<HashMap<usize, usize>>,
+                final_layout: Option<HashMap<usize, usize>>,
+                number_samples: Option<usize>,
+            ) -> PyResult<(f64, bool)> {
+                let original = convert_into_circuit(original)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let transpiled = convert_into_circuit(transpiled)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let check = check_equivalence(
+                    &original,
+                    &transpiled,
+                    initial_layout.as_ref(),
+                    final_layout.as_ref(),
+                    number_samples,
+                )
+                .map_err(|err| {
+                    PyValueError::new_err(format!("Cannot check equivalence: {}", err))
+                })?;
+                Ok((check.fidelity_estimate, check.exact))
+            }
+
+            /// Remap a circuit onto the device qubits with the best calibration data.
+            ///
+            /// The circuit qubits are placed on connected device qubits without inserting SWAP
+            /// gates, choosing the placement with the highest qubit and two-qubit gate fidelities.
+            /// Readout registers are left unchanged.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to remap.
+            ///
+            /// Returns:
+            ///     Tuple[Circuit, Dict[int, int]]: The remapped circuit and the device qubit of
+            ///         every circuit qubit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be placed on the device without SWAP gates.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn remap_to_best_qubits(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<(CircuitWrapper, HashMap<usize, usize>)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let (remapped, permutation) =
+                    remap_to_best_qubits(&circuit, &aws_device).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot remap circuit: {}", err))
+                    })?;
+                Ok((CircuitWrapper { internal: remapped }, permutation))
+            }
+
+            /// Cut a circuit that is too large for the device into fragments (experimental).
+            ///
+            /// CNOT and ControlledPauliZ gates acting on different fragments are replaced by six
+            /// terms of local operations. The expectation value of a product observable is the sum
+            /// over the variants of the coefficient times the product of the fragment results,
+            /// where every fragment result is multiplied by -1 for each readout register `cut_<n>`
+            /// of the fragment measuring True.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to cut.
+            ///
+            /// Returns:
+            ///     Tuple[List[List[int]], List[Tuple[float, List[Circuit]]]]: The circuit
+            ///         qubits of every fragment and the coefficient and fragment circuits of every
+            ///         variant.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be cut.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn cut_circuit(
+                &self,
+                circuit: &Bound<PyAny>,
+            ) -> PyResult<(Vec<Vec<usize>>, Vec<(f64, Vec<CircuitWrapper>)>)> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let cut = cut_circuit(&circuit, &aws_device)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot cut circuit: {}", err)))?;
+                let variants = cut
+                    .variants
+                    .into_iter()
+                    .map(|variant| {
+                        let circuits = variant
+                            .circuits
+                            .into_iter()
+                            .map(|internal| CircuitWrapper { internal })
+                            .collect();
+                        (variant.coefficient, circuits)
+                    })
+                    .collect();
+                Ok((cut.fragments, variants))
+            }
+
+            /// Commute RotateZ gates through a circuit and merge them.
+            ///
+            /// RotateZ gates are moved towards the end of the circuit until they reach a gate they
+            /// do not commute with. GPi and GPi2 gates are passed by shifting their phase and
+            /// rotations directly before a measurement are removed.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to optimize.
+            ///
+            /// Returns:
+            ///     Circuit: The optimized circuit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn optimize_virtual_z(&self, circuit: &Bound<PyAny>) -> PyResult<CircuitWrapper> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                Ok(CircuitWrapper {
+                    internal: optimize_virtual_z(&circuit),
+                })
+            }
+
+            /// Convert a circuit into a Braket OpenQASM 3 program for the device.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to convert.
+            ///     verbatim (bool): Whether to wrap the gates in verbatim boxes acting on the
+            ///         physical qubits, so that they are run without being compiled. Defaults to
+            ///         False.
+            ///
+            /// Returns:
+            ///     str: The OpenQASM 3 program.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be expressed in Braket OpenQASM 3 or cannot
+            ///         be run verbatim on the device.
+            #[pyo3(signature = (circuit, verbatim=false))]
+            pub fn to_openqasm3(&self, circuit: &Bound<PyAny>, verbatim: bool) -> PyResult<String> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                if verbatim {
+                    circuit_to_openqasm3_verbatim(&circuit, &aws_device)
+                } else {
+                    circuit_to_openqasm3(&circuit, &aws_device)
+                }
+                .map_err(|err| PyValueError::new_err(format!("Cannot convert circuit: {}", err)))
+            }
+
+            /// Convert a circuit into a Braket OpenQASM 3 program computing the PauliZ products of
+            /// a measurement.
+            ///
+            /// The PauliZ products of the readout of a PauliZProduct measurement are added as
+            /// Braket Expectation or Sample result types, so that they are computed by Braket
+            /// instead of returning the measured shots. The circuit has to contain the basis
+            /// rotations of the measurement.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to convert.
+            ///     input (PauliZProductInput): The input of the PauliZProduct measurement.
+            ///     readout (str): The readout register of the PauliZ products.
+            ///     sample (bool): Whether the samples of the products are returned instead of their
+            ///         expectation values. Defaults to False.
+            ///
+            /// Returns:
+            ///     str: The OpenQASM 3 program.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit or not a PauliZProductInput.
+            ///     ValueError: The circuit cannot be expressed in Braket OpenQASM 3 or the
+            ///         readout is not part of the input.
+            #[pyo3(signature = (circuit, input, readout, sample=false))]
+            pub fn to_openqasm3_with_results(
+                &self,
+                circuit: &Bound<PyAny>,
+                input: &Bound<PyAny>,
+                readout: &str,
+                sample: bool,
+            ) -> PyResult<String> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                let input: PauliZProductInput = input
+                    .call_method0("to_json")
+                    .and_then(|json| json.extract::<String>())
+                    .ok()
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .ok_or_else(|| PyTypeError::new_err("Input is not a PauliZProductInput"))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                circuit_to_openqasm3(&circuit, &aws_device)
+                    .and_then(|program| {
+                        let results = pauli_z_product_result_types(&input, readout, sample)?;
+                        Ok(add_openqasm3_result_types(&program, &results))
+                    })
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot convert circuit: {}", err))
+                    })
+            }
+
+            /// Convert a circuit into a Braket OpenQASM 3 program for the Braket simulators.
+            ///
+            /// Controlled gates without Braket equivalent like ControlledControlledPauliZ are
+            /// expressed with `ctrl @` modifiers, which are supported by the simulators but not by
+            /// the devices.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to convert.
+            ///
+            /// Returns:
+            ///     str: The OpenQASM 3 program.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be expressed in Braket OpenQASM 3.
+            #[staticmethod]
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn to_openqasm3_simulator(circuit: &Bound<PyAny>) -> PyResult<String> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                circuit_to_openqasm3_simulator(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot convert circuit: {}", err))
+                })
+            }
+
+            /// Convert a circuit into a Braket JAQCD program.
+            ///
+            /// JAQCD programs always measure all qubits at the end, so definitions and measurements
+            /// of the circuit are not part of the program.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to convert.
+            ///
+            /// Returns:
+            ///     str: The JSON of the JAQCD program.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The circuit cannot be expressed in JAQCD.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn to_jaqcd(&self, circuit: &Bound<PyAny>) -> PyResult<String> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                circuit_to_jaqcd(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot convert circuit: {}", err))
+                })
+            }
+
+            /// Convert a Braket OpenQASM 3 program into a circuit.
+            ///
+            /// Args:
+            ///     program (str): The OpenQASM 3 program.
+            ///
+            /// Returns:
+            ///     Circuit: The circuit.
+            ///
+            /// Raises:
+            ///     ValueError: The program contains an unsupported statement or gate.
+            #[staticmethod]
+            #[pyo3(text_signature = "(program)")]
+            pub fn circuit_from_openqasm3(program: &str) -> PyResult<CircuitWrapper> {
+                Ok(CircuitWrapper {
+                    internal: circuit_from_openqasm3(program).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot convert program: {}", err))
+                    })?,
+                })
+            }
+
+            /// Convert a Braket JAQCD program into a circuit.
+            ///
+            /// Args:
+            ///     program (str): The JSON of the JAQCD program.
+            ///
+            /// Returns:
+            ///     Circuit: The circuit.
+            ///
+            /// Raises:
+            ///     ValueError: The program is not valid or contains an unsupported instruction.
+            #[staticmethod]
+            #[pyo3(text_signature = "(program)")]
+            pub fn circuit_from_jaqcd(program: &str) -> PyResult<CircuitWrapper> {
+                Ok(CircuitWrapper {
+                    internal: circuit_from_jaqcd(program).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot convert program: {}", err))
+                    })?,
+                })
+            }
+
+            /// Sort measurement results returned by Braket into the readout registers of a circuit.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit that was run.
+            ///     measured_qubits (List[int]): The qubits in the order of the bits of every shot.
+            ///     measurements (List[List[bool]]): The measured bits of every shot.
+            ///
+            /// Returns:
+            ///     Dict[str, List[List[bool]]]: The bits of every shot for every readout register.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The measurements do not match the measurements of the circuit.
+            #[pyo3(text_signature = "(circuit, measured_qubits, measurements)")]
+            pub fn map_measurement_results(
+                &self,
+                circuit: &Bound<PyAny>,
+                measured_qubits: Vec<usize>,
+                measurements: Vec<Vec<bool>>,
+            ) -> PyResult<HashMap<String, Vec<Vec<bool>>>> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                MeasurementMapping::from_circuit(&circuit)
+                    .and_then(|mapping| mapping.map_results(&measured_qubits, &measurements))
+                    .map_err(|err| {
+                        PyValueError::new_err(format!("Cannot map measurements: {}", err))
+                    })
+            }
+
+            /// Return the number of shots of the Braket task running a circuit.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to inspect.
+            ///
+            /// Returns:
+            ///     Optional[int]: The number of shots, None if the circuit does not set a
+            ///         number of measurements.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: The measurement pragmas of the circuit are not compatible.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn braket_shots(&self, circuit: &Bound<PyAny>) -> PyResult<Option<usize>> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                braket_shots(&circuit).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot determine shots: {}", err))
+                })
+            }
+
+            /// Return the free parameters of the symbolic angles of a circuit.
+            ///
+            /// The free parameters are declared as inputs of the OpenQASM 3 program of the circuit.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit to inspect.
+            ///
+            /// Returns:
+            ///     List[str]: The sorted names of the free parameters.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            #[pyo3(text_signature = "(circuit)")]
+            pub fn free_parameters(&self, circuit: &Bound<PyAny>) -> PyResult<Vec<String>> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                Ok(free_parameters(&circuit))
+            }
+
+            /// Return the values of the free parameters of a circuit passed as inputs of a Braket
+            /// task.
+            ///
+            /// Free parameters without a given value take the value of the InputSymbolic operation
+            /// of the circuit.
+            ///
+            /// Args:
+            ///     circuit (Circuit): The circuit that is run.
+            ///     values (Dict[str, float]): The values of the free parameters.
+            ///
+            /// Returns:
+            ///     Dict[str, float]: The value of every free parameter of the circuit.
+            ///
+            /// Raises:
+            ///     TypeError: The input is not a Circuit.
+            ///     ValueError: A free parameter has no value or a value is given for an unknown
+            ///         parameter.
+            #[pyo3(text_signature = "(circuit, values)")]
+            pub fn braket_inputs(
+                &self,
+                circuit: &Bound<PyAny>,
+                values: HashMap<String, f64>,
+            ) -> PyResult<HashMap<String, f64>> {
+                let circuit = convert_into_circuit(circuit)
+                    .map_err(|_| PyTypeError::new_err("Input is not a Circuit"))?;
+                braket_inputs(&circuit, &values)
+                    .map_err(|err| PyValueError::new_err(format!("Cannot set inputs: {}", err)))
+            }
+
+            /// Export all noise of the device as the qoqo noise models used by noisy simulators
+            /// like qoqo-quest.
+            ///
+            /// The list contains the decoherence during the gates and the two-qubit gate errors as
+            /// DecoherenceOnGateModels and the state preparation and measurement errors as an
+            /// ImperfectReadoutModel. Models without any noise are left out.
+            ///
+            /// Returns:
+            ///     List[Union[DecoherenceOnGateModel, ImperfectReadoutModel]]: The noise models
+            ///         of the device.
+            ///
+            /// Raises:
+            ///     ValueError: The noise of the device cannot be converted.
+            pub fn to_noise_models(&self) -> PyResult<Vec<PyObject>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let noise_models = aws_device.to_noise_models().map_err(|err| {
+                    PyValueError::new_err(format!("Cannot convert device to noise models: {}", err))
+                })?;
+                Python::with_gil(|py| -> PyResult<Vec<PyObject>> {
+                    noise_models
+                        .into_iter()
+                        .map(|noise_model| match noise_model {
+                            NoiseModel::DecoherenceOnGateModel(internal) => {
+                                Ok(DecoherenceOnGateModelWrapper { internal }.into_py(py))
+                            }
+                            NoiseModel::ImperfectReadoutModel(internal) => {
+                                Ok(ImperfectReadoutModelWrapper { internal }.into_py(py))
+                            }
+                            NoiseModel::ContinuousDecoherenceModel(internal) => {
+                                Ok(ContinuousDecoherenceModelWrapper { internal }.into_py(py))
+                            }
+                            _ => Err(PyValueError::new_err("Unsupported noise model")),
+                        })
+                        .collect()
+                })
+            }
+
+            /// Validate the noise of the device.
+            ///
+            /// Checks that the decoherence rate matrix of every qubit is symmetric and positive
+            /// semi-definite.
+            ///
+            /// Raises:
+            ///     ValueError: A decoherence rate matrix is unphysical.
+            pub fn validate_noise(&self) -> PyResult<()> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device
+                    .validate_noise()
+                    .map_err(|err| PyValueError::new_err(format!("Invalid noise: {}", err)))
+            }
+
+            /// Return the decoherence the qubits of a native gate undergo during the gate.
+            ///
+            /// Args:
+            ///     gate (str): The hqslang name of the gate.
+            ///     qubits (List[int]): The qubits the gate acts on.
+            ///
+            /// Returns:
+            ///     Dict[int, numpy.array]: The decoherence rate matrix multiplied by the gate
+            ///         time for each qubit with decoherence.
+            ///
+            /// Raises:
+            ///     ValueError: The gate is not available on the device for the given qubits.
+            #[pyo3(text_signature = "(gate, qubits)")]
+            pub fn noise_during_gate(
+                &self,
+                gate: &str,
+                qubits: Vec<usize>,
+            ) -> PyResult<HashMap<usize, Py<PyArray2<f64>>>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                let noise = aws_device.noise_during_gate(gate, &qubits).map_err(|err| {
+                    PyValueError::new_err(format!("Cannot compute noise: {}", err))
+                })?;
+                Python::with_gil(|py| -> PyResult<HashMap<usize, Py<PyArray2<f64>>>> {
+                    Ok(noise
+                        .into_iter()
+                        .map(|(qubit, matrix)| (qubit, matrix.to_pyarray_bound(py).unbind()))
+                        .collect())
+                })
+            }
+
+            /// Return the coupling map of the device in the format used by Qiskit.
+            ///
+            /// Every edge is reported in each direction in which a native two-qubit gate is
+            /// available.
+            ///
+            /// Returns:
+            ///     List[List[int]]: The list of [control, target] pairs.
+            pub fn coupling_map(&self) -> Vec<[usize; 2]> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.coupling_map()
+            }
+
+            /// Return the connectivity of the device as a networkx Graph.
+            ///
+            /// The nodes carry the attributes `T1`, `T2` and `readout_error` and the edges the
+            /// attributes `gate_time` and `fidelity`. Attributes that are not set on the device are
+            /// None.
+            ///
+            /// Returns:
+            ///     networkx.Graph: The connectivity graph of the device.
+            ///
+            /// Raises:
+            ///     ImportError: networkx is not installed.
+            pub fn to_networkx(&self) -> PyResult<PyObject> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Python::with_gil(|py| -> PyResult<PyObject> {
+                    let graph = PyModule::import_bound(py, "networkx")?.call_method0("Graph")?;
+                    for qubit in 0..aws_device.number_qubits() {
+                        let (t1, t2) = aws_device.coherence_times(qubit);
+                        let attributes = PyDict::new_bound(py);
+                        attributes.set_item("T1", t1)?;
+                        attributes.set_item("T2", t2)?;
+                        attributes.set_item("readout_error", aws_device.readout_error(qubit))?;
+                        graph.call_method("add_node", (qubit,), Some(&attributes))?;
+                    }
+                    for (first, second) in aws_device.two_qubit_edges() {
+                        let attributes = PyDict::new_bound(py);
+                        attributes.set_item(
+                            "gate_time",
+                            aws_device.edge_weight(first, second, EdgeWeight::GateTime),
+                        )?;
+                        attributes.set_item(
+                            "fidelity",
+                            aws_device.edge_weight(first, second, EdgeWeight::Fidelity),
+                        )?;
+                        graph.call_method("add_edge", (first, second), Some(&attributes))?;
+                    }
+                    Ok(graph.unbind())
+                })
+            }
+
+            /// Return a Graphviz DOT description of the connectivity of the device.
+            ///
+            /// Qubits are colored from green (no decoherence) to red (largest decoherence) and
+            /// edges are labeled with their gate times.
+            ///
+            /// Returns:
+            ///     str: The DOT description of the connectivity graph.
+            pub fn to_dot(&self) -> String {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.to_dot()
+            }
+
+            /// Return the number of edges on a shortest path between two qubits.
+            ///
+            /// Args:
+            ///     qubit_a (int): The first qubit.
+            ///     qubit_b (int): The second qubit.
+            ///
+            /// Returns:
+            ///     Optional[int]: The distance, None if the qubits are not connected or not in
+            ///         the device.
+            #[pyo3(text_signature = "(qubit_a, qubit_b)")]
+            pub fn distance(&self, qubit_a: usize, qubit_b: usize) -> Option<usize> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.distance(qubit_a, qubit_b)
+            }
+
+            /// Return a shortest path between two qubits.
+            ///
+            /// Args:
+            ///     qubit_a (int): The first qubit.
+            ///     qubit_b (int): The second qubit.
+            ///
+            /// Returns:
+            ///     Optional[List[int]]: The qubits of the path from qubit_a to qubit_b,
+            ///                          None if the qubits are not connected or not in the device.
+            #[pyo3(text_signature = "(qubit_a, qubit_b)")]
+            pub fn shortest_path(&self, qubit_a: usize, qubit_b: usize) -> Option<Vec<usize>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.shortest_path(qubit_a, qubit_b)
+            }
+
+            /// Return the distances between all pairs of qubits.
+            ///
+            /// Returns:
+            ///     List[List[Optional[int]]]: The matrix of distances, None for unconnected pairs.
+            pub fn distance_matrix(&self) -> Vec<Vec<Option<usize>>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.distance_matrix()
+            }
+
+            /// Restrict the device to a subset of its qubits.
+            ///
+            /// The qubits are relabeled by their position in the list, keeping only the gate times
+            /// between the chosen qubits and their decoherence rates.
+            ///
+            /// Args:
+            ///     qubits (List[int]): The qubits of the device to keep.
+            ///
+            /// Returns:
+            ///     GenericDevice: The restricted device.
+            ///
+            /// Raises:
+            ///     ValueError: A qubit is not in the device or appears more than once.
+            #[pyo3(text_signature = "(qubits)")]
+            pub fn sub_device(&self, qubits: Vec<usize>) -> PyResult<GenericDeviceWrapper> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(GenericDeviceWrapper {
+                    internal: aws_device.sub_device(&qubits).map_err(|err| {
+                        PyValueError::new_err(format!("Cannot restrict device: {}", err))
+                    })?,
+                })
+            }
+
+            /// Find a chain of connected qubits of a given length.
+            ///
+            /// Chains of qubits with a higher estimated fidelity are preferred.
+            ///
+            /// Args:
+            ///     length (int): The number of qubits of the chain.
+            ///
+            /// Returns:
+            ///     Optional[List[int]]: The qubits of the chain, None if the device has no such
+            ///         chain.
+            #[pyo3(text_signature = "(length)")]
+            pub fn find_linear_chain(&self, length: usize) -> Option<Vec<usize>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.find_linear_chain(length)
+            }
+
+            /// Return the type of connectivity of the device.
+            ///
+            /// Returns:
+            ///     str: One of "AllToAll", "Linear", "Lattice" or "Arbitrary".
+            pub fn connectivity(&self) -> String {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.connectivity().to_string()
+            }
+
+            /// Return the edges of the device together with their weights.
+            ///
+            /// Args:
+            ///     weight (str): The weight of the edges, "gate_time", "fidelity" or "error_rate".
+            ///
+            /// Returns:
+            ///     List[Tuple[int, int, float]]: The (control, target, weight) triples of all
+            ///         edges.
+            ///
+            /// Raises:
+            ///     ValueError: Unknown edge weight.
+            #[pyo3(text_signature = "(weight)")]
+            pub fn two_qubit_edges_weighted(
+                &self,
+                weight: &str,
+            ) -> PyResult<Vec<(usize, usize, f64)>> {
+                let weight: EdgeWeight = weight
+                    .parse()
+                    .map_err(|err| PyValueError::new_err(format!("{:?}", err)))?;
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Ok(aws_device.two_qubit_edges_weighted(weight))
+            }
+
+            /// Return the connected components of the device.
+            ///
+            /// Disabled qubits are not part of any component.
+            ///
+            /// Returns:
+            ///     List[List[int]]: The sorted qubits of every component, largest components first.
+            pub fn connected_components(&self) -> Vec<Vec<usize>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.connected_components()
+            }
+
+            /// Plot the connectivity of the device with matplotlib.
+            ///
+            /// Qubits are drawn at their layout coordinates and colored from green (no decoherence)
+            /// to red (largest decoherence), edges are colored from red (fidelity 0) to green
+            /// (fidelity 1).
+            ///
+            /// Args:
+            ///     ax (Optional[matplotlib.axes.Axes]): The axes to draw on, a new figure is
+            ///         created if None.
+            ///
+            /// Returns:
+            ///     matplotlib.axes.Axes: The axes the device was drawn on.
+            ///
+            /// Raises:
+            ///     ImportError: matplotlib is not installed.
+            #[pyo3(signature = (ax=None))]
+            pub fn plot(&self, ax: Option<&Bound<PyAny>>) -> PyResult<PyObject> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                Python::with_gil(|py| -> PyResult<PyObject> {
+                    let pyplot = PyModule::import_bound(py, "matplotlib.pyplot")?;
+                    let colormap = PyModule::import_bound(py, "matplotlib")?
+                        .getattr("colormaps")?
+                        .get_item("RdYlGn")?;
+                    let ax = match ax {
+                        Some(ax) => ax.clone(),
+                        None => pyplot.call_method0("subplots")?.get_item(1)?,
+                    };
+                    let positions = aws_device.qubit_positions();
+
+                    for (first, second, fidelity) in
+                        aws_device.two_qubit_edges_weighted(EdgeWeight::Fidelity)
+                    {
+                        let kwargs = PyDict::new_bound(py);
+                        kwargs.set_item("color", colormap.call1((fidelity,))?)?;
+                        kwargs.set_item("zorder", 1)?;
+                        ax.call_method(
+                            "plot",
+                            (
+                                vec![positions[first].0, positions[second].0],
+                                vec![positions[first].1, positions[second].1],
+                            ),
+                            Some(&kwargs),
+                        )?;
+                    }
+
+                    let rates: Vec<f64> = (0..aws_device.number_qubits())
+                        .map(|qubit| {
+                            aws_device
+                                .qubit_decoherence_rates(&qubit)
+                                .map(|rates| rates.diag().sum())
+                                .unwrap_or(0.0)
+                        })
+                        .collect();
+                    let max_rate = rates.iter().copied().fold(0.0, f64::max);
+                    let colors: Vec<f64> = rates
+                        .iter()
+                        .map(|rate| {
+                            if max_rate > 0.0 {
+                                1.0 - rate / max_rate
+                            } else {
+                                1.0
+                            }
+                        })
+                        .collect();
+                    let kwargs = PyDict::new_bound(py);
+                    kwargs.set_item("c", colors)?;
+                    kwargs.set_item("cmap", &colormap)?;
+                    kwargs.set_item("vmin", 0.0)?;
+                    kwargs.set_item("vmax", 1.0)?;
+                    kwargs.set_item("s", 300)?;
+                    kwargs.set_item("edgecolors", "black")?;
+                    kwargs.set_item("zorder", 2)?;
+                    ax.call_method(
+                        "scatter",
+                        (
+                            positions.iter().map(|(x, _)| *x).collect::<Vec<f64>>(),
+                            positions.iter().map(|(_, y)| *y).collect::<Vec<f64>>(),
+                        ),
+                        Some(&kwargs),
+                    )?;
+                    for (qubit, (x, y)) in positions.iter().enumerate() {
+                        let kwargs = PyDict::new_bound(py);
+                        kwargs.set_item("ha", "center")?;
+                        kwargs.set_item("va", "center")?;
+                        kwargs.set_item("fontsize", 8)?;
+                        kwargs.set_item("zorder", 3)?;
+                        ax.call_method("text", (*x, *y, qubit.to_string()), Some(&kwargs))?;
+                    }
+                    ax.call_method1("set_aspect", ("equal",))?;
+                    ax.call_method0("set_axis_off")?;
+                    Ok(ax.unbind())
+                })
+            }
+
+            /// Search an embedding of the two-qubit interaction graph of a circuit into the device.
+            ///
+            /// Every pair of interacting circuit qubits is mapped to a pair of connected device
+            /// qubits, so the circuit can be run without SWAP gates.
+            ///
+            /// Args:
+            ///     interaction_edges (List[Tuple[int, int]]): The pairs of circuit qubits acted
+            ///         on by two-qubit gates.
+            ///
+            /// Returns:
+            ///     Optional[Dict[int, int]]: The device qubit of every circuit qubit, None if
+            ///         no placement was found.
+            #[pyo3(text_signature = "(interaction_edges)")]
+            pub fn find_placement(
+                &self,
+                interaction_edges: Vec<(usize, usize)>,
+            ) -> Option<HashMap<usize, usize>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.find_placement(&interaction_edges)
+            }
+
+            /// Return chains of connected qubits of a given length with the highest edge fidelity.
+            ///
+            /// The chains maximize the product of the fidelities of their edges instead of their
+            /// length.
+            ///
+            /// Args:
+            ///     length (int): The number of qubits of the chains.
+            ///
+            /// Returns:
+            ///     List[List[int]]: The qubits of the best chains in order, empty if there is
+            ///         no chain of that length.
+            #[pyo3(text_signature = "(length)")]
+            pub fn best_chains(&self, length: usize) -> Vec<Vec<usize>> {
+                let aws_device: AWSDevice = self.internal.clone().into();
+                aws_device.best_chains(length)
+            }
+
+            #[doc = concat!(" Return the json representation of the ", $name, ".")]
+            ///
+            /// Returns:
+            #[doc = concat!("     str: The serialized form of ", $name, ".")]
+            ///
+            /// Raises:
+            #[doc = concat!("     ValueError: Cannot serialize ", $name, " to json.")]
+            pub fn to_json(&self) -> PyResult<String> {
+                serde_json::to_string(&self.internal).map_err(|_| {
+                    PyValueError::new_err(concat!("Cannot serialize ", $name, " to json"))
+                })
+            }
+
+            #[doc = concat!(" Convert the json representation of a ", $name, " to a ", $name, ".")]
+            ///
+            /// Args:
+            #[doc = concat!("     input (str): The serialized ", $name, " in json form.")]
+            ///
+            /// Returns:
+            #[doc = concat!("     ", $name, ": The deserialized ", $name, ".")]
+            ///
+            /// Raises:
+            #[doc = concat!("     ValueError: Input cannot be deserialized to ", $name, ".")]
+            #[staticmethod]
+            #[pyo3(text_signature = "(input)")]
+            pub fn from_json(input: &str) -> PyResult<Self> {
+                Ok(Self {
+                    internal: serde_json::from_str(input).map_err(|_| {
+                        PyValueError::new_err(concat!("Input cannot be deserialized to ", $name))
+                    })?,
+                })
+            }
+
+            #[doc = concat!(" Return the representation of the ", $name, ".")]
+            ///
+            /// Returns:
+            ///     str: The name, region, number of qubits, native gates and calibration state
+            ///         of the device.
+            pub fn __repr__(&self) -> String {
+                device_repr($name, self.internal.clone().into())
+            }
+
+            #[doc = concat!(
+                " Return the string representation of the ",
+                $name,
+                ", see `__repr__`."
+            )]
+            ///
+            /// Returns:
+            ///     str: The name, region, number of qubits, native gates and calibration state
+            ///         of the device.
+            pub fn __str__(&self) -> String {
+                self.__repr__()
+            }
+
+            #[doc = concat!(" Return a copy of the ", $name, " (copy here produces a deepcopy).")]
+            ///
+            /// The gate times and decoherence rates of the copy can be changed without changing the
+            /// original device.
+            ///
+            /// Returns:
+            #[doc = concat!("     ", $name, ": A deep copy of self.")]
+            pub fn __copy__(&self) -> Self {
+                self.clone()
+            }
+
+            #[doc = concat!(" Return a deep copy of the ", $name, ".")]
+            ///
+            /// Args:
+            ///     memodict (dict): The memodict of the deepcopy.
+            ///
+            /// Returns:
+            #[doc = concat!("     ", $name, ": A deep copy of self.")]
+            pub fn __deepcopy__(&self, _memodict: &Bound<PyAny>) -> Self {
+                self.clone()
+            }
+
+            #[doc = concat!(
+                " Return the __richcmp__ magic method to perform rich comparison operations on ",
+                $name,
+                "."
+            )]
+            ///
+            /// Args:
+            ///     other: The object to compare self to.
+            ///     op: Whether they should be equal or not.
+            ///
+            /// Returns:
+            ///     bool: Whether the two devices compared evaluated to True or False.
+            ///
+            /// Raises:
+            ///     NotImplementedError: Other comparison not implemented.
+            fn __richcmp__(&self, other: Py<PyAny>, op: CompareOp) -> PyResult<bool> {
+                let other = Self::from_pyany(other);
+                match op {
+                    CompareOp::Eq => match other {
+                        Ok(other) => Ok(self.internal == other),
+                        _ => Ok(false),
+                    },
+                    CompareOp::Ne => match other {
+                        Ok(other) => Ok(self.internal != other),
+                        _ => Ok(true),
+                    },
+                    _ => Err(PyNotImplementedError::new_err(
+                        "Other comparison not implemented",
+                    )),
+                }
+            }
+
+            $($methods)*
+        }
+
+        impl $wrapper {
+            /// Fallible conversion of generic python object...
+            pub fn from_pyany(input: Py<PyAny>) -> PyResult<$device> {
+                Python::with_gil(|py| -> PyResult<$device> {
+                    let input = input.bind(py);
+                    if let Ok(try_downcast) = input.extract::<$wrapper>() {
+                        Ok(try_downcast.internal)
+                    } else {
+                        let get_bytes = input.call_method0("to_bincode")?;
+                        let bytes = get_bytes.extract::<Vec<u8>>()?;
+                        deserialize(&bytes[..]).map_err(|err| {
+                            PyValueError::new_err(format!(
+                                concat!("Cannot treat input as ", $name, ": {}"),
+                                err
+                            ))
+                        })
+                    }
+                })
+            }
+        }
+
+        impl Default for $wrapper {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+pub(crate) use device_wrapper;
@@ -20,6 +20,9 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use pyo3::wrap_pymodule;
 
+pub mod backend;
+pub use backend::*;
+
 pub mod devices;
 pub use devices::*;
 
@@ -30,6 +33,8 @@ pub use devices::*;
 fn qoqo_for_braket_devices(_py: Python, module: &Bound<PyModule>) -> PyResult<()> {
     let wrapper = wrap_pymodule!(devices::aws_devices);
     module.add_wrapped(wrapper)?;
+    let wrapper = wrap_pymodule!(backend::aws_backend);
+    module.add_wrapped(wrapper)?;
 
     let system = PyModule::import_bound(_py, "sys")?;
     let binding = system.getattr("modules")?;
@@ -38,5 +43,9 @@ fn qoqo_for_braket_devices(_py: Python, module: &Bound<PyModule>) -> PyResult<()
         "qoqo_for_braket_devices.devices",
         module.getattr("aws_devices")?,
     )?;
+    system_modules.set_item(
+        "qoqo_for_braket_devices.backend",
+        module.getattr("aws_backend")?,
+    )?;
     Ok(())
 }
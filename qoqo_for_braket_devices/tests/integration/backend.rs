@@ -0,0 +1,146 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pyo3::prelude::*;
+use pyo3::types::IntoPyDict;
+
+use qoqo_for_braket_devices::*;
+
+/// Test constructing an AWSConfig with and without optional arguments
+#[test]
+fn test_aws_config() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let config_type = py.get_type_bound::<AWSConfigWrapper>();
+        let config = config_type.call1(("bucket", "prefix")).unwrap();
+        let s3_bucket: String = config.call_method0("s3_bucket").unwrap().extract().unwrap();
+        assert_eq!(s3_bucket, "bucket");
+        let region: Option<String> = config.call_method0("region").unwrap().extract().unwrap();
+        assert_eq!(region, None);
+
+        let kwargs = [
+            ("profile", "braket"),
+            ("region", "us-east-1"),
+            ("endpoint_url", "http://localhost:4566"),
+        ]
+        .into_py_dict_bound(py);
+        let config = config_type
+            .call(("bucket", "prefix"), Some(&kwargs))
+            .unwrap();
+        let profile: Option<String> = config.call_method0("profile").unwrap().extract().unwrap();
+        assert_eq!(profile, Some("braket".to_string()));
+        let endpoint_url: Option<String> = config
+            .call_method0("endpoint_url")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(endpoint_url, Some("http://localhost:4566".to_string()));
+        config
+            .call_method1("set_region", (None::<String>,))
+            .unwrap();
+        let region: Option<String> = config.call_method0("region").unwrap().extract().unwrap();
+        assert_eq!(region, None);
+
+        let role: Option<(String, Option<String>, String)> = config
+            .call_method0("assume_role")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(role, None);
+        let kwargs = [("external_id", "external")].into_py_dict_bound(py);
+        config
+            .call_method(
+                "set_assume_role",
+                ("arn:aws:iam::123456789012:role/braket",),
+                Some(&kwargs),
+            )
+            .unwrap();
+        let role: Option<(String, Option<String>, String)> = config
+            .call_method0("assume_role")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(
+            role,
+            Some((
+                "arn:aws:iam::123456789012:role/braket".to_string(),
+                Some("external".to_string()),
+                "qoqo-for-braket".to_string()
+            ))
+        );
+        config
+            .call_method1("set_assume_role", (None::<String>,))
+            .unwrap();
+        let role: Option<(String, Option<String>, String)> = config
+            .call_method0("assume_role")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(role, None);
+    })
+}
+
+/// Test constructing an APIBackend from the AWS devices only
+#[test]
+fn test_api_backend_new() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let config = py
+            .get_type_bound::<AWSConfigWrapper>()
+            .call1(("bucket", "prefix"))
+            .unwrap();
+        let device = py.get_type_bound::<OQCLucyDeviceWrapper>().call0().unwrap();
+        let backend_type = py.get_type_bound::<APIBackendWrapper>();
+        assert!(backend_type.call1((device, config.clone())).is_ok());
+        assert!(backend_type.call1(("device", config)).is_err());
+    })
+}
+
+/// Test the awaitable methods of APIBackend reject invalid inputs before awaiting
+#[test]
+fn test_api_backend_async_invalid_input() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let config = py
+            .get_type_bound::<AWSConfigWrapper>()
+            .call1(("bucket", "prefix"))
+            .unwrap();
+        let device = py.get_type_bound::<OQCLucyDeviceWrapper>().call0().unwrap();
+        let backend = py
+            .get_type_bound::<APIBackendWrapper>()
+            .call1((device, config))
+            .unwrap();
+        let err = backend
+            .call_method1("submit_async", ("circuit",))
+            .unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        let err = backend
+            .call_method1("run_circuit_async", ("circuit",))
+            .unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyTypeError>(py));
+        assert!(backend.call_method1("results_async", ("task",)).is_err());
+    })
+}
+
+/// Test setting the log level of the backends
+#[test]
+fn test_set_log_level() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let module = pyo3::wrap_pymodule!(aws_backend)(py);
+        let module = module.bind(py);
+        assert!(module.call_method1("set_log_level", ("debug",)).is_ok());
+        assert!(module.call_method1("set_log_level", ("OFF",)).is_ok());
+        let err = module.call_method1("set_log_level", ("loud",)).unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+    })
+}
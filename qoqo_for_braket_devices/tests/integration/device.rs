@@ -10,6 +10,8 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use ndarray::array;
+use numpy::ToPyArray;
 use pyo3::prelude::*;
 use pyo3::types::PyType;
 
@@ -311,6 +313,65 @@ fn test_damping_dephasing_decoherence(device: Py<PyAny>) {
     });
 }
 
+/// Test that two-qubit gate times cannot be set between unconnected qubits
+#[test_case(new_device(AWSDevice::from(OQCLucyDevice::new())), "EchoCrossResonance"; "lucy")]
+#[test_case(new_device(AWSDevice::from(RigettiAspenM3Device::new())), "XY"; "aspen3")]
+fn test_set_two_qubit_gate_time_unconnected(device: Py<PyAny>, gate: &str) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        assert!(device
+            .call_method1(py, "set_two_qubit_gate_time", (gate, 0, 4, 0.5))
+            .is_err());
+        let two_qubit_time = device
+            .call_method1(py, "two_qubit_gate_time", (gate, 0, 4))
+            .unwrap()
+            .extract::<Option<f64>>(py)
+            .unwrap();
+        assert_eq!(two_qubit_time, None);
+    });
+}
+
+/// Test set_qubit_decoherence_rates method
+#[test_case(new_device(AWSDevice::from(IonQHarmonyDevice::new())); "harmony")]
+#[test_case(new_device(AWSDevice::from(IonQAria1Device::new())); "aria1")]
+#[test_case(new_device(AWSDevice::from(OQCLucyDevice::new())); "lucy")]
+#[test_case(new_device(AWSDevice::from(RigettiAspenM3Device::new())); "aspen3")]
+fn test_set_qubit_decoherence_rates(device: Py<PyAny>) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let rates = array![[0.1, 0.0, 0.0], [0.0, 0.2, 0.0], [0.0, 0.0, 0.3]];
+        device
+            .call_method1(
+                py,
+                "set_qubit_decoherence_rates",
+                (0, rates.to_pyarray_bound(py)),
+            )
+            .unwrap();
+        let result = device
+            .call_method1(py, "qubit_decoherence_rates", (0,))
+            .unwrap()
+            .extract::<Vec<Vec<f64>>>(py)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                vec![0.1, 0.0, 0.0],
+                vec![0.0, 0.2, 0.0],
+                vec![0.0, 0.0, 0.3]
+            ]
+        );
+
+        let wrong_shape = array![[0.1, 0.0], [0.0, 0.2]];
+        assert!(device
+            .call_method1(
+                py,
+                "set_qubit_decoherence_rates",
+                (0, wrong_shape.to_pyarray_bound(py)),
+            )
+            .is_err());
+    });
+}
+
 /// Test single_qubit_gate_names and two_qubit_gate_names functions of the devices
 #[test_case(AWSDevice::from(IonQHarmonyDevice::new()), new_device(AWSDevice::from(IonQHarmonyDevice::new())); "harmony")]
 #[test_case(AWSDevice::from(IonQAria1Device::new()), new_device(AWSDevice::from(IonQAria1Device::new())); "aria1")]
@@ -331,3 +392,122 @@ fn test_to_generic_device(device: AWSDevice, pyo3_device: Py<PyAny>) {
         assert_eq!(result, rust_result);
     })
 }
+
+/// Test to_json and from_json methods
+#[test_case(new_device(AWSDevice::from(IonQHarmonyDevice::new())); "harmony")]
+#[test_case(new_device(AWSDevice::from(IonQAria1Device::new())); "aria1")]
+#[test_case(new_device(AWSDevice::from(OQCLucyDevice::new())); "lucy")]
+#[test_case(new_device(AWSDevice::from(RigettiAspenM3Device::new())); "aspen3")]
+fn test_json(device: Py<PyAny>) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        device.call_method1(py, "add_damping", (0, 0.5)).unwrap();
+        let json = device
+            .call_method0(py, "to_json")
+            .unwrap()
+            .extract::<String>(py)
+            .unwrap();
+        let deserialized = device.call_method1(py, "from_json", (&json,)).unwrap();
+        let rates = deserialized
+            .call_method1(py, "qubit_decoherence_rates", (0,))
+            .unwrap()
+            .extract::<Vec<Vec<f64>>>(py)
+            .unwrap();
+        assert_eq!(rates[0][0], 0.5);
+        let json_again = deserialized
+            .call_method0(py, "to_json")
+            .unwrap()
+            .extract::<String>(py)
+            .unwrap();
+        assert_eq!(json_again, json);
+
+        assert!(device.call_method1(py, "from_json", ("{}",)).is_err());
+    });
+}
+
+/// Test __richcmp__ of the devices
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "harmony")]
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "aria1")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "lucy")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "aspen3")]
+fn test_richcmp(device: AWSDevice) {
+    pyo3::prepare_freethreaded_python();
+    let first = new_device(device.clone());
+    let second = new_device(device);
+    Python::with_gil(|py| {
+        let first = first.bind(py);
+        let second = second.bind(py);
+        assert!(first.eq(second).unwrap());
+        assert!(!first.ne(second).unwrap());
+
+        second.call_method1("add_damping", (0, 0.5)).unwrap();
+        assert!(!first.eq(second).unwrap());
+        assert!(first.ne(second).unwrap());
+
+        assert!(!first.eq("device").unwrap());
+        assert!(first.ne("device").unwrap());
+        assert!(first.lt(second).is_err());
+    });
+}
+
+/// Test __copy__ and __deepcopy__ of the devices
+#[test_case(new_device(AWSDevice::from(IonQHarmonyDevice::new())); "harmony")]
+#[test_case(new_device(AWSDevice::from(IonQAria1Device::new())); "aria1")]
+#[test_case(new_device(AWSDevice::from(OQCLucyDevice::new())); "lucy")]
+#[test_case(new_device(AWSDevice::from(RigettiAspenM3Device::new())); "aspen3")]
+fn test_copy_deepcopy(device: Py<PyAny>) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device = device.bind(py);
+        let copy = device.call_method0("__copy__").unwrap();
+        let deepcopy = device
+            .call_method1("__deepcopy__", (pyo3::types::PyDict::new_bound(py),))
+            .unwrap();
+        assert!(device.eq(&copy).unwrap());
+        assert!(device.eq(&deepcopy).unwrap());
+
+        copy.call_method1("add_damping", (0, 0.5)).unwrap();
+        deepcopy.call_method1("add_dephasing", (0, 0.2)).unwrap();
+        assert!(device.ne(&copy).unwrap());
+        assert!(device.ne(&deepcopy).unwrap());
+        let rates = device
+            .call_method1("qubit_decoherence_rates", (0,))
+            .unwrap()
+            .extract::<Vec<Vec<f64>>>()
+            .unwrap();
+        assert_eq!(rates, vec![vec![0.0; 3]; 3]);
+    });
+}
+
+/// Test __repr__ and __str__ of the devices
+#[test_case(new_device(AWSDevice::from(OQCLucyDevice::new())), "OQCLucyDevice"; "lucy")]
+#[test_case(new_device(AWSDevice::from(RigettiAspenM3Device::new())), "RigettiAspenM3Device"; "aspen3")]
+#[test_case(new_device(AWSDevice::from(IonQAria1Device::new())), "IonQAria1Device"; "aria1")]
+fn test_repr(device: Py<PyAny>, class_name: &str) {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let device = device.bind(py);
+        let repr = device.repr().unwrap().to_string();
+        let name = device
+            .call_method0("name")
+            .unwrap()
+            .extract::<String>()
+            .unwrap();
+        let number_qubits = device
+            .call_method0("number_qubits")
+            .unwrap()
+            .extract::<usize>()
+            .unwrap();
+        assert!(repr.starts_with(&format!("{}(name=\"{}\"", class_name, name)));
+        assert!(repr.contains(&format!("number_qubits={}", number_qubits)));
+        assert!(repr.ends_with("calibrated=False)"));
+        assert_eq!(device.str().unwrap().to_string(), repr);
+
+        device.call_method1("add_damping", (0, 0.5)).unwrap();
+        assert!(device
+            .repr()
+            .unwrap()
+            .to_string()
+            .ends_with("calibrated=True)"));
+    });
+}
@@ -10,5 +10,8 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod backend;
+
 #[cfg(test)]
 mod device;
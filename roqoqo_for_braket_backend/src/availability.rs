@@ -0,0 +1,49 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Device availability
+//!
+//! Provides the availability of a Braket device for the quantum tasks of a backend.
+
+/// Availability of a Braket device for the quantum tasks of a backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceAvailability {
+    /// The ARN of the device.
+    pub device_arn: String,
+    /// The status of the device, `ONLINE`, `OFFLINE` or `RETIRED`.
+    pub status: String,
+    /// The number of quantum tasks in the public queue of the device, `None` when Braket does
+    /// not report it.
+    pub queue_size: Option<usize>,
+    /// The reservation the tasks of the backend run in, `None` for the public queue.
+    pub reservation_arn: Option<String>,
+}
+
+impl DeviceAvailability {
+    /// Returns whether the device accepts quantum tasks.
+    pub fn is_online(&self) -> bool {
+        self.status == "ONLINE"
+    }
+
+    /// Returns the number of quantum tasks the tasks of the backend wait behind.
+    ///
+    /// # Returns
+    ///
+    /// The size of the public queue, zero for tasks of a reservation, which do not wait in the
+    /// public queue, and `None` when the size of the queue is unknown.
+    pub fn waiting_tasks(&self) -> Option<usize> {
+        match self.reservation_arn {
+            Some(_) => Some(0),
+            None => self.queue_size,
+        }
+    }
+}
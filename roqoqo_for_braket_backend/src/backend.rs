@@ -0,0 +1,2042 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Braket backend
+//!
+//! Provides the backend submitting qoqo circuits as quantum tasks to the AWS Braket devices.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use futures::stream::FuturesUnordered;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+
+use aws_sdk_braket::error::{BuildError, DisplayErrorContext};
+use aws_sdk_braket::operation::get_device::GetDeviceOutput;
+use aws_sdk_braket::operation::get_job::GetJobOutput;
+use aws_sdk_braket::operation::get_quantum_task::GetQuantumTaskOutput;
+use aws_sdk_braket::primitives::DateTime;
+use aws_sdk_braket::types::{
+    AlgorithmSpecification, Association, AssociationType, CompressionType, ContainerImage,
+    DeviceConfig, InstanceConfig, InstanceType, JobCheckpointConfig, JobOutputDataConfig,
+    JobStoppingCondition, QuantumTaskSummary, QueueName, QueuePriority, ScriptModeConfig,
+    SearchQuantumTasksFilter, SearchQuantumTasksFilterOperator,
+};
+use roqoqo::backends::{EvaluatingBackend, RegisterResult};
+use roqoqo::measurements::Measure;
+use roqoqo::operations::Operation;
+use roqoqo::registers::{BitOutputRegister, ComplexOutputRegister, FloatOutputRegister};
+use roqoqo::{Circuit, RoqoqoBackendError};
+use roqoqo_for_braket_devices::analysis::DEFAULT_NUMBER_SHOTS;
+use roqoqo_for_braket_devices::braket_ir::{
+    braket_inputs, braket_shots, circuit_to_openqasm3, circuit_to_openqasm3_verbatim,
+    free_parameters, MeasurementMapping,
+};
+use roqoqo_for_braket_devices::AWSDevice;
+use serde_json::json;
+
+use crate::retry::random_token;
+use crate::{
+    AWSConfig, ConcurrencyLimit, CostTracker, DeviceAvailability, HybridJob, IonQOptions,
+    JobDefinition, JobStatus, Pacer, QuantumTask, ResultFetcher, RetryPolicy, RigettiOptions,
+    SubmissionPayload, TaskFilter, TaskMetadata, TaskStatus, TaskSummary,
+};
+
+/// Default time between two status requests while waiting for a task.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default time after which waiting for a task is aborted, QPU queues can take many hours.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Maximum number of user tags of an AWS resource.
+const MAX_TAGS: usize = 50;
+
+/// Default number of requests of a batch sent to AWS at the same time.
+const DEFAULT_MAX_CONCURRENCY: usize = 10;
+
+/// The bit, float and complex readout registers of a circuit.
+pub type Registers = (
+    HashMap<String, BitOutputRegister>,
+    HashMap<String, FloatOutputRegister>,
+    HashMap<String, ComplexOutputRegister>,
+);
+
+/// Appends the shots of the registers of one circuit to the registers of previous circuits.
+pub(crate) fn extend_registers<T>(
+    registers: &mut HashMap<String, Vec<T>>,
+    new_registers: HashMap<String, Vec<T>>,
+) {
+    for (name, shots) in new_registers {
+        registers.entry(name).or_default().extend(shots);
+    }
+}
+
+/// Converts a timestamp returned by AWS.
+fn system_time(date_time: &DateTime) -> Result<SystemTime, RoqoqoBackendError> {
+    SystemTime::try_from(*date_time).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!("Invalid timestamp {}: {}", date_time, err),
+    })
+}
+
+/// Converts the summary of a quantum task returned by a search.
+fn task_summary(summary: &QuantumTaskSummary) -> Result<TaskSummary, RoqoqoBackendError> {
+    Ok(TaskSummary {
+        task_arn: summary.quantum_task_arn().to_string(),
+        status: summary.status().as_str().parse()?,
+        device_arn: summary.device_arn().to_string(),
+        shots: summary.shots() as usize,
+        created_at: system_time(summary.created_at())?,
+        ended_at: summary.ended_at().map(system_time).transpose()?,
+        tags: summary.tags().cloned().unwrap_or_default(),
+    })
+}
+
+/// Returns the label of a binding of free parameters in the results of a parameter sweep.
+///
+/// # Arguments
+///
+/// * `values` - The values of the free parameters.
+///
+/// # Returns
+///
+/// The parameters as `name=value` sorted by name and separated by commas, e.g. `phi=0.5,theta=1`.
+pub fn parameter_label(values: &HashMap<String, f64>) -> String {
+    let values: BTreeMap<&String, &f64> = values.iter().collect();
+    values
+        .into_iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Returns the Braket action of an OpenQASM program with the values of its free parameters.
+fn braket_action(openqasm: &str, inputs: &HashMap<String, f64>) -> String {
+    json!({
+        "braketSchemaHeader": {"name": "braket.ir.openqasm.program", "version": "1"},
+        "source": openqasm,
+        "inputs": inputs,
+    })
+    .to_string()
+}
+
+/// Returns the circuits of a measurement, each preceded by its constant circuit.
+pub(crate) fn measurement_circuits<T: Measure>(measurement: &T) -> Vec<Circuit> {
+    measurement
+        .circuits()
+        .map(|circuit| match measurement.constant_circuit() {
+            Some(constant_circuit) => constant_circuit.clone() + circuit.clone(),
+            None => circuit.clone(),
+        })
+        .collect()
+}
+
+/// Combines the registers of the circuits of a measurement, in the order of the circuits.
+pub(crate) fn combine_registers(results: Vec<Registers>) -> Registers {
+    let mut bit_registers = HashMap::new();
+    let mut float_registers = HashMap::new();
+    let mut complex_registers = HashMap::new();
+    for (bits, floats, complexes) in results {
+        extend_registers(&mut bit_registers, bits);
+        extend_registers(&mut float_registers, floats);
+        extend_registers(&mut complex_registers, complexes);
+    }
+    (bit_registers, float_registers, complex_registers)
+}
+
+/// Backend running qoqo circuits on an AWS Braket device.
+///
+/// The circuits are converted into Braket OpenQASM 3 programs for the device and submitted as
+/// quantum tasks. The results of the tasks are written by Braket to an S3 bucket, from which the
+/// backend downloads them and sorts the measured bits into the readout registers of the circuit.
+///
+/// The access to AWS (credentials, region and result bucket) is set by the [AWSConfig] of the
+/// backend. The estimated cost of the submitted tasks is accumulated by a [CostTracker], which
+/// refuses further submissions once its maximum cost is reached.
+///
+/// Every method has an async variant running on the tokio runtime of the caller. The blocking
+/// methods run on a runtime owned by the backend and are only available for backends created with
+/// [APIBackend::new], they must not be called from within an async context.
+#[derive(Debug)]
+pub struct APIBackend {
+    /// The device the circuits are run on.
+    device: AWSDevice,
+    /// The configuration of the access to AWS.
+    config: AWSConfig,
+    /// The time between two status requests while waiting for a task.
+    poll_interval: Duration,
+    /// The time after which waiting for a task is aborted.
+    timeout: Duration,
+    /// The number of requests of a batch sent to AWS at the same time.
+    max_concurrency: usize,
+    /// The limit of the task creation requests of the backend sent at the same time.
+    device_limit: ConcurrencyLimit,
+    /// The limit of the task creation requests sent at the same time shared with other backends.
+    global_limit: Option<ConcurrencyLimit>,
+    /// The reservation the tasks and jobs run in, `None` for the public queue.
+    reservation_arn: Option<String>,
+    /// The tags of the created tasks and jobs.
+    tags: HashMap<String, String>,
+    /// The error mitigation options of the tasks on IonQ devices.
+    ionq_options: Option<IonQOptions>,
+    /// The compilation options of the tasks on Rigetti devices.
+    rigetti_options: Option<RigettiOptions>,
+    /// Whether submissions only record their payload instead of creating tasks.
+    dry_run: bool,
+    /// The payloads recorded by submissions in dry-run mode.
+    dry_run_payloads: Mutex<Vec<SubmissionPayload>>,
+    /// The ARNs of the submitted tasks that may not have finished yet.
+    submitted_tasks: Mutex<Vec<String>>,
+    /// The estimated cost of the submitted tasks.
+    cost_tracker: Mutex<CostTracker>,
+    /// The policy for retrying requests failing with throttling or transient errors.
+    retry_policy: RetryPolicy,
+    /// The pacing of the requests, slowing down while requests are throttled.
+    pacer: Pacer,
+    /// The client of the Braket API.
+    braket_client: aws_sdk_braket::Client,
+    /// The downloader of the task results.
+    result_fetcher: ResultFetcher,
+    /// The runtime executing the requests of the blocking methods, `None` for backends created
+    /// with [APIBackend::new_async].
+    runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl APIBackend {
+    /// Creates a new APIBackend.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the circuits are run on.
+    /// * `config` - The configuration of the access to AWS.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(APIBackend)` - The new backend.
+    /// * `Err(RoqoqoBackendError)` - The runtime executing the requests could not be started.
+    pub fn new(device: AWSDevice, config: AWSConfig) -> Result<Self, RoqoqoBackendError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not start the runtime of the backend: {}", err),
+            })?;
+        let mut backend = runtime.block_on(Self::new_async(device, config));
+        backend.runtime = Some(runtime);
+        Ok(backend)
+    }
+
+    /// Creates a new APIBackend used from an async context.
+    ///
+    /// The backend does not own a runtime, only the async methods can be used.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the circuits are run on.
+    /// * `config` - The configuration of the access to AWS.
+    ///
+    /// # Returns
+    ///
+    /// The new backend.
+    pub async fn new_async(device: AWSDevice, config: AWSConfig) -> Self {
+        let sdk_config = config.load(&device).await;
+        // Emulators and proxies do not resolve buckets from the host name of a request
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(config.endpoint_url().is_some())
+            .build();
+        let pacer = Pacer::default();
+        let mut result_fetcher = ResultFetcher::new(aws_sdk_s3::Client::from_conf(s3_config));
+        result_fetcher.set_pacer(pacer.clone());
+        APIBackend {
+            device,
+            config,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            device_limit: ConcurrencyLimit::new(DEFAULT_MAX_CONCURRENCY),
+            global_limit: None,
+            reservation_arn: None,
+            tags: HashMap::new(),
+            ionq_options: None,
+            rigetti_options: None,
+            dry_run: false,
+            dry_run_payloads: Mutex::new(Vec::new()),
+            submitted_tasks: Mutex::new(Vec::new()),
+            cost_tracker: Mutex::new(CostTracker::default()),
+            retry_policy: RetryPolicy::default(),
+            pacer,
+            braket_client: aws_sdk_braket::Client::new(&sdk_config),
+            result_fetcher,
+            runtime: None,
+        }
+    }
+
+    /// Returns the device the circuits are run on.
+    pub fn device(&self) -> &AWSDevice {
+        &self.device
+    }
+
+    /// Returns the configuration of the access to AWS.
+    pub fn config(&self) -> &AWSConfig {
+        &self.config
+    }
+
+    /// Sets the time between two status requests while waiting for a task.
+    ///
+    /// # Arguments
+    ///
+    /// * `poll_interval` - The time between two status requests.
+    pub fn set_poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = poll_interval;
+    }
+
+    /// Sets the time after which waiting for a task is aborted.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time to wait for a task.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Sets the number of requests of a batch sent to AWS at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_concurrency` - The maximum number of concurrent requests, at least one.
+    pub fn set_max_concurrency(&mut self, max_concurrency: usize) {
+        self.max_concurrency = max_concurrency.max(1);
+    }
+
+    /// Returns the limit of the task creation requests of the backend sent at the same time.
+    pub fn device_limit(&self) -> &ConcurrencyLimit {
+        &self.device_limit
+    }
+
+    /// Sets the limit of the task creation requests of the backend sent at the same time.
+    ///
+    /// Unlike the maximum concurrency, which applies to each batch, the limit applies to all
+    /// submissions of the backend, e.g. of several batches submitted at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `permits` - The maximum number of concurrent creation requests, at least one.
+    pub fn set_device_limit(&mut self, permits: usize) {
+        self.device_limit = ConcurrencyLimit::new(permits);
+    }
+
+    /// Returns the limit of the task creation requests shared with other backends.
+    pub fn global_limit(&self) -> Option<&ConcurrencyLimit> {
+        self.global_limit.as_ref()
+    }
+
+    /// Sets the limit of the task creation requests shared with other backends.
+    ///
+    /// # Arguments
+    ///
+    /// * `global_limit` - The limit, set on all backends whose requests it bounds together,
+    ///   `None` for no shared limit.
+    pub fn set_global_limit(&mut self, global_limit: Option<ConcurrencyLimit>) {
+        self.global_limit = global_limit;
+    }
+
+    /// Returns the reservation the tasks and jobs run in, `None` for the public queue.
+    pub fn reservation_arn(&self) -> Option<&str> {
+        self.reservation_arn.as_deref()
+    }
+
+    /// Sets the reservation the tasks and jobs of the backend run in.
+    ///
+    /// Tasks and jobs of a reservation run in the dedicated-access window of the reservation
+    /// instead of waiting in the public queue of the device. Submissions outside of the window
+    /// of the reservation are rejected by Braket.
+    ///
+    /// # Arguments
+    ///
+    /// * `reservation_arn` - The ARN of the reservation, `None` for the public queue.
+    pub fn set_reservation_arn(&mut self, reservation_arn: Option<&str>) {
+        self.reservation_arn = reservation_arn.map(|reservation_arn| reservation_arn.to_string());
+    }
+
+    /// Returns the tags of the created tasks and jobs.
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    /// Sets a tag attached to every created task and job, e.g. for cost attribution.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the tag, up to 128 characters and not starting with `aws:`.
+    /// * `value` - The value of the tag, up to 256 characters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The tag was set, replacing a tag with the same key.
+    /// * `Err(RoqoqoBackendError)` - The tag is not accepted by AWS.
+    pub fn set_tag(&mut self, key: &str, value: &str) -> Result<(), RoqoqoBackendError> {
+        let invalid = |reason: &str| RoqoqoBackendError::GenericError {
+            msg: format!("Invalid tag {}: {}", key, reason),
+        };
+        if key.is_empty() || key.chars().count() > 128 {
+            return Err(invalid("the key must have 1 to 128 characters"));
+        }
+        if key.to_lowercase().starts_with("aws:") {
+            return Err(invalid("the prefix aws: is reserved"));
+        }
+        if value.chars().count() > 256 {
+            return Err(invalid("the value must have at most 256 characters"));
+        }
+        if !self.tags.contains_key(key) && self.tags.len() >= MAX_TAGS {
+            return Err(invalid(&format!("at most {} tags are allowed", MAX_TAGS)));
+        }
+        self.tags.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Removes a tag attached to the created tasks and jobs.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the tag.
+    ///
+    /// # Returns
+    ///
+    /// The value of the removed tag, `None` if the tag was not set.
+    pub fn remove_tag(&mut self, key: &str) -> Option<String> {
+        self.tags.remove(key)
+    }
+
+    /// Returns the error mitigation options of the tasks on IonQ devices.
+    pub fn ionq_options(&self) -> Option<IonQOptions> {
+        self.ionq_options
+    }
+
+    /// Sets the error mitigation options of the tasks on IonQ devices.
+    ///
+    /// The options are sent as the device parameters of the submitted tasks. With sharpening,
+    /// the registers of the tasks retrieved by the backend are built from the sharpened results,
+    /// see [crate::BraketResult::sharpen].
+    ///
+    /// # Arguments
+    ///
+    /// * `ionq_options` - The options, `None` to run the tasks with the defaults of the device.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The options were set.
+    /// * `Err(RoqoqoBackendError)` - The options are not supported by the device.
+    pub fn set_ionq_options(
+        &mut self,
+        ionq_options: Option<IonQOptions>,
+    ) -> Result<(), RoqoqoBackendError> {
+        if let Some(options) = &ionq_options {
+            options.validate(&self.device)?;
+        }
+        self.ionq_options = ionq_options;
+        Ok(())
+    }
+
+    /// Returns the compilation options of the tasks on Rigetti devices.
+    pub fn rigetti_options(&self) -> Option<RigettiOptions> {
+        self.rigetti_options
+    }
+
+    /// Sets the compilation options of the tasks on Rigetti devices.
+    ///
+    /// The qubit rewiring option is sent as the device parameters of the submitted tasks.
+    /// Verbatim circuits are converted with
+    /// [roqoqo_for_braket_devices::braket_ir::circuit_to_openqasm3_verbatim] and have to consist
+    /// of native gates acting on connected qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `rigetti_options` - The options, `None` to let Braket compile the circuits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The options were set.
+    /// * `Err(RoqoqoBackendError)` - The device is not a Rigetti device.
+    pub fn set_rigetti_options(
+        &mut self,
+        rigetti_options: Option<RigettiOptions>,
+    ) -> Result<(), RoqoqoBackendError> {
+        if let Some(options) = &rigetti_options {
+            options.validate(&self.device)?;
+        }
+        self.rigetti_options = rigetti_options;
+        Ok(())
+    }
+
+    /// Returns whether submissions only record their payload instead of creating tasks.
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Sets whether submissions only record their payload instead of creating tasks.
+    ///
+    /// In dry-run mode, circuits are converted and validated as usual, but the payloads of the
+    /// requests are recorded instead of being sent, see [APIBackend::take_dry_run_payloads]. The
+    /// returned tasks have ARNs starting with `dry-run/`, no cost is recorded and all requests
+    /// to AWS, e.g. for the status of a task, fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `dry_run` - Whether AWS is not contacted.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Returns the payloads recorded in dry-run mode and clears them.
+    ///
+    /// # Returns
+    ///
+    /// The payloads in the order of the submissions.
+    pub fn take_dry_run_payloads(&self) -> Vec<SubmissionPayload> {
+        match self.dry_run_payloads.lock() {
+            Ok(mut payloads) => std::mem::take(&mut *payloads),
+            Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+        }
+    }
+
+    /// Builds the payload of the request creating the quantum task of a circuit.
+    ///
+    /// The circuit is converted and validated as in [APIBackend::submit_async], without
+    /// contacting AWS.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SubmissionPayload)` - The payload of the request.
+    /// * `Err(RoqoqoBackendError)` - The circuit cannot be run on the device.
+    pub fn payload(&self, circuit: &Circuit) -> Result<SubmissionPayload, RoqoqoBackendError> {
+        let shots = braket_shots(circuit)?.unwrap_or(DEFAULT_NUMBER_SHOTS);
+        self.device
+            .validate_shots(shots)
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Cannot submit circuit: {}", err),
+            })?;
+        let openqasm = if self.rigetti_options.is_some_and(|options| options.verbatim) {
+            circuit_to_openqasm3_verbatim(circuit, &self.device)?
+        } else {
+            circuit_to_openqasm3(circuit, &self.device)?
+        };
+        let device_parameters = match (self.ionq_options, self.rigetti_options) {
+            (Some(options), _) => Some(options.device_parameters().to_string()),
+            (None, Some(options)) => Some(options.device_parameters().to_string()),
+            (None, None) => None,
+        };
+        let inputs = braket_inputs(circuit, &HashMap::new())?;
+        let mapping = MeasurementMapping::from_circuit(circuit)?;
+        let action = braket_action(&openqasm, &inputs);
+        Ok(SubmissionPayload {
+            device_arn: self.device.clone().name(),
+            openqasm,
+            action,
+            device_parameters,
+            shots,
+            output_s3_bucket: self.config.s3_bucket().to_string(),
+            output_s3_key_prefix: self.config.s3_prefix().to_string(),
+            reservation_arn: self.reservation_arn.clone(),
+            tags: self.tags.clone(),
+            mapping,
+        })
+    }
+
+    /// Returns the estimated cost of the tasks submitted by the backend.
+    ///
+    /// # Returns
+    ///
+    /// A copy of the cost tracker of the backend.
+    pub fn cost_tracker(&self) -> CostTracker {
+        match self.cost_tracker.lock() {
+            Ok(cost_tracker) => cost_tracker.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Sets the cost tracker accumulating the estimated cost of the submitted tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost_tracker` - The cost tracker, e.g. with a maximum cost or the cost of earlier tasks.
+    pub fn set_cost_tracker(&mut self, cost_tracker: CostTracker) {
+        self.cost_tracker = Mutex::new(cost_tracker);
+    }
+
+    /// Sets the policy for retrying requests failing with throttling or transient errors.
+    ///
+    /// The policy applies to all requests of the backend, including the download of results.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy` - The retry policy.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+        self.result_fetcher.set_retry_policy(retry_policy);
+    }
+
+    /// Returns the pacing of the requests of the backend.
+    pub fn pacer(&self) -> &Pacer {
+        &self.pacer
+    }
+
+    /// Sets the pacing of the requests of the backend.
+    ///
+    /// The pacer applies to all requests of the backend, including the polling of tasks and the
+    /// download of results.
+    ///
+    /// # Arguments
+    ///
+    /// * `pacer` - The pacer, e.g. a clone of the pacer of another backend to pace the requests
+    ///   of both together.
+    pub fn set_pacer(&mut self, pacer: Pacer) {
+        self.result_fetcher.set_pacer(pacer.clone());
+        self.pacer = pacer;
+    }
+
+    /// Updates the decoherence rates of the device from its current calibration.
+    ///
+    /// See [APIBackend::fetch_calibration_async].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The decoherence rates were updated.
+    /// * `Err(RoqoqoBackendError)` - The device properties could not be requested or parsed.
+    pub fn fetch_calibration(&mut self) -> Result<(), RoqoqoBackendError> {
+        let properties = self.block_on(self.device_properties_async())?;
+        self.device
+            .set_coherence_times_from_properties(&properties)
+            .map_err(RoqoqoBackendError::from)
+    }
+
+    /// Updates the decoherence rates of the device from its current calibration.
+    ///
+    /// The device properties are requested from Braket and the T1 and T2 times of the qubits are
+    /// set with [AWSDevice::set_coherence_times_from_properties].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The decoherence rates were updated.
+    /// * `Err(RoqoqoBackendError)` - The device properties could not be requested or parsed.
+    #[tracing::instrument(
+        name = "fetch_calibration",
+        skip_all,
+        fields(device = %self.device.clone().name()),
+        err
+    )]
+    pub async fn fetch_calibration_async(&mut self) -> Result<(), RoqoqoBackendError> {
+        let properties = self.device_properties_async().await?;
+        self.device
+            .set_coherence_times_from_properties(&properties)
+            .map_err(RoqoqoBackendError::from)?;
+        tracing::info!("Updated the decoherence rates from the device properties");
+        Ok(())
+    }
+
+    /// Returns the availability of the device for the tasks of the backend.
+    ///
+    /// See [APIBackend::availability_async].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DeviceAvailability)` - The availability of the device.
+    /// * `Err(RoqoqoBackendError)` - The device could not be requested.
+    pub fn availability(&self) -> Result<DeviceAvailability, RoqoqoBackendError> {
+        self.block_on(self.availability_async())
+    }
+
+    /// Submits a circuit as a quantum task to the device.
+    ///
+    /// See [APIBackend::submit_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QuantumTask)` - The handle of the submitted task.
+    /// * `Err(RoqoqoBackendError)` - The circuit cannot be run on the device or the task could
+    ///   not be created.
+    pub fn submit(&self, circuit: &Circuit) -> Result<QuantumTask, RoqoqoBackendError> {
+        self.block_on(self.submit_async(circuit))
+    }
+
+    /// Submits a routed circuit, whose results are sorted into the registers of the original.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit before routing.
+    /// * `routed` - The routed circuit run on the device.
+    /// * `layout` - The device qubit of every qubit of the original circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QuantumTask)` - The handle of the submitted task.
+    /// * `Err(RoqoqoBackendError)` - The routed circuit cannot be run on the device, does not
+    ///   measure the qubits of the original circuit or its task could not be created.
+    pub fn submit_routed(
+        &self,
+        circuit: &Circuit,
+        routed: &Circuit,
+        layout: &HashMap<usize, usize>,
+    ) -> Result<QuantumTask, RoqoqoBackendError> {
+        self.block_on(self.submit_routed_async(circuit, routed, layout))
+    }
+
+    /// Returns the current status of a quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The status of the task.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested.
+    pub fn status(&self, task: &QuantumTask) -> Result<TaskStatus, RoqoqoBackendError> {
+        self.block_on(self.status_async(task))
+    }
+
+    /// Returns the metadata of a quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_arn` - The ARN of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskMetadata)` - The metadata of the task.
+    /// * `Err(RoqoqoBackendError)` - The metadata could not be requested.
+    pub fn task_metadata(&self, task_arn: &str) -> Result<TaskMetadata, RoqoqoBackendError> {
+        self.block_on(self.task_metadata_async(task_arn))
+    }
+
+    /// Lists the quantum tasks of the account in the region of the backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The criteria the listed tasks must match.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<TaskSummary>)` - The summaries of the matching tasks.
+    /// * `Err(RoqoqoBackendError)` - The tasks could not be requested.
+    pub fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<TaskSummary>, RoqoqoBackendError> {
+        self.block_on(self.list_tasks_async(filter))
+    }
+
+    /// Waits until a quantum task has completed, failed or been cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The final status of the task.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested or the task did not finish
+    ///   within the timeout of the backend.
+    pub fn poll(&self, task: &QuantumTask) -> Result<TaskStatus, RoqoqoBackendError> {
+        self.block_on(self.poll_async(task))
+    }
+
+    /// Waits for a quantum task and returns its measured registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of the
+    ///   circuit of the task.
+    /// * `Err(RoqoqoBackendError)` - The task failed, was cancelled or did not finish within the
+    ///   timeout of the backend, or its results could not be downloaded.
+    pub fn results(&self, task: &QuantumTask) -> RegisterResult {
+        self.block_on(self.results_async(task))
+    }
+
+    /// Submits a batch of circuits as quantum tasks to the device.
+    ///
+    /// See [APIBackend::submit_batch_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<QuantumTask>)` - The handles of the submitted tasks in the order of the circuits.
+    /// * `Err(RoqoqoBackendError)` - A circuit cannot be run on the device or its task could not
+    ///   be created.
+    pub fn submit_batch(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<Vec<QuantumTask>, RoqoqoBackendError> {
+        self.block_on(self.submit_batch_async(circuits))
+    }
+
+    /// Waits for a batch of quantum tasks and returns their measured registers.
+    ///
+    /// See [APIBackend::results_batch_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The submitted tasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Registers>)` - The readout registers of every task in the order of the tasks.
+    /// * `Err(RoqoqoBackendError)` - The results of a task could not be retrieved.
+    pub fn results_batch(
+        &self,
+        tasks: &[QuantumTask],
+    ) -> Result<Vec<Registers>, RoqoqoBackendError> {
+        self.block_on(self.results_batch_async(tasks))
+    }
+
+    /// Waits for a batch of quantum tasks and passes the results of every task to a callback as
+    /// soon as the task has finished.
+    ///
+    /// See [APIBackend::results_stream].
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The submitted tasks.
+    /// * `callback` - Called with the index of every task and its readout registers, or the
+    ///   error retrieving them, in the order in which the tasks finish.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The callback was called for every task.
+    /// * `Err(RoqoqoBackendError)` - The backend cannot run blocking methods.
+    pub fn for_each_result<F>(
+        &self,
+        tasks: &[QuantumTask],
+        mut callback: F,
+    ) -> Result<(), RoqoqoBackendError>
+    where
+        F: FnMut(usize, RegisterResult),
+    {
+        self.block_on(async {
+            self.results_stream(tasks)
+                .for_each(|(index, result)| {
+                    callback(index, result);
+                    async {}
+                })
+                .await;
+            Ok(())
+        })
+    }
+
+    /// Runs a batch of circuits on the device.
+    ///
+    /// See [APIBackend::run_circuit_batch_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Registers>)` - The readout registers of every circuit in the order of the
+    ///   circuits.
+    /// * `Err(RoqoqoBackendError)` - A circuit could not be run.
+    pub fn run_circuit_batch(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<Vec<Registers>, RoqoqoBackendError> {
+        self.block_on(self.run_circuit_batch_async(circuits))
+    }
+
+    /// Runs circuits with free parameters for several bindings of the parameters.
+    ///
+    /// See [APIBackend::run_parameter_sweep_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits with free parameters.
+    /// * `parameter_sets` - The bindings of the free parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BTreeMap<String, Registers>)` - The combined registers of the circuits for every
+    ///   binding, keyed by [parameter_label].
+    /// * `Err(RoqoqoBackendError)` - A circuit could not be run or two bindings have the same
+    ///   label.
+    pub fn run_parameter_sweep(
+        &self,
+        circuits: &[Circuit],
+        parameter_sets: &[HashMap<String, f64>],
+    ) -> Result<BTreeMap<String, Registers>, RoqoqoBackendError> {
+        self.block_on(self.run_parameter_sweep_async(circuits, parameter_sets))
+    }
+
+    /// Cancels a quantum task.
+    ///
+    /// See [APIBackend::cancel_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `task_arn` - The ARN of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The status of the task after the cancellation request.
+    /// * `Err(RoqoqoBackendError)` - The task could not be cancelled.
+    pub fn cancel(&self, task_arn: &str) -> Result<TaskStatus, RoqoqoBackendError> {
+        self.block_on(self.cancel_async(task_arn))
+    }
+
+    /// Cancels all unfinished quantum tasks submitted by the backend.
+    ///
+    /// See [APIBackend::cancel_all_async].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The ARNs of the tasks whose cancellation was requested.
+    /// * `Err(RoqoqoBackendError)` - At least one task could not be cancelled.
+    pub fn cancel_all(&self) -> Result<Vec<String>, RoqoqoBackendError> {
+        self.block_on(self.cancel_all_async())
+    }
+
+    /// Creates a hybrid job running a workload next to the device.
+    ///
+    /// See [APIBackend::create_job_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The definition of the job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HybridJob)` - The handle of the created job.
+    /// * `Err(RoqoqoBackendError)` - The definition is invalid or the job could not be created.
+    pub fn create_job(&self, definition: &JobDefinition) -> Result<HybridJob, RoqoqoBackendError> {
+        self.block_on(self.create_job_async(definition))
+    }
+
+    /// Returns the current status of a hybrid job.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The status of the job.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested.
+    pub fn job_status(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        self.block_on(self.job_status_async(job))
+    }
+
+    /// Waits until a hybrid job has completed, failed or been cancelled.
+    ///
+    /// See [APIBackend::poll_job_async].
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The final status of the job.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested, the job failed or did
+    ///   not finish within the timeout of the backend.
+    pub fn poll_job(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        self.block_on(self.poll_job_async(job))
+    }
+
+    /// Cancels a hybrid job.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The status of the job after the cancellation request.
+    /// * `Err(RoqoqoBackendError)` - The job could not be cancelled.
+    pub fn cancel_job(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        self.block_on(self.cancel_job_async(job))
+    }
+
+    /// Returns the availability of the device for the tasks of the backend.
+    ///
+    /// The status and the size of the public quantum task queue of the device are requested from
+    /// Braket. With a reservation, the tasks of the backend do not wait in the public queue.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DeviceAvailability)` - The availability of the device.
+    /// * `Err(RoqoqoBackendError)` - The device could not be requested.
+    pub async fn availability_async(&self) -> Result<DeviceAvailability, RoqoqoBackendError> {
+        let output = self.get_device().await?;
+        // Braket reports large queues as e.g. ">4000", which is returned as the lower bound
+        let queue_size = output
+            .device_queue_info()
+            .iter()
+            .find(|info| {
+                info.queue() == &QueueName::QuantumTasksQueue
+                    && info.queue_priority() != Some(&QueuePriority::Priority)
+            })
+            .and_then(|info| info.queue_size().trim_start_matches('>').parse().ok());
+        Ok(DeviceAvailability {
+            device_arn: output.device_arn().to_string(),
+            status: output.device_status().as_str().to_string(),
+            queue_size,
+            reservation_arn: self.reservation_arn.clone(),
+        })
+    }
+
+    /// Submits a circuit as a quantum task to the device.
+    ///
+    /// The circuit is converted with
+    /// [roqoqo_for_braket_devices::braket_ir::circuit_to_openqasm3], its free parameters take the
+    /// values of its InputSymbolic operations. Circuits that do not set a number of measurements
+    /// are run with 100 shots. The estimated cost of the task is recorded by the cost tracker of
+    /// the backend, tasks exceeding its maximum cost are not submitted. With a reservation, the
+    /// task runs in the reservation instead of the public queue. In dry-run mode, the payload of
+    /// the task is recorded instead, see [APIBackend::set_dry_run].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QuantumTask)` - The handle of the submitted task.
+    /// * `Err(RoqoqoBackendError)` - The circuit cannot be run on the device, the task would
+    ///   exceed the maximum cost or could not be created.
+    pub async fn submit_async(&self, circuit: &Circuit) -> Result<QuantumTask, RoqoqoBackendError> {
+        self.submit_payload_async(self.payload(circuit)?).await
+    }
+
+    /// Submits a routed circuit, whose results are sorted into the registers of the original.
+    ///
+    /// The routed circuit is submitted as in [APIBackend::submit_async]. The measurement mapping
+    /// of the task is built from the original circuit and relabeled with the layout, see
+    /// [MeasurementMapping::remap_qubits], so the results of the task fill the readout registers
+    /// of the original circuit with the bits of its qubits, wherever routing placed them.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit before routing.
+    /// * `routed` - The routed circuit run on the device, e.g. by
+    ///   [roqoqo_for_braket_devices::routing::route_circuit].
+    /// * `layout` - The device qubit of every qubit of the original circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QuantumTask)` - The handle of the submitted task.
+    /// * `Err(RoqoqoBackendError)` - The routed circuit cannot be run on the device, does not
+    ///   measure the qubits of the original circuit or its task could not be created.
+    pub async fn submit_routed_async(
+        &self,
+        circuit: &Circuit,
+        routed: &Circuit,
+        layout: &HashMap<usize, usize>,
+    ) -> Result<QuantumTask, RoqoqoBackendError> {
+        let mut payload = self.payload(routed)?;
+        let mapping = MeasurementMapping::from_circuit(circuit)?.remap_qubits(layout);
+        let measured = payload.mapping.measured_qubits();
+        if let Some(qubit) = mapping
+            .measured_qubits()
+            .into_iter()
+            .find(|qubit| measured.binary_search(qubit).is_err())
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Routed circuit does not measure device qubit {} needed by the readout \
+                     registers of the original circuit",
+                    qubit
+                ),
+            });
+        }
+        payload.mapping = mapping;
+        self.submit_payload_async(payload).await
+    }
+
+    /// Creates the quantum task of a payload built by [APIBackend::payload].
+    #[tracing::instrument(
+        name = "submit",
+        skip_all,
+        fields(
+            device = %payload.device_arn,
+            shots = payload.shots,
+            task_arn = tracing::field::Empty,
+        ),
+        err
+    )]
+    async fn submit_payload_async(
+        &self,
+        payload: SubmissionPayload,
+    ) -> Result<QuantumTask, RoqoqoBackendError> {
+        let shots = payload.shots;
+        if self.dry_run {
+            tracing::debug!("Recording the payload in dry-run mode");
+            return self.record_dry_run(payload);
+        }
+        let associations = self.associations()?;
+        // The permits are held during all attempts of the creation request
+        let _device_permit = self.device_limit.acquire().await?;
+        let _global_permit = match &self.global_limit {
+            Some(global_limit) => Some(global_limit.acquire().await?),
+            None => None,
+        };
+        let cost = self.lock_cost_tracker()?.add_task(&self.device, shots)?;
+
+        // The client token is kept for all attempts, so a retried request cannot create a
+        // second task
+        let client_token = random_token();
+        let output = match self
+            .retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .create_quantum_task()
+                    .client_token(&client_token)
+                    .device_arn(&payload.device_arn)
+                    .action(&payload.action)
+                    .set_device_parameters(payload.device_parameters.clone())
+                    .shots(shots as i64)
+                    .output_s3_bucket(&payload.output_s3_bucket)
+                    .output_s3_key_prefix(&payload.output_s3_key_prefix)
+                    .set_associations(associations.clone())
+                    .set_tags(Some(payload.tags.clone()))
+                    .send()
+            })
+            .await
+        {
+            Ok(output) => output,
+            Err(err) => {
+                self.lock_cost_tracker()?.remove_task(cost, shots);
+                return Err(RoqoqoBackendError::NetworkError {
+                    msg: format!(
+                        "Could not create the quantum task: {}",
+                        DisplayErrorContext(err)
+                    ),
+                });
+            }
+        };
+        tracing::Span::current().record("task_arn", output.quantum_task_arn());
+        tracing::info!(cost, "Created quantum task");
+        self.track_task(output.quantum_task_arn());
+        Ok(QuantumTask::new(
+            output.quantum_task_arn(),
+            shots,
+            payload.mapping,
+        ))
+    }
+
+    /// Returns the current status of a quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The status of the task.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested.
+    pub async fn status_async(&self, task: &QuantumTask) -> Result<TaskStatus, RoqoqoBackendError> {
+        let output = self.get_task(task.task_arn()).await?;
+        output.status().as_str().parse()
+    }
+
+    /// Returns the metadata of a quantum task.
+    ///
+    /// The task does not have to be submitted by the backend, but it has to be in the region of
+    /// the backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_arn` - The ARN of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskMetadata)` - The metadata of the task.
+    /// * `Err(RoqoqoBackendError)` - The metadata could not be requested.
+    pub async fn task_metadata_async(
+        &self,
+        task_arn: &str,
+    ) -> Result<TaskMetadata, RoqoqoBackendError> {
+        let output = self.get_task(task_arn).await?;
+        Ok(TaskMetadata {
+            task_arn: output.quantum_task_arn().to_string(),
+            status: output.status().as_str().parse()?,
+            device_arn: output.device_arn().to_string(),
+            shots: output.shots() as usize,
+            created_at: system_time(output.created_at())?,
+            ended_at: output.ended_at().map(system_time).transpose()?,
+            failure_reason: output.failure_reason().map(|reason| reason.to_string()),
+        })
+    }
+
+    /// Lists the quantum tasks of the account in the region of the backend.
+    ///
+    /// The tasks are not restricted to the tasks submitted by the backend or to its device. The
+    /// device and status criteria of the filter are sent to Braket, the tag and creation time
+    /// criteria are applied to the returned summaries. All pages of the search are requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The criteria the listed tasks must match.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<TaskSummary>)` - The summaries of the matching tasks.
+    /// * `Err(RoqoqoBackendError)` - The tasks could not be requested.
+    pub async fn list_tasks_async(
+        &self,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskSummary>, RoqoqoBackendError> {
+        self.check_not_dry_run()?;
+        let invalid = |err: BuildError| RoqoqoBackendError::GenericError {
+            msg: format!("Invalid quantum task filter: {}", err),
+        };
+        let mut filters = Vec::new();
+        if let Some(device_arn) = &filter.device_arn {
+            filters.push(
+                SearchQuantumTasksFilter::builder()
+                    .name("deviceArn")
+                    .operator(SearchQuantumTasksFilterOperator::Equal)
+                    .values(device_arn)
+                    .build()
+                    .map_err(invalid)?,
+            );
+        }
+        if let Some(status) = filter.status {
+            filters.push(
+                SearchQuantumTasksFilter::builder()
+                    .name("status")
+                    .operator(SearchQuantumTasksFilterOperator::Equal)
+                    .values(status.to_string())
+                    .build()
+                    .map_err(invalid)?,
+            );
+        }
+        let mut summaries = Vec::new();
+        let mut next_token: Option<String> = None;
+        loop {
+            let output = self
+                .retry_policy
+                .run(&self.pacer, || {
+                    self.braket_client
+                        .search_quantum_tasks()
+                        .set_filters(Some(filters.clone()))
+                        .set_next_token(next_token.clone())
+                        .send()
+                })
+                .await
+                .map_err(|err| RoqoqoBackendError::NetworkError {
+                    msg: format!(
+                        "Could not search quantum tasks: {}",
+                        DisplayErrorContext(err)
+                    ),
+                })?;
+            for summary in output.quantum_tasks() {
+                let summary = task_summary(summary)?;
+                if filter.matches(&summary) {
+                    summaries.push(summary);
+                }
+            }
+            match output.next_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+        Ok(summaries)
+    }
+
+    /// Waits until a quantum task has completed, failed or been cancelled.
+    ///
+    /// The status of the task is requested in the poll interval of the backend, the caller's
+    /// runtime is free to run other tasks in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The final status of the task.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested or the task did not finish
+    ///   within the timeout of the backend.
+    pub async fn poll_async(&self, task: &QuantumTask) -> Result<TaskStatus, RoqoqoBackendError> {
+        let output = self.wait_for_task(task).await?;
+        output.status().as_str().parse()
+    }
+
+    /// Waits for a quantum task and returns its measured registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of the
+    ///   circuit of the task.
+    /// * `Err(RoqoqoBackendError)` - The task failed, was cancelled or did not finish within the
+    ///   timeout of the backend, or its results could not be downloaded.
+    pub async fn results_async(&self, task: &QuantumTask) -> RegisterResult {
+        let output = self.wait_for_task(task).await?;
+        match output.status().as_str().parse()? {
+            TaskStatus::Completed => {}
+            status => {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Quantum task {} finished with status {}: {}",
+                        task.task_arn(),
+                        status,
+                        output.failure_reason().unwrap_or("no reason given")
+                    ),
+                })
+            }
+        }
+        let mut result = self
+            .result_fetcher
+            .fetch(output.output_s3_bucket(), output.output_s3_directory())
+            .await?;
+        if self.ionq_options.is_some_and(|options| options.sharpen) {
+            result.sharpen(task.shots())?;
+        }
+        result.registers(task.mapping())
+    }
+
+    /// Submits a batch of circuits as quantum tasks to the device.
+    ///
+    /// The circuits are submitted in parallel, with at most the maximum concurrency of the
+    /// backend of creation requests sent at the same time, further bounded by the device and
+    /// global limits of the backend. When a submission fails, the tasks submitted before keep
+    /// running.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<QuantumTask>)` - The handles of the submitted tasks in the order of the circuits.
+    /// * `Err(RoqoqoBackendError)` - A circuit cannot be run on the device or its task could not
+    ///   be created.
+    pub async fn submit_batch_async(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<Vec<QuantumTask>, RoqoqoBackendError> {
+        stream::iter(circuits)
+            .map(|circuit| self.submit_async(circuit))
+            .buffered(self.max_concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Waits for a batch of quantum tasks and returns their measured registers.
+    ///
+    /// At most the maximum concurrency of the backend of tasks are polled and downloaded at the
+    /// same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The submitted tasks.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Registers>)` - The readout registers of every task in the order of the tasks.
+    /// * `Err(RoqoqoBackendError)` - The results of a task could not be retrieved.
+    pub async fn results_batch_async(
+        &self,
+        tasks: &[QuantumTask],
+    ) -> Result<Vec<Registers>, RoqoqoBackendError> {
+        stream::iter(tasks)
+            .map(|task| self.results_async(task))
+            .buffered(self.max_concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Returns the results of a batch of quantum tasks as soon as every task has finished.
+    ///
+    /// Unlike [APIBackend::results_batch_async], the stream does not wait for the whole batch
+    /// and does not stop at the first failed task, so long sweeps can be analysed while they
+    /// run. All tasks are polled at the same time, so a task that finishes early is returned
+    /// early even if it comes after many long running tasks. Throttled status requests are slowed
+    /// down by the pacer of the backend, the maximum concurrency only limits the submission.
+    ///
+    /// # Arguments
+    ///
+    /// * `tasks` - The submitted tasks.
+    ///
+    /// # Returns
+    ///
+    /// A stream of the index of every task with its readout registers, or the error retrieving
+    /// them, in the order in which the tasks finish.
+    pub fn results_stream<'a>(
+        &'a self,
+        tasks: &'a [QuantumTask],
+    ) -> impl Stream<Item = (usize, RegisterResult)> + 'a {
+        tasks
+            .iter()
+            .enumerate()
+            .map(move |(index, task)| async move { (index, self.results_async(task).await) })
+            .collect::<FuturesUnordered<_>>()
+    }
+
+    /// Runs a batch of circuits on the device.
+    ///
+    /// All circuits are submitted with [APIBackend::submit_batch_async] before waiting for the
+    /// first results.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Registers>)` - The readout registers of every circuit in the order of the
+    ///   circuits.
+    /// * `Err(RoqoqoBackendError)` - A circuit could not be run.
+    pub async fn run_circuit_batch_async(
+        &self,
+        circuits: &[Circuit],
+    ) -> Result<Vec<Registers>, RoqoqoBackendError> {
+        let tasks = self.submit_batch_async(circuits).await?;
+        self.results_batch_async(&tasks).await
+    }
+
+    /// Runs circuits with free parameters asynchronously for several bindings of the parameters.
+    ///
+    /// Every circuit is converted to OpenQASM once, with its free parameters declared as inputs,
+    /// see [roqoqo_for_braket_devices::braket_ir::free_parameters]. For every binding, a batch
+    /// of tasks running all circuits with the bound values is submitted, free parameters without
+    /// a value take the value of their InputSymbolic operation. All tasks are submitted before
+    /// waiting for the first results, the registers of the circuits of a binding are combined in
+    /// the order of the circuits. Every binding has to have a different [parameter_label].
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The circuits with free parameters.
+    /// * `parameter_sets` - The bindings of the free parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BTreeMap<String, Registers>)` - The combined registers of the circuits for every
+    ///   binding, keyed by [parameter_label].
+    /// * `Err(RoqoqoBackendError)` - A circuit cannot be run on the device, a binding has a
+    ///   parameter that is not a free parameter of any circuit, two bindings have the same label
+    ///   or a task could not be run.
+    pub async fn run_parameter_sweep_async(
+        &self,
+        circuits: &[Circuit],
+        parameter_sets: &[HashMap<String, f64>],
+    ) -> Result<BTreeMap<String, Registers>, RoqoqoBackendError> {
+        let payloads: Vec<SubmissionPayload> = circuits
+            .iter()
+            .map(|circuit| self.payload(circuit))
+            .collect::<Result<_, _>>()?;
+        let parameters: Vec<Vec<String>> = circuits.iter().map(free_parameters).collect();
+
+        let mut labels: HashSet<String> = HashSet::new();
+        let mut submissions: Vec<(usize, SubmissionPayload)> = Vec::new();
+        for (index, values) in parameter_sets.iter().enumerate() {
+            let label = parameter_label(values);
+            if !labels.insert(label.clone()) {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("Parameter binding {} appears more than once", label),
+                });
+            }
+            if let Some(name) = values
+                .keys()
+                .find(|name| !parameters.iter().any(|names| names.contains(name)))
+            {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!("{} is not a free parameter of the circuits", name),
+                });
+            }
+            for ((circuit, payload), names) in circuits.iter().zip(&payloads).zip(&parameters) {
+                let values: HashMap<String, f64> = values
+                    .iter()
+                    .filter(|(name, _)| names.contains(name))
+                    .map(|(name, value)| (name.clone(), *value))
+                    .collect();
+                let inputs = braket_inputs(circuit, &values)?;
+                let mut payload = payload.clone();
+                payload.action = braket_action(&payload.openqasm, &inputs);
+                submissions.push((index, payload));
+            }
+        }
+
+        let tasks: Vec<QuantumTask> = stream::iter(submissions.iter())
+            .map(|(_, payload)| self.submit_payload_async(payload.clone()))
+            .buffered(self.max_concurrency)
+            .try_collect()
+            .await?;
+        let mut results: Vec<Vec<Registers>> = vec![Vec::new(); parameter_sets.len()];
+        for ((index, _), registers) in submissions
+            .iter()
+            .zip(self.results_batch_async(&tasks).await?)
+        {
+            results[*index].push(registers);
+        }
+        Ok(parameter_sets
+            .iter()
+            .zip(results)
+            .map(|(values, results)| (parameter_label(values), combine_registers(results)))
+            .collect())
+    }
+
+    /// Cancels a quantum task.
+    ///
+    /// Braket first moves a cancelled task to the CANCELLING status and to CANCELLED once the
+    /// device has dropped it, [APIBackend::poll_async] waits for the final CANCELLED status. Tasks
+    /// that have already finished are not changed and their final status is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_arn` - The ARN of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TaskStatus)` - The status of the task after the cancellation request.
+    /// * `Err(RoqoqoBackendError)` - The task could not be cancelled.
+    pub async fn cancel_async(&self, task_arn: &str) -> Result<TaskStatus, RoqoqoBackendError> {
+        let status: TaskStatus = self.get_task(task_arn).await?.status().as_str().parse()?;
+        if status.is_terminal() || status == TaskStatus::Cancelling {
+            return Ok(status);
+        }
+        let client_token = random_token();
+        match self
+            .retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .cancel_quantum_task()
+                    .client_token(&client_token)
+                    .quantum_task_arn(task_arn)
+                    .send()
+            })
+            .await
+        {
+            Ok(output) => output.cancellation_status().as_str().parse(),
+            Err(err) => {
+                // The task can finish between the status request and the cancellation
+                let status: TaskStatus =
+                    self.get_task(task_arn).await?.status().as_str().parse()?;
+                if status.is_terminal() || status == TaskStatus::Cancelling {
+                    Ok(status)
+                } else {
+                    Err(RoqoqoBackendError::NetworkError {
+                        msg: format!(
+                            "Could not cancel quantum task {}: {}",
+                            task_arn,
+                            DisplayErrorContext(err)
+                        ),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Cancels all unfinished quantum tasks submitted by the backend.
+    ///
+    /// The cancellation is requested for every submitted task that has not completed, failed or
+    /// been cancelled yet. A failed cancellation does not stop the cancellation of the other
+    /// tasks. Tasks that are cancelled or have finished are no longer tracked, tasks whose
+    /// cancellation failed stay tracked, so that calling the method again retries them.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The ARNs of the tasks whose cancellation was requested.
+    /// * `Err(RoqoqoBackendError)` - At least one task could not be cancelled.
+    pub async fn cancel_all_async(&self) -> Result<Vec<String>, RoqoqoBackendError> {
+        let task_arns = self.unfinished_tasks();
+        let results: Vec<(String, Result<TaskStatus, RoqoqoBackendError>)> =
+            stream::iter(task_arns)
+                .map(|task_arn| async move {
+                    let status = self.cancel_async(&task_arn).await;
+                    (task_arn, status)
+                })
+                .buffered(self.max_concurrency)
+                .collect()
+                .await;
+        let mut cancelled: Vec<String> = Vec::new();
+        let mut done: Vec<String> = Vec::new();
+        let mut errors: Vec<String> = Vec::new();
+        for (task_arn, result) in results {
+            match result {
+                // The status is either terminal or the task is being cancelled
+                Ok(status) => {
+                    if matches!(status, TaskStatus::Cancelling | TaskStatus::Cancelled) {
+                        cancelled.push(task_arn.clone());
+                    }
+                    done.push(task_arn);
+                }
+                Err(err) => errors.push(format!("{}: {}", task_arn, err)),
+            }
+        }
+        self.untrack_tasks(&done);
+        if errors.is_empty() {
+            Ok(cancelled)
+        } else {
+            Err(RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not cancel {} quantum tasks, they are still tracked: {}",
+                    errors.len(),
+                    errors.join("; ")
+                ),
+            })
+        }
+    }
+
+    /// Creates a hybrid job running a workload next to the device.
+    ///
+    /// The job runs the algorithm of the definition with the device of the backend. Its output
+    /// is written to `jobs/<job_name>` below the configured S3 key prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The definition of the job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HybridJob)` - The handle of the created job.
+    /// * `Err(RoqoqoBackendError)` - The definition is invalid or the job could not be created.
+    pub async fn create_job_async(
+        &self,
+        definition: &JobDefinition,
+    ) -> Result<HybridJob, RoqoqoBackendError> {
+        self.check_not_dry_run()?;
+        let invalid = |err: BuildError| RoqoqoBackendError::GenericError {
+            msg: format!("Invalid hybrid job definition: {}", err),
+        };
+        let mut algorithm = AlgorithmSpecification::builder().script_mode_config(
+            ScriptModeConfig::builder()
+                .entry_point(&definition.entry_point)
+                .s3_uri(&definition.source_module)
+                .compression_type(CompressionType::Gzip)
+                .build()
+                .map_err(invalid)?,
+        );
+        if let Some(image_uri) = &definition.image_uri {
+            algorithm = algorithm.container_image(
+                ContainerImage::builder()
+                    .uri(image_uri)
+                    .build()
+                    .map_err(invalid)?,
+            );
+        }
+        let algorithm = algorithm.build();
+        let instance_config = InstanceConfig::builder()
+            .instance_type(InstanceType::from(definition.instance_type.as_str()))
+            .instance_count(definition.instance_count as i32)
+            .volume_size_in_gb(definition.volume_size_gb as i32)
+            .build()
+            .map_err(invalid)?;
+        let output_config = JobOutputDataConfig::builder()
+            .s3_path(format!(
+                "s3://{}/{}/jobs/{}",
+                self.config.s3_bucket(),
+                self.config.s3_prefix(),
+                definition.job_name
+            ))
+            .build()
+            .map_err(invalid)?;
+        let checkpoint_config = match &definition.checkpoint {
+            Some(checkpoint) => Some(
+                JobCheckpointConfig::builder()
+                    .s3_uri(&checkpoint.s3_uri)
+                    .set_local_path(checkpoint.local_path.clone())
+                    .build()
+                    .map_err(invalid)?,
+            ),
+            None => None,
+        };
+        let device_config = DeviceConfig::builder()
+            .device(self.device.clone().name())
+            .build()
+            .map_err(invalid)?;
+        let stopping_condition = JobStoppingCondition::builder()
+            .max_runtime_in_seconds(definition.max_runtime.as_secs() as i32)
+            .build();
+        let associations = self.associations()?;
+
+        // The client token is kept for all attempts, so a retried request cannot create a
+        // second job
+        let client_token = random_token();
+        let output = self
+            .retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .create_job()
+                    .client_token(&client_token)
+                    .job_name(&definition.job_name)
+                    .role_arn(&definition.role_arn)
+                    .algorithm_specification(algorithm.clone())
+                    .set_hyper_parameters(Some(definition.hyperparameters.clone()))
+                    .instance_config(instance_config.clone())
+                    .output_data_config(output_config.clone())
+                    .set_checkpoint_config(checkpoint_config.clone())
+                    .device_config(device_config.clone())
+                    .stopping_condition(stopping_condition.clone())
+                    .set_associations(associations.clone())
+                    .set_tags(Some(self.tags.clone()))
+                    .send()
+            })
+            .await
+            .map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not create hybrid job {}: {}",
+                    definition.job_name,
+                    DisplayErrorContext(err)
+                ),
+            })?;
+        Ok(HybridJob::new(output.job_arn()))
+    }
+
+    /// Returns the current status of a hybrid job.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The status of the job.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested.
+    pub async fn job_status_async(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        self.get_job(job.job_arn()).await?.status().as_str().parse()
+    }
+
+    /// Waits until a hybrid job has completed, failed or been cancelled.
+    ///
+    /// The status of the job is requested in the poll interval of the backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The final status of the job, completed or cancelled.
+    /// * `Err(RoqoqoBackendError)` - The status could not be requested, the job failed or did
+    ///   not finish within the timeout of the backend.
+    pub async fn poll_job_async(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        let start = Instant::now();
+        loop {
+            let output = self.get_job(job.job_arn()).await?;
+            let status: JobStatus = output.status().as_str().parse()?;
+            if status == JobStatus::Failed {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Hybrid job {} failed: {}",
+                        job.job_arn(),
+                        output.failure_reason().unwrap_or("no reason given")
+                    ),
+                });
+            }
+            if status.is_terminal() {
+                return Ok(status);
+            }
+            if start.elapsed() >= self.timeout {
+                return Err(RoqoqoBackendError::Timeout {
+                    msg: format!(
+                        "Hybrid job {} did not finish within {} s, last status {}",
+                        job.job_arn(),
+                        self.timeout.as_secs(),
+                        status
+                    ),
+                });
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Cancels a hybrid job.
+    ///
+    /// Jobs that have already finished are not changed and their final status is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The created job.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JobStatus)` - The status of the job after the cancellation request.
+    /// * `Err(RoqoqoBackendError)` - The job could not be cancelled.
+    pub async fn cancel_job_async(&self, job: &HybridJob) -> Result<JobStatus, RoqoqoBackendError> {
+        let status = self.job_status_async(job).await?;
+        if status.is_terminal() || status == JobStatus::Cancelling {
+            return Ok(status);
+        }
+        let output = self
+            .retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .cancel_job()
+                    .job_arn(job.job_arn())
+                    .send()
+            })
+            .await
+            .map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not cancel hybrid job {}: {}",
+                    job.job_arn(),
+                    DisplayErrorContext(err)
+                ),
+            })?;
+        output.cancellation_status().as_str().parse()
+    }
+
+    /// Remembers a submitted task for [APIBackend::cancel_all_async].
+    fn track_task(&self, task_arn: &str) {
+        if let Ok(mut tasks) = self.submitted_tasks.lock() {
+            tasks.push(task_arn.to_string());
+        }
+    }
+
+    /// Returns the tracked submitted tasks, which are kept until [APIBackend::untrack_tasks].
+    fn unfinished_tasks(&self) -> Vec<String> {
+        match self.submitted_tasks.lock() {
+            Ok(tasks) => tasks.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Stops tracking tasks that are cancelled or have finished.
+    fn untrack_tasks(&self, task_arns: &[String]) {
+        let mut tasks = match self.submitted_tasks.lock() {
+            Ok(tasks) => tasks,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        tasks.retain(|task_arn| !task_arns.contains(task_arn));
+    }
+
+    /// Records the payload of a submission in dry-run mode.
+    fn record_dry_run(
+        &self,
+        payload: SubmissionPayload,
+    ) -> Result<QuantumTask, RoqoqoBackendError> {
+        let mut payloads =
+            self.dry_run_payloads
+                .lock()
+                .map_err(|_| RoqoqoBackendError::GenericError {
+                    msg: "Dry-run payloads are unavailable after a panic during a submission"
+                        .to_string(),
+                })?;
+        let task_arn = format!("dry-run/{}", payloads.len());
+        let task = QuantumTask::new(&task_arn, payload.shots, payload.mapping.clone());
+        payloads.push(payload);
+        Ok(task)
+    }
+
+    /// Returns an error in dry-run mode, where AWS must not be contacted.
+    fn check_not_dry_run(&self) -> Result<(), RoqoqoBackendError> {
+        if self.dry_run {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "Backend is in dry-run mode and does not contact AWS".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Locks the cost tracker for recording a task.
+    fn lock_cost_tracker(&self) -> Result<MutexGuard<'_, CostTracker>, RoqoqoBackendError> {
+        self.cost_tracker
+            .lock()
+            .map_err(|_| RoqoqoBackendError::GenericError {
+                msg: "Cost tracker is unavailable after a panic during a submission".to_string(),
+            })
+    }
+
+    /// Runs a future of an async method on the runtime of the backend.
+    pub(crate) fn block_on<T>(
+        &self,
+        future: impl Future<Output = Result<T, RoqoqoBackendError>>,
+    ) -> Result<T, RoqoqoBackendError> {
+        match &self.runtime {
+            Some(runtime) => runtime.block_on(future),
+            None => Err(RoqoqoBackendError::GenericError {
+                msg: "Backend created with new_async only supports the async methods".to_string(),
+            }),
+        }
+    }
+
+    /// Requests the properties of the device from Braket.
+    async fn device_properties_async(&self) -> Result<String, RoqoqoBackendError> {
+        Ok(self.get_device().await?.device_capabilities().to_string())
+    }
+
+    /// Requests the current state of the device from Braket.
+    async fn get_device(&self) -> Result<GetDeviceOutput, RoqoqoBackendError> {
+        self.check_not_dry_run()?;
+        let device_arn = self.device.clone().name();
+        self.retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .get_device()
+                    .device_arn(&device_arn)
+                    .send()
+            })
+            .await
+            .map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not request device {}: {}",
+                    device_arn,
+                    DisplayErrorContext(err)
+                ),
+            })
+    }
+
+    /// Returns the associations of the tasks and jobs with the reservation of the backend.
+    fn associations(&self) -> Result<Option<Vec<Association>>, RoqoqoBackendError> {
+        match &self.reservation_arn {
+            None => Ok(None),
+            Some(reservation_arn) => {
+                let association = Association::builder()
+                    .arn(reservation_arn)
+                    .r#type(AssociationType::ReservationTimeWindowArn)
+                    .build()
+                    .map_err(|err| RoqoqoBackendError::GenericError {
+                        msg: format!("Invalid reservation {}: {}", reservation_arn, err),
+                    })?;
+                Ok(Some(vec![association]))
+            }
+        }
+    }
+
+    /// Requests the current state of a quantum task from Braket.
+    async fn get_task(&self, task_arn: &str) -> Result<GetQuantumTaskOutput, RoqoqoBackendError> {
+        self.check_not_dry_run()?;
+        self.retry_policy
+            .run(&self.pacer, || {
+                self.braket_client
+                    .get_quantum_task()
+                    .quantum_task_arn(task_arn)
+                    .send()
+            })
+            .await
+            .map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not request quantum task {}: {}",
+                    task_arn,
+                    DisplayErrorContext(err)
+                ),
+            })
+    }
+
+    /// Requests the current state of a hybrid job from Braket.
+    async fn get_job(&self, job_arn: &str) -> Result<GetJobOutput, RoqoqoBackendError> {
+        self.check_not_dry_run()?;
+        self.retry_policy
+            .run(&self.pacer, || {
+                self.braket_client.get_job().job_arn(job_arn).send()
+            })
+            .await
+            .map_err(|err| RoqoqoBackendError::NetworkError {
+                msg: format!(
+                    "Could not request hybrid job {}: {}",
+                    job_arn,
+                    DisplayErrorContext(err)
+                ),
+            })
+    }
+
+    /// Requests the state of a quantum task until it has reached a terminal status.
+    #[tracing::instrument(name = "poll", skip_all, fields(task_arn = %task.task_arn()), err)]
+    async fn wait_for_task(
+        &self,
+        task: &QuantumTask,
+    ) -> Result<GetQuantumTaskOutput, RoqoqoBackendError> {
+        let start = Instant::now();
+        loop {
+            let output = self.get_task(task.task_arn()).await?;
+            let status: TaskStatus = output.status().as_str().parse()?;
+            if status.is_terminal() {
+                tracing::info!(%status, "Quantum task finished");
+                return Ok(output);
+            }
+            tracing::debug!(%status, "Quantum task has not finished");
+            if start.elapsed() >= self.timeout {
+                return Err(RoqoqoBackendError::Timeout {
+                    msg: format!(
+                        "Quantum task {} did not finish within {} s, last status {}",
+                        task.task_arn(),
+                        self.timeout.as_secs(),
+                        status
+                    ),
+                });
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+}
+
+impl Drop for APIBackend {
+    fn drop(&mut self) {
+        // Dropping a runtime waits for its tasks, which panics inside the runtime of an async
+        // caller.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl EvaluatingBackend for APIBackend {
+    /// Runs a circuit on the device and returns its readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The iterator over the operations of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of the
+    ///   circuit.
+    /// * `Err(RoqoqoBackendError)` - The circuit could not be run, see [APIBackend::submit] and
+    ///   [APIBackend::results].
+    fn run_circuit_iterator<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> RegisterResult {
+        let circuit: Circuit = circuit.cloned().collect();
+        let task = self.submit(&circuit)?;
+        self.results(&task)
+    }
+
+    /// Runs all circuits of a measurement on the device and returns the combined registers.
+    ///
+    /// All circuits are submitted with [APIBackend::run_circuit_batch] before waiting for the
+    /// first results, so that the tasks wait in the queue of the device at the same time. The
+    /// shots of registers written by several circuits are appended in the order of the circuits.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The measurement whose circuits are run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of all
+    ///   circuits of the measurement.
+    /// * `Err(RoqoqoBackendError)` - A circuit could not be run.
+    fn run_measurement_registers<T>(&self, measurement: &T) -> RegisterResult
+    where
+        T: Measure,
+    {
+        let circuits = measurement_circuits(measurement);
+        Ok(combine_registers(self.run_circuit_batch(&circuits)?))
+    }
+}
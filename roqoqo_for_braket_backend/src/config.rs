@@ -0,0 +1,226 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! AWS configuration
+//!
+//! Provides the configuration of the access to AWS used by the networked parts of the backend.
+
+use aws_config::retry::RetryConfig;
+use aws_config::sts::AssumeRoleProvider;
+use aws_config::{BehaviorVersion, SdkConfig};
+use aws_sdk_braket::config::{Region, SharedCredentialsProvider};
+use roqoqo_for_braket_devices::AWSDevice;
+
+/// An IAM role assumed through AWS STS, e.g. to access Braket in another account.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AssumeRole {
+    /// The ARN of the role.
+    pub role_arn: String,
+    /// The external ID required by the trust policy of the role, if any.
+    pub external_id: Option<String>,
+    /// The name of the role session, shown in CloudTrail.
+    pub session_name: String,
+}
+
+impl AssumeRole {
+    /// Creates a new AssumeRole.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_arn` - The ARN of the role.
+    /// * `external_id` - The external ID required by the trust policy of the role, if any.
+    /// * `session_name` - The name of the role session.
+    ///
+    /// # Returns
+    ///
+    /// The role to assume.
+    pub fn new(role_arn: &str, external_id: Option<&str>, session_name: &str) -> Self {
+        AssumeRole {
+            role_arn: role_arn.to_string(),
+            external_id: external_id.map(|external_id| external_id.to_string()),
+            session_name: session_name.to_string(),
+        }
+    }
+}
+
+/// Configuration of the access to AWS.
+///
+/// The credentials are resolved by the default AWS credential chain: environment variables, the
+/// shared config and credentials files (with the default profile or the configured one) and the
+/// container or instance metadata. When a role is configured, these credentials are only used to
+/// assume the role and all requests are sent with the temporary credentials of the role. The
+/// region defaults to the region of the device, the results of the quantum tasks are written to
+/// the configured S3 bucket and key prefix. The requests are sent to the AWS endpoints unless an
+/// endpoint URL is configured, e.g. of an emulator like moto or localstack or of a proxy.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AWSConfig {
+    /// The S3 bucket the task results are written to.
+    s3_bucket: String,
+    /// The key prefix of the task results in the S3 bucket.
+    s3_prefix: String,
+    /// The profile of the shared AWS config files, `None` for the default profile.
+    profile: Option<String>,
+    /// The AWS region, `None` for the region of the device.
+    region: Option<String>,
+    /// The IAM role assumed for all requests, `None` to use the resolved credentials directly.
+    assume_role: Option<AssumeRole>,
+    /// The URL all requests are sent to, `None` for the AWS endpoints.
+    endpoint_url: Option<String>,
+}
+
+impl AWSConfig {
+    /// Creates a new AWSConfig.
+    ///
+    /// # Arguments
+    ///
+    /// * `s3_bucket` - The S3 bucket the task results are written to.
+    /// * `s3_prefix` - The key prefix of the task results in the S3 bucket.
+    ///
+    /// # Returns
+    ///
+    /// The configuration using the default credentials and the region of the device.
+    pub fn new(s3_bucket: &str, s3_prefix: &str) -> Self {
+        AWSConfig {
+            s3_bucket: s3_bucket.to_string(),
+            s3_prefix: s3_prefix.to_string(),
+            profile: None,
+            region: None,
+            assume_role: None,
+            endpoint_url: None,
+        }
+    }
+
+    /// Returns the S3 bucket the task results are written to.
+    pub fn s3_bucket(&self) -> &str {
+        &self.s3_bucket
+    }
+
+    /// Returns the key prefix of the task results in the S3 bucket.
+    pub fn s3_prefix(&self) -> &str {
+        &self.s3_prefix
+    }
+
+    /// Returns the profile of the shared AWS config files, `None` for the default profile.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Sets the profile of the shared AWS config files the credentials are read from.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The name of the profile, `None` for the default profile.
+    pub fn set_profile(&mut self, profile: Option<&str>) {
+        self.profile = profile.map(|profile| profile.to_string());
+    }
+
+    /// Returns the configured AWS region, `None` when the region of the device is used.
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Sets the AWS region of the requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The region, `None` for the region of the device.
+    pub fn set_region(&mut self, region: Option<&str>) {
+        self.region = region.map(|region| region.to_string());
+    }
+
+    /// Returns the IAM role assumed for all requests.
+    pub fn assume_role(&self) -> Option<&AssumeRole> {
+        self.assume_role.as_ref()
+    }
+
+    /// Sets the IAM role assumed for all requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `assume_role` - The role, `None` to use the resolved credentials directly.
+    pub fn set_assume_role(&mut self, assume_role: Option<AssumeRole>) {
+        self.assume_role = assume_role;
+    }
+
+    /// Returns the URL all requests are sent to, `None` when the AWS endpoints are used.
+    pub fn endpoint_url(&self) -> Option<&str> {
+        self.endpoint_url.as_deref()
+    }
+
+    /// Sets the URL all requests are sent to instead of the AWS endpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_url` - The URL, e.g. `http://localhost:4566`, `None` for the AWS endpoints.
+    pub fn set_endpoint_url(&mut self, endpoint_url: Option<&str>) {
+        self.endpoint_url = endpoint_url.map(|endpoint_url| endpoint_url.to_string());
+    }
+
+    /// Returns the AWS region used for a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the requests are for.
+    ///
+    /// # Returns
+    ///
+    /// The configured region, or the region of the device if none is configured.
+    pub fn region_for(&self, device: &AWSDevice) -> String {
+        self.region
+            .clone()
+            .unwrap_or_else(|| device.clone().region().to_string())
+    }
+
+    /// Loads the AWS SDK configuration for the requests of a device.
+    ///
+    /// The retries of the SDK are disabled, requests are retried with the
+    /// [crate::RetryPolicy] of the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the requests are for.
+    ///
+    /// # Returns
+    ///
+    /// The SDK configuration with the resolved credentials provider and region.
+    pub async fn load(&self, device: &AWSDevice) -> SdkConfig {
+        let region = Region::new(self.region_for(device));
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(region.clone())
+            .retry_config(RetryConfig::disabled());
+        if let Some(profile) = &self.profile {
+            loader = loader.profile_name(profile);
+        }
+        if let Some(endpoint_url) = &self.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
+        let config = loader.load().await;
+        match &self.assume_role {
+            None => config,
+            Some(assume_role) => {
+                let mut provider = AssumeRoleProvider::builder(&assume_role.role_arn)
+                    .session_name(&assume_role.session_name)
+                    .region(region);
+                if let Some(external_id) = &assume_role.external_id {
+                    provider = provider.external_id(external_id);
+                }
+                // The temporary credentials of the role are refreshed by the provider before
+                // they expire
+                let provider = provider.configure(&config).build().await;
+                config
+                    .into_builder()
+                    .credentials_provider(SharedCredentialsProvider::new(provider))
+                    .build()
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cost tracking
+//!
+//! Provides the accounting of the estimated cost of the quantum tasks submitted by a backend.
+
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_devices::AWSDevice;
+
+/// Accumulates the estimated cost of submitted quantum tasks and enforces a maximum cost.
+///
+/// The cost of a task is estimated from the prices of the device, see
+/// [AWSDevice::price_per_task] and [AWSDevice::price_per_shot]. It does not include the cost of
+/// S3 storage or of hybrid jobs, and the prices of the devices may differ from the current AWS
+/// prices.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CostTracker {
+    /// The maximum total cost in USD, `None` for no limit.
+    max_cost: Option<f64>,
+    /// The estimated cost of the recorded tasks in USD.
+    total_cost: f64,
+    /// The number of recorded tasks.
+    number_tasks: usize,
+    /// The number of shots of the recorded tasks.
+    number_shots: usize,
+}
+
+impl CostTracker {
+    /// Creates a new CostTracker without recorded tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_cost` - The maximum total cost in USD, `None` for no limit.
+    ///
+    /// # Returns
+    ///
+    /// The new cost tracker.
+    pub fn new(max_cost: Option<f64>) -> Self {
+        CostTracker {
+            max_cost,
+            ..CostTracker::default()
+        }
+    }
+
+    /// Returns the estimated cost of a quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the task runs on.
+    /// * `shots` - The number of shots of the task.
+    ///
+    /// # Returns
+    ///
+    /// The price of the task plus the price of its shots in USD.
+    pub fn estimate_cost(device: &AWSDevice, shots: usize) -> f64 {
+        device.price_per_task() + shots as f64 * device.price_per_shot()
+    }
+
+    /// Returns the maximum total cost in USD, `None` for no limit.
+    pub fn max_cost(&self) -> Option<f64> {
+        self.max_cost
+    }
+
+    /// Sets the maximum total cost, the recorded tasks are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_cost` - The maximum total cost in USD, `None` for no limit.
+    pub fn set_max_cost(&mut self, max_cost: Option<f64>) {
+        self.max_cost = max_cost;
+    }
+
+    /// Returns the estimated cost of the recorded tasks in USD.
+    pub fn total_cost(&self) -> f64 {
+        self.total_cost
+    }
+
+    /// Returns the number of recorded tasks.
+    pub fn number_tasks(&self) -> usize {
+        self.number_tasks
+    }
+
+    /// Returns the number of shots of the recorded tasks.
+    pub fn number_shots(&self) -> usize {
+        self.number_shots
+    }
+
+    /// Returns the cost that can still be spent before the maximum cost is reached.
+    ///
+    /// # Returns
+    ///
+    /// The remaining cost in USD, `None` for no limit.
+    pub fn remaining_cost(&self) -> Option<f64> {
+        self.max_cost
+            .map(|max_cost| (max_cost - self.total_cost).max(0.0))
+    }
+
+    /// Records a quantum task if its cost fits into the maximum cost.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the task runs on.
+    /// * `shots` - The number of shots of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The estimated cost of the recorded task in USD.
+    /// * `Err(RoqoqoBackendError)` - The task would exceed the maximum cost and was not recorded.
+    pub fn add_task(
+        &mut self,
+        device: &AWSDevice,
+        shots: usize,
+    ) -> Result<f64, RoqoqoBackendError> {
+        let cost = CostTracker::estimate_cost(device, shots);
+        if let Some(max_cost) = self.max_cost {
+            if self.total_cost + cost > max_cost {
+                return Err(RoqoqoBackendError::GenericError {
+                    msg: format!(
+                        "Quantum task with {} shots costs {:.2} USD, exceeding the maximum cost \
+                         of {:.2} USD with {:.2} USD already spent",
+                        shots, cost, max_cost, self.total_cost
+                    ),
+                });
+            }
+        }
+        self.total_cost += cost;
+        self.number_tasks += 1;
+        self.number_shots += shots;
+        Ok(cost)
+    }
+
+    /// Removes a recorded task whose creation failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cost` - The estimated cost of the task returned by [CostTracker::add_task].
+    /// * `shots` - The number of shots of the task.
+    pub(crate) fn remove_task(&mut self, cost: f64, shots: usize) {
+        self.total_cost = (self.total_cost - cost).max(0.0);
+        self.number_tasks = self.number_tasks.saturating_sub(1);
+        self.number_shots = self.number_shots.saturating_sub(shots);
+    }
+
+    /// Clears the recorded tasks, the maximum cost is kept.
+    pub fn reset(&mut self) {
+        self.total_cost = 0.0;
+        self.number_tasks = 0;
+        self.number_shots = 0;
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Device parameters
+//!
+//! Provides the typed device-specific options of quantum tasks, sent to Braket as the device
+//! parameters of a task.
+
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_devices::AWSDevice;
+use serde_json::{json, Value};
+
+/// Error mitigation options of IonQ devices.
+///
+/// Debiasing runs the shots of a task on symmetrized variants of the circuit, which averages out
+/// systematic errors of the device. The measured shots are the average of the variants;
+/// sharpening replaces them by the majority vote of the variants, which works best for circuits
+/// with a single expected outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IonQOptions {
+    /// Whether the task is run with debiasing.
+    pub debias: bool,
+    /// Whether the registers are built from the sharpened instead of the averaged results,
+    /// requires debiasing.
+    pub sharpen: bool,
+}
+
+impl IonQOptions {
+    /// Creates new IonQOptions with debiasing.
+    ///
+    /// # Arguments
+    ///
+    /// * `sharpen` - Whether the registers are built from the sharpened results.
+    ///
+    /// # Returns
+    ///
+    /// The options running tasks with debiasing.
+    pub fn debias(sharpen: bool) -> Self {
+        IonQOptions {
+            debias: true,
+            sharpen,
+        }
+    }
+
+    /// Checks that the options can be used for the tasks of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the tasks run on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The options are supported by the device.
+    /// * `Err(RoqoqoBackendError)` - The device is not an IonQ device, does not support error
+    ///   mitigation or sharpening is requested without debiasing.
+    pub fn validate(&self, device: &AWSDevice) -> Result<(), RoqoqoBackendError> {
+        let invalid = |msg: &str| RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Invalid IonQ options for {}: {}",
+                device.clone().name(),
+                msg
+            ),
+        };
+        match device {
+            AWSDevice::IonQAria1Device(_) => {}
+            AWSDevice::IonQHarmonyDevice(_) if self.debias => {
+                return Err(invalid(
+                    "error mitigation is only supported by Aria and Forte",
+                ))
+            }
+            AWSDevice::IonQHarmonyDevice(_) => {}
+            _ => return Err(invalid("the device is not an IonQ device")),
+        }
+        if self.sharpen && !self.debias {
+            return Err(invalid("sharpening requires debiasing"));
+        }
+        Ok(())
+    }
+
+    /// Returns the Braket device parameters of the options.
+    pub(crate) fn device_parameters(&self) -> Value {
+        let mut parameters = json!({
+            "braketSchemaHeader": {
+                "name": "braket.device_schema.ionq.ionq_device_parameters",
+                "version": "1"
+            },
+            "paradigmParameters": gate_model_parameters(false),
+        });
+        if self.debias {
+            parameters["errorMitigation"] =
+                json!([{"type": "braket.device_schema.error_mitigation.debias.Debias"}]);
+        }
+        parameters
+    }
+}
+
+/// Compilation options of Rigetti devices.
+///
+/// By default Braket compiles the circuits of Rigetti tasks, which may rewire the qubits and
+/// replace the gates. Circuits transpiled and routed for the device, e.g. with
+/// [roqoqo_for_braket_devices::transpile::to_rigetti_native_verbatim], can be run exactly as
+/// written instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RigettiOptions {
+    /// Whether the logical qubits of the circuit are mapped to the physical qubits of the same
+    /// index instead of being rewired by the compiler.
+    pub disable_qubit_rewiring: bool,
+    /// Whether the gates are wrapped in verbatim boxes, so that they are not compiled, see
+    /// [roqoqo_for_braket_devices::braket_ir::circuit_to_openqasm3_verbatim].
+    pub verbatim: bool,
+}
+
+impl RigettiOptions {
+    /// Creates new RigettiOptions running circuits exactly as written.
+    ///
+    /// # Returns
+    ///
+    /// The options without compilation and qubit rewiring.
+    pub fn exact() -> Self {
+        RigettiOptions {
+            disable_qubit_rewiring: true,
+            verbatim: true,
+        }
+    }
+
+    /// Checks that the options can be used for the tasks of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device the tasks run on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The options are supported by the device.
+    /// * `Err(RoqoqoBackendError)` - The device is not a Rigetti device.
+    pub fn validate(&self, device: &AWSDevice) -> Result<(), RoqoqoBackendError> {
+        match device {
+            AWSDevice::RigettiAspenM3Device(_) => Ok(()),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Invalid Rigetti options for {}: the device is not a Rigetti device",
+                    device.clone().name()
+                ),
+            }),
+        }
+    }
+
+    /// Returns the Braket device parameters of the options.
+    pub(crate) fn device_parameters(&self) -> Value {
+        json!({
+            "braketSchemaHeader": {
+                "name": "braket.device_schema.rigetti.rigetti_device_parameters",
+                "version": "1"
+            },
+            "paradigmParameters": gate_model_parameters(self.disable_qubit_rewiring),
+        })
+    }
+}
+
+/// Returns the Braket gate model parameters of an OpenQASM program.
+fn gate_model_parameters(disable_qubit_rewiring: bool) -> Value {
+    // The number of qubits is taken from the OpenQASM program
+    json!({
+        "braketSchemaHeader": {
+            "name": "braket.device_schema.gate_model_parameters",
+            "version": "1"
+        },
+        "qubitCount": 0,
+        "disableQubitRewiring": disable_qubit_rewiring,
+    })
+}
@@ -0,0 +1,209 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hybrid jobs
+//!
+//! Provides the definition and the handles of Braket Hybrid Jobs, which run a classical
+//! algorithm, e.g. a variational loop, on an instance next to the quantum device.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use roqoqo::RoqoqoBackendError;
+
+/// Default instance type running the algorithm of a hybrid job.
+const DEFAULT_INSTANCE_TYPE: &str = "ml.m5.large";
+
+/// Default size of the storage volume of the instance in GB.
+const DEFAULT_VOLUME_SIZE_GB: usize = 30;
+
+/// Default maximum run time of a hybrid job, the maximum allowed by Braket.
+const DEFAULT_MAX_RUNTIME: Duration = Duration::from_secs(5 * 24 * 60 * 60);
+
+/// Location of the checkpoints of a hybrid job.
+///
+/// Checkpoints written by the algorithm to the local path are copied to S3, and copied back when
+/// a job is started with the same checkpoint location, so that an interrupted variational loop
+/// can be resumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobCheckpoint {
+    /// The S3 URI the checkpoints are copied to.
+    pub s3_uri: String,
+    /// The path of the checkpoints on the instance, `None` for the Braket default `/opt/jobs/checkpoints`.
+    pub local_path: Option<String>,
+}
+
+/// Definition of a Braket Hybrid Job running a parameterized workload next to the device.
+///
+/// The algorithm is a Python module packed as a gzipped tar archive in S3. The hyperparameters
+/// are passed to the algorithm as strings, the ARN of the device as the environment variable
+/// `AMZN_BRAKET_DEVICE_ARN`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobDefinition {
+    /// The name of the job, unique in the account and region.
+    pub job_name: String,
+    /// The ARN of the IAM role the job runs with.
+    pub role_arn: String,
+    /// The S3 URI of the gzipped tar archive of the algorithm.
+    pub source_module: String,
+    /// The function run by the job, e.g. `algorithm.main:run`.
+    pub entry_point: String,
+    /// The hyperparameters passed to the algorithm.
+    pub hyperparameters: HashMap<String, String>,
+    /// The location of the checkpoints, `None` when the job does not write checkpoints.
+    pub checkpoint: Option<JobCheckpoint>,
+    /// The URI of the container image running the algorithm, `None` for the Braket base image.
+    pub image_uri: Option<String>,
+    /// The instance type running the algorithm.
+    pub instance_type: String,
+    /// The number of instances running the algorithm.
+    pub instance_count: usize,
+    /// The size of the storage volume of every instance in GB.
+    pub volume_size_gb: usize,
+    /// The time after which the job is stopped.
+    pub max_runtime: Duration,
+}
+
+impl JobDefinition {
+    /// Creates a new JobDefinition running on a single `ml.m5.large` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `job_name` - The name of the job.
+    /// * `role_arn` - The ARN of the IAM role the job runs with.
+    /// * `source_module` - The S3 URI of the gzipped tar archive of the algorithm.
+    /// * `entry_point` - The function run by the job.
+    ///
+    /// # Returns
+    ///
+    /// The job definition without hyperparameters and checkpoints.
+    pub fn new(job_name: &str, role_arn: &str, source_module: &str, entry_point: &str) -> Self {
+        JobDefinition {
+            job_name: job_name.to_string(),
+            role_arn: role_arn.to_string(),
+            source_module: source_module.to_string(),
+            entry_point: entry_point.to_string(),
+            hyperparameters: HashMap::new(),
+            checkpoint: None,
+            image_uri: None,
+            instance_type: DEFAULT_INSTANCE_TYPE.to_string(),
+            instance_count: 1,
+            volume_size_gb: DEFAULT_VOLUME_SIZE_GB,
+            max_runtime: DEFAULT_MAX_RUNTIME,
+        }
+    }
+
+    /// Sets a hyperparameter passed to the algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the hyperparameter.
+    /// * `value` - The value of the hyperparameter.
+    pub fn set_hyperparameter(&mut self, name: &str, value: &str) {
+        self.hyperparameters
+            .insert(name.to_string(), value.to_string());
+    }
+}
+
+/// Status of a Braket Hybrid Job.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JobStatus {
+    /// The job is waiting for its instance and device.
+    Queued,
+    /// The algorithm of the job is running.
+    Running,
+    /// The algorithm has finished.
+    Completed,
+    /// The job has failed.
+    Failed,
+    /// The cancellation of the job has been requested.
+    Cancelling,
+    /// The job has been cancelled.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns whether the status of the job can no longer change.
+    ///
+    /// # Returns
+    ///
+    /// True for completed, failed and cancelled jobs.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = RoqoqoBackendError;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "QUEUED" => Ok(JobStatus::Queued),
+            "RUNNING" => Ok(JobStatus::Running),
+            "COMPLETED" => Ok(JobStatus::Completed),
+            "FAILED" => Ok(JobStatus::Failed),
+            "CANCELLING" => Ok(JobStatus::Cancelling),
+            "CANCELLED" => Ok(JobStatus::Cancelled),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!("Unknown hybrid job status {}", status),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            JobStatus::Queued => "QUEUED",
+            JobStatus::Running => "RUNNING",
+            JobStatus::Completed => "COMPLETED",
+            JobStatus::Failed => "FAILED",
+            JobStatus::Cancelling => "CANCELLING",
+            JobStatus::Cancelled => "CANCELLED",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// A hybrid job created on AWS Braket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridJob {
+    /// The ARN of the job.
+    job_arn: String,
+}
+
+impl HybridJob {
+    /// Creates the handle of a created hybrid job.
+    ///
+    /// # Arguments
+    ///
+    /// * `job_arn` - The ARN of the job.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the job.
+    pub fn new(job_arn: &str) -> Self {
+        HybridJob {
+            job_arn: job_arn.to_string(),
+        }
+    }
+
+    /// Returns the ARN of the job.
+    pub fn job_arn(&self) -> &str {
+        &self.job_arn
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # roqoqo_for_braket_backend
+//!
+//! Braket backend for roqoqo.
+//!
+//! Runs qoqo circuits as quantum tasks on the AWS Braket devices of roqoqo_for_braket_devices.
+
+pub mod availability;
+pub use availability::DeviceAvailability;
+
+pub mod backend;
+pub use backend::{parameter_label, APIBackend, Registers};
+
+pub mod config;
+pub use config::{AWSConfig, AssumeRole};
+
+pub mod cost;
+pub use cost::CostTracker;
+
+pub mod device_parameters;
+pub use device_parameters::{IonQOptions, RigettiOptions};
+
+pub mod job;
+pub use job::{HybridJob, JobCheckpoint, JobDefinition, JobStatus};
+
+pub mod limit;
+pub use limit::ConcurrencyLimit;
+
+pub mod local;
+pub use local::LocalBackend;
+
+pub mod pacing;
+pub use pacing::Pacer;
+
+pub mod payload;
+pub use payload::SubmissionPayload;
+
+pub mod queued;
+pub use queued::{QueuedProgram, QueuedTask};
+
+pub mod results;
+pub use results::{BraketResult, ResultFetcher, ResultTypeValue, ResultValue};
+
+pub mod retry;
+pub use retry::RetryPolicy;
+
+pub mod selection;
+pub use selection::{DeviceSelection, SelectingBackend};
+
+pub mod task;
+pub use task::{QuantumTask, TaskFilter, TaskMetadata, TaskStatus, TaskSummary};
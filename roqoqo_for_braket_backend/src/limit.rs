@@ -0,0 +1,75 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Concurrency limits
+//!
+//! Provides the limits of the number of quantum tasks created at the same time.
+
+use std::sync::Arc;
+
+use roqoqo::RoqoqoBackendError;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Limit of the number of creation requests sent to Braket at the same time.
+///
+/// Clones share their permits, so one limit can be set on several backends to bound the requests
+/// of all of them, e.g. to stay below the task creation limits of the AWS account.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit {
+    /// The semaphore handing out the permits.
+    semaphore: Arc<Semaphore>,
+    /// The total number of permits.
+    permits: usize,
+}
+
+impl ConcurrencyLimit {
+    /// Creates a new ConcurrencyLimit.
+    ///
+    /// # Arguments
+    ///
+    /// * `permits` - The maximum number of requests sent at the same time, at least one.
+    ///
+    /// # Returns
+    ///
+    /// The new limit.
+    pub fn new(permits: usize) -> Self {
+        let permits = permits.max(1);
+        ConcurrencyLimit {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            permits,
+        }
+    }
+
+    /// Returns the maximum number of requests sent at the same time.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Returns the number of requests that can currently be sent without waiting.
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Waits until a request can be sent.
+    ///
+    /// # Returns
+    ///
+    /// The permit of the request, released when it is dropped.
+    pub(crate) async fn acquire(&self) -> Result<SemaphorePermit<'_>, RoqoqoBackendError> {
+        self.semaphore
+            .acquire()
+            .await
+            .map_err(|_| RoqoqoBackendError::GenericError {
+                msg: "Concurrency limit has been closed".to_string(),
+            })
+    }
+}
@@ -0,0 +1,483 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local backend
+//!
+//! Provides the backend simulating qoqo circuits locally under the constraints of an AWS Braket
+//! device.
+
+use std::collections::{BTreeSet, HashMap};
+
+use num_complex::Complex64;
+use qoqo_calculator::{Calculator, CalculatorFloat};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use roqoqo::backends::{EvaluatingBackend, RegisterResult};
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoBackendError, RoqoqoError};
+use roqoqo_for_braket_devices::analysis::DEFAULT_NUMBER_SHOTS;
+use roqoqo_for_braket_devices::braket_ir::{braket_shots, MeasurementMapping};
+use roqoqo_for_braket_devices::verification::{apply_gate, gate_matrix};
+use roqoqo_for_braket_devices::{decorate_circuit_with_noise, AWSDevice};
+
+/// Maximum number of qubits of a simulated circuit, the state vector then takes 512 MB.
+const MAX_QUBITS: usize = 25;
+
+/// Backend simulating circuits with a state vector under the constraints of a Braket device.
+///
+/// Circuits are rejected like they would be by the device: they have to consist of native gates
+/// acting on connected qubits and request a number of shots accepted by the device, see
+/// [AWSDevice::validate_circuit]. As on Braket, the measured qubits are read out at the end of
+/// the circuit and circuits that do not set a number of measurements are run with 100 shots.
+///
+/// With noise enabled, the decoherence of the device during every gate is simulated with
+/// quantum trajectories, see [roqoqo_for_braket_devices::decorate_circuit_with_noise], and the
+/// preparation and readout errors of the device are applied to every shot. Leakage rates of the
+/// device are informational only and not simulated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalBackend {
+    /// The device whose constraints and noise are simulated.
+    device: AWSDevice,
+    /// Whether the noise of the device is simulated.
+    noise: bool,
+    /// The seed of the random measurement outcomes, `None` for a random seed.
+    seed: Option<u64>,
+}
+
+impl LocalBackend {
+    /// Creates a new LocalBackend simulating a device without noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device whose constraints are simulated.
+    ///
+    /// # Returns
+    ///
+    /// The new backend.
+    pub fn new(device: AWSDevice) -> Self {
+        LocalBackend {
+            device,
+            noise: false,
+            seed: None,
+        }
+    }
+
+    /// Returns the device whose constraints and noise are simulated.
+    pub fn device(&self) -> &AWSDevice {
+        &self.device
+    }
+
+    /// Returns whether the noise of the device is simulated.
+    pub fn noise(&self) -> bool {
+        self.noise
+    }
+
+    /// Sets whether the noise of the device is simulated.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise` - True to simulate the decoherence, preparation and readout errors of the device.
+    pub fn set_noise(&mut self, noise: bool) {
+        self.noise = noise;
+    }
+
+    /// Sets the seed of the random measurement outcomes, making the results reproducible.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed, `None` for a random seed on every run.
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Simulates a circuit and returns the measured bits of every shot.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to simulate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Vec<usize>, Vec<Vec<bool>>))` - The measured qubits and the bits of every shot.
+    /// * `Err(RoqoqoBackendError)` - The circuit cannot be run on the device or simulated.
+    pub fn simulate(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<(Vec<usize>, Vec<Vec<bool>>), RoqoqoBackendError> {
+        self.device.validate_circuit(circuit).map_err(|errors| {
+            let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+            RoqoqoBackendError::GenericError {
+                msg: format!("Circuit cannot be run on the device: {}", errors.join("; ")),
+            }
+        })?;
+        let shots = braket_shots(circuit)?.unwrap_or(DEFAULT_NUMBER_SHOTS);
+        self.device
+            .validate_shots(shots)
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Circuit cannot be run on the device: {}", err),
+            })?;
+        let measured_qubits = MeasurementMapping::from_circuit(circuit)?.measured_qubits();
+
+        // The free parameters take the values of the InputSymbolic operations, as on Braket
+        let mut calculator = Calculator::new();
+        for operation in circuit.iter() {
+            if let Operation::InputSymbolic(input) = operation {
+                calculator.set_variable(input.name(), *input.input());
+            }
+        }
+        let mut circuit = circuit.substitute_parameters(&calculator)?;
+        if self.noise {
+            circuit = decorate_circuit_with_noise(&circuit, &self.device);
+        }
+
+        // Only the qubits used by the circuit are simulated, relabeled to 0..n
+        let mut qubits: BTreeSet<usize> = measured_qubits.iter().copied().collect();
+        for operation in circuit.iter() {
+            if let InvolvedQubits::Set(involved) = operation.involved_qubits() {
+                qubits.extend(involved);
+            }
+        }
+        if qubits.len() > MAX_QUBITS {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: format!(
+                    "Circuit uses {} qubits but at most {} can be simulated",
+                    qubits.len(),
+                    MAX_QUBITS
+                ),
+            });
+        }
+        let relabeling: HashMap<usize, usize> = qubits
+            .iter()
+            .enumerate()
+            .map(|(index, qubit)| (*qubit, index))
+            .collect();
+        let positions: Vec<usize> = measured_qubits
+            .iter()
+            .map(|qubit| relabeling[qubit])
+            .collect();
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let states = if self.noise {
+            // Every shot is a separate trajectory of the noisy circuit
+            let mut states = Vec::with_capacity(shots);
+            for _ in 0..shots {
+                let mut state = self.initial_state(&qubits, &mut rng);
+                run_circuit(&circuit, &relabeling, &mut state, Some(&mut rng))?;
+                states.extend(sample_states(&state, 1, &mut rng));
+            }
+            states
+        } else {
+            let mut state = vec![Complex64::new(0.0, 0.0); 1 << qubits.len()];
+            state[0] = Complex64::new(1.0, 0.0);
+            run_circuit(&circuit, &relabeling, &mut state, None)?;
+            sample_states(&state, shots, &mut rng)
+        };
+
+        let measurements = states
+            .into_iter()
+            .map(|index| {
+                measured_qubits
+                    .iter()
+                    .zip(positions.iter())
+                    .map(|(qubit, position)| {
+                        let bit = (index >> position) & 1 == 1;
+                        if self.noise {
+                            self.read_out(*qubit, bit, &mut rng)
+                        } else {
+                            bit
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok((measured_qubits, measurements))
+    }
+
+    /// Returns the state of a trajectory after the noisy initialisation of the qubits.
+    ///
+    /// The preparation error and the thermal population of a qubit are combined as independent
+    /// bit flips.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The simulated device qubits in the order of the state vector.
+    /// * `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// The initial state vector.
+    fn initial_state(&self, qubits: &BTreeSet<usize>, rng: &mut StdRng) -> Vec<Complex64> {
+        let mut index = 0;
+        for (position, qubit) in qubits.iter().enumerate() {
+            let preparation = self.device.preparation_error(*qubit).unwrap_or(0.0);
+            let thermal = self.device.thermal_population(*qubit).unwrap_or(0.0);
+            if rng.gen::<f64>() < preparation + thermal - 2.0 * preparation * thermal {
+                index |= 1 << position;
+            }
+        }
+        let mut state = vec![Complex64::new(0.0, 0.0); 1 << qubits.len()];
+        state[index] = Complex64::new(1.0, 0.0);
+        state
+    }
+
+    /// Applies the readout error of a qubit to a measured bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The measured device qubit.
+    /// * `bit` - The state of the qubit.
+    /// * `rng` - The random number generator.
+    ///
+    /// # Returns
+    ///
+    /// The bit read out by the device.
+    fn read_out(&self, qubit: usize, bit: bool, rng: &mut StdRng) -> bool {
+        match self.device.readout_error(qubit) {
+            Some((prob_detect_0_as_1, prob_detect_1_as_0)) => {
+                let flip = if bit {
+                    prob_detect_1_as_0
+                } else {
+                    prob_detect_0_as_1
+                };
+                bit ^ (rng.gen::<f64>() < flip)
+            }
+            None => bit,
+        }
+    }
+}
+
+impl EvaluatingBackend for LocalBackend {
+    /// Simulates a circuit and returns its readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The iterator over the operations of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of the
+    ///   circuit.
+    /// * `Err(RoqoqoBackendError)` - The circuit could not be simulated, see [LocalBackend::simulate].
+    fn run_circuit_iterator<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> RegisterResult {
+        let circuit: Circuit = circuit.cloned().collect();
+        let (measured_qubits, measurements) = self.simulate(&circuit)?;
+        let bit_registers = MeasurementMapping::from_circuit(&circuit)?
+            .map_results(&measured_qubits, &measurements)?;
+        Ok((bit_registers, HashMap::new(), HashMap::new()))
+    }
+}
+
+/// Applies the operations of a circuit to a state vector.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit with substituted parameters.
+/// * `relabeling` - The state vector qubit of every device qubit.
+/// * `state` - The state vector.
+/// * `rng` - The random number generator of the trajectory, `None` when noise is not simulated.
+///
+/// # Returns
+///
+/// * `Ok(())` - The circuit was applied.
+/// * `Err(RoqoqoBackendError)` - The circuit contains an operation that cannot be simulated.
+fn run_circuit(
+    circuit: &Circuit,
+    relabeling: &HashMap<usize, usize>,
+    state: &mut [Complex64],
+    mut rng: Option<&mut StdRng>,
+) -> Result<(), RoqoqoBackendError> {
+    for operation in circuit.iter() {
+        match (operation, rng.as_deref_mut()) {
+            (
+                Operation::DefinitionBit(_)
+                | Operation::DefinitionFloat(_)
+                | Operation::DefinitionComplex(_)
+                | Operation::DefinitionUsize(_)
+                | Operation::InputSymbolic(_)
+                | Operation::MeasureQubit(_)
+                | Operation::PragmaSetNumberOfMeasurements(_)
+                | Operation::PragmaRepeatedMeasurement(_)
+                | Operation::PragmaGlobalPhase(_)
+                | Operation::PragmaSleep(_),
+                _,
+            ) => continue,
+            (Operation::PragmaDamping(damping), Some(rng)) => apply_decay(
+                state,
+                relabeling[damping.qubit()],
+                float(&damping.probability())?,
+                false,
+                rng,
+            ),
+            (Operation::PragmaDephasing(dephasing), Some(rng)) => apply_phase_flip(
+                state,
+                relabeling[dephasing.qubit()],
+                float(&dephasing.probability())?,
+                rng,
+            ),
+            (Operation::PragmaGeneralNoise(noise), Some(rng)) => {
+                let rates = noise.rates();
+                if rates
+                    .indexed_iter()
+                    .any(|((row, column), rate)| row != column && *rate != 0.0)
+                {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "Off-diagonal decoherence rates of qubit {} cannot be simulated",
+                            noise.qubit()
+                        ),
+                    });
+                }
+                let time = float(noise.gate_time())?;
+                let qubit = relabeling[noise.qubit()];
+                apply_decay(
+                    state,
+                    qubit,
+                    1.0 - (-rates[[0, 0]] * time).exp(),
+                    false,
+                    rng,
+                );
+                apply_decay(state, qubit, 1.0 - (-rates[[1, 1]] * time).exp(), true, rng);
+                let dephasing = 0.5 * (1.0 - (-2.0 * rates[[2, 2]] * time).exp());
+                apply_phase_flip(state, qubit, dephasing, rng);
+            }
+            _ => {
+                if !operation.tags().contains(&"GateOperation") {
+                    return Err(RoqoqoBackendError::GenericError {
+                        msg: format!(
+                            "Operation {} cannot be simulated by the LocalBackend",
+                            operation.hqslang()
+                        ),
+                    });
+                }
+                let (matrix, qubits) = gate_matrix(operation)?;
+                let qubits: Vec<usize> = qubits.iter().map(|qubit| relabeling[qubit]).collect();
+                apply_gate(state, &matrix, &qubits);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the value of a parameter of a circuit with substituted parameters.
+fn float(value: &CalculatorFloat) -> Result<f64, RoqoqoBackendError> {
+    value
+        .float()
+        .copied()
+        .map_err(|err| RoqoqoError::from(err).into())
+}
+
+/// Applies one step of a damping or excitation process to a trajectory.
+///
+/// # Arguments
+///
+/// * `state` - The state vector of the trajectory.
+/// * `qubit` - The state vector qubit.
+/// * `probability` - The probability that the excited (damping) or ground (excitation) state decays.
+/// * `excitation` - True for a decay from |0> to |1>, false for a decay from |1> to |0>.
+/// * `rng` - The random number generator of the trajectory.
+fn apply_decay(
+    state: &mut [Complex64],
+    qubit: usize,
+    probability: f64,
+    excitation: bool,
+    rng: &mut StdRng,
+) {
+    if probability <= 0.0 {
+        return;
+    }
+    let mask = 1_usize << qubit;
+    let is_source = |index: usize| (index & mask != 0) != excitation;
+    let population: f64 = (0..state.len())
+        .filter(|index| is_source(*index))
+        .map(|index| state[index].norm_sqr())
+        .sum();
+    if rng.gen::<f64>() < probability * population {
+        // Quantum jump: the population of the decaying state moves to the other state
+        for index in (0..state.len()).filter(|index| is_source(*index)) {
+            state[index ^ mask] = state[index];
+            state[index] = Complex64::new(0.0, 0.0);
+        }
+    } else {
+        let scale = (1.0 - probability).sqrt();
+        for index in (0..state.len()).filter(|index| is_source(*index)) {
+            state[index] *= scale;
+        }
+    }
+    normalize(state);
+}
+
+/// Applies a random phase flip to a qubit of a trajectory.
+///
+/// # Arguments
+///
+/// * `state` - The state vector of the trajectory.
+/// * `qubit` - The state vector qubit.
+/// * `probability` - The probability of the phase flip.
+/// * `rng` - The random number generator of the trajectory.
+fn apply_phase_flip(state: &mut [Complex64], qubit: usize, probability: f64, rng: &mut StdRng) {
+    if rng.gen::<f64>() < probability {
+        let mask = 1_usize << qubit;
+        for (index, amplitude) in state.iter_mut().enumerate() {
+            if index & mask != 0 {
+                *amplitude = -*amplitude;
+            }
+        }
+    }
+}
+
+/// Normalizes a state vector.
+fn normalize(state: &mut [Complex64]) {
+    let norm = state
+        .iter()
+        .map(|amplitude| amplitude.norm_sqr())
+        .sum::<f64>()
+        .sqrt();
+    if norm > 0.0 {
+        for amplitude in state.iter_mut() {
+            *amplitude /= norm;
+        }
+    }
+}
+
+/// Samples basis states from the probabilities of a state vector.
+///
+/// # Arguments
+///
+/// * `state` - The state vector.
+/// * `shots` - The number of samples.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The index of the basis state of every sample.
+fn sample_states(state: &[Complex64], shots: usize, rng: &mut StdRng) -> Vec<usize> {
+    // The random numbers are sorted, so the cumulative probabilities are computed only once
+    let mut randoms: Vec<(f64, usize)> = (0..shots).map(|shot| (rng.gen::<f64>(), shot)).collect();
+    randoms.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    let mut samples = vec![0; shots];
+    let mut index = 0;
+    let mut cumulative = state[0].norm_sqr();
+    for (random, shot) in randoms {
+        while random >= cumulative && index + 1 < state.len() {
+            index += 1;
+            cumulative += state[index].norm_sqr();
+        }
+        samples[shot] = index;
+    }
+    samples
+}
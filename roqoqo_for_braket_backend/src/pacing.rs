@@ -0,0 +1,128 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request pacing
+//!
+//! Provides the adaptive slowing down of AWS requests while the requests are throttled.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default delay before every request after the first throttled request.
+const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(200);
+
+/// Default maximum delay before every request.
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Factor the delay is reduced by after every successful request.
+const RECOVERY_FACTOR: f64 = 0.75;
+
+/// Adaptive pacing of the requests of a backend.
+///
+/// Every request waits the current delay of the pacer before it is sent. The delay starts at
+/// zero, is doubled, to at least the initial delay, whenever a request is throttled and is
+/// reduced by a quarter after every successful request, dropping back to zero below a quarter of
+/// the initial delay. This keeps long polling and submission loops below the request rate
+/// accepted by AWS instead of exhausting the retries of every request. Clones share their
+/// delay, so one pacer can be set on several backends using the same account.
+#[derive(Debug, Clone)]
+pub struct Pacer {
+    /// The delay before the first request after the first throttled request.
+    initial_delay: Duration,
+    /// The maximum delay before every request.
+    max_delay: Duration,
+    /// The current delay and the number of throttled requests.
+    state: Arc<Mutex<PacerState>>,
+}
+
+/// The state of a pacer shared by its clones.
+#[derive(Debug, Default)]
+struct PacerState {
+    /// The current delay before every request.
+    delay: Duration,
+    /// The number of throttled requests.
+    throttled_requests: usize,
+}
+
+impl Default for Pacer {
+    fn default() -> Self {
+        Pacer::new(DEFAULT_INITIAL_DELAY, DEFAULT_MAX_DELAY)
+    }
+}
+
+impl Pacer {
+    /// Creates a new Pacer.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_delay` - The delay before every request after the first throttled request.
+    /// * `max_delay` - The maximum delay before every request.
+    ///
+    /// # Returns
+    ///
+    /// The new pacer without delay.
+    pub fn new(initial_delay: Duration, max_delay: Duration) -> Self {
+        Pacer {
+            initial_delay,
+            max_delay: max_delay.max(initial_delay),
+            state: Arc::new(Mutex::new(PacerState::default())),
+        }
+    }
+
+    /// Returns the current delay before every request.
+    pub fn delay(&self) -> Duration {
+        self.with_state(|state| state.delay)
+    }
+
+    /// Returns the number of throttled requests.
+    pub fn throttled_requests(&self) -> usize {
+        self.with_state(|state| state.throttled_requests)
+    }
+
+    /// Records a throttled request and increases the delay.
+    pub fn record_throttled(&self) {
+        self.with_state(|state| {
+            state.throttled_requests += 1;
+            state.delay = state
+                .delay
+                .saturating_mul(2)
+                .max(self.initial_delay)
+                .min(self.max_delay);
+        })
+    }
+
+    /// Records a successful request and reduces the delay.
+    pub fn record_success(&self) {
+        self.with_state(|state| {
+            state.delay = state.delay.mul_f64(RECOVERY_FACTOR);
+            if state.delay < self.initial_delay / 4 {
+                state.delay = Duration::ZERO;
+            }
+        })
+    }
+
+    /// Waits the current delay before a request.
+    pub(crate) async fn wait(&self) {
+        let delay = self.delay();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Runs a function on the state, also after a panic while the state was locked.
+    fn with_state<T>(&self, function: impl FnOnce(&mut PacerState) -> T) -> T {
+        match self.state.lock() {
+            Ok(mut state) => function(&mut state),
+            Err(poisoned) => function(&mut poisoned.into_inner()),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Submission payloads
+//!
+//! Provides the request a backend sends to Braket to create a quantum task, for review,
+//! debugging and golden-file tests of the conversion of circuits.
+
+use std::collections::HashMap;
+
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_devices::braket_ir::MeasurementMapping;
+
+/// The payload of the request creating the quantum task of a circuit.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SubmissionPayload {
+    /// The ARN of the device the task runs on.
+    pub device_arn: String,
+    /// The OpenQASM 3 program of the circuit.
+    pub openqasm: String,
+    /// The action of the task, the OpenQASM program and its inputs as Braket IR JSON.
+    pub action: String,
+    /// The device parameters of the task as JSON, `None` when the device is run with its defaults.
+    pub device_parameters: Option<String>,
+    /// The number of shots of the task.
+    pub shots: usize,
+    /// The S3 bucket the results are written to.
+    pub output_s3_bucket: String,
+    /// The key prefix of the results in the S3 bucket.
+    pub output_s3_key_prefix: String,
+    /// The reservation the task runs in, `None` for the public queue.
+    pub reservation_arn: Option<String>,
+    /// The tags of the task.
+    pub tags: HashMap<String, String>,
+    /// The assignment of the bits of the readout registers to the measured qubits.
+    pub mapping: MeasurementMapping,
+}
+
+impl SubmissionPayload {
+    /// Serializes the payload to pretty-printed JSON, e.g. for golden files.
+    pub fn to_json(&self) -> Result<String, RoqoqoBackendError> {
+        serde_json::to_string_pretty(self).map_err(|err| RoqoqoBackendError::GenericError {
+            msg: format!("Could not serialize submission payload: {}", err),
+        })
+    }
+}
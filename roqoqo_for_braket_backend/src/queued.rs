@@ -0,0 +1,451 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Queued tasks
+//!
+//! Provides handles of submitted quantum tasks that can be saved to disk and resumed in a later
+//! process, so that the submitting process does not need to wait for long QPU queues.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use roqoqo::measurements::{Measure, MeasureExpectationValues};
+use roqoqo::{Circuit, QuantumProgram, RoqoqoBackendError};
+use roqoqo_for_braket_devices::AWSDevice;
+
+use crate::backend::{combine_registers, measurement_circuits};
+use crate::{APIBackend, QuantumTask, Registers};
+
+/// A quantum task running a single circuit that can be persisted and resumed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct QueuedTask {
+    /// The submitted quantum task.
+    task: QuantumTask,
+    /// The ARN of the device the task runs on.
+    device_arn: String,
+}
+
+impl QueuedTask {
+    /// Creates the handle of a submitted quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted quantum task.
+    /// * `device` - The device the task runs on.
+    ///
+    /// # Returns
+    ///
+    /// The queued task.
+    pub fn new(task: QuantumTask, device: &AWSDevice) -> Self {
+        QueuedTask {
+            task,
+            device_arn: device.clone().name(),
+        }
+    }
+
+    /// Submits a circuit and returns the handle of its task.
+    ///
+    /// See [QueuedTask::submit_async].
+    pub fn submit(backend: &APIBackend, circuit: &Circuit) -> Result<Self, RoqoqoBackendError> {
+        backend.block_on(QueuedTask::submit_async(backend, circuit))
+    }
+
+    /// Submits a circuit asynchronously and returns the handle of its task.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend the circuit is submitted with.
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QueuedTask)` - The handle of the submitted task.
+    /// * `Err(RoqoqoBackendError)` - The circuit could not be submitted.
+    pub async fn submit_async(
+        backend: &APIBackend,
+        circuit: &Circuit,
+    ) -> Result<Self, RoqoqoBackendError> {
+        let task = backend.submit_async(circuit).await?;
+        Ok(QueuedTask::new(task, backend.device()))
+    }
+
+    /// Returns the submitted quantum task.
+    pub fn task(&self) -> &QuantumTask {
+        &self.task
+    }
+
+    /// Returns the ARN of the device the task runs on.
+    pub fn device_arn(&self) -> &str {
+        &self.device_arn
+    }
+
+    /// Returns the registers of the task once it has finished.
+    ///
+    /// See [QueuedTask::registers_async].
+    pub fn registers(&self, backend: &APIBackend) -> Result<Option<Registers>, RoqoqoBackendError> {
+        backend.block_on(self.registers_async(backend))
+    }
+
+    /// Returns the registers of the task asynchronously once it has finished.
+    ///
+    /// Does not wait for the task, use [APIBackend::poll_async] to wait.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - A backend for the device the task runs on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Registers))` - The registers of the completed task.
+    /// * `Ok(None)` - The task has not finished yet.
+    /// * `Err(RoqoqoBackendError)` - The backend is for another device, or the task has failed or
+    ///   has been cancelled.
+    pub async fn registers_async(
+        &self,
+        backend: &APIBackend,
+    ) -> Result<Option<Registers>, RoqoqoBackendError> {
+        check_device(backend, &self.device_arn)?;
+        if !backend.status_async(&self.task).await?.is_terminal() {
+            return Ok(None);
+        }
+        backend.results_async(&self.task).await.map(Some)
+    }
+
+    /// Serializes the queued task to JSON.
+    pub fn to_json(&self) -> Result<String, RoqoqoBackendError> {
+        to_json(self)
+    }
+
+    /// Deserializes a queued task from JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON written by [QueuedTask::to_json].
+    pub fn from_json(json: &str) -> Result<Self, RoqoqoBackendError> {
+        from_json(json)
+    }
+
+    /// Saves the queued task as a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file, an existing file is overwritten.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RoqoqoBackendError> {
+        save(&self.to_json()?, path.as_ref())
+    }
+
+    /// Loads a queued task from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file written by [QueuedTask::save].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RoqoqoBackendError> {
+        QueuedTask::from_json(&load(path.as_ref())?)
+    }
+}
+
+/// The quantum tasks running a quantum program that can be persisted and resumed.
+///
+/// Holds the measurement of the program with the parameters substituted, so that the expectation
+/// values can be evaluated from the registers without the original program.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QueuedProgram {
+    /// The program with the parameters substituted and no input parameters.
+    program: QuantumProgram,
+    /// The submitted quantum tasks, one for each circuit of the measurement.
+    tasks: Vec<QuantumTask>,
+    /// The ARN of the device the tasks run on.
+    device_arn: String,
+}
+
+impl QueuedProgram {
+    /// Submits the circuits of a quantum program and returns the handle of their tasks.
+    ///
+    /// See [QueuedProgram::submit_async].
+    pub fn submit(
+        backend: &APIBackend,
+        program: &QuantumProgram,
+        parameters: &[f64],
+    ) -> Result<Self, RoqoqoBackendError> {
+        backend.block_on(QueuedProgram::submit_async(backend, program, parameters))
+    }
+
+    /// Submits the circuits of a quantum program asynchronously and returns the handle of their
+    /// tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend the circuits are submitted with.
+    /// * `program` - The quantum program to run.
+    /// * `parameters` - The values of the input parameters of the program, in order.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QueuedProgram)` - The handle of the submitted tasks.
+    /// * `Err(RoqoqoBackendError)` - The parameters do not match the program or a circuit could
+    ///   not be submitted.
+    pub async fn submit_async(
+        backend: &APIBackend,
+        program: &QuantumProgram,
+        parameters: &[f64],
+    ) -> Result<Self, RoqoqoBackendError> {
+        // Every supported program type holds a measurement and its input parameter names
+        macro_rules! substitute_program {
+            ($($variant:ident),+) => {
+                match program {
+                    $(QuantumProgram::$variant {
+                        measurement,
+                        input_parameter_names,
+                    } => {
+                        let measurement =
+                            substitute(measurement, input_parameter_names, parameters)?;
+                        let circuits = measurement_circuits(&measurement);
+                        let program = QuantumProgram::$variant {
+                            measurement,
+                            input_parameter_names: Vec::new(),
+                        };
+                        (program, circuits)
+                    })+
+                    _ => {
+                        return Err(RoqoqoBackendError::GenericError {
+                            msg: "Quantum program type is not supported by QueuedProgram"
+                                .to_string(),
+                        })
+                    }
+                }
+            };
+        }
+        let (program, circuits) = substitute_program!(
+            PauliZProduct,
+            CheatedPauliZProduct,
+            Cheated,
+            ClassicalRegister
+        );
+        let tasks = backend.submit_batch_async(&circuits).await?;
+        Ok(QueuedProgram {
+            program,
+            tasks,
+            device_arn: backend.device().clone().name(),
+        })
+    }
+
+    /// Returns the quantum program with the parameters substituted.
+    pub fn program(&self) -> &QuantumProgram {
+        &self.program
+    }
+
+    /// Returns the submitted quantum tasks, one for each circuit of the measurement.
+    pub fn tasks(&self) -> &[QuantumTask] {
+        &self.tasks
+    }
+
+    /// Returns the ARN of the device the tasks run on.
+    pub fn device_arn(&self) -> &str {
+        &self.device_arn
+    }
+
+    /// Returns the combined registers of the tasks once all of them have finished.
+    ///
+    /// See [QueuedProgram::registers_async].
+    pub fn registers(&self, backend: &APIBackend) -> Result<Option<Registers>, RoqoqoBackendError> {
+        backend.block_on(self.registers_async(backend))
+    }
+
+    /// Returns the combined registers of the tasks asynchronously once all of them have finished.
+    ///
+    /// Does not wait for the tasks, use [APIBackend::poll_async] to wait.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - A backend for the device the tasks run on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Registers))` - The registers of all circuits of the measurement.
+    /// * `Ok(None)` - Not all tasks have finished yet.
+    /// * `Err(RoqoqoBackendError)` - The backend is for another device, or a task has failed or
+    ///   has been cancelled.
+    pub async fn registers_async(
+        &self,
+        backend: &APIBackend,
+    ) -> Result<Option<Registers>, RoqoqoBackendError> {
+        check_device(backend, &self.device_arn)?;
+        for task in self.tasks.iter() {
+            if !backend.status_async(task).await?.is_terminal() {
+                return Ok(None);
+            }
+        }
+        let results = backend.results_batch_async(&self.tasks).await?;
+        Ok(Some(combine_registers(results)))
+    }
+
+    /// Returns the expectation values of the program once all tasks have finished.
+    ///
+    /// See [QueuedProgram::expectation_values_async].
+    pub fn expectation_values(
+        &self,
+        backend: &APIBackend,
+    ) -> Result<Option<HashMap<String, f64>>, RoqoqoBackendError> {
+        backend.block_on(self.expectation_values_async(backend))
+    }
+
+    /// Returns the expectation values of the program asynchronously once all tasks have finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - A backend for the device the tasks run on.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(HashMap<String, f64>))` - The expectation values of the measurement.
+    /// * `Ok(None)` - Not all tasks have finished yet.
+    /// * `Err(RoqoqoBackendError)` - The program measures classical registers, or the registers
+    ///   could not be retrieved or evaluated.
+    pub async fn expectation_values_async(
+        &self,
+        backend: &APIBackend,
+    ) -> Result<Option<HashMap<String, f64>>, RoqoqoBackendError> {
+        if matches!(self.program, QuantumProgram::ClassicalRegister { .. }) {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "ClassicalRegister programs have no expectation values, use registers"
+                    .to_string(),
+            });
+        }
+        let (bits, floats, complexes) = match self.registers_async(backend).await? {
+            Some(registers) => registers,
+            None => return Ok(None),
+        };
+        let values = match &self.program {
+            QuantumProgram::PauliZProduct { measurement, .. } => {
+                measurement.evaluate(bits, floats, complexes)?
+            }
+            QuantumProgram::CheatedPauliZProduct { measurement, .. } => {
+                measurement.evaluate(bits, floats, complexes)?
+            }
+            QuantumProgram::Cheated { measurement, .. } => {
+                measurement.evaluate(bits, floats, complexes)?
+            }
+            _ => None,
+        };
+        values
+            .ok_or_else(|| RoqoqoBackendError::GenericError {
+                msg: "Expectation values could not be evaluated from the registers".to_string(),
+            })
+            .map(Some)
+    }
+
+    /// Serializes the queued program to JSON.
+    pub fn to_json(&self) -> Result<String, RoqoqoBackendError> {
+        to_json(self)
+    }
+
+    /// Deserializes a queued program from JSON.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON written by [QueuedProgram::to_json].
+    pub fn from_json(json: &str) -> Result<Self, RoqoqoBackendError> {
+        from_json(json)
+    }
+
+    /// Saves the queued program as a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file, an existing file is overwritten.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RoqoqoBackendError> {
+        save(&self.to_json()?, path.as_ref())
+    }
+
+    /// Loads a queued program from a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file written by [QueuedProgram::save].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RoqoqoBackendError> {
+        QueuedProgram::from_json(&load(path.as_ref())?)
+    }
+}
+
+/// Substitutes the input parameters of a measurement of a quantum program.
+fn substitute<T: Measure>(
+    measurement: &T,
+    input_parameter_names: &[String],
+    parameters: &[f64],
+) -> Result<T, RoqoqoBackendError> {
+    if input_parameter_names.len() != parameters.len() {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Quantum program expects {} parameters, {} were given",
+                input_parameter_names.len(),
+                parameters.len()
+            ),
+        });
+    }
+    let substituted_parameters: HashMap<String, f64> = input_parameter_names
+        .iter()
+        .cloned()
+        .zip(parameters.iter().copied())
+        .collect();
+    Ok(measurement.substitute_parameters(substituted_parameters)?)
+}
+
+/// Checks that a backend is for the device the tasks were submitted to.
+fn check_device(backend: &APIBackend, device_arn: &str) -> Result<(), RoqoqoBackendError> {
+    let backend_arn = backend.device().clone().name();
+    if backend_arn != device_arn {
+        return Err(RoqoqoBackendError::GenericError {
+            msg: format!(
+                "Tasks were submitted to device {}, the backend is for device {}",
+                device_arn, backend_arn
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Serializes queued tasks to JSON.
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, RoqoqoBackendError> {
+    serde_json::to_string(value).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!("Could not serialize queued tasks: {}", err),
+    })
+}
+
+/// Deserializes queued tasks from JSON.
+fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, RoqoqoBackendError> {
+    serde_json::from_str(json).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!("Could not deserialize queued tasks: {}", err),
+    })
+}
+
+/// Writes the JSON of queued tasks to a file, overwriting an existing file.
+fn save(json: &str, path: &Path) -> Result<(), RoqoqoBackendError> {
+    fs::write(path, json).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!(
+            "Could not write queued tasks to {}: {}",
+            path.display(),
+            err
+        ),
+    })
+}
+
+/// Reads the JSON of queued tasks from a file.
+fn load(path: &Path) -> Result<String, RoqoqoBackendError> {
+    fs::read_to_string(path).map_err(|err| RoqoqoBackendError::GenericError {
+        msg: format!(
+            "Could not read queued tasks from {}: {}",
+            path.display(),
+            err
+        ),
+    })
+}
@@ -0,0 +1,386 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Task results
+//!
+//! Provides the retrieval of the results of Braket quantum tasks from S3 and their conversion
+//! into qoqo registers.
+
+use std::collections::HashMap;
+
+use aws_sdk_s3::error::DisplayErrorContext;
+use num_complex::Complex64;
+use roqoqo::backends::RegisterResult;
+use roqoqo::registers::{ComplexOutputRegister, FloatOutputRegister};
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_devices::braket_ir::MeasurementMapping;
+use serde_json::Value;
+
+use crate::{Pacer, RetryPolicy};
+
+/// Name of the result object Braket writes to the output directory of a task.
+const RESULT_FILE: &str = "results.json";
+
+/// The value of a result type computed by Braket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultValue {
+    /// A single real number, e.g. an expectation value or a variance.
+    Float(f64),
+    /// A list of real numbers, e.g. probabilities or samples.
+    FloatVector(Vec<f64>),
+    /// A list of complex numbers, e.g. amplitudes or a state vector.
+    ComplexVector(Vec<Complex64>),
+}
+
+/// A result type of a Braket task together with its computed value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultTypeValue {
+    /// The Braket name of the result type, e.g. `expectation`.
+    pub name: String,
+    /// The qubits the result type acts on, empty when it acts on all qubits.
+    pub targets: Vec<usize>,
+    /// The value computed by Braket.
+    pub value: ResultValue,
+}
+
+/// The result of a Braket gate model quantum task.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BraketResult {
+    /// The measured bits of every shot.
+    pub measurements: Vec<Vec<bool>>,
+    /// The qubits in the order of the bits of every shot.
+    pub measured_qubits: Vec<usize>,
+    /// The result types requested by the program of the task.
+    pub result_types: Vec<ResultTypeValue>,
+    /// The sharpened probabilities of the measured bitstrings of IonQ tasks with debiasing.
+    pub sharpened_probabilities: Option<HashMap<String, f64>>,
+}
+
+impl BraketResult {
+    /// Parses the result object of a Braket gate model quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The JSON of the result object, `braket.task_result.gate_model_task_result`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BraketResult)` - The parsed result.
+    /// * `Err(RoqoqoBackendError)` - The JSON is not a valid gate model task result.
+    pub fn from_json(result: &str) -> Result<Self, RoqoqoBackendError> {
+        let invalid = |msg: String| RoqoqoBackendError::GenericError {
+            msg: format!("Could not parse Braket task result: {}", msg),
+        };
+        let result: Value = serde_json::from_str(result).map_err(|err| invalid(err.to_string()))?;
+
+        let measurements = match result.get("measurements") {
+            Some(Value::Array(shots)) => shots
+                .iter()
+                .map(|shot| {
+                    shot.as_array()
+                        .map(|bits| bits.iter().map(|bit| bit.as_u64() == Some(1)).collect())
+                        .ok_or_else(|| invalid("a shot is not a list of bits".to_string()))
+                })
+                .collect::<Result<Vec<Vec<bool>>, RoqoqoBackendError>>()?,
+            Some(Value::Null) | None => Vec::new(),
+            Some(_) => return Err(invalid("measurements are not a list".to_string())),
+        };
+        let measured_qubits = qubits(result.get("measuredQubits"))
+            .ok_or_else(|| invalid("measured qubits are not a list of qubits".to_string()))?;
+
+        let mut result_types = Vec::new();
+        if let Some(Value::Array(entries)) = result.get("resultTypes") {
+            for entry in entries {
+                let result_type = &entry["type"];
+                let name = result_type["type"]
+                    .as_str()
+                    .ok_or_else(|| invalid("a result type has no name".to_string()))?;
+                let value = result_value(name, result_type, &entry["value"]).ok_or_else(|| {
+                    invalid(format!("the value of result type {} is not valid", name))
+                })?;
+                result_types.push(ResultTypeValue {
+                    name: name.to_string(),
+                    targets: qubits(result_type.get("targets")).unwrap_or_default(),
+                    value,
+                });
+            }
+        }
+
+        let sharpened_probabilities =
+            match &result["additionalMetadata"]["ionqMetadata"]["sharpenedProbabilities"] {
+                Value::Object(probabilities) => Some(
+                    probabilities
+                        .iter()
+                        .map(|(bits, probability)| {
+                            probability
+                                .as_f64()
+                                .map(|probability| (bits.clone(), probability))
+                        })
+                        .collect::<Option<HashMap<String, f64>>>()
+                        .ok_or_else(|| {
+                            invalid("sharpened probabilities are not numbers".to_string())
+                        })?,
+                ),
+                _ => None,
+            };
+
+        Ok(BraketResult {
+            measurements,
+            measured_qubits,
+            result_types,
+            sharpened_probabilities,
+        })
+    }
+
+    /// Replaces the measured shots by shots distributed as the sharpened probabilities.
+    ///
+    /// Every bitstring gets the number of shots of its probability rounded down, the remaining
+    /// shots go to the bitstrings with the largest remainders.
+    ///
+    /// # Arguments
+    ///
+    /// * `shots` - The number of shots of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The measurements were replaced.
+    /// * `Err(RoqoqoBackendError)` - The result has no sharpened probabilities or their
+    ///   bitstrings do not match the measured qubits.
+    pub fn sharpen(&mut self, shots: usize) -> Result<(), RoqoqoBackendError> {
+        let probabilities = self.sharpened_probabilities.as_ref().ok_or_else(|| {
+            RoqoqoBackendError::GenericError {
+                msg: "Task result has no sharpened probabilities, the task was not run with \
+                      debiasing"
+                    .to_string(),
+            }
+        })?;
+        let mut states: Vec<(&String, f64)> = probabilities
+            .iter()
+            .map(|(bits, probability)| (bits, probability * shots as f64))
+            .collect();
+        if states
+            .iter()
+            .any(|(bits, _)| bits.len() != self.measured_qubits.len())
+        {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "Sharpened bitstrings do not match the measured qubits".to_string(),
+            });
+        }
+        // Sorted by remainder and bitstring, so that the distribution is deterministic
+        states.sort_by(|a, b| {
+            b.1.fract()
+                .partial_cmp(&a.1.fract())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(b.0))
+        });
+        let mut counts: Vec<usize> = states.iter().map(|(_, count)| *count as usize).collect();
+        let assigned: usize = counts.iter().sum();
+        for count in counts.iter_mut().take(shots.saturating_sub(assigned)) {
+            *count += 1;
+        }
+        self.measurements = states
+            .iter()
+            .zip(counts)
+            .flat_map(|((bits, _), count)| {
+                let shot: Vec<bool> = bits.chars().map(|bit| bit == '1').collect();
+                std::iter::repeat(shot).take(count)
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Converts the result into the readout registers of the circuit of the task.
+    ///
+    /// The measured bits are sorted into the bit registers with the measurement mapping of the
+    /// circuit. The values of the result types are written to registers named after the Braket
+    /// result type: single values like expectation values are appended to the first row of a
+    /// float register, lists of real values like probabilities add one row to a float register and
+    /// lists of complex values like amplitudes add one row to a complex register, in the order
+    /// of the result types of the program.
+    ///
+    /// # Arguments
+    ///
+    /// * `mapping` - The measurement mapping of the circuit of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The registers of the result.
+    /// * `Err(RoqoqoBackendError)` - The measured bits do not fit the measurement mapping.
+    pub fn registers(&self, mapping: &MeasurementMapping) -> RegisterResult {
+        let bit_registers = mapping.map_results(&self.measured_qubits, &self.measurements)?;
+        let mut float_registers: HashMap<String, FloatOutputRegister> = HashMap::new();
+        let mut complex_registers: HashMap<String, ComplexOutputRegister> = HashMap::new();
+        for result_type in self.result_types.iter() {
+            match &result_type.value {
+                ResultValue::Float(value) => {
+                    let register = float_registers
+                        .entry(result_type.name.clone())
+                        .or_insert_with(|| vec![Vec::new()]);
+                    register[0].push(*value);
+                }
+                ResultValue::FloatVector(values) => float_registers
+                    .entry(result_type.name.clone())
+                    .or_default()
+                    .push(values.clone()),
+                ResultValue::ComplexVector(values) => complex_registers
+                    .entry(result_type.name.clone())
+                    .or_default()
+                    .push(values.clone()),
+            }
+        }
+        Ok((bit_registers, float_registers, complex_registers))
+    }
+}
+
+/// Reads a list of qubits, a missing list is empty.
+fn qubits(value: Option<&Value>) -> Option<Vec<usize>> {
+    match value {
+        Some(Value::Array(qubits)) => qubits
+            .iter()
+            .map(|qubit| qubit.as_u64().map(|qubit| qubit as usize))
+            .collect(),
+        Some(Value::Null) | None => Some(Vec::new()),
+        Some(_) => None,
+    }
+}
+
+/// Reads a complex number stored by Braket as the pair of its real and imaginary part.
+fn complex(value: &Value) -> Option<Complex64> {
+    let parts = value.as_array()?;
+    Some(Complex64::new(
+        parts.first()?.as_f64()?,
+        parts.get(1)?.as_f64()?,
+    ))
+}
+
+/// Reads the value of a result type.
+fn result_value(name: &str, result_type: &Value, value: &Value) -> Option<ResultValue> {
+    match name {
+        "expectation" | "variance" => value.as_f64().map(ResultValue::Float),
+        "probability" | "sample" => value
+            .as_array()?
+            .iter()
+            .map(|value| value.as_f64())
+            .collect::<Option<Vec<f64>>>()
+            .map(ResultValue::FloatVector),
+        "statevector" => value
+            .as_array()?
+            .iter()
+            .map(complex)
+            .collect::<Option<Vec<Complex64>>>()
+            .map(ResultValue::ComplexVector),
+        "amplitude" => {
+            let amplitudes = value.as_object()?;
+            // The amplitudes are returned in the order of the requested states
+            let states: Vec<&str> = match result_type["states"].as_array() {
+                Some(states) => states.iter().filter_map(|state| state.as_str()).collect(),
+                None => {
+                    let mut states: Vec<&str> = amplitudes.keys().map(|s| s.as_str()).collect();
+                    states.sort_unstable();
+                    states
+                }
+            };
+            states
+                .into_iter()
+                .map(|state| amplitudes.get(state).and_then(complex))
+                .collect::<Option<Vec<Complex64>>>()
+                .map(ResultValue::ComplexVector)
+        }
+        _ => None,
+    }
+}
+
+/// Downloads the results of Braket quantum tasks from S3.
+#[derive(Debug, Clone)]
+pub struct ResultFetcher {
+    /// The client of the S3 API.
+    client: aws_sdk_s3::Client,
+    /// The policy for retrying downloads failing with throttling or transient errors.
+    retry_policy: RetryPolicy,
+    /// The pacing of the downloads.
+    pacer: Pacer,
+}
+
+impl ResultFetcher {
+    /// Creates a new ResultFetcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client of the S3 API with access to the result bucket.
+    ///
+    /// # Returns
+    ///
+    /// The new result fetcher.
+    pub fn new(client: aws_sdk_s3::Client) -> Self {
+        ResultFetcher {
+            client,
+            retry_policy: RetryPolicy::default(),
+            pacer: Pacer::default(),
+        }
+    }
+
+    /// Sets the policy for retrying downloads failing with throttling or transient errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_policy` - The retry policy.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Sets the pacing of the downloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `pacer` - The pacer, e.g. shared with the backend.
+    pub fn set_pacer(&mut self, pacer: Pacer) {
+        self.pacer = pacer;
+    }
+
+    /// Downloads and parses the result of a quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `bucket` - The S3 bucket of the task results, `outputS3Bucket` of the task.
+    /// * `directory` - The output directory of the task, `outputS3Directory` of the task.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BraketResult)` - The result of the task.
+    /// * `Err(RoqoqoBackendError)` - The result could not be downloaded or parsed.
+    #[tracing::instrument(name = "download", skip_all, fields(bucket = %bucket, directory = %directory), err)]
+    pub async fn fetch(
+        &self,
+        bucket: &str,
+        directory: &str,
+    ) -> Result<BraketResult, RoqoqoBackendError> {
+        let key = format!("{}/{}", directory.trim_end_matches('/'), RESULT_FILE);
+        let download_error = |msg: String| RoqoqoBackendError::NetworkError {
+            msg: format!("Could not download s3://{}/{}: {}", bucket, key, msg),
+        };
+        let result = self
+            .retry_policy
+            .run(&self.pacer, || {
+                self.client.get_object().bucket(bucket).key(&key).send()
+            })
+            .await
+            .map_err(|err| download_error(DisplayErrorContext(err).to_string()))?
+            .body
+            .collect()
+            .await
+            .map_err(|err| download_error(err.to_string()))?
+            .into_bytes();
+        tracing::debug!(bytes = result.len(), "Downloaded task result");
+        let result = std::str::from_utf8(&result).map_err(|err| download_error(err.to_string()))?;
+        BraketResult::from_json(result)
+    }
+}
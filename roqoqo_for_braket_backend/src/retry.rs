@@ -0,0 +1,212 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Retry policy
+//!
+//! Provides the retrying of AWS requests failing with throttling or transient errors.
+
+use std::future::Future;
+use std::time::Duration;
+
+use aws_sdk_braket::config::http::HttpResponse;
+use aws_sdk_braket::error::{ProvideErrorMetadata, SdkError};
+
+use crate::Pacer;
+
+/// Error codes of AWS services for requests rejected because of the request rate.
+const THROTTLING_CODES: [&str; 5] = [
+    "ThrottlingException",
+    "Throttling",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+    "SlowDown",
+];
+
+/// Error codes of AWS services for transient errors of requests that can succeed when they are
+/// sent again.
+const TRANSIENT_CODES: [&str; 3] = [
+    "InternalServiceException",
+    "ServiceUnavailable",
+    "RequestTimeout",
+];
+
+/// Policy for retrying AWS requests failing with throttling or transient errors.
+///
+/// A failed request is sent again after an exponentially growing backoff with full jitter: the
+/// n-th retry waits a random time between zero and `initial_backoff * 2^(n-1)`, capped at
+/// `max_backoff`. Requests are retried when they time out, cannot be dispatched or fail with
+/// HTTP status 429, a server error or a throttling error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is sent, including the first attempt.
+    pub max_attempts: usize,
+    /// The upper bound of the backoff before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum upper bound of the backoff before a retry.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new RetryPolicy.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_attempts` - The maximum number of times a request is sent, at least one.
+    /// * `initial_backoff` - The upper bound of the backoff before the first retry.
+    /// * `max_backoff` - The maximum upper bound of the backoff before a retry.
+    ///
+    /// # Returns
+    ///
+    /// The new retry policy.
+    pub fn new(max_attempts: usize, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// Creates a policy sending every request only once.
+    ///
+    /// # Returns
+    ///
+    /// The retry policy without retries.
+    pub fn no_retry() -> Self {
+        RetryPolicy::new(1, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Returns the upper bound of the backoff before a retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of the retry, starting at one for the first retry.
+    ///
+    /// # Returns
+    ///
+    /// The maximum time waited before the retry.
+    pub fn max_backoff_before(&self, retry: usize) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31) as u32;
+        self.initial_backoff
+            .saturating_mul(1_u32 << exponent)
+            .min(self.max_backoff)
+    }
+
+    /// Returns the jittered backoff before a retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of the retry, starting at one for the first retry.
+    ///
+    /// # Returns
+    ///
+    /// A random time between zero and [RetryPolicy::max_backoff_before].
+    pub fn backoff_before(&self, retry: usize) -> Duration {
+        self.max_backoff_before(retry)
+            .mul_f64(rand::random::<f64>())
+    }
+
+    /// Sends a request until it succeeds, fails with an error that is not retryable or the
+    /// maximum number of attempts is reached.
+    ///
+    /// Every attempt waits the delay of the pacer first, and throttled and successful attempts
+    /// are recorded by the pacer.
+    ///
+    /// # Arguments
+    ///
+    /// * `pacer` - The pacer of the requests.
+    /// * `request` - Sends the request once.
+    ///
+    /// # Returns
+    ///
+    /// The result of the last attempt.
+    pub(crate) async fn run<T, E, F, Fut>(
+        &self,
+        pacer: &Pacer,
+        mut request: F,
+    ) -> Result<T, SdkError<E, HttpResponse>>
+    where
+        E: ProvideErrorMetadata,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, SdkError<E, HttpResponse>>>,
+    {
+        let mut attempt = 1;
+        loop {
+            pacer.wait().await;
+            let result = request().await;
+            match &result {
+                Ok(_) => pacer.record_success(),
+                Err(err) if is_throttling(err) => pacer.record_throttled(),
+                Err(_) => {}
+            }
+            match result {
+                Err(err) if attempt < self.max_attempts && is_retryable(&err) => {
+                    let backoff = self.backoff_before(attempt);
+                    tracing::warn!(
+                        attempt,
+                        code = err.code(),
+                        throttled = is_throttling(&err),
+                        backoff_ms = backoff.as_millis() as u64,
+                        "Retrying failed AWS request"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Returns whether a failed request can succeed when it is sent again.
+fn is_retryable<E: ProvideErrorMetadata>(err: &SdkError<E, HttpResponse>) -> bool {
+    match err {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(service_error) => {
+            service_error.raw().status().as_u16() >= 500
+                || is_throttling(err)
+                || err
+                    .code()
+                    .is_some_and(|code| TRANSIENT_CODES.contains(&code))
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether a request was rejected because of the request rate.
+fn is_throttling<E: ProvideErrorMetadata>(err: &SdkError<E, HttpResponse>) -> bool {
+    match err {
+        SdkError::ServiceError(service_error) => {
+            service_error.raw().status().as_u16() == 429
+                || err
+                    .code()
+                    .is_some_and(|code| THROTTLING_CODES.contains(&code))
+        }
+        _ => false,
+    }
+}
+
+/// Returns a random token identifying a request, e.g. the client token of a task creation.
+pub(crate) fn random_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
@@ -0,0 +1,344 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Device selection
+//!
+//! Provides a backend choosing the device of every circuit from a list of candidate devices.
+
+use std::future::Future;
+use std::sync::Mutex;
+
+use futures::future::join_all;
+use roqoqo::backends::{EvaluatingBackend, RegisterResult};
+use roqoqo::operations::Operation;
+use roqoqo::{Circuit, RoqoqoBackendError};
+use roqoqo_for_braket_devices::{AWSDevice, DeviceScore};
+
+use crate::{APIBackend, AWSConfig, ConcurrencyLimit, QuantumTask};
+
+/// Default weight of the waiting tasks in the score of a device, a queue of 100 tasks halves the
+/// score.
+const DEFAULT_QUEUE_WEIGHT: f64 = 0.01;
+
+/// The device chosen for a circuit and the scores it was chosen by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSelection {
+    /// The index of the device in the candidates of the backend.
+    pub index: usize,
+    /// The ARN of the device.
+    pub device_arn: String,
+    /// The score of the device for the circuit, see [AWSDevice::score_for_circuit].
+    pub score: DeviceScore,
+    /// The number of tasks the circuit waits behind, `None` when it is unknown.
+    pub waiting_tasks: Option<usize>,
+    /// The combined score of the device including its queue.
+    pub total: f64,
+}
+
+/// Backend running every circuit on the best of a list of candidate devices.
+///
+/// For every circuit, the candidates that are online and can run the circuit are ranked by the
+/// score of [AWSDevice::score_for_circuit], which accounts for the native gates, connectivity,
+/// estimated fidelity and estimated cost, divided by one plus the queue weight times the number
+/// of tasks waiting in the queue of the device. Every choice is recorded, see
+/// [SelectingBackend::selections].
+///
+/// As for [APIBackend], the blocking methods are only available for backends created with
+/// [SelectingBackend::new].
+#[derive(Debug)]
+pub struct SelectingBackend {
+    /// The backends of the candidate devices.
+    backends: Vec<APIBackend>,
+    /// The weight of the waiting tasks in the score of a device.
+    queue_weight: f64,
+    /// The devices chosen for the submitted circuits, in the order of the submissions.
+    selections: Mutex<Vec<DeviceSelection>>,
+    /// The runtime executing the requests of the blocking methods, `None` for backends created
+    /// with [SelectingBackend::new_async].
+    runtime: Option<tokio::runtime::Runtime>,
+}
+
+impl SelectingBackend {
+    /// Creates a new SelectingBackend.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - The candidate devices.
+    /// * `config` - The configuration of the access to AWS, the region defaults to the region of
+    ///   every device.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SelectingBackend)` - The new backend.
+    /// * `Err(RoqoqoBackendError)` - No devices were given or the runtime executing the requests
+    ///   could not be started.
+    pub fn new(devices: Vec<AWSDevice>, config: AWSConfig) -> Result<Self, RoqoqoBackendError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| RoqoqoBackendError::GenericError {
+                msg: format!("Could not start the runtime of the backend: {}", err),
+            })?;
+        let mut backend = runtime.block_on(Self::new_async(devices, config))?;
+        backend.runtime = Some(runtime);
+        Ok(backend)
+    }
+
+    /// Creates a new SelectingBackend used from an async context.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - The candidate devices.
+    /// * `config` - The configuration of the access to AWS.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SelectingBackend)` - The new backend.
+    /// * `Err(RoqoqoBackendError)` - No devices were given.
+    pub async fn new_async(
+        devices: Vec<AWSDevice>,
+        config: AWSConfig,
+    ) -> Result<Self, RoqoqoBackendError> {
+        if devices.is_empty() {
+            return Err(RoqoqoBackendError::GenericError {
+                msg: "SelectingBackend needs at least one candidate device".to_string(),
+            });
+        }
+        let backends = join_all(
+            devices
+                .into_iter()
+                .map(|device| APIBackend::new_async(device, config.clone())),
+        )
+        .await;
+        Ok(SelectingBackend {
+            backends,
+            queue_weight: DEFAULT_QUEUE_WEIGHT,
+            selections: Mutex::new(Vec::new()),
+            runtime: None,
+        })
+    }
+
+    /// Returns the backends of the candidate devices.
+    pub fn backends(&self) -> &[APIBackend] {
+        &self.backends
+    }
+
+    /// Returns the backend of a candidate device, e.g. to set its tags or reservation.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the device in the candidates.
+    pub fn backend_mut(&mut self, index: usize) -> Option<&mut APIBackend> {
+        self.backends.get_mut(index)
+    }
+
+    /// Sets a limit of the task creation requests shared by all candidate devices.
+    ///
+    /// # Arguments
+    ///
+    /// * `permits` - The maximum number of concurrent creation requests, `None` for no shared
+    ///   limit.
+    pub fn set_global_limit(&mut self, permits: Option<usize>) {
+        let global_limit = permits.map(ConcurrencyLimit::new);
+        for backend in self.backends.iter_mut() {
+            backend.set_global_limit(global_limit.clone());
+        }
+    }
+
+    /// Sets the weight of the waiting tasks in the score of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue_weight` - The weight, zero to ignore the queues of the devices.
+    pub fn set_queue_weight(&mut self, queue_weight: f64) {
+        self.queue_weight = queue_weight.max(0.0);
+    }
+
+    /// Returns the devices chosen for the submitted circuits, in the order of the submissions.
+    pub fn selections(&self) -> Vec<DeviceSelection> {
+        match self.selections.lock() {
+            Ok(selections) => selections.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Chooses the device for a circuit.
+    ///
+    /// See [SelectingBackend::select_async].
+    pub fn select(&self, circuit: &Circuit) -> Result<DeviceSelection, RoqoqoBackendError> {
+        self.block_on(self.select_async(circuit))
+    }
+
+    /// Submits a circuit to the best candidate device.
+    ///
+    /// See [SelectingBackend::submit_async].
+    pub fn submit(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<(QuantumTask, DeviceSelection), RoqoqoBackendError> {
+        self.block_on(self.submit_async(circuit))
+    }
+
+    /// Waits for a quantum task and returns its readout registers.
+    ///
+    /// See [SelectingBackend::results_async].
+    pub fn results(&self, task: &QuantumTask, selection: &DeviceSelection) -> RegisterResult {
+        self.block_on(self.results_async(task, selection))
+    }
+
+    /// Chooses the device for a circuit asynchronously.
+    ///
+    /// The availability of all candidates is requested concurrently. The choice is not recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DeviceSelection)` - The candidate with the highest combined score.
+    /// * `Err(RoqoqoBackendError)` - No candidate is online and can run the circuit.
+    pub async fn select_async(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<DeviceSelection, RoqoqoBackendError> {
+        let availabilities = join_all(
+            self.backends
+                .iter()
+                .map(|backend| backend.availability_async()),
+        )
+        .await;
+        let mut best: Option<DeviceSelection> = None;
+        for (index, (backend, availability)) in self.backends.iter().zip(availabilities).enumerate()
+        {
+            // Candidates that cannot be requested are skipped like offline devices
+            let availability = match availability {
+                Ok(availability) if availability.is_online() => availability,
+                _ => continue,
+            };
+            if backend.payload(circuit).is_err() {
+                continue;
+            }
+            let score = backend.device().score_for_circuit(circuit);
+            let waiting_tasks = availability.waiting_tasks();
+            let total =
+                score.total() / (1.0 + self.queue_weight * waiting_tasks.unwrap_or(0) as f64);
+            if !best.as_ref().is_some_and(|best| best.total >= total) {
+                best = Some(DeviceSelection {
+                    index,
+                    device_arn: availability.device_arn,
+                    score,
+                    waiting_tasks,
+                    total,
+                });
+            }
+        }
+        best.ok_or_else(|| RoqoqoBackendError::GenericError {
+            msg: "No candidate device is online and can run the circuit".to_string(),
+        })
+    }
+
+    /// Submits a circuit asynchronously to the best candidate device.
+    ///
+    /// The device is chosen with [SelectingBackend::select_async] and the choice is recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to run.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((QuantumTask, DeviceSelection))` - The handle of the submitted task and the chosen
+    ///   device.
+    /// * `Err(RoqoqoBackendError)` - No candidate can run the circuit or the task could not be
+    ///   created.
+    pub async fn submit_async(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<(QuantumTask, DeviceSelection), RoqoqoBackendError> {
+        let selection = self.select_async(circuit).await?;
+        let task = self.backends[selection.index].submit_async(circuit).await?;
+        if let Ok(mut selections) = self.selections.lock() {
+            selections.push(selection.clone());
+        }
+        Ok((task, selection))
+    }
+
+    /// Waits asynchronously for a quantum task and returns its readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The submitted task.
+    /// * `selection` - The device the task was submitted to.
+    ///
+    /// # Returns
+    ///
+    /// The registers of the task, see [APIBackend::results_async].
+    pub async fn results_async(
+        &self,
+        task: &QuantumTask,
+        selection: &DeviceSelection,
+    ) -> RegisterResult {
+        match self.backends.get(selection.index) {
+            Some(backend) => backend.results_async(task).await,
+            None => Err(RoqoqoBackendError::GenericError {
+                msg: format!("Backend has no candidate device {}", selection.index),
+            }),
+        }
+    }
+
+    /// Runs a future of an async method on the runtime of the backend.
+    fn block_on<T>(
+        &self,
+        future: impl Future<Output = Result<T, RoqoqoBackendError>>,
+    ) -> Result<T, RoqoqoBackendError> {
+        match &self.runtime {
+            Some(runtime) => runtime.block_on(future),
+            None => Err(RoqoqoBackendError::GenericError {
+                msg: "Backend created with new_async only supports the async methods".to_string(),
+            }),
+        }
+    }
+}
+
+impl Drop for SelectingBackend {
+    fn drop(&mut self) {
+        // Dropping a runtime waits for its tasks, which panics inside the runtime of an async
+        // caller.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl EvaluatingBackend for SelectingBackend {
+    /// Runs a circuit on the best candidate device and returns its readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The iterator over the operations of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((bit_registers, float_registers, complex_registers))` - The readout registers of the
+    ///   circuit.
+    /// * `Err(RoqoqoBackendError)` - The circuit could not be run, see
+    ///   [SelectingBackend::submit] and [SelectingBackend::results].
+    fn run_circuit_iterator<'a>(
+        &self,
+        circuit: impl Iterator<Item = &'a Operation>,
+    ) -> RegisterResult {
+        let circuit: Circuit = circuit.cloned().collect();
+        let (task, selection) = self.submit(&circuit)?;
+        self.results(&task, &selection)
+    }
+}
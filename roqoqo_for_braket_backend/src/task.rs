@@ -0,0 +1,223 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quantum tasks
+//!
+//! Provides the handles of the quantum tasks submitted to AWS Braket.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use roqoqo::RoqoqoBackendError;
+use roqoqo_for_braket_devices::braket_ir::MeasurementMapping;
+
+/// Status of a Braket quantum task.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TaskStatus {
+    /// The task has been created.
+    Created,
+    /// The task is waiting in the queue of the device.
+    Queued,
+    /// The task is running on the device.
+    Running,
+    /// The task has finished and its results are available.
+    Completed,
+    /// The task has failed.
+    Failed,
+    /// The cancellation of the task has been requested.
+    Cancelling,
+    /// The task has been cancelled.
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// Returns whether the status of the task can no longer change.
+    ///
+    /// # Returns
+    ///
+    /// True for completed, failed and cancelled tasks.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled
+        )
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = RoqoqoBackendError;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "CREATED" => Ok(TaskStatus::Created),
+            "QUEUED" => Ok(TaskStatus::Queued),
+            "RUNNING" => Ok(TaskStatus::Running),
+            "COMPLETED" => Ok(TaskStatus::Completed),
+            "FAILED" => Ok(TaskStatus::Failed),
+            "CANCELLING" => Ok(TaskStatus::Cancelling),
+            "CANCELLED" => Ok(TaskStatus::Cancelled),
+            _ => Err(RoqoqoBackendError::GenericError {
+                msg: format!("Unknown quantum task status {}", status),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            TaskStatus::Created => "CREATED",
+            TaskStatus::Queued => "QUEUED",
+            TaskStatus::Running => "RUNNING",
+            TaskStatus::Completed => "COMPLETED",
+            TaskStatus::Failed => "FAILED",
+            TaskStatus::Cancelling => "CANCELLING",
+            TaskStatus::Cancelled => "CANCELLED",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// A quantum task submitted to AWS Braket.
+///
+/// Holds the ARN of the task together with the layout of the readout registers of the submitted
+/// circuit, which is needed to sort the measurement results back into the registers.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct QuantumTask {
+    /// The ARN of the task.
+    task_arn: String,
+    /// The number of shots of the task.
+    shots: usize,
+    /// The assignment of the bits of the readout registers to the measured qubits.
+    mapping: MeasurementMapping,
+}
+
+impl QuantumTask {
+    /// Creates the handle of a submitted quantum task.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_arn` - The ARN of the task.
+    /// * `shots` - The number of shots of the task.
+    /// * `mapping` - The measurement mapping of the submitted circuit.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the task.
+    pub fn new(task_arn: &str, shots: usize, mapping: MeasurementMapping) -> Self {
+        QuantumTask {
+            task_arn: task_arn.to_string(),
+            shots,
+            mapping,
+        }
+    }
+
+    /// Returns the ARN of the task.
+    pub fn task_arn(&self) -> &str {
+        &self.task_arn
+    }
+
+    /// Returns the number of shots of the task.
+    pub fn shots(&self) -> usize {
+        self.shots
+    }
+
+    /// Returns the measurement mapping of the submitted circuit.
+    pub fn mapping(&self) -> &MeasurementMapping {
+        &self.mapping
+    }
+}
+
+/// Metadata of a Braket quantum task, as shown in the Braket console.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskMetadata {
+    /// The ARN of the task.
+    pub task_arn: String,
+    /// The status of the task.
+    pub status: TaskStatus,
+    /// The ARN of the device the task runs on.
+    pub device_arn: String,
+    /// The number of shots of the task.
+    pub shots: usize,
+    /// The time the task was created.
+    pub created_at: SystemTime,
+    /// The time the task finished, `None` while it has not finished.
+    pub ended_at: Option<SystemTime>,
+    /// The reason the task failed, `None` unless it failed.
+    pub failure_reason: Option<String>,
+}
+
+/// Summary of a Braket quantum task returned by [crate::APIBackend::list_tasks].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskSummary {
+    /// The ARN of the task.
+    pub task_arn: String,
+    /// The status of the task.
+    pub status: TaskStatus,
+    /// The ARN of the device the task runs on.
+    pub device_arn: String,
+    /// The number of shots of the task.
+    pub shots: usize,
+    /// The time the task was created.
+    pub created_at: SystemTime,
+    /// The time the task finished, `None` while it has not finished.
+    pub ended_at: Option<SystemTime>,
+    /// The tags of the task.
+    pub tags: HashMap<String, String>,
+}
+
+/// Filter of the quantum tasks listed by [crate::APIBackend::list_tasks].
+///
+/// A task is listed when it matches all set criteria, the default filter lists all tasks.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TaskFilter {
+    /// The ARN of the device the tasks run on, `None` for all devices.
+    pub device_arn: Option<String>,
+    /// The status of the tasks, `None` for all statuses.
+    pub status: Option<TaskStatus>,
+    /// The tags the tasks must have with the given values.
+    pub tags: HashMap<String, String>,
+    /// The earliest creation time of the tasks, `None` for no lower bound.
+    pub created_after: Option<SystemTime>,
+    /// The latest creation time of the tasks, `None` for no upper bound.
+    pub created_before: Option<SystemTime>,
+}
+
+impl TaskFilter {
+    /// Returns whether a task matches the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary` - The summary of the task.
+    ///
+    /// # Returns
+    ///
+    /// True when the task matches all set criteria of the filter.
+    pub fn matches(&self, summary: &TaskSummary) -> bool {
+        self.device_arn
+            .as_ref()
+            .map_or(true, |device_arn| device_arn == &summary.device_arn)
+            && self.status.map_or(true, |status| status == summary.status)
+            && self
+                .tags
+                .iter()
+                .all(|(key, value)| summary.tags.get(key) == Some(value))
+            && self
+                .created_after
+                .map_or(true, |created_after| summary.created_at >= created_after)
+            && self
+                .created_before
+                .map_or(true, |created_before| summary.created_at <= created_before)
+    }
+}
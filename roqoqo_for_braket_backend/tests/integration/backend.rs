@@ -0,0 +1,509 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::measurements::ClassicalRegister;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoBackendError};
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+#[test_case("CREATED", TaskStatus::Created, false)]
+#[test_case("QUEUED", TaskStatus::Queued, false)]
+#[test_case("RUNNING", TaskStatus::Running, false)]
+#[test_case("COMPLETED", TaskStatus::Completed, true)]
+#[test_case("FAILED", TaskStatus::Failed, true)]
+#[test_case("CANCELLING", TaskStatus::Cancelling, false)]
+#[test_case("CANCELLED", TaskStatus::Cancelled, true)]
+fn test_task_status(name: &str, status: TaskStatus, terminal: bool) {
+    assert_eq!(name.parse::<TaskStatus>().unwrap(), status);
+    assert_eq!(status.to_string(), name);
+    assert_eq!(status.is_terminal(), terminal);
+}
+
+#[test]
+fn test_task_status_unknown() {
+    assert!("DONE".parse::<TaskStatus>().is_err());
+}
+
+#[test]
+fn test_submit_rejected_before_submission() {
+    let backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.device(), &AWSDevice::from(OQCLucyDevice::new()));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(20);
+    circuit += MeasureQubit::new(20, "ro".to_string(), 0);
+    assert!(matches!(
+        backend.submit(&circuit),
+        Err(RoqoqoBackendError::RoqoqoError(_))
+    ));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += PragmaSetNumberOfMeasurements::new(1_000_000, "ro".to_string());
+    assert!(backend.submit(&circuit).is_err());
+}
+
+#[tokio::test]
+async fn test_submit_async_rejected_before_submission() {
+    let backend = APIBackend::new_async(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .await;
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(20);
+    circuit += MeasureQubit::new(20, "ro".to_string(), 0);
+    assert!(matches!(
+        backend.submit_async(&circuit).await,
+        Err(RoqoqoBackendError::RoqoqoError(_))
+    ));
+    // Backends without own runtime only support the async methods
+    assert!(matches!(
+        backend.submit(&circuit),
+        Err(RoqoqoBackendError::GenericError { .. })
+    ));
+}
+
+#[test]
+fn test_evaluating_backend_rejected_before_submission() {
+    let backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(20);
+    circuit += MeasureQubit::new(20, "ro".to_string(), 0);
+    assert!(backend.run_circuit(&circuit).is_err());
+
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![circuit],
+    };
+    assert!(backend.run_measurement_registers(&measurement).is_err());
+}
+
+#[test]
+fn test_run_circuit_batch_rejected_before_submission() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_max_concurrency(0);
+    assert!(backend.run_circuit_batch(&[]).unwrap().is_empty());
+
+    let mut valid = Circuit::new();
+    valid += DefinitionBit::new("ro".to_string(), 1, true);
+    valid += MeasureQubit::new(0, "ro".to_string(), 0);
+    let mut invalid = Circuit::new();
+    invalid += DefinitionBit::new("ro".to_string(), 1, true);
+    invalid += MeasureQubit::new(20, "ro".to_string(), 0);
+    assert!(matches!(
+        backend.submit_batch(&[invalid, valid]),
+        Err(RoqoqoBackendError::RoqoqoError(_))
+    ));
+}
+
+#[test]
+fn test_cancel_all_without_tasks() {
+    let backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(backend.cancel_all().unwrap().is_empty());
+}
+
+#[test]
+fn test_aws_config() {
+    let mut config = AWSConfig::new("bucket", "prefix");
+    assert_eq!(config.s3_bucket(), "bucket");
+    assert_eq!(config.s3_prefix(), "prefix");
+    assert_eq!(config.profile(), None);
+    assert_eq!(config.region(), None);
+    assert_eq!(config.region_for(&OQCLucyDevice::new().into()), "eu-west-2");
+
+    config.set_profile(Some("braket"));
+    config.set_region(Some("us-east-1"));
+    assert_eq!(config.profile(), Some("braket"));
+    assert_eq!(config.region_for(&OQCLucyDevice::new().into()), "us-east-1");
+
+    assert_eq!(config.assume_role(), None);
+    let role = AssumeRole::new(
+        "arn:aws:iam::123456789012:role/braket",
+        Some("external"),
+        "session",
+    );
+    config.set_assume_role(Some(role.clone()));
+    assert_eq!(config.assume_role(), Some(&role));
+    assert_eq!(role.external_id, Some("external".to_string()));
+
+    assert_eq!(config.endpoint_url(), None);
+    config.set_endpoint_url(Some("http://localhost:4566"));
+    assert_eq!(config.endpoint_url(), Some("http://localhost:4566"));
+
+    let backend = APIBackend::new(OQCLucyDevice::new().into(), config.clone()).unwrap();
+    assert_eq!(backend.config(), &config);
+}
+
+#[test]
+fn test_reservation_arn() {
+    let mut backend = APIBackend::new(
+        IonQAria1Device::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.reservation_arn(), None);
+    let reservation_arn = "arn:aws:braket:us-east-1:123456789012:reservation/1";
+    backend.set_reservation_arn(Some(reservation_arn));
+    assert_eq!(backend.reservation_arn(), Some(reservation_arn));
+    backend.set_reservation_arn(None);
+    assert_eq!(backend.reservation_arn(), None);
+}
+
+#[test]
+fn test_device_availability() {
+    let mut availability = DeviceAvailability {
+        device_arn: "arn:aws:braket:us-east-1::device/qpu/ionq/Aria-1".to_string(),
+        status: "ONLINE".to_string(),
+        queue_size: Some(42),
+        reservation_arn: None,
+    };
+    assert!(availability.is_online());
+    assert_eq!(availability.waiting_tasks(), Some(42));
+
+    availability.reservation_arn =
+        Some("arn:aws:braket:us-east-1:123456789012:reservation/1".to_string());
+    assert_eq!(availability.waiting_tasks(), Some(0));
+
+    availability.status = "OFFLINE".to_string();
+    assert!(!availability.is_online());
+}
+
+#[test]
+fn test_dry_run() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(!backend.dry_run());
+    backend.set_dry_run(true);
+    assert!(backend.dry_run());
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let payload = backend.payload(&circuit).unwrap();
+    assert_eq!(payload.device_arn, backend.device().clone().name());
+    assert_eq!(payload.shots, 100);
+    assert_eq!(payload.output_s3_bucket, "bucket");
+    assert_eq!(payload.output_s3_key_prefix, "prefix");
+    assert_eq!(payload.reservation_arn, None);
+    assert!(payload.action.contains("braket.ir.openqasm.program"));
+    let json = payload.to_json().unwrap();
+    assert_eq!(
+        serde_json::from_str::<SubmissionPayload>(&json).unwrap(),
+        payload
+    );
+
+    let task = backend.submit(&circuit).unwrap();
+    assert_eq!(task.task_arn(), "dry-run/0");
+    assert_eq!(backend.submit(&circuit).unwrap().task_arn(), "dry-run/1");
+    assert!(backend.status(&task).is_err());
+    assert_eq!(backend.cost_tracker().number_tasks(), 0);
+    assert_eq!(
+        backend.take_dry_run_payloads(),
+        vec![payload.clone(), payload]
+    );
+    assert!(backend.take_dry_run_payloads().is_empty());
+}
+
+#[test]
+fn test_task_filter() {
+    let created_at = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+    let summary = TaskSummary {
+        task_arn: "task".to_string(),
+        status: TaskStatus::Completed,
+        device_arn: "device".to_string(),
+        shots: 100,
+        created_at,
+        ended_at: None,
+        tags: [("project".to_string(), "vqe".to_string())]
+            .into_iter()
+            .collect(),
+    };
+    assert!(TaskFilter::default().matches(&summary));
+
+    let mut filter = TaskFilter {
+        device_arn: Some("device".to_string()),
+        status: Some(TaskStatus::Completed),
+        created_after: Some(created_at),
+        created_before: Some(created_at),
+        ..TaskFilter::default()
+    };
+    filter.tags.insert("project".to_string(), "vqe".to_string());
+    assert!(filter.matches(&summary));
+
+    let other_device = TaskFilter {
+        device_arn: Some("other".to_string()),
+        ..filter.clone()
+    };
+    assert!(!other_device.matches(&summary));
+    let other_status = TaskFilter {
+        status: Some(TaskStatus::Failed),
+        ..filter.clone()
+    };
+    assert!(!other_status.matches(&summary));
+    let later = TaskFilter {
+        created_after: Some(created_at + std::time::Duration::from_secs(1)),
+        ..filter.clone()
+    };
+    assert!(!later.matches(&summary));
+    let mut other_tag = filter;
+    other_tag
+        .tags
+        .insert("project".to_string(), "qaoa".to_string());
+    assert!(!other_tag.matches(&summary));
+}
+
+#[test]
+fn test_list_tasks_dry_run() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_dry_run(true);
+    assert!(backend.list_tasks(&TaskFilter::default()).is_err());
+}
+
+#[test]
+fn test_submit_routed_dry_run() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_dry_run(true);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += CNOT::new(0, 1);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let (routed, layout) =
+        roqoqo_for_braket_devices::routing::route_circuit(&circuit, backend.device()).unwrap();
+    let task = backend.submit_routed(&circuit, &routed, &layout).unwrap();
+    let expected = roqoqo_for_braket_devices::braket_ir::MeasurementMapping::from_circuit(&circuit)
+        .unwrap()
+        .remap_qubits(&layout);
+    assert_eq!(task.mapping(), &expected);
+    assert_eq!(
+        task.mapping().measured_qubits(),
+        backend.take_dry_run_payloads()[0].mapping.measured_qubits()
+    );
+
+    let mut unmeasured = Circuit::new();
+    unmeasured += DefinitionBit::new("ro".to_string(), 2, true);
+    unmeasured += CNOT::new(0, 1);
+    assert!(backend
+        .submit_routed(&circuit, &unmeasured, &layout)
+        .is_err());
+}
+
+#[test]
+fn test_for_each_result_reports_every_task() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_dry_run(true);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let tasks = backend
+        .submit_batch(&[circuit.clone(), circuit.clone(), circuit])
+        .unwrap();
+
+    // Dry-run tasks cannot be polled, the error of every task is reported
+    let mut indices = Vec::new();
+    backend
+        .for_each_result(&tasks, |index, result| {
+            assert!(result.is_err());
+            indices.push(index);
+        })
+        .unwrap();
+    indices.sort_unstable();
+    assert_eq!(indices, vec![0, 1, 2]);
+}
+
+#[test]
+fn test_tags() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(backend.tags().is_empty());
+    backend.set_tag("project", "qoqo").unwrap();
+    backend.set_tag("cost-center", "1234").unwrap();
+    backend.set_tag("project", "braket").unwrap();
+    assert_eq!(backend.tags().len(), 2);
+    assert_eq!(backend.tags().get("project"), Some(&"braket".to_string()));
+
+    assert!(backend.set_tag("", "value").is_err());
+    assert!(backend.set_tag("aws:createdBy", "value").is_err());
+    assert!(backend.set_tag("experiment-id", &"x".repeat(257)).is_err());
+    for index in 0..48 {
+        backend.set_tag(&format!("tag-{}", index), "value").unwrap();
+    }
+    assert!(backend.set_tag("experiment-id", "1").is_err());
+    assert_eq!(backend.remove_tag("project"), Some("braket".to_string()));
+    assert_eq!(backend.remove_tag("project"), None);
+    backend.set_tag("experiment-id", "1").unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert_eq!(&backend.payload(&circuit).unwrap().tags, backend.tags());
+}
+
+#[test]
+fn test_concurrency_limits() {
+    let limit = ConcurrencyLimit::new(0);
+    assert_eq!(limit.permits(), 1);
+    let limit = ConcurrencyLimit::new(4);
+    assert_eq!(limit.permits(), 4);
+    assert_eq!(limit.available(), 4);
+
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.device_limit().permits(), 10);
+    assert!(backend.global_limit().is_none());
+    backend.set_device_limit(2);
+    assert_eq!(backend.device_limit().permits(), 2);
+
+    let mut other = APIBackend::new(
+        IonQAria1Device::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_global_limit(Some(limit.clone()));
+    other.set_global_limit(Some(limit.clone()));
+    assert_eq!(backend.global_limit().unwrap().permits(), 4);
+    assert_eq!(other.global_limit().unwrap().available(), 4);
+}
+
+#[test]
+fn test_rigetti_options() {
+    let mut backend = APIBackend::new(
+        RigettiAspenM3Device::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.rigetti_options(), None);
+    backend
+        .set_rigetti_options(Some(RigettiOptions::exact()))
+        .unwrap();
+    assert_eq!(backend.rigetti_options(), Some(RigettiOptions::exact()));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateX::new(0, std::f64::consts::PI.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let payload = backend.payload(&circuit).unwrap();
+    assert!(payload.openqasm.contains("#pragma braket verbatim"));
+    let parameters = payload.device_parameters.unwrap();
+    assert!(parameters.contains("braket.device_schema.rigetti.rigetti_device_parameters"));
+    assert!(parameters.contains("\"disableQubitRewiring\":true"));
+
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(backend
+        .set_rigetti_options(Some(RigettiOptions::default()))
+        .is_err());
+}
+
+#[test]
+fn test_parameter_label() {
+    let values: std::collections::HashMap<String, f64> =
+        [("theta".to_string(), 1.0), ("phi".to_string(), 0.5)]
+            .into_iter()
+            .collect();
+    assert_eq!(parameter_label(&values), "phi=0.5,theta=1");
+    assert_eq!(parameter_label(&std::collections::HashMap::new()), "");
+}
+
+#[test]
+fn test_run_parameter_sweep_dry_run() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    backend.set_dry_run(true);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateZ::new(0, "theta".into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let parameter_sets: Vec<std::collections::HashMap<String, f64>> = vec![
+        [("theta".to_string(), 0.5)].into_iter().collect(),
+        [("theta".to_string(), 1.5)].into_iter().collect(),
+    ];
+    let unknown: Vec<std::collections::HashMap<String, f64>> =
+        vec![[("phi".to_string(), 0.5)].into_iter().collect()];
+    assert!(backend
+        .run_parameter_sweep(&[circuit.clone()], &unknown)
+        .is_err());
+    assert!(backend.take_dry_run_payloads().is_empty());
+    let duplicated: Vec<std::collections::HashMap<String, f64>> =
+        vec![parameter_sets[0].clone(), parameter_sets[0].clone()];
+    assert!(backend
+        .run_parameter_sweep(&[circuit.clone()], &duplicated)
+        .is_err());
+    assert!(backend.take_dry_run_payloads().is_empty());
+
+    // The tasks are recorded, the results cannot be requested in dry-run mode
+    assert!(backend
+        .run_parameter_sweep(&[circuit], &parameter_sets)
+        .is_err());
+    let payloads = backend.take_dry_run_payloads();
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0].openqasm, payloads[1].openqasm);
+    assert!(payloads[0].action.contains("0.5"));
+    assert!(payloads[1].action.contains("1.5"));
+}
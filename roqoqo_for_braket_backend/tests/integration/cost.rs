@@ -0,0 +1,70 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::*;
+
+#[test]
+fn test_cost_tracker() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let cost = CostTracker::estimate_cost(&device, 100);
+    assert!((cost - device.price_per_task() - 100.0 * device.price_per_shot()).abs() < 1e-12);
+
+    let mut tracker = CostTracker::new(None);
+    assert_eq!(tracker.remaining_cost(), None);
+    assert!((tracker.add_task(&device, 100).unwrap() - cost).abs() < 1e-12);
+    assert!((tracker.add_task(&device, 100).unwrap() - cost).abs() < 1e-12);
+    assert!((tracker.total_cost() - 2.0 * cost).abs() < 1e-12);
+    assert_eq!(tracker.number_tasks(), 2);
+    assert_eq!(tracker.number_shots(), 200);
+
+    tracker.reset();
+    assert_eq!(tracker.total_cost(), 0.0);
+    assert_eq!(tracker.number_tasks(), 0);
+    assert_eq!(tracker.number_shots(), 0);
+}
+
+#[test]
+fn test_cost_tracker_max_cost() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let cost = CostTracker::estimate_cost(&device, 100);
+    let mut tracker = CostTracker::new(Some(1.5 * cost));
+    assert_eq!(tracker.max_cost(), Some(1.5 * cost));
+    assert!(tracker.add_task(&device, 100).is_ok());
+    assert!(tracker.add_task(&device, 100).is_err());
+    assert_eq!(tracker.number_tasks(), 1);
+    assert!((tracker.remaining_cost().unwrap() - 0.5 * cost).abs() < 1e-12);
+
+    tracker.set_max_cost(None);
+    assert!(tracker.add_task(&device, 100).is_ok());
+    assert_eq!(tracker.number_tasks(), 2);
+}
+
+#[test]
+fn test_submit_rejected_above_max_cost() {
+    let mut backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.cost_tracker(), CostTracker::default());
+    backend.set_cost_tracker(CostTracker::new(Some(0.0)));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert!(backend.submit(&circuit).is_err());
+    assert_eq!(backend.cost_tracker().number_tasks(), 0);
+}
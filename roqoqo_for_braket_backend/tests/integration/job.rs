@@ -0,0 +1,60 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use roqoqo_for_braket_backend::*;
+use test_case::test_case;
+
+#[test_case("QUEUED", JobStatus::Queued, false)]
+#[test_case("RUNNING", JobStatus::Running, false)]
+#[test_case("COMPLETED", JobStatus::Completed, true)]
+#[test_case("FAILED", JobStatus::Failed, true)]
+#[test_case("CANCELLING", JobStatus::Cancelling, false)]
+#[test_case("CANCELLED", JobStatus::Cancelled, true)]
+fn test_job_status(name: &str, status: JobStatus, terminal: bool) {
+    assert_eq!(name.parse::<JobStatus>().unwrap(), status);
+    assert_eq!(status.to_string(), name);
+    assert_eq!(status.is_terminal(), terminal);
+}
+
+#[test]
+fn test_job_status_unknown() {
+    assert!("CREATED".parse::<JobStatus>().is_err());
+}
+
+#[test]
+fn test_job_definition() {
+    let mut definition = JobDefinition::new(
+        "vqe",
+        "arn:aws:iam::123456789012:role/braket-jobs",
+        "s3://bucket/vqe/source.tar.gz",
+        "vqe.main:run",
+    );
+    assert_eq!(definition.instance_type, "ml.m5.large");
+    assert_eq!(definition.instance_count, 1);
+    assert_eq!(definition.volume_size_gb, 30);
+    assert_eq!(definition.max_runtime, Duration::from_secs(432_000));
+    assert!(definition.hyperparameters.is_empty());
+    assert_eq!(definition.checkpoint, None);
+
+    definition.set_hyperparameter("layers", "3");
+    definition.set_hyperparameter("layers", "4");
+    assert_eq!(definition.hyperparameters.len(), 1);
+    assert_eq!(definition.hyperparameters["layers"], "4");
+
+    let job = HybridJob::new("arn:aws:braket:us-east-1:123456789012:job/vqe");
+    assert_eq!(
+        job.job_arn(),
+        "arn:aws:braket:us-east-1:123456789012:job/vqe"
+    );
+}
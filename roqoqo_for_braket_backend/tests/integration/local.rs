@@ -0,0 +1,94 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::backends::EvaluatingBackend;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+/// Circuit flipping qubit 0 and measuring qubits 0 and 1 50 times.
+fn flip_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(50, "ro".to_string());
+    circuit
+}
+
+#[test]
+fn test_local_backend_without_noise() {
+    let backend = LocalBackend::new(OQCLucyDevice::new().into());
+    assert!(!backend.noise());
+    let (bit_registers, float_registers, complex_registers) =
+        backend.run_circuit(&flip_circuit()).unwrap();
+    assert_eq!(bit_registers["ro"], vec![vec![true, false]; 50]);
+    assert!(float_registers.is_empty());
+    assert!(complex_registers.is_empty());
+}
+
+#[test]
+fn test_local_backend_superposition() {
+    let mut backend = LocalBackend::new(OQCLucyDevice::new().into());
+    backend.set_seed(Some(42));
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += SqrtPauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaSetNumberOfMeasurements::new(1000, "ro".to_string());
+
+    let (bit_registers, _, _) = backend.run_circuit(&circuit).unwrap();
+    let ones = bit_registers["ro"].iter().filter(|shot| shot[0]).count();
+    assert!(ones > 400 && ones < 600);
+    let (repeated, _, _) = backend.run_circuit(&circuit).unwrap();
+    assert_eq!(repeated, bit_registers);
+}
+
+#[test]
+fn test_local_backend_with_noise() {
+    let mut device: AWSDevice = OQCLucyDevice::new().into();
+    device.set_readout_error(1, 1.0, 0.0).unwrap();
+    device.set_preparation_error(0, 1.0).unwrap();
+    let mut backend = LocalBackend::new(device);
+    backend.set_noise(true);
+    assert!(backend.noise());
+
+    // The preparation error undoes the flip of qubit 0, qubit 1 is always read out as 1
+    let (bit_registers, _, _) = backend.run_circuit(&flip_circuit()).unwrap();
+    assert_eq!(bit_registers["ro"], vec![vec![false, true]; 50]);
+}
+
+#[test]
+fn test_local_backend_damping() {
+    let mut device: AWSDevice = OQCLucyDevice::new().into();
+    device.add_damping(0, 1e6).unwrap();
+    let mut backend = LocalBackend::new(device);
+    backend.set_noise(true);
+
+    let (bit_registers, _, _) = backend.run_circuit(&flip_circuit()).unwrap();
+    assert_eq!(bit_registers["ro"], vec![vec![false, false]; 50]);
+}
+
+#[test_case(Hadamard::new(0).into(); "non-native gate")]
+#[test_case(PauliX::new(20).into(); "qubit outside of the device")]
+#[test_case(PragmaSetNumberOfMeasurements::new(1_000_000, "ro".to_string()).into(); "too many shots")]
+fn test_local_backend_rejects(operation: Operation) {
+    let backend = LocalBackend::new(OQCLucyDevice::new().into());
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += operation;
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert!(backend.run_circuit(&circuit).is_err());
+}
@@ -0,0 +1,35 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod backend;
+
+#[cfg(test)]
+mod cost;
+
+#[cfg(test)]
+mod job;
+
+#[cfg(test)]
+mod local;
+
+#[cfg(test)]
+mod queued;
+
+#[cfg(test)]
+mod results;
+
+#[cfg(test)]
+mod retry;
+
+#[cfg(test)]
+mod selection;
@@ -0,0 +1,99 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::measurements::{ClassicalRegister, PauliZProduct, PauliZProductInput};
+use roqoqo::operations::*;
+use roqoqo::{Circuit, QuantumProgram};
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::braket_ir::MeasurementMapping;
+use roqoqo_for_braket_devices::*;
+
+fn queued_task() -> QueuedTask {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let mapping = MeasurementMapping::from_circuit(&circuit).unwrap();
+    let task = QuantumTask::new(
+        "arn:aws:braket:eu-west-2:123456789012:quantum-task/1",
+        100,
+        mapping,
+    );
+    QueuedTask::new(task, &OQCLucyDevice::new().into())
+}
+
+#[test]
+fn test_queued_task_json() {
+    let queued = queued_task();
+    assert_eq!(
+        queued.device_arn(),
+        "arn:aws:braket:eu-west-2::device/qpu/oqc/Lucy"
+    );
+    let json = queued.to_json().unwrap();
+    assert_eq!(QueuedTask::from_json(&json).unwrap(), queued);
+    assert!(QueuedTask::from_json("{}").is_err());
+}
+
+#[test]
+fn test_queued_task_save_load() {
+    let queued = queued_task();
+    let path = std::env::temp_dir().join("roqoqo_for_braket_queued_task.json");
+    queued.save(&path).unwrap();
+    assert_eq!(QueuedTask::load(&path).unwrap(), queued);
+    std::fs::remove_file(&path).unwrap();
+    assert!(QueuedTask::load(&path).is_err());
+}
+
+#[test]
+fn test_queued_task_other_device() {
+    let queued = queued_task();
+    let backend = APIBackend::new(
+        IonQAria1Device::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(queued.registers(&backend).is_err());
+}
+
+#[test]
+fn test_queued_program_parameters() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let measurement = ClassicalRegister {
+        constant_circuit: None,
+        circuits: vec![circuit],
+    };
+    let program = QuantumProgram::ClassicalRegister {
+        measurement,
+        input_parameter_names: vec!["theta".to_string()],
+    };
+    let backend = APIBackend::new(
+        OQCLucyDevice::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert!(QueuedProgram::submit(&backend, &program, &[]).is_err());
+    assert!(QueuedProgram::submit(&backend, &program, &[0.1, 0.2]).is_err());
+
+    let measurement = PauliZProduct {
+        constant_circuit: None,
+        circuits: Vec::new(),
+        input: PauliZProductInput::new(1, false),
+    };
+    let program = QuantumProgram::PauliZProduct {
+        measurement,
+        input_parameter_names: vec!["theta".to_string()],
+    };
+    assert!(QueuedProgram::submit(&backend, &program, &[]).is_err());
+}
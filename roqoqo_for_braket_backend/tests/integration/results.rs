@@ -0,0 +1,150 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::braket_ir::MeasurementMapping;
+
+#[test]
+fn test_result_registers() {
+    let result = r#"{
+        "braketSchemaHeader": {"name": "braket.task_result.gate_model_task_result", "version": "1"},
+        "measurements": [[0, 1], [1, 1], [0, 0]],
+        "measuredQubits": [2, 0],
+        "resultTypes": [
+            {"type": {"type": "expectation", "observable": ["z"], "targets": [0]}, "value": 0.25},
+            {"type": {"type": "expectation", "observable": ["z", "z"], "targets": [0, 2]}, "value": -1.0},
+            {"type": {"type": "probability", "targets": [2]}, "value": [0.5, 0.5]},
+            {"type": {"type": "amplitude", "states": ["11", "00"]}, "value": {"00": [0.5, 0.0], "11": [0.0, -0.5]}}
+        ]
+    }"#;
+    let result = BraketResult::from_json(result).unwrap();
+    assert_eq!(result.measured_qubits, vec![2, 0]);
+    assert_eq!(result.measurements[0], vec![false, true]);
+    assert_eq!(result.result_types.len(), 4);
+    assert_eq!(result.result_types[1].targets, vec![0, 2]);
+    assert_eq!(result.result_types[0].value, ResultValue::Float(0.25));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(2, "ro".to_string(), 2);
+    let mapping = MeasurementMapping::from_circuit(&circuit).unwrap();
+    let (bits, floats, complexes) = result.registers(&mapping).unwrap();
+    assert_eq!(
+        bits["ro"],
+        vec![
+            vec![true, false, false],
+            vec![true, false, true],
+            vec![false, false, false]
+        ]
+    );
+    assert_eq!(floats["expectation"], vec![vec![0.25, -1.0]]);
+    assert_eq!(floats["probability"], vec![vec![0.5, 0.5]]);
+    assert_eq!(
+        complexes["amplitude"],
+        vec![vec![Complex64::new(0.0, -0.5), Complex64::new(0.5, 0.0)]]
+    );
+}
+
+#[test]
+fn test_result_only_result_types() {
+    let result = r#"{"resultTypes": [{"type": {"type": "statevector"}, "value": [[1.0, 0.0], [0.0, 0.0]]}]}"#;
+    let result = BraketResult::from_json(result).unwrap();
+    assert!(result.measurements.is_empty());
+    let (bits, floats, complexes) = result.registers(&MeasurementMapping::default()).unwrap();
+    assert!(bits.is_empty());
+    assert!(floats.is_empty());
+    assert_eq!(complexes["statevector"][0].len(), 2);
+}
+
+#[test]
+fn test_result_invalid() {
+    assert!(BraketResult::from_json("not json").is_err());
+    assert!(BraketResult::from_json(r#"{"measurements": 1}"#).is_err());
+    assert!(BraketResult::from_json(r#"{"measuredQubits": [-1]}"#).is_err());
+    assert!(BraketResult::from_json(
+        r#"{"resultTypes": [{"type": {"type": "expectation"}, "value": [1.0]}]}"#
+    )
+    .is_err());
+
+    let result =
+        BraketResult::from_json(r#"{"measurements": [[1]], "measuredQubits": [1]}"#).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let mapping = MeasurementMapping::from_circuit(&circuit).unwrap();
+    assert!(result.registers(&mapping).is_err());
+}
+
+#[test]
+fn test_result_sharpen() {
+    let result = r#"{
+        "measurements": [[0, 1], [1, 1], [0, 0]],
+        "measuredQubits": [0, 1],
+        "additionalMetadata": {"ionqMetadata": {"sharpenedProbabilities": {"00": 0.5, "11": 0.5}}}
+    }"#;
+    let mut result = BraketResult::from_json(result).unwrap();
+    assert_eq!(result.sharpened_probabilities.as_ref().unwrap()["11"], 0.5);
+    result.sharpen(3).unwrap();
+    assert_eq!(
+        result.measurements,
+        vec![vec![false, false], vec![false, false], vec![true, true]]
+    );
+
+    let mut result =
+        BraketResult::from_json(r#"{"measurements": [[0]], "measuredQubits": [0]}"#).unwrap();
+    assert!(result.sharpen(1).is_err());
+    result.sharpened_probabilities = Some([("00".to_string(), 1.0)].into_iter().collect());
+    assert!(result.sharpen(1).is_err());
+}
+
+#[test]
+fn test_ionq_options() {
+    let mut backend = APIBackend::new(
+        roqoqo_for_braket_devices::IonQAria1Device::new().into(),
+        AWSConfig::new("bucket", "prefix"),
+    )
+    .unwrap();
+    assert_eq!(backend.ionq_options(), None);
+    assert!(backend
+        .set_ionq_options(Some(IonQOptions {
+            debias: false,
+            sharpen: true
+        }))
+        .is_err());
+    backend
+        .set_ionq_options(Some(IonQOptions::debias(true)))
+        .unwrap();
+    assert_eq!(backend.ionq_options(), Some(IonQOptions::debias(true)));
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let parameters = backend
+        .payload(&circuit)
+        .unwrap()
+        .device_parameters
+        .unwrap();
+    assert!(parameters.contains("braket.device_schema.ionq.ionq_device_parameters"));
+    assert!(parameters.contains("braket.device_schema.error_mitigation.debias.Debias"));
+
+    let harmony: roqoqo_for_braket_devices::AWSDevice =
+        roqoqo_for_braket_devices::IonQHarmonyDevice::new().into();
+    assert!(IonQOptions::debias(false).validate(&harmony).is_err());
+    assert!(IonQOptions::default().validate(&harmony).is_ok());
+    let lucy: roqoqo_for_braket_devices::AWSDevice =
+        roqoqo_for_braket_devices::OQCLucyDevice::new().into();
+    assert!(IonQOptions::default().validate(&lucy).is_err());
+}
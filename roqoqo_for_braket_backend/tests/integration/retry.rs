@@ -0,0 +1,69 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use roqoqo_for_braket_backend::*;
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy::new(4, Duration::from_millis(100), Duration::from_secs(1));
+    assert_eq!(policy.max_attempts, 4);
+    assert_eq!(policy.max_backoff_before(1), Duration::from_millis(100));
+    assert_eq!(policy.max_backoff_before(2), Duration::from_millis(200));
+    assert_eq!(policy.max_backoff_before(4), Duration::from_millis(800));
+    assert_eq!(policy.max_backoff_before(5), Duration::from_secs(1));
+    assert_eq!(policy.max_backoff_before(1000), Duration::from_secs(1));
+    for retry in 1..10 {
+        assert!(policy.backoff_before(retry) <= policy.max_backoff_before(retry));
+    }
+}
+
+#[test]
+fn test_retry_policy_attempts() {
+    assert_eq!(
+        RetryPolicy::new(0, Duration::ZERO, Duration::ZERO).max_attempts,
+        1
+    );
+    assert_eq!(RetryPolicy::no_retry().max_attempts, 1);
+    assert_eq!(
+        RetryPolicy::no_retry().max_backoff_before(3),
+        Duration::ZERO
+    );
+    assert_eq!(RetryPolicy::default().max_attempts, 5);
+}
+
+#[test]
+fn test_pacer() {
+    let pacer = Pacer::new(Duration::from_millis(100), Duration::from_millis(500));
+    assert_eq!(pacer.delay(), Duration::ZERO);
+    pacer.record_success();
+    assert_eq!(pacer.delay(), Duration::ZERO);
+
+    let shared = pacer.clone();
+    shared.record_throttled();
+    assert_eq!(pacer.delay(), Duration::from_millis(100));
+    pacer.record_throttled();
+    assert_eq!(pacer.delay(), Duration::from_millis(200));
+    for _ in 0..5 {
+        pacer.record_throttled();
+    }
+    assert_eq!(pacer.delay(), Duration::from_millis(500));
+    assert_eq!(shared.throttled_requests(), 7);
+
+    pacer.record_success();
+    assert_eq!(pacer.delay(), Duration::from_millis(375));
+    for _ in 0..20 {
+        pacer.record_success();
+    }
+    assert_eq!(pacer.delay(), Duration::ZERO);
+}
@@ -0,0 +1,49 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_backend::*;
+use roqoqo_for_braket_devices::*;
+
+#[test]
+fn test_selecting_backend_new() {
+    assert!(SelectingBackend::new(Vec::new(), AWSConfig::new("bucket", "prefix")).is_err());
+
+    let devices: Vec<AWSDevice> = vec![OQCLucyDevice::new().into(), IonQAria1Device::new().into()];
+    let mut backend =
+        SelectingBackend::new(devices.clone(), AWSConfig::new("bucket", "prefix")).unwrap();
+    assert_eq!(backend.backends().len(), 2);
+    assert_eq!(backend.backends()[1].device(), &devices[1]);
+    assert!(backend.backend_mut(1).is_some());
+    assert!(backend.backend_mut(2).is_none());
+    assert!(backend.selections().is_empty());
+}
+
+#[test]
+fn test_selecting_backend_without_available_device() {
+    let devices: Vec<AWSDevice> = vec![OQCLucyDevice::new().into(), IonQAria1Device::new().into()];
+    let mut backend = SelectingBackend::new(devices, AWSConfig::new("bucket", "prefix")).unwrap();
+    // Backends in dry-run mode cannot request the availability of their device
+    for index in 0..2 {
+        backend.backend_mut(index).unwrap().set_dry_run(true);
+    }
+    backend.set_queue_weight(0.0);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert!(backend.select(&circuit).is_err());
+    assert!(backend.submit(&circuit).is_err());
+    assert!(backend.selections().is_empty());
+}
@@ -0,0 +1,158 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circuit analysis
+//!
+//! Provides functions to analyse qoqo circuits with respect to the AWS devices they are run on.
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::{
+    InvolveQubits, InvolvedQubits, Operate, OperateSingleQubit, OperateTwoQubit, Operation,
+    SingleQubitGateOperation, TwoQubitGateOperation,
+};
+use roqoqo::Circuit;
+
+use crate::AWSDevice;
+
+mod duration;
+pub use crate::analysis::duration::CircuitDuration;
+
+mod fidelity;
+pub use crate::analysis::fidelity::CircuitFidelity;
+
+mod pricing;
+pub(crate) use crate::analysis::pricing::default_prices;
+pub use crate::analysis::pricing::{DevicePrices, PriceTable};
+
+mod report;
+pub use crate::analysis::report::{gate_report, GateReport};
+
+mod scoring;
+pub use crate::analysis::scoring::{recommend_device, DeviceScore};
+
+mod validation;
+pub use crate::analysis::validation::ValidationError;
+
+/// Number of shots used when a circuit does not define the number of measurements.
+pub const DEFAULT_NUMBER_SHOTS: usize = 100;
+
+/// Returns the qubits a gate operation acts on.
+///
+/// # Arguments
+///
+/// * `operation` - The operation to inspect.
+///
+/// # Returns
+///
+/// * `Some(Vec<usize>)` - The qubits of the gate, `[qubit]` or `[control, target]` for single and two qubit gates.
+/// * `None` - The operation is not a gate operation.
+pub(crate) fn gate_qubits(operation: &Operation) -> Option<Vec<usize>> {
+    if !operation.tags().contains(&"GateOperation") {
+        return None;
+    }
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        return Some(vec![*gate.qubit()]);
+    }
+    if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        return Some(vec![*gate.control(), *gate.target()]);
+    }
+    match operation.involved_qubits() {
+        InvolvedQubits::Set(qubits) => {
+            let mut qubits: Vec<usize> = qubits.into_iter().collect();
+            qubits.sort_unstable();
+            Some(qubits)
+        }
+        _ => Some(Vec::new()),
+    }
+}
+
+/// Returns the gate time of a gate on the device.
+///
+/// # Arguments
+///
+/// * `device` - The device the gate is run on.
+/// * `hqslang` - The hqslang name of the gate.
+/// * `qubits` - The qubits the gate acts on, as returned by [gate_qubits].
+///
+/// # Returns
+///
+/// * `Some<f64>` - The gate time.
+/// * `None` - The gate is not available on the device for the given qubits.
+pub(crate) fn gate_time(device: &AWSDevice, hqslang: &str, qubits: &[usize]) -> Option<f64> {
+    match qubits {
+        [qubit] => device.single_qubit_gate_time(hqslang, qubit),
+        [control, target] => device.two_qubit_gate_time(hqslang, control, target),
+        [control_0, control_1, target] => {
+            device.three_qubit_gate_time(hqslang, control_0, control_1, target)
+        }
+        _ => device.multi_qubit_gate_time(hqslang, qubits),
+    }
+}
+
+/// Returns the number of qubits a circuit needs, i.e. the largest qubit index plus one.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to inspect.
+///
+/// # Returns
+///
+/// The number of qubits of the circuit.
+pub(crate) fn circuit_number_qubits(circuit: &Circuit) -> usize {
+    circuit
+        .iter()
+        .filter_map(|operation| match operation.involved_qubits() {
+            InvolvedQubits::Set(qubits) => qubits.into_iter().max(),
+            _ => None,
+        })
+        .max()
+        .map_or(0, |qubit| qubit + 1)
+}
+
+/// Returns the number of shots a circuit requests.
+///
+/// The number of shots is set by a PragmaSetNumberOfMeasurements or PragmaRepeatedMeasurement
+/// operation, and defaults to [DEFAULT_NUMBER_SHOTS].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to inspect.
+///
+/// # Returns
+///
+/// The number of shots of the circuit.
+pub(crate) fn circuit_number_shots(circuit: &Circuit) -> usize {
+    circuit
+        .iter()
+        .find_map(|operation| match operation {
+            Operation::PragmaSetNumberOfMeasurements(x) => Some(*x.number_measurements()),
+            Operation::PragmaRepeatedMeasurement(x) => Some(*x.number_measurements()),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_NUMBER_SHOTS)
+}
+
+/// Returns the total decoherence rate of a qubit on the device.
+///
+/// # Arguments
+///
+/// * `device` - The device the qubit belongs to.
+/// * `qubit` - The qubit for which the rate is returned.
+///
+/// # Returns
+///
+/// The trace of the qubit's decoherence rate matrix, zero if no rates are set.
+pub(crate) fn total_decoherence_rate(device: &AWSDevice, qubit: usize) -> f64 {
+    device
+        .qubit_decoherence_rates(&qubit)
+        .map_or(0.0, |rates| rates.diag().sum())
+}
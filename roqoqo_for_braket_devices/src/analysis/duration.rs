@@ -0,0 +1,102 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::{Operate, Operation};
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::{circuit_number_qubits, gate_qubits, gate_time};
+use crate::AWSDevice;
+
+/// Estimated wall-clock duration of a circuit on an AWS device.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircuitDuration {
+    /// Time at which the last operation on each qubit of the circuit finishes.
+    pub qubit_durations: Vec<f64>,
+    /// Duration of the whole circuit, the largest of the qubit durations.
+    pub total: f64,
+}
+
+impl AWSDevice {
+    /// Estimates how long a circuit runs on the device.
+    ///
+    /// The circuit is scheduled greedily: every operation starts as soon as all its qubits are
+    /// free. Gates take their gate time on the device, MeasureQubit and PragmaRepeatedMeasurement
+    /// the measurement times of the qubits (zero when not set) and PragmaSleep its sleep time.
+    /// PragmaRepeatedMeasurement measures all qubits at the same time after all other operations.
+    /// Other operations take no time.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to schedule.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CircuitDuration)` - The duration per qubit and of the whole circuit.
+    /// * `Err(RoqoqoError)` - A gate is not available on the device or a sleep time is symbolic.
+    pub fn estimate_duration(&self, circuit: &Circuit) -> Result<CircuitDuration, RoqoqoError> {
+        let mut clocks = vec![0.0; circuit_number_qubits(circuit)];
+        let measurement_time = |qubit: usize| self.measurement_time(qubit).unwrap_or(0.0);
+        for operation in circuit.iter() {
+            if let Some(qubits) = gate_qubits(operation) {
+                let hqslang = operation.hqslang();
+                let time =
+                    gate_time(self, hqslang, &qubits).ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!(
+                            "Gate {} is not available on qubits {:?} of the device",
+                            hqslang, qubits
+                        ),
+                    })?;
+                schedule(&mut clocks, &qubits, time);
+                continue;
+            }
+            match operation {
+                Operation::MeasureQubit(measurement) => {
+                    let qubit = *measurement.qubit();
+                    clocks[qubit] += measurement_time(qubit);
+                }
+                Operation::PragmaRepeatedMeasurement(_) => {
+                    let start = clocks.iter().copied().fold(0.0, f64::max);
+                    for (qubit, clock) in clocks.iter_mut().enumerate() {
+                        *clock = start + measurement_time(qubit);
+                    }
+                }
+                Operation::PragmaSleep(sleep) => {
+                    let time = sleep.sleep_time().float()?;
+                    schedule(&mut clocks, sleep.qubits(), *time);
+                }
+                _ => {}
+            }
+        }
+        let total = clocks.iter().copied().fold(0.0, f64::max);
+        Ok(CircuitDuration {
+            qubit_durations: clocks,
+            total,
+        })
+    }
+}
+
+/// Schedules an operation on some qubits after all previous operations on them have finished.
+///
+/// # Arguments
+///
+/// * `clocks` - The times at which the qubits are free, updated in place.
+/// * `qubits` - The qubits the operation acts on.
+/// * `time` - The duration of the operation.
+fn schedule(clocks: &mut [f64], qubits: &[usize], time: f64) {
+    let start = qubits
+        .iter()
+        .map(|qubit| clocks[*qubit])
+        .fold(0.0, f64::max);
+    for qubit in qubits {
+        clocks[*qubit] = start + time;
+    }
+}
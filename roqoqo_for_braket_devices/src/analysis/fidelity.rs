@@ -0,0 +1,112 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::{Operate, Operation};
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::{circuit_number_qubits, gate_qubits, gate_time, total_decoherence_rate};
+use crate::AWSDevice;
+
+/// Estimated probability that a circuit runs without error on an AWS device.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircuitFidelity {
+    /// Product of the fidelities of all operations acting on each qubit of the circuit.
+    pub qubit_fidelities: Vec<f64>,
+    /// Product of the fidelities of all operations of the circuit.
+    pub total: f64,
+}
+
+impl AWSDevice {
+    /// Estimates the probability that a circuit runs without error on the device.
+    ///
+    /// The fidelities stored on the device are multiplied along the circuit:
+    /// the state preparation of every qubit used by the circuit, the decoherence of the qubits
+    /// during each gate, the two-qubit error rates of the edges and the readout errors of the
+    /// measured qubits, where a readout fidelity is one minus the mean of both assignment errors.
+    /// Decoherence of idle qubits is not taken into account.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to estimate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CircuitFidelity)` - The fidelity per qubit and of the whole circuit.
+    /// * `Err(RoqoqoError)` - A gate is not available on the device.
+    pub fn estimate_fidelity(&self, circuit: &Circuit) -> Result<CircuitFidelity, RoqoqoError> {
+        let number_qubits = circuit_number_qubits(circuit);
+        let mut qubit_fidelities = vec![1.0; number_qubits];
+        let mut total = 1.0;
+        let mut used = vec![false; number_qubits];
+        let readout_fidelity = |qubit: usize| {
+            self.readout_error(qubit)
+                .map_or(1.0, |(p01, p10)| 1.0 - (p01 + p10) / 2.0)
+        };
+
+        for operation in circuit.iter() {
+            if let Some(qubits) = gate_qubits(operation) {
+                let hqslang = operation.hqslang();
+                let time =
+                    gate_time(self, hqslang, &qubits).ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!(
+                            "Gate {} is not available on qubits {:?} of the device",
+                            hqslang, qubits
+                        ),
+                    })?;
+                let gate_fidelity = match qubits[..] {
+                    [control, target] => {
+                        1.0 - self.two_qubit_error_rate(control, target).unwrap_or(0.0)
+                    }
+                    _ => 1.0,
+                };
+                total *= gate_fidelity;
+                for qubit in qubits {
+                    let fidelity = (-total_decoherence_rate(self, qubit) * time).exp();
+                    total *= fidelity;
+                    qubit_fidelities[qubit] *= fidelity * gate_fidelity;
+                    used[qubit] = true;
+                }
+                continue;
+            }
+            match operation {
+                Operation::MeasureQubit(measurement) => {
+                    let qubit = *measurement.qubit();
+                    let fidelity = readout_fidelity(qubit);
+                    total *= fidelity;
+                    qubit_fidelities[qubit] *= fidelity;
+                    used[qubit] = true;
+                }
+                Operation::PragmaRepeatedMeasurement(_) => {
+                    for (qubit, qubit_fidelity) in qubit_fidelities.iter_mut().enumerate() {
+                        let fidelity = readout_fidelity(qubit);
+                        total *= fidelity;
+                        *qubit_fidelity *= fidelity;
+                        used[qubit] = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (qubit, qubit_fidelity) in qubit_fidelities.iter_mut().enumerate() {
+            if used[qubit] {
+                let fidelity = 1.0 - self.preparation_error(qubit).unwrap_or(0.0);
+                total *= fidelity;
+                *qubit_fidelity *= fidelity;
+            }
+        }
+        Ok(CircuitFidelity {
+            qubit_fidelities,
+            total,
+        })
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::AWSDevice;
+
+/// Prices AWS charges for running quantum tasks on a device.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DevicePrices {
+    /// The price per quantum task in USD.
+    pub per_task: f64,
+    /// The price per shot in USD.
+    pub per_shot: f64,
+}
+
+/// Returns the on-demand prices of a device.
+///
+/// The prices are taken from the Amazon Braket pricing page,
+/// <https://aws.amazon.com/braket/pricing/>:
+///
+/// | Device               | Price per task | Price per shot |
+/// |----------------------|----------------|----------------|
+/// | IonQ Harmony         | 0.30 USD       | 0.01 USD       |
+/// | IonQ Aria-1          | 0.30 USD       | 0.03 USD       |
+/// | OQC Lucy             | 0.30 USD       | 0.00035 USD    |
+/// | Rigetti Aspen-M-3    | 0.30 USD       | 0.00035 USD    |
+///
+/// # Arguments
+///
+/// * `device` - The device whose prices are returned.
+///
+/// # Returns
+///
+/// The on-demand prices of the device.
+pub(crate) fn default_prices(device: &AWSDevice) -> DevicePrices {
+    let (per_task, per_shot) = match device {
+        AWSDevice::IonQHarmonyDevice(_) => (0.3, 0.01),
+        AWSDevice::IonQAria1Device(_) => (0.3, 0.03),
+        AWSDevice::OQCLucyDevice(_) => (0.3, 0.00035),
+        AWSDevice::RigettiAspenM3Device(_) => (0.3, 0.00035),
+    };
+    DevicePrices { per_task, per_shot }
+}
+
+/// Table of the device prices used to estimate the cost of running circuits.
+///
+/// A new table holds the on-demand prices of the Amazon Braket pricing page, which are also
+/// returned by [AWSDevice::price_per_task] and [AWSDevice::price_per_shot]. AWS changes its prices
+/// from time to time and reservations or negotiated discounts are charged differently, so the
+/// prices of a device can be overridden with [PriceTable::set_prices].
+#[derive(Debug, PartialEq, Clone)]
+pub struct PriceTable {
+    /// The prices of the devices that differ from the on-demand prices, keyed by the device ARN.
+    prices: HashMap<String, DevicePrices>,
+}
+
+impl PriceTable {
+    /// Creates a new PriceTable with the on-demand prices of all devices.
+    ///
+    /// # Returns
+    ///
+    /// The price table.
+    pub fn new() -> Self {
+        PriceTable {
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Overrides the prices of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device whose prices are set.
+    /// * `prices` - The prices charged for the device.
+    pub fn set_prices(&mut self, device: &AWSDevice, prices: DevicePrices) {
+        self.prices
+            .insert(device.clone().name().to_string(), prices);
+    }
+
+    /// Returns the prices of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device whose prices are returned.
+    ///
+    /// # Returns
+    ///
+    /// The prices set with [PriceTable::set_prices], the on-demand prices otherwise.
+    pub fn prices(&self, device: &AWSDevice) -> DevicePrices {
+        self.prices
+            .get(device.clone().name())
+            .copied()
+            .unwrap_or_else(|| default_prices(device))
+    }
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::Operate;
+use roqoqo::Circuit;
+
+use crate::analysis::gate_qubits;
+use crate::AWSDevice;
+
+/// Statistics of the gates of a circuit with respect to the native gates of an AWS device.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct GateReport {
+    /// Number of gates per hqslang name for the gates that are native to the device.
+    pub native_gates: HashMap<String, usize>,
+    /// Number of gates per hqslang name for the gates that are not native to the device.
+    pub non_native_gates: HashMap<String, usize>,
+    /// Depth of the circuit counting only gates acting on two or more qubits.
+    pub two_qubit_depth: usize,
+    /// Number of two-qubit gates per pair of qubits, the smaller qubit first.
+    pub edge_usage: HashMap<(usize, usize), usize>,
+    /// Number of two-qubit gates acting on qubits that are not connected in the device.
+    pub unconnected_gates: usize,
+}
+
+impl GateReport {
+    /// Returns the fraction of the gates of the circuit that are native to the device.
+    ///
+    /// # Returns
+    ///
+    /// The fraction of native gates, one for circuits without gates.
+    pub fn native_fraction(&self) -> f64 {
+        let native: usize = self.native_gates.values().sum();
+        let non_native: usize = self.non_native_gates.values().sum();
+        if native + non_native == 0 {
+            1.0
+        } else {
+            native as f64 / (native + non_native) as f64
+        }
+    }
+}
+
+/// Collects statistics of the gates of a circuit on an AWS device.
+///
+/// Gates are counted as native in the same way as in [AWSDevice::validate_circuit]. The edge
+/// usage includes all two-qubit gates, whether or not the qubits are connected in the device.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to analyse.
+/// * `device` - The device the circuit is run on.
+///
+/// # Returns
+///
+/// The gate statistics of the circuit.
+pub fn gate_report(circuit: &Circuit, device: &AWSDevice) -> GateReport {
+    let edges: HashSet<(usize, usize)> = device.two_qubit_edges().into_iter().collect();
+    let single_qubit_gates = device.single_qubit_gate_names();
+    let two_qubit_gates = device.two_qubit_gate_names();
+    let multi_qubit_gates = device.multi_qubit_gate_names();
+
+    let mut report = GateReport::default();
+    let mut layers: HashMap<usize, usize> = HashMap::new();
+    for operation in circuit.iter() {
+        let qubits = match gate_qubits(operation) {
+            Some(qubits) => qubits,
+            None => continue,
+        };
+        let hqslang = operation.hqslang().to_string();
+        let is_native = match qubits.len() {
+            1 => single_qubit_gates.contains(&hqslang),
+            2 => two_qubit_gates.contains(&hqslang),
+            _ => multi_qubit_gates.contains(&hqslang),
+        };
+        let counts = if is_native {
+            &mut report.native_gates
+        } else {
+            &mut report.non_native_gates
+        };
+        *counts.entry(hqslang).or_insert(0) += 1;
+
+        if qubits.len() < 2 {
+            continue;
+        }
+        let layer = qubits
+            .iter()
+            .map(|qubit| layers.get(qubit).copied().unwrap_or(0))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        for qubit in qubits.iter() {
+            layers.insert(*qubit, layer);
+        }
+        report.two_qubit_depth = report.two_qubit_depth.max(layer);
+
+        if let [control, target] = qubits[..] {
+            let edge = (control.min(target), control.max(target));
+            *report.edge_usage.entry(edge).or_insert(0) += 1;
+            if !edges.contains(&(control, target)) && !edges.contains(&(target, control)) {
+                report.unconnected_gates += 1;
+            }
+        }
+    }
+    report
+}
@@ -0,0 +1,201 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::Operate;
+use roqoqo::Circuit;
+
+use crate::analysis::{
+    circuit_number_qubits, circuit_number_shots, gate_qubits, gate_time, total_decoherence_rate,
+    PriceTable,
+};
+use crate::AWSDevice;
+
+/// Largest relative increase of the combined score for a device with many unused qubits.
+const QUBIT_HEADROOM_WEIGHT: f64 = 0.1;
+
+/// Score describing how well a circuit can be run on an AWS device.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DeviceScore {
+    /// Whether the device has enough qubits to run the circuit.
+    pub fits_qubits: bool,
+    /// Fraction of the qubits of the device not used by the circuit, zero if it does not fit.
+    pub qubit_headroom: f64,
+    /// Fraction of the gates in the circuit that are native gates of the device.
+    pub native_gate_coverage: f64,
+    /// Fraction of the two-qubit gates in the circuit that act on connected qubits of the device.
+    pub connectivity_match: f64,
+    /// Estimated probability that the circuit runs without a decoherence event.
+    pub estimated_fidelity: f64,
+    /// Estimated cost in USD of running the circuit with its number of shots.
+    pub estimated_cost: f64,
+}
+
+impl DeviceScore {
+    /// Returns the combined score, a higher score meaning a better device.
+    ///
+    /// Devices without enough qubits get a score of zero. Otherwise the score is the product
+    /// of the native gate coverage, the connectivity match and the estimated fidelity,
+    /// divided by one plus the estimated cost. Spare qubits leave room for routing and for
+    /// avoiding bad qubits, so the score is raised by up to 10 % with the qubit headroom.
+    ///
+    /// # Returns
+    ///
+    /// The combined score.
+    pub fn total(&self) -> f64 {
+        if !self.fits_qubits {
+            return 0.0;
+        }
+        self.native_gate_coverage
+            * self.connectivity_match
+            * self.estimated_fidelity
+            * (1.0 + QUBIT_HEADROOM_WEIGHT * self.qubit_headroom)
+            / (1.0 + self.estimated_cost)
+    }
+}
+
+impl AWSDevice {
+    /// Scores how well a circuit can be run on the device.
+    ///
+    /// The cost is estimated with the on-demand prices of the device, see
+    /// [AWSDevice::score_for_circuit_with_prices] for other prices.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to score.
+    ///
+    /// # Returns
+    ///
+    /// The score of the device for the circuit.
+    pub fn score_for_circuit(&self, circuit: &Circuit) -> DeviceScore {
+        self.score_for_circuit_with_prices(circuit, &PriceTable::new())
+    }
+
+    /// Scores how well a circuit can be run on the device with the prices of a price table.
+    ///
+    /// Gates that are not native to the device or that act on unconnected qubits
+    /// do not contribute to the estimated fidelity.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to score.
+    /// * `prices` - The prices the cost is estimated with.
+    ///
+    /// # Returns
+    ///
+    /// The score of the device for the circuit.
+    pub fn score_for_circuit_with_prices(
+        &self,
+        circuit: &Circuit,
+        prices: &PriceTable,
+    ) -> DeviceScore {
+        let edges: HashSet<(usize, usize)> = self.two_qubit_edges().into_iter().collect();
+        let single_qubit_gates = self.single_qubit_gate_names();
+        let two_qubit_gates = self.two_qubit_gate_names();
+        let multi_qubit_gates = self.multi_qubit_gate_names();
+
+        let mut number_gates = 0;
+        let mut number_native_gates = 0;
+        let mut number_two_qubit_gates = 0;
+        let mut number_connected_gates = 0;
+        let mut log_fidelity = 0.0;
+        for operation in circuit.iter() {
+            let qubits = match gate_qubits(operation) {
+                Some(qubits) => qubits,
+                None => continue,
+            };
+            let hqslang = operation.hqslang().to_string();
+            number_gates += 1;
+            let is_native = match qubits.len() {
+                1 => single_qubit_gates.contains(&hqslang),
+                2 => two_qubit_gates.contains(&hqslang),
+                _ => multi_qubit_gates.contains(&hqslang),
+            };
+            if is_native {
+                number_native_gates += 1;
+            }
+            if let [control, target] = qubits[..] {
+                number_two_qubit_gates += 1;
+                if edges.contains(&(control, target)) || edges.contains(&(target, control)) {
+                    number_connected_gates += 1;
+                }
+            }
+            if let Some(time) = gate_time(self, &hqslang, &qubits) {
+                log_fidelity -= qubits
+                    .iter()
+                    .map(|qubit| total_decoherence_rate(self, *qubit) * time)
+                    .sum::<f64>();
+            }
+        }
+
+        let native_gate_coverage = if number_gates == 0 {
+            1.0
+        } else {
+            number_native_gates as f64 / number_gates as f64
+        };
+        let connectivity_match = if number_two_qubit_gates == 0 {
+            1.0
+        } else {
+            number_connected_gates as f64 / number_two_qubit_gates as f64
+        };
+        let number_qubits = circuit_number_qubits(circuit);
+        let fits_qubits = number_qubits <= self.number_qubits();
+        let qubit_headroom = if fits_qubits && self.number_qubits() > 0 {
+            (self.number_qubits() - number_qubits) as f64 / self.number_qubits() as f64
+        } else {
+            0.0
+        };
+        let device_prices = prices.prices(self);
+        DeviceScore {
+            fits_qubits,
+            qubit_headroom,
+            native_gate_coverage,
+            connectivity_match,
+            estimated_fidelity: log_fidelity.exp(),
+            estimated_cost: device_prices.per_task
+                + device_prices.per_shot * circuit_number_shots(circuit) as f64,
+        }
+    }
+}
+
+/// Ranks devices by how well they can run a circuit.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to be run.
+/// * `devices` - The candidate devices.
+/// * `prices` - The prices the costs are estimated with, [PriceTable::new] for the on-demand
+///   prices.
+///
+/// # Returns
+///
+/// The indices of the devices in `devices` together with their scores, sorted from best to worst.
+pub fn recommend_device(
+    circuit: &Circuit,
+    devices: &[AWSDevice],
+    prices: &PriceTable,
+) -> Vec<(usize, DeviceScore)> {
+    let mut ranking: Vec<(usize, DeviceScore)> = devices
+        .iter()
+        .enumerate()
+        .map(|(index, device)| (index, device.score_for_circuit_with_prices(circuit, prices)))
+        .collect();
+    ranking.sort_by(|a, b| {
+        b.1.total()
+            .partial_cmp(&a.1.total())
+            .unwrap_or(Ordering::Equal)
+    });
+    ranking
+}
@@ -0,0 +1,330 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::{Operate, Operation};
+use roqoqo::Circuit;
+
+use crate::analysis::{circuit_number_qubits, gate_qubits, gate_time};
+use crate::braket_ir::braket_shots;
+use crate::AWSDevice;
+
+/// Reason why a circuit cannot be run on an AWS device.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ValidationError {
+    /// The circuit uses more qubits than the device has.
+    TooManyQubits {
+        /// Number of qubits used by the circuit.
+        number_qubits: usize,
+        /// Number of qubits of the device.
+        device_qubits: usize,
+    },
+    /// A gate of the circuit is not a native gate of the device.
+    NonNativeGate {
+        /// Position of the gate in the circuit.
+        index: usize,
+        /// hqslang name of the gate.
+        gate: String,
+    },
+    /// A two-qubit gate acts on qubits that are not connected in the device.
+    UnconnectedQubits {
+        /// Position of the gate in the circuit.
+        index: usize,
+        /// hqslang name of the gate.
+        gate: String,
+        /// The control qubit of the gate.
+        control: usize,
+        /// The target qubit of the gate.
+        target: usize,
+    },
+    /// A native gate is not available on its qubits, e.g. because a qubit is disabled.
+    UnavailableGate {
+        /// Position of the gate in the circuit.
+        index: usize,
+        /// hqslang name of the gate.
+        gate: String,
+        /// The qubits the gate acts on.
+        qubits: Vec<usize>,
+    },
+    /// The number of shots is outside of the range accepted by the device.
+    InvalidShots {
+        /// The requested number of shots.
+        shots: usize,
+        /// The minimum number of shots of the device.
+        minimum: usize,
+        /// The maximum number of shots of the device.
+        maximum: usize,
+    },
+    /// The measurement operations cannot be combined into one Braket task, see
+    /// [crate::braket_ir::braket_shots].
+    InvalidMeasurements {
+        /// Why the measurements cannot be combined.
+        message: String,
+    },
+    /// A gate acts on a measured qubit but the device does not support mid-circuit measurements.
+    MidCircuitMeasurement {
+        /// Position of the gate in the circuit.
+        index: usize,
+        /// hqslang name of the gate.
+        gate: String,
+        /// The measured qubit the gate acts on.
+        qubit: usize,
+    },
+    /// A non-gate operation that cannot be run on the device.
+    UnsupportedOperation {
+        /// Position of the operation in the circuit.
+        index: usize,
+        /// hqslang name of the operation.
+        operation: String,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::TooManyQubits {
+                number_qubits,
+                device_qubits,
+            } => write!(
+                f,
+                "Circuit uses {} qubits but the device only has {}",
+                number_qubits, device_qubits
+            ),
+            ValidationError::NonNativeGate { index, gate } => write!(
+                f,
+                "Operation {}: gate {} is not a native gate of the device",
+                index, gate
+            ),
+            ValidationError::UnconnectedQubits {
+                index,
+                gate,
+                control,
+                target,
+            } => write!(
+                f,
+                "Operation {}: gate {} acts on qubits {} and {} which are not connected",
+                index, gate, control, target
+            ),
+            ValidationError::UnavailableGate {
+                index,
+                gate,
+                qubits,
+            } => write!(
+                f,
+                "Operation {}: gate {} is not available on qubits {:?}",
+                index, gate, qubits
+            ),
+            ValidationError::InvalidShots {
+                shots,
+                minimum,
+                maximum,
+            } => write!(
+                f,
+                "{} shots are requested but the device only accepts between {} and {} shots",
+                shots, minimum, maximum
+            ),
+            ValidationError::InvalidMeasurements { message } => {
+                write!(f, "Invalid measurements: {}", message)
+            }
+            ValidationError::MidCircuitMeasurement { index, gate, qubit } => write!(
+                f,
+                "Operation {}: gate {} acts on qubit {} after it has been measured",
+                index, gate, qubit
+            ),
+            ValidationError::UnsupportedOperation { index, operation } => write!(
+                f,
+                "Operation {}: {} is not supported by the device",
+                index, operation
+            ),
+        }
+    }
+}
+
+/// Returns whether a non-gate operation can be submitted to an AWS device.
+///
+/// # Arguments
+///
+/// * `operation` - The operation to check.
+///
+/// # Returns
+///
+/// True for definitions, measurements and the pragmas setting the number of measurements.
+fn is_supported_operation(operation: &Operation) -> bool {
+    matches!(
+        operation,
+        Operation::DefinitionBit(_)
+            | Operation::DefinitionFloat(_)
+            | Operation::DefinitionComplex(_)
+            | Operation::DefinitionUsize(_)
+            | Operation::InputSymbolic(_)
+            | Operation::MeasureQubit(_)
+            | Operation::PragmaSetNumberOfMeasurements(_)
+            | Operation::PragmaRepeatedMeasurement(_)
+    )
+}
+
+impl AWSDevice {
+    /// Checks whether a circuit can be run on the device as it is.
+    ///
+    /// The circuit must fit on the device, consist only of native gates acting on available and
+    /// connected qubits, and must not contain operations other than definitions and measurements.
+    /// PragmaSleep operations are allowed on devices supporting delay instructions, see
+    /// [AWSDevice::supports_delay]. The number of shots set by the measurement pragmas must be
+    /// accepted by the device, see [AWSDevice::validate_shots], and the measurements must fit
+    /// into one Braket task, see [crate::braket_ir::braket_shots]. Gates acting on measured qubits
+    /// are only allowed on devices supporting mid-circuit measurements, see
+    /// [AWSDevice::supports_mid_circuit_measurement]. PragmaConditional operations are allowed on
+    /// devices supporting classical feedforward, see [AWSDevice::supports_feedforward], and
+    /// their circuits are validated as well. Errors found in such a circuit refer to the
+    /// positions of the operations inside of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to validate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The circuit can be run on the device.
+    /// * `Err(Vec<ValidationError>)` - All problems found in the circuit.
+    pub fn validate_circuit(&self, circuit: &Circuit) -> Result<(), Vec<ValidationError>> {
+        let edges: HashSet<(usize, usize)> = self.two_qubit_edges().into_iter().collect();
+        let single_qubit_gates = self.single_qubit_gate_names();
+        let two_qubit_gates = self.two_qubit_gate_names();
+        let multi_qubit_gates = self.multi_qubit_gate_names();
+
+        let mut errors: Vec<ValidationError> = Vec::new();
+        let mut measured: HashSet<usize> = HashSet::new();
+        let number_qubits = circuit_number_qubits(circuit);
+        if number_qubits > self.number_qubits() {
+            errors.push(ValidationError::TooManyQubits {
+                number_qubits,
+                device_qubits: self.number_qubits(),
+            });
+        }
+        match braket_shots(circuit) {
+            Ok(Some(shots)) => {
+                if let Err(error) = self.validate_shots(shots) {
+                    errors.push(error);
+                }
+            }
+            Ok(None) => (),
+            Err(error) => errors.push(ValidationError::InvalidMeasurements {
+                message: error.to_string(),
+            }),
+        }
+        for (index, operation) in circuit.iter().enumerate() {
+            let hqslang = operation.hqslang().to_string();
+            let qubits = match gate_qubits(operation) {
+                Some(qubits) => qubits,
+                None => {
+                    if let Operation::MeasureQubit(measurement) = operation {
+                        measured.insert(*measurement.qubit());
+                    }
+                    if let Operation::PragmaConditional(conditional) = operation {
+                        if self.supports_feedforward() {
+                            if let Err(conditional_errors) =
+                                self.validate_circuit(conditional.circuit())
+                            {
+                                errors.extend(conditional_errors);
+                            }
+                            continue;
+                        }
+                    }
+                    let is_delay =
+                        matches!(operation, Operation::PragmaSleep(_)) && self.supports_delay();
+                    if !is_supported_operation(operation) && !is_delay {
+                        errors.push(ValidationError::UnsupportedOperation {
+                            index,
+                            operation: hqslang,
+                        });
+                    }
+                    continue;
+                }
+            };
+            if !self.supports_mid_circuit_measurement() {
+                if let Some(qubit) = qubits.iter().find(|qubit| measured.contains(qubit)) {
+                    errors.push(ValidationError::MidCircuitMeasurement {
+                        index,
+                        gate: hqslang,
+                        qubit: *qubit,
+                    });
+                    continue;
+                }
+            }
+            let is_native = match qubits.len() {
+                1 => single_qubit_gates.contains(&hqslang),
+                2 => two_qubit_gates.contains(&hqslang),
+                _ => multi_qubit_gates.contains(&hqslang),
+            };
+            if !is_native {
+                errors.push(ValidationError::NonNativeGate {
+                    index,
+                    gate: hqslang,
+                });
+                continue;
+            }
+            if qubits.iter().any(|qubit| *qubit >= self.number_qubits()) {
+                continue;
+            }
+            if let [control, target] = qubits[..] {
+                if !edges.contains(&(control, target)) && !edges.contains(&(target, control)) {
+                    errors.push(ValidationError::UnconnectedQubits {
+                        index,
+                        gate: hqslang,
+                        control,
+                        target,
+                    });
+                    continue;
+                }
+            }
+            if gate_time(self, &hqslang, &qubits).is_none() {
+                errors.push(ValidationError::UnavailableGate {
+                    index,
+                    gate: hqslang,
+                    qubits,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks whether the device accepts a task with the given number of shots.
+    ///
+    /// # Arguments
+    ///
+    /// * `shots` - The requested number of shots.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The number of shots is accepted by the device.
+    /// * `Err(ValidationError)` - The number of shots is outside of [AWSDevice::shots_range].
+    pub fn validate_shots(&self, shots: usize) -> Result<(), ValidationError> {
+        let (minimum, maximum) = self.shots_range();
+        if shots < minimum || shots > maximum {
+            Err(ValidationError::InvalidShots {
+                shots,
+                minimum,
+                maximum,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
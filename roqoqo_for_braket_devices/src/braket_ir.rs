@@ -0,0 +1,358 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Braket IR
+//!
+//! Provides the conversion of qoqo circuits into the program formats accepted by AWS Braket.
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::operations::*;
+use roqoqo::RoqoqoError;
+
+mod jaqcd;
+pub use crate::braket_ir::jaqcd::{circuit_from_jaqcd, circuit_to_jaqcd};
+
+mod measurement;
+pub use crate::braket_ir::measurement::{braket_shots, MeasurementMapping};
+
+mod openqasm;
+pub use crate::braket_ir::openqasm::{
+    circuit_from_openqasm3, circuit_to_openqasm3, circuit_to_openqasm3_simulator,
+    circuit_to_openqasm3_verbatim,
+};
+
+mod parameters;
+pub use crate::braket_ir::parameters::{braket_inputs, free_parameters};
+
+mod result_types;
+pub use crate::braket_ir::result_types::{
+    add_jaqcd_result_types, add_openqasm3_result_types, pauli_z_product_result_types, ResultType,
+};
+
+/// A gate in the form used by the Braket program formats.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct BraketGate {
+    /// The Braket name of the gate.
+    pub name: &'static str,
+    /// The angles of the gate, symbolic angles are free parameters of the program.
+    pub parameters: Vec<CalculatorFloat>,
+    /// The qubits of the gate, controls first.
+    pub qubits: Vec<usize>,
+    /// The number of `ctrl @` modifiers of the gate, the first qubits are the added controls.
+    pub controls: usize,
+}
+
+/// Converts a qoqo gate into the corresponding Braket gate.
+///
+/// # Arguments
+///
+/// * `operation` - The gate to convert.
+///
+/// # Returns
+///
+/// * `Ok(BraketGate)` - The Braket gate.
+/// * `Err(RoqoqoError)` - The gate has no Braket equivalent.
+pub(crate) fn braket_gate(operation: &Operation) -> Result<BraketGate, RoqoqoError> {
+    let (name, parameters, qubits) = match operation {
+        Operation::Identity(gate) => ("i", vec![], vec![*gate.qubit()]),
+        Operation::Hadamard(gate) => ("h", vec![], vec![*gate.qubit()]),
+        Operation::PauliX(gate) => ("x", vec![], vec![*gate.qubit()]),
+        Operation::PauliY(gate) => ("y", vec![], vec![*gate.qubit()]),
+        Operation::PauliZ(gate) => ("z", vec![], vec![*gate.qubit()]),
+        Operation::SGate(gate) => ("s", vec![], vec![*gate.qubit()]),
+        Operation::TGate(gate) => ("t", vec![], vec![*gate.qubit()]),
+        Operation::SqrtPauliX(gate) => ("v", vec![], vec![*gate.qubit()]),
+        Operation::InvSqrtPauliX(gate) => ("vi", vec![], vec![*gate.qubit()]),
+        Operation::RotateX(gate) => ("rx", vec![gate.theta().clone()], vec![*gate.qubit()]),
+        Operation::RotateY(gate) => ("ry", vec![gate.theta().clone()], vec![*gate.qubit()]),
+        Operation::RotateZ(gate) => ("rz", vec![gate.theta().clone()], vec![*gate.qubit()]),
+        Operation::PhaseShiftState1(gate) => (
+            "phaseshift",
+            vec![gate.theta().clone()],
+            vec![*gate.qubit()],
+        ),
+        Operation::GPi(gate) => ("gpi", vec![gate.theta().clone()], vec![*gate.qubit()]),
+        Operation::GPi2(gate) => ("gpi2", vec![gate.theta().clone()], vec![*gate.qubit()]),
+        Operation::CNOT(gate) => ("cnot", vec![], vec![*gate.control(), *gate.target()]),
+        Operation::ControlledPauliZ(gate) => ("cz", vec![], vec![*gate.control(), *gate.target()]),
+        Operation::ControlledPauliY(gate) => ("cy", vec![], vec![*gate.control(), *gate.target()]),
+        Operation::SWAP(gate) => ("swap", vec![], vec![*gate.control(), *gate.target()]),
+        Operation::ISwap(gate) => ("iswap", vec![], vec![*gate.control(), *gate.target()]),
+        Operation::EchoCrossResonance(gate) => {
+            ("ecr", vec![], vec![*gate.control(), *gate.target()])
+        }
+        Operation::ControlledPhaseShift(gate) => (
+            "cphaseshift",
+            vec![gate.theta().clone()],
+            vec![*gate.control(), *gate.target()],
+        ),
+        Operation::XY(gate) => (
+            "xy",
+            vec![gate.theta().clone()],
+            vec![*gate.control(), *gate.target()],
+        ),
+        Operation::MolmerSorensenXX(gate) => (
+            "ms",
+            vec![
+                CalculatorFloat::ZERO,
+                CalculatorFloat::ZERO,
+                FRAC_PI_2.into(),
+            ],
+            vec![*gate.control(), *gate.target()],
+        ),
+        Operation::VariableMSXX(gate) => (
+            "xx",
+            vec![gate.theta().clone()],
+            vec![*gate.control(), *gate.target()],
+        ),
+        Operation::Toffoli(gate) => (
+            "ccnot",
+            vec![],
+            vec![*gate.control_0(), *gate.control_1(), *gate.target()],
+        ),
+        _ => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Gate {} has no Braket equivalent", operation.hqslang()),
+            })
+        }
+    };
+    Ok(BraketGate {
+        name,
+        parameters,
+        qubits,
+        controls: 0,
+    })
+}
+
+/// Converts a qoqo gate into Braket gates, decomposing controlled gates without Braket equivalent.
+///
+/// ControlledControlledPauliZ, ControlledControlledPhaseShift and ControlledRotateX are either
+/// expressed with `ctrl @` modifiers or decomposed into Braket gates without modifiers for
+/// targets that do not support them. All other gates are converted with [braket_gate].
+///
+/// # Arguments
+///
+/// * `operation` - The gate to convert.
+/// * `modifiers` - Whether the target of the program supports `ctrl @` modifiers.
+///
+/// # Returns
+///
+/// * `Ok(Vec<BraketGate>)` - The Braket gates.
+/// * `Err(RoqoqoError)` - The gate has no Braket equivalent.
+pub(crate) fn braket_gates(
+    operation: &Operation,
+    modifiers: bool,
+) -> Result<Vec<BraketGate>, RoqoqoError> {
+    let gate =
+        |name: &'static str, parameters: Vec<CalculatorFloat>, qubits: Vec<usize>| BraketGate {
+            name,
+            parameters,
+            qubits,
+            controls: 0,
+        };
+    let gates = match operation {
+        Operation::ControlledControlledPauliZ(op) => {
+            let (control_0, control_1, target) = (*op.control_0(), *op.control_1(), *op.target());
+            if modifiers {
+                vec![BraketGate {
+                    controls: 2,
+                    ..gate("z", vec![], vec![control_0, control_1, target])
+                }]
+            } else {
+                vec![
+                    gate("h", vec![], vec![target]),
+                    gate("ccnot", vec![], vec![control_0, control_1, target]),
+                    gate("h", vec![], vec![target]),
+                ]
+            }
+        }
+        Operation::ControlledControlledPhaseShift(op) => {
+            let (control_0, control_1, target) = (*op.control_0(), *op.control_1(), *op.target());
+            if modifiers {
+                vec![BraketGate {
+                    controls: 2,
+                    ..gate(
+                        "phaseshift",
+                        vec![op.theta().clone()],
+                        vec![control_0, control_1, target],
+                    )
+                }]
+            } else {
+                let half = op.theta().clone() / 2.0;
+                vec![
+                    gate("cphaseshift", vec![half.clone()], vec![control_1, target]),
+                    gate("cnot", vec![], vec![control_0, control_1]),
+                    gate("cphaseshift", vec![-half.clone()], vec![control_1, target]),
+                    gate("cnot", vec![], vec![control_0, control_1]),
+                    gate("cphaseshift", vec![half], vec![control_0, target]),
+                ]
+            }
+        }
+        Operation::ControlledRotateX(op) => {
+            let (control, target) = (*op.control(), *op.target());
+            if modifiers {
+                vec![BraketGate {
+                    controls: 1,
+                    ..gate("rx", vec![op.theta().clone()], vec![control, target])
+                }]
+            } else {
+                let half = op.theta().clone() / 2.0;
+                vec![
+                    gate("h", vec![], vec![target]),
+                    gate("rz", vec![half.clone()], vec![target]),
+                    gate("cnot", vec![], vec![control, target]),
+                    gate("rz", vec![-half], vec![target]),
+                    gate("cnot", vec![], vec![control, target]),
+                    gate("h", vec![], vec![target]),
+                ]
+            }
+        }
+        _ => vec![braket_gate(operation)?],
+    };
+    Ok(gates)
+}
+
+/// Converts a Braket gate into the corresponding qoqo gate.
+///
+/// # Arguments
+///
+/// * `name` - The Braket name of the gate.
+/// * `parameters` - The angles of the gate.
+/// * `qubits` - The qubits of the gate, controls first.
+///
+/// # Returns
+///
+/// * `Ok(Operation)` - The qoqo gate.
+/// * `Err(RoqoqoError)` - The gate is unknown or has the wrong number of angles or qubits.
+pub(crate) fn qoqo_gate(
+    name: &str,
+    parameters: &[CalculatorFloat],
+    qubits: &[usize],
+) -> Result<Operation, RoqoqoError> {
+    let operation: Operation = match (name, parameters, qubits) {
+        ("i", [], [qubit]) => Identity::new(*qubit).into(),
+        ("h", [], [qubit]) => Hadamard::new(*qubit).into(),
+        ("x", [], [qubit]) => PauliX::new(*qubit).into(),
+        ("y", [], [qubit]) => PauliY::new(*qubit).into(),
+        ("z", [], [qubit]) => PauliZ::new(*qubit).into(),
+        ("s", [], [qubit]) => SGate::new(*qubit).into(),
+        ("si", [], [qubit]) => PhaseShiftState1::new(*qubit, (-FRAC_PI_2).into()).into(),
+        ("t", [], [qubit]) => TGate::new(*qubit).into(),
+        ("ti", [], [qubit]) => PhaseShiftState1::new(*qubit, (-FRAC_PI_4).into()).into(),
+        ("v", [], [qubit]) => SqrtPauliX::new(*qubit).into(),
+        ("vi", [], [qubit]) => InvSqrtPauliX::new(*qubit).into(),
+        ("rx", [theta], [qubit]) => RotateX::new(*qubit, theta.clone()).into(),
+        ("ry", [theta], [qubit]) => RotateY::new(*qubit, theta.clone()).into(),
+        ("rz", [theta], [qubit]) => RotateZ::new(*qubit, theta.clone()).into(),
+        ("phaseshift", [theta], [qubit]) => PhaseShiftState1::new(*qubit, theta.clone()).into(),
+        ("gpi", [theta], [qubit]) => GPi::new(*qubit, theta.clone()).into(),
+        ("gpi2", [theta], [qubit]) => GPi2::new(*qubit, theta.clone()).into(),
+        ("cnot", [], [control, target]) => CNOT::new(*control, *target).into(),
+        ("cz", [], [control, target]) => ControlledPauliZ::new(*control, *target).into(),
+        ("cy", [], [control, target]) => ControlledPauliY::new(*control, *target).into(),
+        ("swap", [], [control, target]) => SWAP::new(*control, *target).into(),
+        ("iswap", [], [control, target]) => ISwap::new(*control, *target).into(),
+        ("ecr", [], [control, target]) => EchoCrossResonance::new(*control, *target).into(),
+        ("cphaseshift", [theta], [control, target]) => {
+            ControlledPhaseShift::new(*control, *target, theta.clone()).into()
+        }
+        ("xy", [theta], [control, target]) => XY::new(*control, *target, theta.clone()).into(),
+        ("xx", [theta], [control, target]) => {
+            VariableMSXX::new(*control, *target, theta.clone()).into()
+        }
+        ("ms", [phi_0, phi_1], [control, target])
+            if is_value(phi_0, 0.0) && is_value(phi_1, 0.0) =>
+        {
+            MolmerSorensenXX::new(*control, *target).into()
+        }
+        ("ms", [phi_0, phi_1, theta], [control, target])
+            if is_value(phi_0, 0.0) && is_value(phi_1, 0.0) =>
+        {
+            if is_value(theta, FRAC_PI_2) {
+                MolmerSorensenXX::new(*control, *target).into()
+            } else {
+                VariableMSXX::new(*control, *target, theta.clone()).into()
+            }
+        }
+        ("ccnot", [], [control_0, control_1, target]) => {
+            Toffoli::new(*control_0, *control_1, *target).into()
+        }
+        _ => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Gate {} with {} angles on {} qubits has no qoqo equivalent",
+                    name,
+                    parameters.len(),
+                    qubits.len()
+                ),
+            })
+        }
+    };
+    Ok(operation)
+}
+
+/// Converts a Braket gate with `ctrl @` modifiers into the corresponding qoqo gate.
+///
+/// # Arguments
+///
+/// * `name` - The Braket name of the gate without modifiers.
+/// * `controls` - The number of `ctrl @` modifiers of the gate.
+/// * `parameters` - The angles of the gate.
+/// * `qubits` - The qubits of the gate, controls first.
+///
+/// # Returns
+///
+/// * `Ok(Operation)` - The qoqo gate.
+/// * `Err(RoqoqoError)` - The controlled gate has no qoqo equivalent.
+pub(crate) fn qoqo_controlled_gate(
+    name: &str,
+    controls: usize,
+    parameters: &[CalculatorFloat],
+    qubits: &[usize],
+) -> Result<Operation, RoqoqoError> {
+    let operation: Operation = match (controls, name, parameters, qubits) {
+        (0, _, _, _) => return qoqo_gate(name, parameters, qubits),
+        (1, "x", _, _) => return qoqo_gate("cnot", parameters, qubits),
+        (1, "y", _, _) => return qoqo_gate("cy", parameters, qubits),
+        (1, "z", _, _) => return qoqo_gate("cz", parameters, qubits),
+        (1, "phaseshift", _, _) => return qoqo_gate("cphaseshift", parameters, qubits),
+        (2, "x", _, _) => return qoqo_gate("ccnot", parameters, qubits),
+        (1, "rx", [theta], [control, target]) => {
+            ControlledRotateX::new(*control, *target, theta.clone()).into()
+        }
+        (2, "z", [], [control_0, control_1, target]) => {
+            ControlledControlledPauliZ::new(*control_0, *control_1, *target).into()
+        }
+        (2, "phaseshift", [theta], [control_0, control_1, target]) => {
+            ControlledControlledPhaseShift::new(*control_0, *control_1, *target, theta.clone())
+                .into()
+        }
+        _ => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Gate {} with {} controls on {} qubits has no qoqo equivalent",
+                    name,
+                    controls,
+                    qubits.len()
+                ),
+            })
+        }
+    };
+    Ok(operation)
+}
+
+/// Returns whether an angle is a float equal to the given value.
+fn is_value(angle: &CalculatorFloat, value: f64) -> bool {
+    matches!(angle, CalculatorFloat::Float(angle) if *angle == value)
+}
@@ -0,0 +1,182 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use qoqo_calculator::CalculatorFloat;
+use serde_json::{json, Map, Value};
+
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::braket_ir::{braket_gates, qoqo_gate, BraketGate};
+
+/// Braket gates that take one control and one target qubit in JAQCD.
+const CONTROLLED_GATES: [&str; 4] = ["cnot", "cy", "cz", "cphaseshift"];
+
+/// Braket gates that are not part of JAQCD and the JAQCD gate replacing them.
+///
+/// The replacement gets the angles of the corresponding entry, `None` marks gates that cannot be
+/// expressed in JAQCD.
+const JAQCD_REPLACEMENTS: [(&str, Option<&str>); 3] =
+    [("ms", Some("xx")), ("gpi", None), ("gpi2", None)];
+
+/// Converts a qoqo circuit into a Braket JAQCD program.
+///
+/// The program is the JSON action format accepted by the Braket simulators. JAQCD programs
+/// always measure all qubits at the end, so definitions and measurements of the circuit are not
+/// part of the program. ControlledControlledPauliZ, ControlledControlledPhaseShift and
+/// ControlledRotateX are decomposed into JAQCD gates.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to convert.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON of the JAQCD program.
+/// * `Err(RoqoqoError)` - The circuit contains an operation that cannot be expressed in JAQCD,
+///   e.g. a gate with symbolic parameters.
+pub fn circuit_to_jaqcd(circuit: &Circuit) -> Result<String, RoqoqoError> {
+    let mut instructions: Vec<Value> = Vec::new();
+    for operation in circuit.iter() {
+        match operation {
+            Operation::DefinitionBit(_)
+            | Operation::MeasureQubit(_)
+            | Operation::PragmaRepeatedMeasurement(_)
+            | Operation::PragmaSetNumberOfMeasurements(_) => (),
+            _ if operation.tags().contains(&"GateOperation") => {
+                for gate in braket_gates(operation, false)? {
+                    instructions.push(jaqcd_instruction(&gate, operation)?);
+                }
+            }
+            _ => {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Operation {} cannot be converted to JAQCD",
+                        operation.hqslang()
+                    ),
+                })
+            }
+        }
+    }
+    let program = json!({
+        "braketSchemaHeader": {"name": "braket.ir.jaqcd.program", "version": "1"},
+        "instructions": instructions,
+        "results": [],
+        "basis_rotation_instructions": [],
+    });
+    Ok(program.to_string())
+}
+
+/// Converts a Braket gate into a JAQCD instruction.
+///
+/// # Arguments
+///
+/// * `gate` - The Braket gate to convert.
+/// * `operation` - The qoqo gate the Braket gate is part of, used in error messages.
+///
+/// # Returns
+///
+/// * `Ok(Value)` - The JSON of the instruction.
+/// * `Err(RoqoqoError)` - The gate cannot be expressed in JAQCD.
+fn jaqcd_instruction(gate: &BraketGate, operation: &Operation) -> Result<Value, RoqoqoError> {
+    let (name, angle) = match JAQCD_REPLACEMENTS
+        .iter()
+        .find(|(name, _)| *name == gate.name)
+    {
+        Some((_, Some(replacement))) => (*replacement, gate.parameters.last()),
+        Some((_, None)) => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Gate {} cannot be converted to JAQCD", operation.hqslang()),
+            })
+        }
+        None => (gate.name, gate.parameters.first()),
+    };
+
+    let mut instruction = Map::new();
+    instruction.insert("type".to_string(), json!(name));
+    match gate.qubits[..] {
+        [target] => {
+            instruction.insert("target".to_string(), json!(target));
+        }
+        [control, target] if CONTROLLED_GATES.contains(&name) => {
+            instruction.insert("control".to_string(), json!(control));
+            instruction.insert("target".to_string(), json!(target));
+        }
+        [first, second] => {
+            instruction.insert("targets".to_string(), json!([first, second]));
+        }
+        [control_0, control_1, target] => {
+            instruction.insert("controls".to_string(), json!([control_0, control_1]));
+            instruction.insert("target".to_string(), json!(target));
+        }
+        _ => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Gate {} cannot be converted to JAQCD", operation.hqslang()),
+            })
+        }
+    }
+    if let Some(angle) = angle {
+        instruction.insert("angle".to_string(), json!(*angle.float()?));
+    }
+    Ok(Value::Object(instruction))
+}
+
+/// Converts a Braket JAQCD program into a qoqo circuit.
+///
+/// Only the instructions of the program are converted, its result types are not part of the
+/// circuit.
+///
+/// # Arguments
+///
+/// * `program` - The JSON of the JAQCD program.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The circuit.
+/// * `Err(RoqoqoError)` - The program is not valid JSON or contains an unsupported instruction.
+pub fn circuit_from_jaqcd(program: &str) -> Result<Circuit, RoqoqoError> {
+    let program: Value =
+        serde_json::from_str(program).map_err(|err| RoqoqoError::GenericError {
+            msg: format!("Could not parse JAQCD program: {}", err),
+        })?;
+    let instructions =
+        program["instructions"]
+            .as_array()
+            .ok_or_else(|| RoqoqoError::GenericError {
+                msg: "JAQCD program has no instructions".to_string(),
+            })?;
+    let mut circuit = Circuit::new();
+    for instruction in instructions {
+        let invalid = || RoqoqoError::GenericError {
+            msg: format!("Invalid JAQCD instruction {}", instruction),
+        };
+        let name = instruction["type"].as_str().ok_or_else(invalid)?;
+        let mut qubits: Vec<usize> = Vec::new();
+        for key in ["controls", "control", "targets", "target"] {
+            match &instruction[key] {
+                Value::Null => (),
+                Value::Array(values) => {
+                    for value in values {
+                        qubits.push(value.as_u64().ok_or_else(invalid)? as usize);
+                    }
+                }
+                value => qubits.push(value.as_u64().ok_or_else(invalid)? as usize),
+            }
+        }
+        let parameters: Vec<CalculatorFloat> = match &instruction["angle"] {
+            Value::Null => Vec::new(),
+            angle => vec![angle.as_f64().ok_or_else(invalid)?.into()],
+        };
+        circuit += qoqo_gate(name, &parameters, &qubits)?;
+    }
+    Ok(circuit)
+}
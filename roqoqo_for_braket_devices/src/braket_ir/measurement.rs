@@ -0,0 +1,262 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::circuit_number_qubits;
+
+/// Assignment of the bits of the readout registers of a circuit to measured qubits.
+///
+/// Braket returns the measurement results as one list of bits per shot, ordered by the measured
+/// qubits. The mapping is built from the DefinitionBit, MeasureQubit and PragmaRepeatedMeasurement
+/// operations of a circuit and sorts these bits back into the readout registers of the circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct MeasurementMapping {
+    /// The measured qubit of every bit of every readout register, `None` for bits that are never
+    /// written.
+    registers: HashMap<String, Vec<Option<usize>>>,
+}
+
+impl MeasurementMapping {
+    /// Builds the measurement mapping of a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit whose measurements are mapped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MeasurementMapping)` - The mapping of the readout registers of the circuit.
+    /// * `Err(RoqoqoError)` - A measurement writes to an undefined register or out of its range.
+    pub fn from_circuit(circuit: &Circuit) -> Result<Self, RoqoqoError> {
+        let number_qubits = circuit_number_qubits(circuit);
+        let mut registers: HashMap<String, Vec<Option<usize>>> = HashMap::new();
+        for operation in circuit.iter() {
+            let measurements: Vec<(&String, usize, usize)> = match operation {
+                Operation::DefinitionBit(definition) => {
+                    registers.insert(definition.name().clone(), vec![None; *definition.length()]);
+                    continue;
+                }
+                Operation::MeasureQubit(measurement) => vec![(
+                    measurement.readout(),
+                    *measurement.readout_index(),
+                    *measurement.qubit(),
+                )],
+                Operation::PragmaRepeatedMeasurement(measurement) => (0..number_qubits)
+                    .filter_map(|qubit| match measurement.qubit_mapping() {
+                        Some(mapping) => mapping
+                            .get(&qubit)
+                            .map(|index| (measurement.readout(), *index, qubit)),
+                        None => Some((measurement.readout(), qubit, qubit)),
+                    })
+                    .collect(),
+                _ => continue,
+            };
+            for (readout, index, qubit) in measurements {
+                let bits = registers
+                    .get_mut(readout)
+                    .ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!("Readout register {} is not defined", readout),
+                    })?;
+                let bit = bits
+                    .get_mut(index)
+                    .ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!(
+                            "Index {} is out of range of readout register {}",
+                            index, readout
+                        ),
+                    })?;
+                *bit = Some(qubit);
+            }
+        }
+        Ok(MeasurementMapping { registers })
+    }
+
+    /// Returns the qubits measured by the circuit, in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// The qubits whose measurement results are needed to fill the readout registers.
+    pub fn measured_qubits(&self) -> Vec<usize> {
+        let mut qubits: Vec<usize> = self
+            .registers
+            .values()
+            .flat_map(|bits| bits.iter().filter_map(|bit| *bit))
+            .collect();
+        qubits.sort_unstable();
+        qubits.dedup();
+        qubits
+    }
+
+    /// Returns the measured qubit of every bit of a readout register.
+    ///
+    /// # Arguments
+    ///
+    /// * `readout` - The name of the readout register.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Vec<Option<usize>>)` - The measured qubit of every bit, `None` for bits that are
+    ///   never written.
+    /// * `None` - The register is not defined in the circuit.
+    pub fn register(&self, readout: &str) -> Option<&Vec<Option<usize>>> {
+        self.registers.get(readout)
+    }
+
+    /// Relabels the measured qubits, e.g. after the qubits of the circuit were remapped.
+    ///
+    /// The mapping of the original circuit relabeled with the layout returned by
+    /// [crate::routing::route_circuit] or [crate::routing::remap_to_best_qubits] sorts the
+    /// results of the remapped circuit into the readout registers of the original circuit. The
+    /// measurements are assumed to be at the end of the circuit, where the layout applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `layout` - The new qubit of every qubit, qubits without an entry keep their index.
+    ///
+    /// # Returns
+    ///
+    /// The mapping with the relabeled qubits.
+    pub fn remap_qubits(&self, layout: &HashMap<usize, usize>) -> Self {
+        let registers = self
+            .registers
+            .iter()
+            .map(|(readout, bits)| {
+                let bits = bits
+                    .iter()
+                    .map(|bit| bit.map(|qubit| *layout.get(&qubit).unwrap_or(&qubit)))
+                    .collect();
+                (readout.clone(), bits)
+            })
+            .collect();
+        MeasurementMapping { registers }
+    }
+
+    /// Sorts the measurement results returned by Braket into the readout registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `measured_qubits` - The qubits in the order of the bits of every shot, the
+    ///   `measuredQubits` of the Braket result.
+    /// * `measurements` - The measured bits of every shot.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HashMap<String, Vec<Vec<bool>>>)` - The bits of every shot for every readout register,
+    ///   bits that are never written are false.
+    /// * `Err(RoqoqoError)` - A qubit needed by a register was not measured or a shot has the
+    ///   wrong length.
+    pub fn map_results(
+        &self,
+        measured_qubits: &[usize],
+        measurements: &[Vec<bool>],
+    ) -> Result<HashMap<String, Vec<Vec<bool>>>, RoqoqoError> {
+        let positions: HashMap<usize, usize> = measured_qubits
+            .iter()
+            .enumerate()
+            .map(|(position, qubit)| (*qubit, position))
+            .collect();
+        if let Some(qubit) = self
+            .measured_qubits()
+            .into_iter()
+            .find(|qubit| !positions.contains_key(qubit))
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Qubit {} is missing in the measurement results", qubit),
+            });
+        }
+        if measurements
+            .iter()
+            .any(|shot| shot.len() != measured_qubits.len())
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Every shot must contain one bit for each of the {} measured qubits",
+                    measured_qubits.len()
+                ),
+            });
+        }
+        Ok(self
+            .registers
+            .iter()
+            .map(|(readout, bits)| {
+                let shots = measurements
+                    .iter()
+                    .map(|shot| {
+                        bits.iter()
+                            .map(|bit| bit.is_some_and(|qubit| shot[positions[&qubit]]))
+                            .collect()
+                    })
+                    .collect();
+                (readout.clone(), shots)
+            })
+            .collect())
+    }
+}
+
+/// Returns the number of shots of the Braket task running a circuit.
+///
+/// The number of shots is set by the PragmaSetNumberOfMeasurements and PragmaRepeatedMeasurement
+/// operations of the circuit. A Braket task has a single number of shots for all registers, so
+/// all pragmas have to request the same number of measurements. A PragmaRepeatedMeasurement
+/// measures all qubits and cannot be combined with MeasureQubit operations.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to inspect.
+///
+/// # Returns
+///
+/// * `Ok(Some(usize))` - The number of shots.
+/// * `Ok(None)` - The circuit does not set a number of measurements.
+/// * `Err(RoqoqoError)` - The measurement pragmas of the circuit are not compatible.
+pub fn braket_shots(circuit: &Circuit) -> Result<Option<usize>, RoqoqoError> {
+    let mut shots: Option<usize> = None;
+    let mut repeated_measurement = false;
+    let mut measure_qubit = false;
+    for operation in circuit.iter() {
+        let number_measurements = match operation {
+            Operation::PragmaSetNumberOfMeasurements(pragma) => *pragma.number_measurements(),
+            Operation::PragmaRepeatedMeasurement(pragma) => {
+                repeated_measurement = true;
+                *pragma.number_measurements()
+            }
+            Operation::MeasureQubit(_) => {
+                measure_qubit = true;
+                continue;
+            }
+            _ => continue,
+        };
+        match shots {
+            Some(shots) if shots != number_measurements => {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Circuit requests both {} and {} measurements, Braket tasks have a \
+                         single number of shots",
+                        shots, number_measurements
+                    ),
+                })
+            }
+            _ => shots = Some(number_measurements),
+        }
+    }
+    if repeated_measurement && measure_qubit {
+        return Err(RoqoqoError::GenericError {
+            msg: "PragmaRepeatedMeasurement cannot be combined with MeasureQubit operations"
+                .to_string(),
+        });
+    }
+    Ok(shots)
+}
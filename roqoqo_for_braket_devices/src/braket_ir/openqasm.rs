@@ -0,0 +1,874 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{E, PI, TAU};
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::circuit_number_qubits;
+use crate::braket_ir::parameters::expression_parameters;
+use crate::braket_ir::{braket_gates, braket_shots, free_parameters, qoqo_controlled_gate};
+use crate::AWSDevice;
+
+/// The maximum number of qubits of the Braket state vector simulator SV1.
+const SIMULATOR_QUBITS: usize = 34;
+
+/// Converts a qoqo circuit into a Braket OpenQASM 3 program.
+///
+/// Gates are translated into the gates of the Braket OpenQASM 3 dialect acting on the qubit
+/// register `q`, readout registers defined with DefinitionBit become bit registers of the same
+/// name and measurements are written into them. The number of measurements is not part of the
+/// program and has to be set when the task is created.
+///
+/// Symbolic angles are written as expressions of their free parameters, which are declared as
+/// `input float` of the program. Their values are set when the task is created, see
+/// [crate::braket_ir::braket_inputs].
+///
+/// PragmaSleep operations become delay instructions with the sleep time in seconds on devices
+/// supporting them, see [AWSDevice::supports_delay].
+///
+/// PragmaConditional operations become `if` statements on their condition bit on devices
+/// supporting classical feedforward, see [AWSDevice::supports_feedforward]. The conditioned
+/// circuit may only contain gate operations.
+///
+/// The AWS devices do not support gate modifiers, so ControlledControlledPauliZ,
+/// ControlledControlledPhaseShift and ControlledRotateX are decomposed into Braket gates, see
+/// [circuit_to_openqasm3_simulator] for programs using `ctrl @` modifiers.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to convert.
+/// * `device` - The device the program is run on.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The OpenQASM 3 program.
+/// * `Err(RoqoqoError)` - The circuit does not fit on the device, sets a number of shots not
+///   accepted by the device or contains an operation that cannot be expressed in Braket
+///   OpenQASM 3.
+pub fn circuit_to_openqasm3(circuit: &Circuit, device: &AWSDevice) -> Result<String, RoqoqoError> {
+    write_openqasm3(circuit, Some(device), false)
+}
+
+/// Converts a qoqo circuit into a Braket OpenQASM 3 program for the Braket simulators.
+///
+/// The program is written like [circuit_to_openqasm3], but controlled gates without Braket
+/// equivalent like ControlledControlledPauliZ are expressed with `ctrl @` modifiers, which are
+/// supported by the simulators. Delay instructions are not supported.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to convert.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The OpenQASM 3 program.
+/// * `Err(RoqoqoError)` - The circuit uses more qubits than the simulators support or contains
+///   an operation that cannot be expressed in Braket OpenQASM 3.
+pub fn circuit_to_openqasm3_simulator(circuit: &Circuit) -> Result<String, RoqoqoError> {
+    write_openqasm3(circuit, None, false)
+}
+
+/// Converts a qoqo circuit into a Braket OpenQASM 3 program that is run verbatim.
+///
+/// The gates are wrapped in `#pragma braket verbatim` boxes, so that Braket runs them on the
+/// device without compiling them. The gates act on the physical qubits of the device, which
+/// are not declared. Measurements are placed outside of the boxes.
+///
+/// The circuit is validated with [AWSDevice::validate_circuit] first, so all gates have to be
+/// native gates of the device acting on connected qubits, see e.g.
+/// [crate::transpile::to_rigetti_native_verbatim] and [crate::routing::route_circuit].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to convert.
+/// * `device` - The device the program is run on.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The OpenQASM 3 program.
+/// * `Err(RoqoqoError)` - The circuit cannot be run verbatim on the device or contains an
+///   operation that cannot be expressed in Braket OpenQASM 3.
+pub fn circuit_to_openqasm3_verbatim(
+    circuit: &Circuit,
+    device: &AWSDevice,
+) -> Result<String, RoqoqoError> {
+    device.validate_circuit(circuit).map_err(|errors| {
+        let errors: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+        RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit cannot be run verbatim on the device: {}",
+                errors.join("; ")
+            ),
+        }
+    })?;
+    write_openqasm3(circuit, Some(device), true)
+}
+
+/// Writes a qoqo circuit as a Braket OpenQASM 3 program.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to convert.
+/// * `device` - The device the program is run on, `None` for the Braket simulators.
+/// * `verbatim` - Whether the gates are wrapped in verbatim boxes acting on physical qubits.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The OpenQASM 3 program.
+/// * `Err(RoqoqoError)` - The circuit does not fit on the device or contains an operation that
+///   cannot be expressed in Braket OpenQASM 3.
+fn write_openqasm3(
+    circuit: &Circuit,
+    device: Option<&AWSDevice>,
+    verbatim: bool,
+) -> Result<String, RoqoqoError> {
+    let number_qubits = circuit_number_qubits(circuit);
+    let device_qubits = device.map_or(SIMULATOR_QUBITS, |device| device.number_qubits());
+    if number_qubits > device_qubits {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit uses {} qubits but the device only has {}",
+                number_qubits, device_qubits
+            ),
+        });
+    }
+    if let Some(device) = device {
+        if let Some(shots) = braket_shots(circuit)? {
+            device
+                .validate_shots(shots)
+                .map_err(|error| RoqoqoError::GenericError {
+                    msg: error.to_string(),
+                })?;
+        }
+    }
+    let supports_delay = device.is_some_and(|device| device.supports_delay());
+    let supports_feedforward = device.is_some_and(|device| device.supports_feedforward());
+    let qubit = |qubit: usize| {
+        if verbatim {
+            format!("${}", qubit)
+        } else {
+            format!("q[{}]", qubit)
+        }
+    };
+
+    let mut program = String::from("OPENQASM 3.0;\n");
+    let mut instructions = String::new();
+    let mut in_box = false;
+    for operation in circuit.iter() {
+        let is_gate = operation.tags().contains(&"GateOperation");
+        if in_box && !is_gate {
+            instructions.push_str("}\n");
+            in_box = false;
+        }
+        match operation {
+            Operation::DefinitionBit(definition) => {
+                program.push_str(&format!(
+                    "bit[{}] {};\n",
+                    definition.length(),
+                    definition.name()
+                ));
+            }
+            Operation::MeasureQubit(measurement) => {
+                instructions.push_str(&format!(
+                    "{}[{}] = measure {};\n",
+                    measurement.readout(),
+                    measurement.readout_index(),
+                    qubit(*measurement.qubit())
+                ));
+            }
+            Operation::PragmaRepeatedMeasurement(measurement) => {
+                for measured in 0..number_qubits {
+                    let index = match measurement.qubit_mapping() {
+                        Some(mapping) => match mapping.get(&measured) {
+                            Some(index) => *index,
+                            None => continue,
+                        },
+                        None => measured,
+                    };
+                    instructions.push_str(&format!(
+                        "{}[{}] = measure {};\n",
+                        measurement.readout(),
+                        index,
+                        qubit(measured)
+                    ));
+                }
+            }
+            Operation::PragmaSleep(sleep) if supports_delay => {
+                let qubits: Vec<String> = sleep.qubits().iter().map(|q| qubit(*q)).collect();
+                instructions.push_str(&format!(
+                    "delay[{}s] {};\n",
+                    sleep.sleep_time().float()?,
+                    qubits.join(", ")
+                ));
+            }
+            Operation::PragmaConditional(conditional) if supports_feedforward => {
+                instructions.push_str(&format!(
+                    "if ({}[{}]) {{\n",
+                    conditional.condition_register(),
+                    conditional.condition_index()
+                ));
+                if verbatim {
+                    instructions.push_str("#pragma braket verbatim\nbox{\n");
+                }
+                for conditioned in conditional.circuit().iter() {
+                    if !conditioned.tags().contains(&"GateOperation") {
+                        return Err(RoqoqoError::GenericError {
+                            msg: format!(
+                                "Operation {} cannot be conditioned in OpenQASM 3",
+                                conditioned.hqslang()
+                            ),
+                        });
+                    }
+                    write_gates(conditioned, device.is_none(), &qubit, &mut instructions)?;
+                }
+                if verbatim {
+                    instructions.push_str("}\n");
+                }
+                instructions.push_str("}\n");
+            }
+            Operation::PragmaSetNumberOfMeasurements(_) | Operation::InputSymbolic(_) => (),
+            _ if is_gate => {
+                if verbatim && !in_box {
+                    instructions.push_str("#pragma braket verbatim\nbox{\n");
+                    in_box = true;
+                }
+                write_gates(operation, device.is_none(), &qubit, &mut instructions)?;
+            }
+            _ => {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Operation {} cannot be converted to OpenQASM 3",
+                        operation.hqslang()
+                    ),
+                })
+            }
+        }
+    }
+    if in_box {
+        instructions.push_str("}\n");
+    }
+    for parameter in free_parameters(circuit) {
+        program.push_str(&format!("input float {};\n", parameter));
+    }
+    if number_qubits > 0 && !verbatim {
+        program.push_str(&format!("qubit[{}] q;\n", number_qubits));
+    }
+    program.push_str(&instructions);
+    Ok(program)
+}
+
+/// Writes the Braket gates of a qoqo gate operation as OpenQASM 3 instructions.
+///
+/// # Arguments
+///
+/// * `operation` - The gate operation to write.
+/// * `modifiers` - Whether controlled gates without Braket equivalent use `ctrl @` modifiers.
+/// * `qubit` - Returns the OpenQASM 3 name of a qubit.
+/// * `instructions` - The instructions the gates are appended to.
+///
+/// # Returns
+///
+/// * `Ok(())` - The gates were written.
+/// * `Err(RoqoqoError)` - The operation cannot be expressed with Braket gates.
+fn write_gates(
+    operation: &Operation,
+    modifiers: bool,
+    qubit: &impl Fn(usize) -> String,
+    instructions: &mut String,
+) -> Result<(), RoqoqoError> {
+    for gate in braket_gates(operation, modifiers)? {
+        instructions.push_str(&"ctrl @ ".repeat(gate.controls));
+        instructions.push_str(gate.name);
+        if !gate.parameters.is_empty() {
+            let parameters: Vec<String> = gate
+                .parameters
+                .iter()
+                .map(|parameter| match parameter {
+                    CalculatorFloat::Float(value) => value.to_string(),
+                    CalculatorFloat::Str(expression) => expression.clone(),
+                })
+                .collect();
+            instructions.push_str(&format!("({})", parameters.join(", ")));
+        }
+        let qubits: Vec<String> = gate.qubits.iter().map(|q| qubit(*q)).collect();
+        instructions.push_str(&format!(" {};\n", qubits.join(", ")));
+    }
+    Ok(())
+}
+
+/// Evaluates an angle expression of an OpenQASM 3 program.
+///
+/// Supports numbers, the constants `pi`, `tau` and `euler`, the operators `+`, `-`, `*` and `/`
+/// and parentheses.
+struct ExpressionParser<'a> {
+    /// The characters of the expression without whitespace.
+    chars: Vec<char>,
+    /// Position of the next character to read.
+    position: usize,
+    /// The expression, used in error messages.
+    source: &'a str,
+}
+
+impl<'a> ExpressionParser<'a> {
+    /// Evaluates an expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The expression to evaluate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The value of the expression.
+    /// * `Err(RoqoqoError)` - The expression is not valid.
+    fn evaluate(source: &'a str) -> Result<f64, RoqoqoError> {
+        let mut parser = ExpressionParser {
+            chars: source.chars().filter(|c| !c.is_whitespace()).collect(),
+            position: 0,
+            source,
+        };
+        let value = parser.sum()?;
+        if parser.position < parser.chars.len() {
+            return Err(parser.error());
+        }
+        Ok(value)
+    }
+
+    /// Returns the error for an invalid expression.
+    fn error(&self) -> RoqoqoError {
+        RoqoqoError::GenericError {
+            msg: format!("Cannot evaluate expression {}", self.source),
+        }
+    }
+
+    /// Returns the next character without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Parses a sum or difference of products.
+    fn sum(&mut self) -> Result<f64, RoqoqoError> {
+        let mut value = self.product()?;
+        while let Some(operator) = self.peek().filter(|c| *c == '+' || *c == '-') {
+            self.position += 1;
+            let operand = self.product()?;
+            value = if operator == '+' {
+                value + operand
+            } else {
+                value - operand
+            };
+        }
+        Ok(value)
+    }
+
+    /// Parses a product or quotient of factors.
+    fn product(&mut self) -> Result<f64, RoqoqoError> {
+        let mut value = self.factor()?;
+        while let Some(operator) = self.peek().filter(|c| *c == '*' || *c == '/') {
+            self.position += 1;
+            let operand = self.factor()?;
+            value = if operator == '*' {
+                value * operand
+            } else {
+                value / operand
+            };
+        }
+        Ok(value)
+    }
+
+    /// Parses a signed number, constant or expression in parentheses.
+    fn factor(&mut self) -> Result<f64, RoqoqoError> {
+        match self.peek() {
+            Some('-') => {
+                self.position += 1;
+                Ok(-self.factor()?)
+            }
+            Some('+') => {
+                self.position += 1;
+                self.factor()
+            }
+            Some('(') => {
+                self.position += 1;
+                let value = self.sum()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error());
+                }
+                self.position += 1;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.position;
+                while let Some(c) = self.peek() {
+                    let exponent_sign = (c == '-' || c == '+')
+                        && matches!(self.chars.get(self.position - 1), Some('e') | Some('E'));
+                    if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign {
+                        self.position += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let number: String = self.chars[start..self.position].iter().collect();
+                number.parse().map_err(|_| self.error())
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let start = self.position;
+                while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    self.position += 1;
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                match name.as_str() {
+                    "pi" | "π" => Ok(PI),
+                    "tau" | "τ" => Ok(TAU),
+                    "euler" | "ℇ" => Ok(E),
+                    _ => Err(self.error()),
+                }
+            }
+            _ => Err(self.error()),
+        }
+    }
+}
+
+/// Block statement of an OpenQASM 3 program that has not been closed yet.
+enum Block {
+    /// A verbatim box.
+    Box,
+    /// An `if` statement on a bit, with the circuit read before the statement.
+    Conditional {
+        register: String,
+        index: usize,
+        outer: Circuit,
+    },
+}
+
+/// Handles the opening and closing braces of box and `if` statements at the start of a statement.
+///
+/// Opening an `if` statement stores the circuit read so far with the block and continues with an
+/// empty circuit collecting the conditioned gates. Closing it restores the outer circuit with a
+/// PragmaConditional of the conditioned gates appended.
+///
+/// # Arguments
+///
+/// * `statement` - The statement.
+/// * `blocks` - The blocks that are open before the statement.
+/// * `circuit` - The circuit the operations of the program are added to.
+/// * `bit_registers` - The declared bit registers and their lengths.
+///
+/// # Returns
+///
+/// * `Ok(&str)` - The rest of the statement.
+/// * `Err(RoqoqoError)` - A brace is not matched, the condition is not a declared bit or a
+///   conditioned operation is not a gate.
+fn strip_blocks<'a>(
+    statement: &'a str,
+    blocks: &mut Vec<Block>,
+    circuit: &mut Circuit,
+    bit_registers: &HashMap<String, usize>,
+) -> Result<&'a str, RoqoqoError> {
+    let mut statement = statement.trim();
+    loop {
+        if let Some(rest) = statement.strip_prefix('}') {
+            match blocks.pop() {
+                Some(Block::Box) => (),
+                Some(Block::Conditional {
+                    register,
+                    index,
+                    outer,
+                }) => {
+                    let conditioned = std::mem::replace(circuit, outer);
+                    if let Some(operation) = conditioned
+                        .iter()
+                        .find(|operation| !operation.tags().contains(&"GateOperation"))
+                    {
+                        return Err(RoqoqoError::GenericError {
+                            msg: format!(
+                                "Operation {} cannot be conditioned in OpenQASM 3",
+                                operation.hqslang()
+                            ),
+                        });
+                    }
+                    *circuit += PragmaConditional::new(register, index, conditioned);
+                }
+                None => {
+                    return Err(RoqoqoError::GenericError {
+                        msg: "Unmatched closing brace".to_string(),
+                    })
+                }
+            }
+            statement = rest.trim_start();
+        } else if let Some(rest) = statement
+            .strip_prefix("box")
+            .and_then(|rest| rest.trim_start().strip_prefix('{'))
+        {
+            blocks.push(Block::Box);
+            statement = rest.trim_start();
+        } else if let Some((condition, rest)) = statement
+            .strip_prefix("if")
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .and_then(|rest| rest.split_once(')'))
+        {
+            let rest =
+                rest.trim_start()
+                    .strip_prefix('{')
+                    .ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!("Invalid if statement {}", statement),
+                    })?;
+            let (register, index) = match split_index(condition)? {
+                (register, Some(index))
+                    if bit_registers
+                        .get(register)
+                        .is_some_and(|length| index < *length) =>
+                {
+                    (register, index)
+                }
+                _ => {
+                    return Err(RoqoqoError::GenericError {
+                        msg: format!(
+                            "Unsupported condition {}, only single declared bits are supported",
+                            condition.trim()
+                        ),
+                    })
+                }
+            };
+            blocks.push(Block::Conditional {
+                register: register.to_string(),
+                index,
+                outer: std::mem::replace(circuit, Circuit::new()),
+            });
+            statement = rest.trim_start();
+        } else {
+            return Ok(statement);
+        }
+    }
+}
+
+/// Removes the `ctrl @` and `pow(n) @` gate modifiers from the start of a statement.
+///
+/// # Returns
+///
+/// * `Ok((usize, usize, &str))` - The number of controls, the power and the rest of the statement.
+/// * `Err(RoqoqoError)` - A power is not a non-negative integer.
+fn strip_modifiers(statement: &str) -> Result<(usize, usize, &str), RoqoqoError> {
+    let mut statement = statement;
+    let mut controls = 0;
+    let mut power = 1;
+    loop {
+        if let Some(rest) = statement
+            .strip_prefix("ctrl")
+            .and_then(|rest| rest.trim_start().strip_prefix('@'))
+        {
+            controls += 1;
+            statement = rest.trim_start();
+        } else if let Some((exponent, rest)) = statement
+            .strip_prefix("pow")
+            .and_then(|rest| rest.trim_start().strip_prefix('('))
+            .and_then(|rest| rest.split_once(')'))
+            .and_then(|(exponent, rest)| {
+                rest.trim_start()
+                    .strip_prefix('@')
+                    .map(|rest| (exponent, rest))
+            })
+        {
+            let exponent: usize =
+                exponent
+                    .trim()
+                    .parse()
+                    .map_err(|_| RoqoqoError::GenericError {
+                        msg: format!("Unsupported power modifier pow({})", exponent),
+                    })?;
+            power *= exponent;
+            statement = rest.trim_start();
+        } else {
+            return Ok((controls, power, statement));
+        }
+    }
+}
+
+/// Splits a declaration or register access like `ro[2]` into the name and the index.
+fn split_index(reference: &str) -> Result<(&str, Option<usize>), RoqoqoError> {
+    let reference = reference.trim();
+    match reference.split_once('[') {
+        Some((name, index)) => {
+            let index = index
+                .strip_suffix(']')
+                .and_then(|index| index.trim().parse().ok())
+                .ok_or_else(|| RoqoqoError::GenericError {
+                    msg: format!("Invalid register access {}", reference),
+                })?;
+            Ok((name.trim(), Some(index)))
+        }
+        None => Ok((reference, None)),
+    }
+}
+
+/// Returns the duration of an OpenQASM 3 duration literal like `100ns` in seconds.
+fn duration_seconds(duration: &str) -> Result<f64, RoqoqoError> {
+    let duration = duration.trim();
+    let units = [
+        ("ns", 1e-9),
+        ("us", 1e-6),
+        ("µs", 1e-6),
+        ("ms", 1e-3),
+        ("s", 1.0),
+    ];
+    units
+        .iter()
+        .find_map(|(unit, scale)| {
+            duration
+                .strip_suffix(unit)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .map(|value| value * scale)
+        })
+        .ok_or_else(|| RoqoqoError::GenericError {
+            msg: format!("Invalid duration {}", duration),
+        })
+}
+
+/// Qubit registers declared in an OpenQASM 3 program.
+#[derive(Default)]
+struct QubitRegisters {
+    /// The first qubit and the length of every register.
+    registers: HashMap<String, (usize, usize)>,
+    /// The first qubit of the next declared register.
+    next_qubit: usize,
+}
+
+impl QubitRegisters {
+    /// Returns the qubits referenced by `$3`, `q[3]` or a whole register `q`.
+    fn qubits(&self, reference: &str) -> Result<Vec<usize>, RoqoqoError> {
+        let reference = reference.trim();
+        if let Some(physical) = reference.strip_prefix('$') {
+            return physical.parse().map(|qubit| vec![qubit]).map_err(|_| {
+                RoqoqoError::GenericError {
+                    msg: format!("Invalid physical qubit {}", reference),
+                }
+            });
+        }
+        let (name, index) = split_index(reference)?;
+        let (offset, length) =
+            self.registers
+                .get(name)
+                .copied()
+                .ok_or_else(|| RoqoqoError::GenericError {
+                    msg: format!("Unknown qubit register {}", name),
+                })?;
+        match index {
+            Some(index) if index < length => Ok(vec![offset + index]),
+            Some(_) => Err(RoqoqoError::GenericError {
+                msg: format!("Qubit {} is out of range", reference),
+            }),
+            None => Ok((offset..offset + length).collect()),
+        }
+    }
+}
+
+/// Returns the angle of an angle expression of an OpenQASM 3 program.
+///
+/// # Arguments
+///
+/// * `expression` - The angle expression.
+/// * `inputs` - The inputs declared in the program.
+///
+/// # Returns
+///
+/// * `Ok(CalculatorFloat)` - The value of a constant expression or the expression itself if it
+///   references declared inputs.
+/// * `Err(RoqoqoError)` - The expression is not valid or references undeclared names.
+fn parameter_value(
+    expression: &str,
+    inputs: &HashSet<String>,
+) -> Result<CalculatorFloat, RoqoqoError> {
+    match ExpressionParser::evaluate(expression) {
+        Ok(value) => Ok(value.into()),
+        Err(err) => {
+            let parameters = expression_parameters(expression);
+            if !parameters.is_empty()
+                && parameters
+                    .iter()
+                    .all(|parameter| inputs.contains(parameter))
+            {
+                Ok(CalculatorFloat::Str(expression.trim().to_string()))
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Converts a Braket OpenQASM 3 program into a qoqo circuit.
+///
+/// Supports the subset of OpenQASM 3 used by Braket circuits: bit, qubit and input
+/// declarations, physical qubits, the Braket gates with `ctrl @` and integer `pow(n) @`
+/// modifiers, delays, measurements into bit registers, verbatim boxes, whose markers are
+/// dropped, and `if` statements on a single bit containing only gates, which become
+/// PragmaConditional operations. Qubit registers are numbered consecutively in the order of
+/// their declaration. Angles referencing declared inputs become symbolic angles, all other angles
+/// have to be constant expressions.
+///
+/// # Arguments
+///
+/// * `program` - The OpenQASM 3 program.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The circuit.
+/// * `Err(RoqoqoError)` - The program contains an unsupported statement or gate.
+pub fn circuit_from_openqasm3(program: &str) -> Result<Circuit, RoqoqoError> {
+    let mut source = String::new();
+    for line in program.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if !line.starts_with('#') {
+            source.push_str(line);
+            source.push('\n');
+        }
+    }
+
+    let mut circuit = Circuit::new();
+    let mut qubit_registers = QubitRegisters::default();
+    let mut bit_registers: HashMap<String, usize> = HashMap::new();
+    let mut inputs: HashSet<String> = HashSet::new();
+    let mut blocks: Vec<Block> = Vec::new();
+    for statement in source.split(';') {
+        let statement = strip_blocks(statement, &mut blocks, &mut circuit, &bit_registers)?;
+        let (controls, power, statement) = strip_modifiers(statement)?;
+        if statement.is_empty()
+            || statement.starts_with("OPENQASM")
+            || statement.starts_with("include")
+        {
+            continue;
+        }
+        let (keyword, rest) = statement
+            .split_once(|c: char| c.is_whitespace() || c == '[' || c == '(')
+            .map(|(keyword, _)| (keyword, &statement[keyword.len()..]))
+            .unwrap_or((statement, ""));
+        match keyword {
+            "bit" | "qubit" => {
+                let (length, name) = match rest.trim_start().strip_prefix('[') {
+                    Some(rest) => {
+                        let (length, name) =
+                            rest.split_once(']')
+                                .ok_or_else(|| RoqoqoError::GenericError {
+                                    msg: format!("Invalid declaration {}", statement),
+                                })?;
+                        let length =
+                            length
+                                .trim()
+                                .parse()
+                                .map_err(|_| RoqoqoError::GenericError {
+                                    msg: format!("Invalid declaration {}", statement),
+                                })?;
+                        (length, name.trim())
+                    }
+                    None => (1, rest.trim()),
+                };
+                if keyword == "bit" {
+                    bit_registers.insert(name.to_string(), length);
+                    circuit += DefinitionBit::new(name.to_string(), length, true);
+                } else {
+                    qubit_registers
+                        .registers
+                        .insert(name.to_string(), (qubit_registers.next_qubit, length));
+                    qubit_registers.next_qubit += length;
+                }
+            }
+            "input" => {
+                let name = rest.split_whitespace().last().unwrap_or_default();
+                if name.is_empty() {
+                    return Err(RoqoqoError::GenericError {
+                        msg: format!("Invalid declaration {}", statement),
+                    });
+                }
+                inputs.insert(name.to_string());
+            }
+            "delay" => {
+                let (duration, operands) = rest
+                    .trim_start()
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.split_once(']'))
+                    .ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!("Invalid delay {}", statement),
+                    })?;
+                let mut qubits: Vec<usize> = Vec::new();
+                for operand in operands.split(',') {
+                    qubits.extend(qubit_registers.qubits(operand)?);
+                }
+                circuit += PragmaSleep::new(qubits, duration_seconds(duration)?.into());
+            }
+            _ if statement.contains("= measure") => {
+                let (target, measured) =
+                    statement
+                        .split_once("= measure")
+                        .ok_or_else(|| RoqoqoError::GenericError {
+                            msg: format!("Invalid measurement {}", statement),
+                        })?;
+                let (readout, index) = split_index(target)?;
+                let qubits = qubit_registers.qubits(measured)?;
+                let indices: Vec<usize> = match index {
+                    Some(index) => vec![index],
+                    None => (0..bit_registers.get(readout).copied().unwrap_or(0)).collect(),
+                };
+                if qubits.len() != indices.len() {
+                    return Err(RoqoqoError::GenericError {
+                        msg: format!("Mismatched register sizes in {}", statement),
+                    });
+                }
+                for (qubit, index) in qubits.into_iter().zip(indices) {
+                    circuit += MeasureQubit::new(qubit, readout.to_string(), index);
+                }
+            }
+            name => {
+                let rest = rest.trim_start();
+                let (parameters, operands) = match rest.strip_prefix('(') {
+                    Some(rest) => {
+                        let (parameters, operands) =
+                            rest.rsplit_once(')')
+                                .ok_or_else(|| RoqoqoError::GenericError {
+                                    msg: format!("Invalid gate {}", statement),
+                                })?;
+                        let parameters = parameters
+                            .split(',')
+                            .map(|parameter| parameter_value(parameter, &inputs))
+                            .collect::<Result<Vec<CalculatorFloat>, RoqoqoError>>()?;
+                        (parameters, operands)
+                    }
+                    None => (Vec::new(), rest),
+                };
+                let mut qubits: Vec<usize> = Vec::new();
+                for operand in operands.split(',') {
+                    let operand_qubits = qubit_registers.qubits(operand)?;
+                    if operand_qubits.len() != 1 {
+                        return Err(RoqoqoError::GenericError {
+                            msg: format!(
+                                "Gates on whole registers are not supported: {}",
+                                statement
+                            ),
+                        });
+                    }
+                    qubits.extend(operand_qubits);
+                }
+                let operation = qoqo_controlled_gate(name, controls, &parameters, &qubits)?;
+                for _ in 0..power {
+                    circuit += operation.clone();
+                }
+            }
+        }
+    }
+    if !blocks.is_empty() {
+        return Err(RoqoqoError::GenericError {
+            msg: "Program ends inside a box or if statement".to_string(),
+        });
+    }
+    Ok(circuit)
+}
@@ -0,0 +1,145 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeSet, HashMap};
+
+use qoqo_calculator::CalculatorFloat;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::braket_ir::braket_gates;
+
+/// Names in symbolic expressions that are constants and not free parameters.
+const CONSTANTS: [&str; 4] = ["pi", "e", "tau", "euler"];
+
+/// Returns the free parameters referenced by a symbolic expression.
+///
+/// Identifiers followed by an opening parenthesis are functions and numbers in scientific
+/// notation are skipped.
+///
+/// # Arguments
+///
+/// * `expression` - The symbolic expression.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The names of the free parameters in order of appearance.
+pub(crate) fn expression_parameters(expression: &str) -> Vec<String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut parameters: Vec<String> = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let start = position;
+        let c = chars[position];
+        if c.is_alphanumeric() || c == '_' || c == '.' {
+            while position < chars.len()
+                && (chars[position].is_alphanumeric()
+                    || chars[position] == '_'
+                    || chars[position] == '.')
+            {
+                position += 1;
+            }
+            let name: String = chars[start..position].iter().collect();
+            let is_function = chars[position..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == '(');
+            let is_number = c.is_ascii_digit() || c == '.';
+            if !is_function
+                && !is_number
+                && !CONSTANTS.contains(&name.as_str())
+                && !parameters.contains(&name)
+            {
+                parameters.push(name);
+            }
+        } else {
+            position += 1;
+        }
+    }
+    parameters
+}
+
+/// Returns the free parameters of a circuit.
+///
+/// The free parameters are the names referenced by the symbolic angles of the gates of the
+/// circuit. They are declared as `input float` in the OpenQASM 3 program of the circuit, so that
+/// their values can be set when the task is created instead of converting the circuit again.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to analyse.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The sorted names of the free parameters.
+pub fn free_parameters(circuit: &Circuit) -> Vec<String> {
+    let mut parameters: BTreeSet<String> = BTreeSet::new();
+    for operation in circuit.iter() {
+        if let Operation::PragmaConditional(conditional) = operation {
+            parameters.extend(free_parameters(conditional.circuit()));
+            continue;
+        }
+        for gate in braket_gates(operation, true).unwrap_or_default() {
+            for parameter in gate.parameters {
+                if let CalculatorFloat::Str(expression) = parameter {
+                    parameters.extend(expression_parameters(&expression));
+                }
+            }
+        }
+    }
+    parameters.into_iter().collect()
+}
+
+/// Returns the values of the free parameters of a circuit passed as inputs of a Braket task.
+///
+/// Free parameters without a given value take the value of the InputSymbolic operation of the
+/// circuit defining them.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit that is run.
+/// * `values` - The values of the free parameters.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, f64>)` - The value of every free parameter of the circuit.
+/// * `Err(RoqoqoError)` - A free parameter has no value or a value is given for a name that is
+///   not a free parameter of the circuit.
+pub fn braket_inputs(
+    circuit: &Circuit,
+    values: &HashMap<String, f64>,
+) -> Result<HashMap<String, f64>, RoqoqoError> {
+    let parameters = free_parameters(circuit);
+    if let Some(name) = values.keys().find(|name| !parameters.contains(name)) {
+        return Err(RoqoqoError::GenericError {
+            msg: format!("{} is not a free parameter of the circuit", name),
+        });
+    }
+    let mut defaults: HashMap<&str, f64> = HashMap::new();
+    for operation in circuit.iter() {
+        if let Operation::InputSymbolic(input) = operation {
+            defaults.insert(input.name().as_str(), *input.input());
+        }
+    }
+    let mut inputs: HashMap<String, f64> = HashMap::new();
+    for name in parameters {
+        let value = values
+            .get(&name)
+            .or_else(|| defaults.get(name.as_str()))
+            .copied()
+            .ok_or_else(|| RoqoqoError::GenericError {
+                msg: format!("No value given for free parameter {}", name),
+            })?;
+        inputs.insert(name, value);
+    }
+    Ok(inputs)
+}
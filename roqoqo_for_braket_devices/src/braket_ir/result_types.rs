@@ -0,0 +1,161 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::{json, Value};
+
+use roqoqo::measurements::PauliZProductInput;
+use roqoqo::RoqoqoError;
+
+/// A result type of a Braket task, computed by Braket instead of returning the measured shots.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ResultType {
+    /// The expectation value of the product of PauliZ operators on the qubits.
+    Expectation(Vec<usize>),
+    /// The eigenvalues of the product of PauliZ operators on the qubits measured in every shot.
+    Sample(Vec<usize>),
+}
+
+impl ResultType {
+    /// Returns the Braket name and the qubits of the result type.
+    fn parts(&self) -> (&'static str, &[usize]) {
+        match self {
+            ResultType::Expectation(qubits) => ("expectation", qubits),
+            ResultType::Sample(qubits) => ("sample", qubits),
+        }
+    }
+}
+
+/// Returns the Braket result types computing the PauliZ products of a qoqo PauliZProduct measurement.
+///
+/// The result types are in the order of the indices of the PauliZ products of the readout, so
+/// the values returned by Braket are the expectation values or samples of the products that are
+/// combined in the measurement input. The basis rotations of the measurement circuits are not
+/// changed, the result types are computed after them.
+///
+/// # Arguments
+///
+/// * `input` - The input of the PauliZProduct measurement.
+/// * `readout` - The readout register of the PauliZ products.
+/// * `sample` - Whether the samples of the products are returned instead of their expectation
+///   values.
+///
+/// # Returns
+///
+/// * `Ok(Vec<ResultType>)` - The result types of the PauliZ products.
+/// * `Err(RoqoqoError)` - The readout is not part of the input or a product acts on no qubits.
+pub fn pauli_z_product_result_types(
+    input: &PauliZProductInput,
+    readout: &str,
+    sample: bool,
+) -> Result<Vec<ResultType>, RoqoqoError> {
+    let masks = input
+        .pauli_product_qubit_masks
+        .get(readout)
+        .ok_or_else(|| RoqoqoError::GenericError {
+            msg: format!("Readout {} is not part of the measurement input", readout),
+        })?;
+    let mut indices: Vec<usize> = masks.keys().copied().collect();
+    indices.sort_unstable();
+    indices
+        .into_iter()
+        .map(|index| {
+            let mut qubits = masks[&index].clone();
+            qubits.sort_unstable();
+            if qubits.is_empty() {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "PauliZ product {} of readout {} acts on no qubits",
+                        index, readout
+                    ),
+                });
+            }
+            Ok(if sample {
+                ResultType::Sample(qubits)
+            } else {
+                ResultType::Expectation(qubits)
+            })
+        })
+        .collect()
+}
+
+/// Adds result types to a Braket OpenQASM 3 program.
+///
+/// The result types are added as `#pragma braket result` statements at the end of the program,
+/// acting on the register `q` if the program declares it and on physical qubits otherwise.
+///
+/// # Arguments
+///
+/// * `program` - The OpenQASM 3 program, see [crate::braket_ir::circuit_to_openqasm3].
+/// * `results` - The result types to add.
+///
+/// # Returns
+///
+/// The OpenQASM 3 program with the result types.
+pub fn add_openqasm3_result_types(program: &str, results: &[ResultType]) -> String {
+    let register = program.lines().any(|line| line.starts_with("qubit["));
+    let mut program = program.to_string();
+    for result in results {
+        let (name, qubits) = result.parts();
+        let observables: Vec<String> = qubits
+            .iter()
+            .map(|qubit| {
+                if register {
+                    format!("z(q[{}])", qubit)
+                } else {
+                    format!("z(${})", qubit)
+                }
+            })
+            .collect();
+        program.push_str(&format!(
+            "#pragma braket result {} {}\n",
+            name,
+            observables.join(" @ ")
+        ));
+    }
+    program
+}
+
+/// Adds result types to a Braket JAQCD program.
+///
+/// # Arguments
+///
+/// * `program` - The JSON of the JAQCD program, see [crate::braket_ir::circuit_to_jaqcd].
+/// * `results` - The result types to add.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON of the JAQCD program with the result types.
+/// * `Err(RoqoqoError)` - The program is not a valid JAQCD program.
+pub fn add_jaqcd_result_types(
+    program: &str,
+    results: &[ResultType],
+) -> Result<String, RoqoqoError> {
+    let mut program: Value =
+        serde_json::from_str(program).map_err(|err| RoqoqoError::GenericError {
+            msg: format!("Could not parse JAQCD program: {}", err),
+        })?;
+    let program_results =
+        program["results"]
+            .as_array_mut()
+            .ok_or_else(|| RoqoqoError::GenericError {
+                msg: "JAQCD program has no results".to_string(),
+            })?;
+    for result in results {
+        let (name, qubits) = result.parts();
+        program_results.push(json!({
+            "type": name,
+            "observable": vec!["z"; qubits.len()],
+            "targets": qubits,
+        }));
+    }
+    Ok(program.to_string())
+}
@@ -0,0 +1,118 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Calibration
+//!
+//! Provides the import of calibration data published by AWS into the AWS devices.
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::RoqoqoError;
+use serde_json::Value;
+
+use crate::AWSDevice;
+
+/// Reads a time from a Braket standardized property and converts it to seconds.
+///
+/// # Arguments
+///
+/// * `property` - The property, of the form `{"value": 1.0, "unit": "S"}`.
+///
+/// # Returns
+///
+/// * `Ok(f64)` - The time in seconds.
+/// * `Err(RoqoqoError)` - The property has no numeric value or an unknown unit.
+fn time_in_seconds(property: &Value) -> Result<f64, RoqoqoError> {
+    let value = property["value"]
+        .as_f64()
+        .ok_or_else(|| RoqoqoError::GenericError {
+            msg: format!("Property {} has no numeric value", property),
+        })?;
+    let factor = match property["unit"].as_str().unwrap_or("S") {
+        "S" => 1.0,
+        "MS" => 1e-3,
+        "US" => 1e-6,
+        "NS" => 1e-9,
+        unit => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Unknown time unit {}", unit),
+            })
+        }
+    };
+    Ok(value * factor)
+}
+
+impl AWSDevice {
+    /// Sets the decoherence rates of the device from Braket standardized device properties.
+    ///
+    /// For every qubit of the `oneQubitProperties` with both a T1 and a T2 time the damping and
+    /// dephasing rates are set with [AWSDevice::set_coherence_times]. The times are converted to
+    /// seconds, so the resulting rates are in 1/s.
+    ///
+    /// # Arguments
+    ///
+    /// * `properties` - The JSON of the standardized properties, either on its own or as the
+    ///   `standardized` entry of the full device properties.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The rates were set.
+    /// * `Err(RoqoqoError)` - The properties could not be parsed or contain unphysical coherence times.
+    pub fn set_coherence_times_from_properties(
+        &mut self,
+        properties: &str,
+    ) -> Result<(), RoqoqoError> {
+        let properties: Value =
+            serde_json::from_str(properties).map_err(|err| RoqoqoError::GenericError {
+                msg: format!("Could not parse device properties: {}", err),
+            })?;
+        let standardized = properties.get("standardized").unwrap_or(&properties);
+        let one_qubit_properties = match standardized["oneQubitProperties"].as_object() {
+            Some(one_qubit_properties) => one_qubit_properties,
+            None => return Ok(()),
+        };
+        for (qubit, qubit_properties) in one_qubit_properties {
+            let qubit: usize = qubit.parse().map_err(|_| RoqoqoError::GenericError {
+                msg: format!("Qubit {} is not a valid qubit index", qubit),
+            })?;
+            if qubit_properties["T1"].is_null() || qubit_properties["T2"].is_null() {
+                continue;
+            }
+            let t1 = time_in_seconds(&qubit_properties["T1"])?;
+            let t2 = time_in_seconds(&qubit_properties["T2"])?;
+            self.set_coherence_times(qubit, t1, t2)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the T1 and T2 times of a qubit derived from its decoherence rates.
+    ///
+    /// This is the inverse of [AWSDevice::set_coherence_times]: T1 is the inverse damping rate and
+    /// 1/T2 = 1/(2 T1) + 2 * dephasing rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the times are returned.
+    ///
+    /// # Returns
+    ///
+    /// The T1 and T2 times, `None` when the corresponding rates vanish.
+    pub fn coherence_times(&self, qubit: usize) -> (Option<f64>, Option<f64>) {
+        let rates = match self.qubit_decoherence_rates(&qubit) {
+            Some(rates) => rates,
+            None => return (None, None),
+        };
+        let t2_rate = rates[[0, 0]] / 2.0 + 2.0 * rates[[2, 2]];
+        let t1 = (rates[[0, 0]] > 0.0).then_some(1.0 / rates[[0, 0]]);
+        let t2 = (t2_rate > 0.0).then_some(1.0 / t2_rate);
+        (t1, t2)
+    }
+}
@@ -0,0 +1,404 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circuit cutting
+//!
+//! Provides the experimental partitioning of qoqo circuits that are too large for an AWS device
+//! into fragments that are run separately.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::f64::consts::FRAC_PI_2;
+
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::{circuit_number_qubits, gate_qubits};
+use crate::AWSDevice;
+
+/// Maximum number of cut gates, every cut multiplies the number of variants by six.
+pub const MAX_CUTS: usize = 4;
+
+/// Number of terms of the decomposition of a cut gate.
+const CUT_TERMS: usize = 6;
+
+/// One term of the decomposition of a cut circuit.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CutVariant {
+    /// The coefficient of the term.
+    pub coefficient: f64,
+    /// The circuit of every fragment, acting on the qubits of the fragment relabeled from zero.
+    pub circuits: Vec<Circuit>,
+    /// The readout registers of every fragment holding mid-circuit measurements of the cuts.
+    pub sign_readouts: Vec<Vec<String>>,
+}
+
+/// A circuit cut into fragments fitting on a device.
+///
+/// The expectation value of an observable that is a product of observables on the fragments is
+/// the sum over the variants of the coefficient times the product of the fragment results. The
+/// result of a fragment is the expectation value of its observable multiplied by `+1` or `-1` for
+/// the outcome `false` or `true` of every register in `sign_readouts`, see
+/// [CircuitCut::recombine].
+#[derive(Debug, PartialEq, Clone)]
+pub struct CircuitCut {
+    /// The circuit qubits of every fragment, the `i`-th qubit becomes qubit `i` of the fragment.
+    pub fragments: Vec<Vec<usize>>,
+    /// The terms of the decomposition.
+    pub variants: Vec<CutVariant>,
+}
+
+impl CircuitCut {
+    /// Combines the results of the fragment circuits into the result of the cut circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - The result of every fragment circuit of every variant, including the signs
+    ///   of the mid-circuit measurements.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(f64)` - The expectation value of the observable on the cut circuit.
+    /// * `Err(RoqoqoError)` - The number of results does not match the variants and fragments.
+    pub fn recombine(&self, results: &[Vec<f64>]) -> Result<f64, RoqoqoError> {
+        if results.len() != self.variants.len()
+            || results
+                .iter()
+                .any(|fragment_results| fragment_results.len() != self.fragments.len())
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Expected results of {} fragments for {} variants",
+                    self.fragments.len(),
+                    self.variants.len()
+                ),
+            });
+        }
+        Ok(self
+            .variants
+            .iter()
+            .zip(results)
+            .map(|(variant, fragment_results)| {
+                variant.coefficient * fragment_results.iter().product::<f64>()
+            })
+            .sum())
+    }
+}
+
+/// Splits the qubits of a circuit into fragments of at most `capacity` qubits.
+///
+/// Fragments are filled one by one, always adding the qubit with the most two-qubit gates with
+/// the qubits already in the fragment, so that few gates act on different fragments.
+fn partition(
+    circuit_qubits: &BTreeSet<usize>,
+    interactions: &HashMap<(usize, usize), usize>,
+    capacity: usize,
+) -> Vec<Vec<usize>> {
+    let mut remaining: Vec<usize> = circuit_qubits.iter().copied().collect();
+    let mut fragments: Vec<Vec<usize>> = Vec::new();
+    while !remaining.is_empty() {
+        let mut fragment = vec![remaining.remove(0)];
+        while fragment.len() < capacity && !remaining.is_empty() {
+            let (position, _) = remaining
+                .iter()
+                .enumerate()
+                .map(|(position, qubit)| {
+                    let gates: usize = fragment
+                        .iter()
+                        .map(|placed| {
+                            interactions
+                                .get(&(*placed.min(qubit), *placed.max(qubit)))
+                                .copied()
+                                .unwrap_or(0)
+                        })
+                        .sum();
+                    (position, gates)
+                })
+                .fold((0, 0), |best, candidate| {
+                    if candidate.1 > best.1 {
+                        candidate
+                    } else {
+                        best
+                    }
+                });
+            fragment.push(remaining.remove(position));
+        }
+        fragment.sort_unstable();
+        fragments.push(fragment);
+    }
+    fragments
+}
+
+/// Adds one term of the decomposition of a cut ControlledPauliZ gate to the fragment circuits.
+///
+/// The gate is decomposed into local RotateZ gates and six terms of local operations, see
+/// K. Mitarai and K. Fujii, New J. Phys. 23, 023021 (2021).
+///
+/// # Arguments
+///
+/// * `circuits` - The fragment circuits.
+/// * `sign_readouts` - The mid-circuit measurement registers of the fragments.
+/// * `term` - The term of the decomposition.
+/// * `cut` - The index of the cut, used to name the measurement registers.
+/// * `first` - The fragment and the relabeled qubit of the first qubit of the gate.
+/// * `second` - The fragment and the relabeled qubit of the second qubit of the gate.
+///
+/// # Returns
+///
+/// The coefficient of the term.
+fn add_cut_term(
+    circuits: &mut [Circuit],
+    sign_readouts: &mut [Vec<String>],
+    term: usize,
+    cut: usize,
+    first: (usize, usize),
+    second: (usize, usize),
+) -> f64 {
+    circuits[first.0] += RotateZ::new(first.1, FRAC_PI_2.into());
+    circuits[second.0] += RotateZ::new(second.1, FRAC_PI_2.into());
+    match term {
+        0 => 0.5,
+        1 => {
+            circuits[first.0] += PauliZ::new(first.1);
+            circuits[second.0] += PauliZ::new(second.1);
+            0.5
+        }
+        _ => {
+            let sign = if term % 2 == 0 { 1.0 } else { -1.0 };
+            let (measured, rotated) = if term < 4 {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            let readout = format!("cut_{}", cut);
+            circuits[measured.0] += MeasureQubit::new(measured.1, readout.clone(), 0);
+            sign_readouts[measured.0].push(readout);
+            circuits[rotated.0] += RotateZ::new(rotated.1, (-sign * FRAC_PI_2).into());
+            0.5 * sign
+        }
+    }
+}
+
+/// Cuts a circuit that is too large for a device into fragments fitting on the device.
+///
+/// This is an experimental feature. The qubits of the circuit are split into fragments of at most
+/// as many qubits as the largest connected group of available qubits of the device, see
+/// [AWSDevice::connected_components]. CNOT and ControlledPauliZ gates acting on
+/// different fragments are cut: they are replaced by a sum of six terms of local operations
+/// including mid-circuit measurements, so every cut multiplies the number of variants by six and
+/// the number of shots needed for the same precision by nine. Circuits needing cuts can therefore
+/// only be cut for devices supporting mid-circuit measurements, see
+/// [AWSDevice::supports_mid_circuit_measurement]. Other gates on different fragments are not
+/// supported and have to be decomposed first, e.g. with [crate::transpile].
+///
+/// Definitions and PragmaSetNumberOfMeasurements are copied into every fragment, measurements
+/// are moved to the fragment of the measured qubit. Braket cannot combine a
+/// PragmaRepeatedMeasurement with MeasureQubit operations, so fragments with mid-circuit
+/// measurements read out the qubits of a PragmaRepeatedMeasurement with MeasureQubit operations
+/// and a PragmaSetNumberOfMeasurements. The results of the fragments are combined with [CircuitCut::recombine].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to cut.
+/// * `device` - The device the fragments are run on.
+///
+/// # Returns
+///
+/// * `Ok(CircuitCut)` - The fragments and variants of the cut circuit.
+/// * `Err(RoqoqoError)` - The circuit needs more than [MAX_CUTS] cuts, needs cuts on a device
+///   without mid-circuit measurements or contains a gate on different fragments that cannot be
+///   cut.
+pub fn cut_circuit(circuit: &Circuit, device: &AWSDevice) -> Result<CircuitCut, RoqoqoError> {
+    let capacity = device
+        .connected_components()
+        .iter()
+        .map(|component| component.len())
+        .max()
+        .unwrap_or(0);
+    if capacity == 0 {
+        return Err(RoqoqoError::GenericError {
+            msg: "The device has no available qubits".to_string(),
+        });
+    }
+    let mut circuit_qubits: BTreeSet<usize> = BTreeSet::new();
+    let mut interactions: HashMap<(usize, usize), usize> = HashMap::new();
+    for operation in circuit.iter() {
+        if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+            circuit_qubits.extend(qubits);
+        }
+        if let Some([first, second]) = gate_qubits(operation).as_deref() {
+            *interactions
+                .entry((*first.min(second), *first.max(second)))
+                .or_insert(0) += 1;
+        }
+    }
+    let fragments = partition(&circuit_qubits, &interactions, capacity);
+    let mut location: HashMap<usize, (usize, usize)> = HashMap::new();
+    let mut mappings: Vec<HashMap<usize, usize>> = Vec::new();
+    for (fragment, qubits) in fragments.iter().enumerate() {
+        let mapping: HashMap<usize, usize> = qubits
+            .iter()
+            .enumerate()
+            .map(|(index, qubit)| (*qubit, index))
+            .collect();
+        for (qubit, index) in &mapping {
+            location.insert(*qubit, (fragment, *index));
+        }
+        mappings.push(mapping);
+    }
+    let fragments_of = |operation: &Operation| -> Option<BTreeSet<usize>> {
+        match operation.involved_qubits() {
+            InvolvedQubits::Set(qubits) => {
+                Some(qubits.iter().map(|qubit| location[qubit].0).collect())
+            }
+            _ => None,
+        }
+    };
+
+    let mut number_cuts = 0;
+    for operation in circuit.iter() {
+        if fragments_of(operation).is_some_and(|involved| involved.len() > 1) {
+            if !matches!(
+                operation,
+                Operation::CNOT(_) | Operation::ControlledPauliZ(_)
+            ) {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Operation {} acts on different fragments and cannot be cut",
+                        operation.hqslang()
+                    ),
+                });
+            }
+            number_cuts += 1;
+        }
+    }
+    if number_cuts > 0 && !device.supports_mid_circuit_measurement() {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit needs {} cuts but the device does not support the mid-circuit \
+                 measurements of cut gates",
+                number_cuts
+            ),
+        });
+    }
+    if number_cuts > MAX_CUTS {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit needs {} cuts but at most {} are supported",
+                number_cuts, MAX_CUTS
+            ),
+        });
+    }
+
+    let number_qubits = circuit_number_qubits(circuit);
+    let mut variants: Vec<CutVariant> = Vec::new();
+    for variant in 0..CUT_TERMS.pow(number_cuts as u32) {
+        let mut bodies = vec![Circuit::new(); fragments.len()];
+        let mut sign_readouts: Vec<Vec<String>> = vec![Vec::new(); fragments.len()];
+        let mut coefficient = 1.0;
+        let mut cut = 0;
+        for operation in circuit.iter() {
+            let involved = match fragments_of(operation) {
+                Some(involved) => involved,
+                None => {
+                    for (fragment, body) in bodies.iter_mut().enumerate() {
+                        match operation {
+                            Operation::PragmaRepeatedMeasurement(measurement) => {
+                                let mapping: HashMap<usize, usize> =
+                                    match measurement.qubit_mapping() {
+                                        Some(mapping) => mapping.clone(),
+                                        None => (0..number_qubits).map(|q| (q, q)).collect(),
+                                    };
+                                let mapping: HashMap<usize, usize> = mapping
+                                    .into_iter()
+                                    .filter_map(|(qubit, index)| {
+                                        mappings[fragment].get(&qubit).map(|local| (*local, index))
+                                    })
+                                    .collect();
+                                *body += PragmaRepeatedMeasurement::new(
+                                    measurement.readout().clone(),
+                                    *measurement.number_measurements(),
+                                    Some(mapping),
+                                );
+                            }
+                            _ => body.add_operation(operation.clone()),
+                        }
+                    }
+                    continue;
+                }
+            };
+            if involved.len() == 1 {
+                let fragment = *involved.iter().next().unwrap_or(&0);
+                bodies[fragment].add_operation(operation.remap_qubits(&mappings[fragment])?);
+                continue;
+            }
+            let term = variant / CUT_TERMS.pow(cut as u32) % CUT_TERMS;
+            let (first, second, hadamard) = match operation {
+                Operation::CNOT(gate) => (*gate.control(), *gate.target(), true),
+                Operation::ControlledPauliZ(gate) => (*gate.control(), *gate.target(), false),
+                _ => unreachable!("Only CNOT and ControlledPauliZ gates are cut"),
+            };
+            let (first, second) = (location[&first], location[&second]);
+            if hadamard {
+                bodies[second.0] += Hadamard::new(second.1);
+            }
+            coefficient *= add_cut_term(&mut bodies, &mut sign_readouts, term, cut, first, second);
+            if hadamard {
+                bodies[second.0] += Hadamard::new(second.1);
+            }
+            cut += 1;
+        }
+
+        let circuits: Vec<Circuit> = bodies
+            .into_iter()
+            .zip(sign_readouts.iter())
+            .map(|(body, readouts)| {
+                let mut fragment = Circuit::new();
+                for readout in readouts {
+                    fragment += DefinitionBit::new(readout.clone(), 1, true);
+                }
+                for operation in body.iter() {
+                    match operation {
+                        Operation::PragmaRepeatedMeasurement(measurement)
+                            if !readouts.is_empty() =>
+                        {
+                            let mapping: BTreeMap<usize, usize> = measurement
+                                .qubit_mapping()
+                                .clone()
+                                .unwrap_or_default()
+                                .into_iter()
+                                .collect();
+                            for (qubit, index) in mapping {
+                                fragment +=
+                                    MeasureQubit::new(qubit, measurement.readout().clone(), index);
+                            }
+                            fragment += PragmaSetNumberOfMeasurements::new(
+                                *measurement.number_measurements(),
+                                measurement.readout().clone(),
+                            );
+                        }
+                        _ => fragment.add_operation(operation.clone()),
+                    }
+                }
+                fragment
+            })
+            .collect();
+        variants.push(CutVariant {
+            coefficient,
+            circuits,
+            sign_readouts,
+        });
+    }
+    Ok(CircuitCut {
+        fragments,
+        variants,
+    })
+}
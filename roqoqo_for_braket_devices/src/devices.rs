@@ -14,9 +14,14 @@
 //!
 //! Provides the devices that are used to execute quantum programs on AWS's devices.
 
+use std::collections::HashMap;
+
+use ndarray::Array2;
 use roqoqo::devices::{GenericDevice, QoqoDevice};
 use roqoqo::RoqoqoError;
 
+use crate::analysis::default_prices;
+
 mod aws_ionq_harmony;
 pub use crate::devices::aws_ionq_harmony::IonQHarmonyDevice;
 
@@ -29,8 +34,106 @@ pub use crate::devices::aws_oqc_lucy::OQCLucyDevice;
 mod aws_rigetti_aspen_m3;
 pub use crate::devices::aws_rigetti_aspen_m3::RigettiAspenM3Device;
 
+/// Tolerance used when checking decoherence rate matrices.
+const DECOHERENCE_TOLERANCE: f64 = 1e-12;
+
+/// Checks that a decoherence rate matrix describes physical noise.
+///
+/// A valid matrix is a symmetric, positive semi-definite 3x3 matrix. Positive semi-definiteness is
+/// checked via the principal minors of the matrix.
+///
+/// # Arguments
+///
+/// * `qubit` - The qubit the rates belong to, used in the error message.
+/// * `rates` - The decoherence rate matrix.
+///
+/// # Returns
+///
+/// * `Ok(())` - The matrix is valid.
+/// * `Err(RoqoqoError)` - The matrix has the wrong shape, is not symmetric or not positive semi-definite.
+pub(crate) fn check_decoherence_rates(
+    qubit: usize,
+    rates: &Array2<f64>,
+) -> Result<(), RoqoqoError> {
+    if rates.shape() != [3, 3] {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Decoherence rates must be a 3x3 matrix, got shape {:?}",
+                rates.shape()
+            ),
+        });
+    }
+    if (rates - &rates.t())
+        .iter()
+        .any(|x| x.abs() > DECOHERENCE_TOLERANCE)
+    {
+        return Err(RoqoqoError::GenericError {
+            msg: format!("Decoherence rates of qubit {} are not symmetric", qubit),
+        });
+    }
+    let minor = |i: usize, j: usize| rates[[i, i]] * rates[[j, j]] - rates[[i, j]] * rates[[j, i]];
+    let determinant = rates[[0, 0]] * minor(1, 2)
+        - rates[[0, 1]] * (rates[[1, 0]] * rates[[2, 2]] - rates[[1, 2]] * rates[[2, 0]])
+        + rates[[0, 2]] * (rates[[1, 0]] * rates[[2, 1]] - rates[[1, 1]] * rates[[2, 0]]);
+    let principal_minors = [
+        rates[[0, 0]],
+        rates[[1, 1]],
+        rates[[2, 2]],
+        minor(0, 1),
+        minor(0, 2),
+        minor(1, 2),
+        determinant,
+    ];
+    if principal_minors.iter().any(|x| *x < -DECOHERENCE_TOLERANCE) {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Decoherence rates of qubit {} are not positive semi-definite",
+                qubit
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Sets the damping and dephasing rates of a decoherence rate matrix from T1 and T2 times.
+///
+/// The damping rate is 1/T1 and the dephasing rate is (1/T2 - 1/(2 T1)) / 2, following the
+/// convention of qoqo where a dephasing rate decays the coherences with twice the rate. The other
+/// entries of the matrix are kept, the result still has to be checked with
+/// [check_decoherence_rates].
+///
+/// # Arguments
+///
+/// * `rates` - The current decoherence rates of the qubit, `None` if none are set.
+/// * `t1` - The energy relaxation time T1.
+/// * `t2` - The coherence time T2, at most 2 T1.
+///
+/// # Returns
+///
+/// * `Ok(Array2<f64>)` - The decoherence rates with the damping and dephasing rates replaced.
+/// * `Err(RoqoqoError)` - The times are not positive or T2 exceeds 2 T1.
+pub(crate) fn rates_from_coherence_times(
+    rates: Option<&Array2<f64>>,
+    t1: f64,
+    t2: f64,
+) -> Result<Array2<f64>, RoqoqoError> {
+    if t1 <= 0.0 || t2 <= 0.0 || t2 > 2.0 * t1 {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Coherence times T1 = {} and T2 = {} must be positive with T2 <= 2 T1",
+                t1, t2
+            ),
+        });
+    }
+    let mut rates = rates.cloned().unwrap_or_else(|| Array2::zeros((3, 3)));
+    rates[[0, 0]] = 1.0 / t1;
+    rates[[2, 2]] = (1.0 / t2 - 1.0 / (2.0 * t1)) / 2.0;
+    Ok(rates)
+}
+
 /// Collection of AWS quantum devices.
 ///
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AWSDevice {
     IonQHarmonyDevice(IonQHarmonyDevice),
     IonQAria1Device(IonQAria1Device),
@@ -67,6 +170,116 @@ impl AWSDevice {
         }
     }
 
+    /// Returns the on-demand price AWS charges per quantum task run on the device.
+    ///
+    /// See [crate::analysis::PriceTable] for overriding the prices.
+    ///
+    /// # Returns
+    ///
+    /// The price per task in USD.
+    pub fn price_per_task(&self) -> f64 {
+        default_prices(self).per_task
+    }
+
+    /// Returns the range of the number of shots of a task accepted by the device.
+    ///
+    /// Tasks with zero shots, computing exact results, are only accepted by the Braket
+    /// simulators and not by the devices.
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum number of shots.
+    pub fn shots_range(&self) -> (usize, usize) {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.shots_range(),
+            AWSDevice::IonQAria1Device(x) => x.shots_range(),
+            AWSDevice::OQCLucyDevice(x) => x.shots_range(),
+            AWSDevice::RigettiAspenM3Device(x) => x.shots_range(),
+        }
+    }
+
+    /// Returns whether the device supports OpenQASM 3 delay instructions.
+    ///
+    /// # Returns
+    ///
+    /// True if PragmaSleep operations can be run on the device.
+    pub fn supports_delay(&self) -> bool {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.supports_delay(),
+            AWSDevice::IonQAria1Device(x) => x.supports_delay(),
+            AWSDevice::OQCLucyDevice(x) => x.supports_delay(),
+            AWSDevice::RigettiAspenM3Device(x) => x.supports_delay(),
+        }
+    }
+
+    /// Returns whether the device supports mid-circuit measurements.
+    ///
+    /// # Returns
+    ///
+    /// True if gates can act on qubits after they have been measured.
+    pub fn supports_mid_circuit_measurement(&self) -> bool {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.supports_mid_circuit_measurement(),
+            AWSDevice::IonQAria1Device(x) => x.supports_mid_circuit_measurement(),
+            AWSDevice::OQCLucyDevice(x) => x.supports_mid_circuit_measurement(),
+            AWSDevice::RigettiAspenM3Device(x) => x.supports_mid_circuit_measurement(),
+        }
+    }
+
+    /// Returns whether the device supports classical feedforward.
+    ///
+    /// # Returns
+    ///
+    /// True if PragmaConditional operations can be run on the device.
+    pub fn supports_feedforward(&self) -> bool {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.supports_feedforward(),
+            AWSDevice::IonQAria1Device(x) => x.supports_feedforward(),
+            AWSDevice::OQCLucyDevice(x) => x.supports_feedforward(),
+            AWSDevice::RigettiAspenM3Device(x) => x.supports_feedforward(),
+        }
+    }
+
+    /// Returns the on-demand price AWS charges per shot run on the device.
+    ///
+    /// See [crate::analysis::PriceTable] for overriding the prices.
+    ///
+    /// # Returns
+    ///
+    /// The price per shot in USD.
+    pub fn price_per_shot(&self) -> f64 {
+        default_prices(self).per_shot
+    }
+
+    /// Returns the largest number of shots per task that fits into a budget.
+    ///
+    /// Every task costs [AWSDevice::price_per_task] plus [AWSDevice::price_per_shot] for each of
+    /// its shots. The number of shots is limited to the maximum of [AWSDevice::shots_range].
+    ///
+    /// # Arguments
+    ///
+    /// * `budget_usd` - The budget in USD for all tasks.
+    /// * `number_tasks` - The number of tasks, each run with the same number of shots.
+    ///
+    /// # Returns
+    ///
+    /// The number of shots per task, `None` if the budget does not cover the minimum number
+    /// of shots of the device for all tasks.
+    pub fn max_shots_within_budget(&self, budget_usd: f64, number_tasks: usize) -> Option<usize> {
+        let (minimum, maximum) = self.shots_range();
+        if number_tasks == 0 {
+            return Some(maximum);
+        }
+        let budget_per_task = budget_usd / number_tasks as f64 - self.price_per_task();
+        // The tolerance avoids losing a shot to rounding when the budget is an exact multiple
+        let shots = (budget_per_task / self.price_per_shot() + 1e-9).floor();
+        if shots < minimum as f64 {
+            None
+        } else {
+            Some((shots as usize).min(maximum))
+        }
+    }
+
     /// Setting the gate time of a single qubit gate.
     ///
     /// # Arguments
@@ -151,6 +364,520 @@ impl AWSDevice {
         }
     }
 
+    /// Sets the decoherence rates of a qubit, replacing any previously set rates.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the rates are set.
+    /// * `rates` - The 3x3 matrix of decoherence rates of the Lindblad equation.
+    pub fn set_qubit_decoherence_rates(
+        &mut self,
+        qubit: usize,
+        rates: Array2<f64>,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_qubit_decoherence_rates(qubit, rates),
+            AWSDevice::IonQAria1Device(x) => x.set_qubit_decoherence_rates(qubit, rates),
+            AWSDevice::OQCLucyDevice(x) => x.set_qubit_decoherence_rates(qubit, rates),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_qubit_decoherence_rates(qubit, rates),
+        }
+    }
+
+    /// Sets the crosstalk strength between two qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_i` - The qubit causing the crosstalk.
+    /// * `qubit_j` - The qubit affected by the crosstalk.
+    /// * `strength` - The crosstalk strength.
+    pub fn set_crosstalk(
+        &mut self,
+        qubit_i: usize,
+        qubit_j: usize,
+        strength: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_crosstalk(qubit_i, qubit_j, strength),
+            AWSDevice::IonQAria1Device(x) => x.set_crosstalk(qubit_i, qubit_j, strength),
+            AWSDevice::OQCLucyDevice(x) => x.set_crosstalk(qubit_i, qubit_j, strength),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_crosstalk(qubit_i, qubit_j, strength),
+        }
+    }
+
+    /// Returns the crosstalk strength between two qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_i` - The qubit causing the crosstalk.
+    /// * `qubit_j` - The qubit affected by the crosstalk.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The crosstalk strength.
+    /// * `None` - No crosstalk is set for the device or a qubit is not part of the device.
+    pub fn crosstalk(&self, qubit_i: usize, qubit_j: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.crosstalk(qubit_i, qubit_j),
+            AWSDevice::IonQAria1Device(x) => x.crosstalk(qubit_i, qubit_j),
+            AWSDevice::OQCLucyDevice(x) => x.crosstalk(qubit_i, qubit_j),
+            AWSDevice::RigettiAspenM3Device(x) => x.crosstalk(qubit_i, qubit_j),
+        }
+    }
+
+    /// Returns the crosstalk matrix of the device.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<Array2<f64>>` - The number_qubits x number_qubits matrix of crosstalk strengths.
+    /// * `None` - No crosstalk is set for the device.
+    pub fn crosstalk_matrix(&self) -> Option<Array2<f64>> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.crosstalk_matrix(),
+            AWSDevice::IonQAria1Device(x) => x.crosstalk_matrix(),
+            AWSDevice::OQCLucyDevice(x) => x.crosstalk_matrix(),
+            AWSDevice::RigettiAspenM3Device(x) => x.crosstalk_matrix(),
+        }
+    }
+
+    /// Sets the error rate of the two-qubit gates acting on an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    /// * `error_rate` - The depolarizing error probability of a two-qubit gate on the edge.
+    pub fn set_two_qubit_error_rate(
+        &mut self,
+        control: usize,
+        target: usize,
+        error_rate: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => {
+                x.set_two_qubit_error_rate(control, target, error_rate)
+            }
+            AWSDevice::IonQAria1Device(x) => {
+                x.set_two_qubit_error_rate(control, target, error_rate)
+            }
+            AWSDevice::OQCLucyDevice(x) => x.set_two_qubit_error_rate(control, target, error_rate),
+            AWSDevice::RigettiAspenM3Device(x) => {
+                x.set_two_qubit_error_rate(control, target, error_rate)
+            }
+        }
+    }
+
+    /// Returns the error rate of the two-qubit gates acting on an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The depolarizing error probability.
+    /// * `None` - No error rate is set for the edge.
+    pub fn two_qubit_error_rate(&self, control: usize, target: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.two_qubit_error_rate(control, target),
+            AWSDevice::IonQAria1Device(x) => x.two_qubit_error_rate(control, target),
+            AWSDevice::OQCLucyDevice(x) => x.two_qubit_error_rate(control, target),
+            AWSDevice::RigettiAspenM3Device(x) => x.two_qubit_error_rate(control, target),
+        }
+    }
+
+    /// Sets the readout assignment error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the readout error is set.
+    /// * `prob_detect_0_as_1` - The probability to measure 1 when the qubit is in state 0.
+    /// * `prob_detect_1_as_0` - The probability to measure 0 when the qubit is in state 1.
+    pub fn set_readout_error(
+        &mut self,
+        qubit: usize,
+        prob_detect_0_as_1: f64,
+        prob_detect_1_as_0: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => {
+                x.set_readout_error(qubit, prob_detect_0_as_1, prob_detect_1_as_0)
+            }
+            AWSDevice::IonQAria1Device(x) => {
+                x.set_readout_error(qubit, prob_detect_0_as_1, prob_detect_1_as_0)
+            }
+            AWSDevice::OQCLucyDevice(x) => {
+                x.set_readout_error(qubit, prob_detect_0_as_1, prob_detect_1_as_0)
+            }
+            AWSDevice::RigettiAspenM3Device(x) => {
+                x.set_readout_error(qubit, prob_detect_0_as_1, prob_detect_1_as_0)
+            }
+        }
+    }
+
+    /// Returns the readout assignment error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the readout error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<(f64, f64)>` - The probabilities to detect 0 as 1 and to detect 1 as 0.
+    /// * `None` - No readout error is set for the qubit.
+    pub fn readout_error(&self, qubit: usize) -> Option<(f64, f64)> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.readout_error(qubit),
+            AWSDevice::IonQAria1Device(x) => x.readout_error(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.readout_error(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.readout_error(qubit),
+        }
+    }
+
+    /// Sets the state preparation error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the preparation error is set.
+    /// * `error` - The probability to prepare the qubit in state 1 instead of state 0.
+    pub fn set_preparation_error(&mut self, qubit: usize, error: f64) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_preparation_error(qubit, error),
+            AWSDevice::IonQAria1Device(x) => x.set_preparation_error(qubit, error),
+            AWSDevice::OQCLucyDevice(x) => x.set_preparation_error(qubit, error),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_preparation_error(qubit, error),
+        }
+    }
+
+    /// Returns the state preparation error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the preparation error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The probability to prepare the qubit in state 1 instead of state 0.
+    /// * `None` - No preparation error is set for the qubit.
+    pub fn preparation_error(&self, qubit: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.preparation_error(qubit),
+            AWSDevice::IonQAria1Device(x) => x.preparation_error(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.preparation_error(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.preparation_error(qubit),
+        }
+    }
+
+    /// Adds the same qubit damping to the noise rates of all qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `damping` - The damping rate added to every qubit.
+    pub fn add_damping_all(&mut self, damping: f64) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.add_damping_all(damping),
+            AWSDevice::IonQAria1Device(x) => x.add_damping_all(damping),
+            AWSDevice::OQCLucyDevice(x) => x.add_damping_all(damping),
+            AWSDevice::RigettiAspenM3Device(x) => x.add_damping_all(damping),
+        }
+    }
+
+    /// Adds the same qubit dephasing to the noise rates of all qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `dephasing` - The dephasing rate added to every qubit.
+    pub fn add_dephasing_all(&mut self, dephasing: f64) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.add_dephasing_all(dephasing),
+            AWSDevice::IonQAria1Device(x) => x.add_dephasing_all(dephasing),
+            AWSDevice::OQCLucyDevice(x) => x.add_dephasing_all(dephasing),
+            AWSDevice::RigettiAspenM3Device(x) => x.add_dephasing_all(dephasing),
+        }
+    }
+
+    /// Adds qubit damping to the noise rates of several qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `dampings` - The damping rates added to the qubits, keyed by qubit.
+    pub fn add_damping_rates(&mut self, dampings: &HashMap<usize, f64>) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.add_damping_rates(dampings),
+            AWSDevice::IonQAria1Device(x) => x.add_damping_rates(dampings),
+            AWSDevice::OQCLucyDevice(x) => x.add_damping_rates(dampings),
+            AWSDevice::RigettiAspenM3Device(x) => x.add_damping_rates(dampings),
+        }
+    }
+
+    /// Adds qubit dephasing to the noise rates of several qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `dephasings` - The dephasing rates added to the qubits, keyed by qubit.
+    pub fn add_dephasing_rates(
+        &mut self,
+        dephasings: &HashMap<usize, f64>,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.add_dephasing_rates(dephasings),
+            AWSDevice::IonQAria1Device(x) => x.add_dephasing_rates(dephasings),
+            AWSDevice::OQCLucyDevice(x) => x.add_dephasing_rates(dephasings),
+            AWSDevice::RigettiAspenM3Device(x) => x.add_dephasing_rates(dephasings),
+        }
+    }
+
+    /// Removes the decoherence rates of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the decoherence rates are removed.
+    pub fn clear_decoherence(&mut self, qubit: usize) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.clear_decoherence(qubit),
+            AWSDevice::IonQAria1Device(x) => x.clear_decoherence(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.clear_decoherence(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.clear_decoherence(qubit),
+        }
+    }
+
+    /// Removes all noise from the device, keeping the gate times.
+    pub fn clear_all_noise(&mut self) {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.clear_all_noise(),
+            AWSDevice::IonQAria1Device(x) => x.clear_all_noise(),
+            AWSDevice::OQCLucyDevice(x) => x.clear_all_noise(),
+            AWSDevice::RigettiAspenM3Device(x) => x.clear_all_noise(),
+        }
+    }
+
+    /// Sets the leakage rate of a qubit.
+    ///
+    /// The leakage rate is informational only and not part of the noise models of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the leakage rate is set.
+    /// * `leakage_rate` - The leakage rate.
+    pub fn set_leakage_rate(&mut self, qubit: usize, leakage_rate: f64) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_leakage_rate(qubit, leakage_rate),
+            AWSDevice::IonQAria1Device(x) => x.set_leakage_rate(qubit, leakage_rate),
+            AWSDevice::OQCLucyDevice(x) => x.set_leakage_rate(qubit, leakage_rate),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_leakage_rate(qubit, leakage_rate),
+        }
+    }
+
+    /// Returns the leakage rate of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the leakage rate is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The leakage rate.
+    /// * `None` - No leakage rate is set for the qubit.
+    pub fn leakage_rate(&self, qubit: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.leakage_rate(qubit),
+            AWSDevice::IonQAria1Device(x) => x.leakage_rate(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.leakage_rate(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.leakage_rate(qubit),
+        }
+    }
+
+    /// Sets the thermal excited-state population of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the thermal population is set.
+    /// * `population` - The probability to find the qubit in state 1 after initialisation.
+    pub fn set_thermal_population(
+        &mut self,
+        qubit: usize,
+        population: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_thermal_population(qubit, population),
+            AWSDevice::IonQAria1Device(x) => x.set_thermal_population(qubit, population),
+            AWSDevice::OQCLucyDevice(x) => x.set_thermal_population(qubit, population),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_thermal_population(qubit, population),
+        }
+    }
+
+    /// Returns the thermal excited-state population of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the thermal population is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The probability to find the qubit in state 1 after initialisation.
+    /// * `None` - No thermal population is set for the qubit.
+    pub fn thermal_population(&self, qubit: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.thermal_population(qubit),
+            AWSDevice::IonQAria1Device(x) => x.thermal_population(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.thermal_population(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.thermal_population(qubit),
+        }
+    }
+
+    /// Sets the damping and dephasing rates of a qubit from its T1 and T2 times.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the rates are set.
+    /// * `t1` - The energy relaxation time T1.
+    /// * `t2` - The coherence time T2, at most 2 T1.
+    pub fn set_coherence_times(
+        &mut self,
+        qubit: usize,
+        t1: f64,
+        t2: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_coherence_times(qubit, t1, t2),
+            AWSDevice::IonQAria1Device(x) => x.set_coherence_times(qubit, t1, t2),
+            AWSDevice::OQCLucyDevice(x) => x.set_coherence_times(qubit, t1, t2),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_coherence_times(qubit, t1, t2),
+        }
+    }
+
+    /// Marks a qubit as unusable.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit that is disabled.
+    pub fn disable_qubit(&mut self, qubit: usize) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.disable_qubit(qubit),
+            AWSDevice::IonQAria1Device(x) => x.disable_qubit(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.disable_qubit(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.disable_qubit(qubit),
+        }
+    }
+
+    /// Marks an edge as unusable.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    pub fn disable_edge(&mut self, control: usize, target: usize) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.disable_edge(control, target),
+            AWSDevice::IonQAria1Device(x) => x.disable_edge(control, target),
+            AWSDevice::OQCLucyDevice(x) => x.disable_edge(control, target),
+            AWSDevice::RigettiAspenM3Device(x) => x.disable_edge(control, target),
+        }
+    }
+
+    /// Adds an edge to the device and sets the gate time of a two-qubit gate on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `gate` - hqslang name of the two-qubit-gate.
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    /// * `gate_time` - gate time for the given gate.
+    pub fn add_edge(
+        &mut self,
+        gate: &str,
+        control: usize,
+        target: usize,
+        gate_time: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.add_edge(gate, control, target, gate_time),
+            AWSDevice::IonQAria1Device(x) => x.add_edge(gate, control, target, gate_time),
+            AWSDevice::OQCLucyDevice(x) => x.add_edge(gate, control, target, gate_time),
+            AWSDevice::RigettiAspenM3Device(x) => x.add_edge(gate, control, target, gate_time),
+        }
+    }
+
+    /// Returns the qubits marked as unusable.
+    ///
+    /// # Returns
+    ///
+    /// The sorted list of disabled qubits.
+    pub fn disabled_qubits(&self) -> Vec<usize> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.disabled_qubits(),
+            AWSDevice::IonQAria1Device(x) => x.disabled_qubits(),
+            AWSDevice::OQCLucyDevice(x) => x.disabled_qubits(),
+            AWSDevice::RigettiAspenM3Device(x) => x.disabled_qubits(),
+        }
+    }
+
+    /// Returns the directed edges on which the native two-qubit gates are calibrated.
+    ///
+    /// # Returns
+    ///
+    /// The `(control, target)` pairs of the calibrated gates.
+    pub fn two_qubit_edges_directed(&self) -> Vec<(usize, usize)> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.two_qubit_edges_directed(),
+            AWSDevice::IonQAria1Device(x) => x.two_qubit_edges_directed(),
+            AWSDevice::OQCLucyDevice(x) => x.two_qubit_edges_directed(),
+            AWSDevice::RigettiAspenM3Device(x) => x.two_qubit_edges_directed(),
+        }
+    }
+
+    /// Returns the layout coordinates of the qubits used to draw the device.
+    ///
+    /// # Returns
+    ///
+    /// The `(x, y)` coordinates of every qubit.
+    pub fn qubit_positions(&self) -> Vec<(f64, f64)> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.qubit_positions(),
+            AWSDevice::IonQAria1Device(x) => x.qubit_positions(),
+            AWSDevice::OQCLucyDevice(x) => x.qubit_positions(),
+            AWSDevice::RigettiAspenM3Device(x) => x.qubit_positions(),
+        }
+    }
+
+    /// Sets the duration of the measurement of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the measurement time is set.
+    /// * `measurement_time` - The duration of the measurement.
+    pub fn set_measurement_time(
+        &mut self,
+        qubit: usize,
+        measurement_time: f64,
+    ) -> Result<(), RoqoqoError> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.set_measurement_time(qubit, measurement_time),
+            AWSDevice::IonQAria1Device(x) => x.set_measurement_time(qubit, measurement_time),
+            AWSDevice::OQCLucyDevice(x) => x.set_measurement_time(qubit, measurement_time),
+            AWSDevice::RigettiAspenM3Device(x) => x.set_measurement_time(qubit, measurement_time),
+        }
+    }
+
+    /// Returns the duration of the measurement of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the measurement time is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The duration of the measurement.
+    /// * `None` - No measurement time is set for the qubit.
+    pub fn measurement_time(&self, qubit: usize) -> Option<f64> {
+        match self {
+            AWSDevice::IonQHarmonyDevice(x) => x.measurement_time(qubit),
+            AWSDevice::IonQAria1Device(x) => x.measurement_time(qubit),
+            AWSDevice::OQCLucyDevice(x) => x.measurement_time(qubit),
+            AWSDevice::RigettiAspenM3Device(x) => x.measurement_time(qubit),
+        }
+    }
+
     /// Converts the device to a qoqo GenericDevice.
     ///
     /// # Returns
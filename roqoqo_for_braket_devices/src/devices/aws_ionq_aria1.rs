@@ -11,12 +11,14 @@
 // limitations under the License.
 
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use roqoqo::{devices::QoqoDevice, RoqoqoError};
 
 use ndarray::{array, Array2};
 
+use crate::devices::{check_decoherence_rates, rates_from_coherence_times};
+use crate::topology::longest_chain;
 use crate::AWSDevice;
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -29,6 +31,36 @@ pub struct IonQAria1Device {
     two_qubit_gates: HashMap<String, TwoQubitGates>,
     /// Decoherence rates for all qubits
     decoherence_rates: HashMap<usize, Array2<f64>>,
+    /// Crosstalk strengths between pairs of qubits
+    #[serde(default)]
+    crosstalk: Option<Array2<f64>>,
+    /// Depolarizing error rates of the two-qubit gates on each edge
+    #[serde(default)]
+    two_qubit_error_rates: HashMap<(usize, usize), f64>,
+    /// Readout assignment errors (probability to detect 0 as 1, probability to detect 1 as 0) of each qubit
+    #[serde(default)]
+    readout_errors: HashMap<usize, (f64, f64)>,
+    /// State preparation errors of each qubit
+    #[serde(default)]
+    preparation_errors: HashMap<usize, f64>,
+    /// Leakage rates out of the computational subspace of each qubit
+    #[serde(default)]
+    leakage_rates: HashMap<usize, f64>,
+    /// Thermal excited-state populations of each qubit
+    #[serde(default)]
+    thermal_populations: HashMap<usize, f64>,
+    /// Qubits marked as unusable
+    #[serde(default)]
+    disabled_qubits: HashSet<usize>,
+    /// Edges marked as unusable, stored as (smaller qubit, larger qubit)
+    #[serde(default)]
+    disabled_edges: HashSet<(usize, usize)>,
+    /// Edges added to the native connectivity of the device
+    #[serde(default)]
+    additional_edges: Vec<(usize, usize)>,
+    /// Durations of the measurement of each qubit
+    #[serde(default)]
+    measurement_times: HashMap<usize, f64>,
 }
 
 type TwoQubitGates = HashMap<(usize, usize), f64>;
@@ -46,6 +78,16 @@ impl IonQAria1Device {
             single_qubit_gates: HashMap::new(),
             two_qubit_gates: HashMap::new(),
             decoherence_rates: HashMap::new(),
+            crosstalk: None,
+            two_qubit_error_rates: HashMap::new(),
+            readout_errors: HashMap::new(),
+            preparation_errors: HashMap::new(),
+            leakage_rates: HashMap::new(),
+            thermal_populations: HashMap::new(),
+            disabled_qubits: HashSet::new(),
+            disabled_edges: HashSet::new(),
+            additional_edges: Vec::new(),
+            measurement_times: HashMap::new(),
         };
 
         for qubit in 0..device.number_qubits() {
@@ -86,6 +128,48 @@ impl IonQAria1Device {
     pub fn region(&self) -> &'static str {
         "us-east-1"
     }
+
+    /// Returns the range of the number of shots of a task accepted by the device.
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum number of shots.
+    pub fn shots_range(&self) -> (usize, usize) {
+        (1, 10000)
+    }
+
+    /// Returns whether the device supports OpenQASM 3 delay instructions.
+    ///
+    /// IonQ devices do not support delay instructions.
+    ///
+    /// # Returns
+    ///
+    /// True if PragmaSleep operations can be run on the device.
+    pub fn supports_delay(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the device supports mid-circuit measurements.
+    ///
+    /// IonQ devices only measure the qubits at the end of a circuit.
+    ///
+    /// # Returns
+    ///
+    /// True if gates can act on qubits after they have been measured.
+    pub fn supports_mid_circuit_measurement(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the device supports classical feedforward.
+    ///
+    /// IonQ devices do not support classically conditioned operations.
+    ///
+    /// # Returns
+    ///
+    /// True if PragmaConditional operations can be run on the device.
+    pub fn supports_feedforward(&self) -> bool {
+        false
+    }
 }
 
 impl Default for IonQAria1Device {
@@ -215,11 +299,14 @@ impl IonQAria1Device {
                 ),
             });
         }
-        let aa = self
+        let rates = self
             .decoherence_rates
-            .entry(qubit)
-            .or_insert_with(|| Array2::zeros((3, 3)));
-        *aa = aa.clone() + array![[damping, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+            .get(&qubit)
+            .cloned()
+            .unwrap_or_else(|| Array2::zeros((3, 3)))
+            + array![[damping, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+        check_decoherence_rates(qubit, &rates)?;
+        self.decoherence_rates.insert(qubit, rates);
         Ok(())
     }
 
@@ -238,13 +325,634 @@ impl IonQAria1Device {
                 ),
             });
         }
-        let aa = self
+        let rates = self
             .decoherence_rates
-            .entry(qubit)
-            .or_insert_with(|| Array2::zeros((3, 3)));
-        *aa = aa.clone() + array![[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, dephasing]];
+            .get(&qubit)
+            .cloned()
+            .unwrap_or_else(|| Array2::zeros((3, 3)))
+            + array![[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, dephasing]];
+        check_decoherence_rates(qubit, &rates)?;
+        self.decoherence_rates.insert(qubit, rates);
+        Ok(())
+    }
+
+    /// Sets the decoherence rates of a qubit, replacing any previously set rates.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the rates are set.
+    /// * `rates` - The 3x3 matrix of decoherence rates of the Lindblad equation.
+    pub fn set_qubit_decoherence_rates(
+        &mut self,
+        qubit: usize,
+        rates: Array2<f64>,
+    ) -> Result<(), RoqoqoError> {
+        if qubit >= self.number_qubits {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Qubit {} out of range for device of size {}",
+                    qubit, self.number_qubits
+                ),
+            });
+        }
+        check_decoherence_rates(qubit, &rates)?;
+        self.decoherence_rates.insert(qubit, rates);
+        Ok(())
+    }
+
+    /// Sets the crosstalk strength between two qubits.
+    ///
+    /// The crosstalk is stored in a matrix of size number_qubits x number_qubits,
+    /// that is created with all strengths set to zero on the first call.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_i` - The qubit causing the crosstalk.
+    /// * `qubit_j` - The qubit affected by the crosstalk.
+    /// * `strength` - The crosstalk strength.
+    pub fn set_crosstalk(
+        &mut self,
+        qubit_i: usize,
+        qubit_j: usize,
+        strength: f64,
+    ) -> Result<(), RoqoqoError> {
+        for qubit in [qubit_i, qubit_j] {
+            if qubit >= self.number_qubits {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Qubit {} out of range for device of size {}",
+                        qubit, self.number_qubits
+                    ),
+                });
+            }
+        }
+        if qubit_i == qubit_j {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Cannot set crosstalk of qubit {} with itself", qubit_i),
+            });
+        }
+        let number_qubits = self.number_qubits;
+        let crosstalk = self
+            .crosstalk
+            .get_or_insert_with(|| Array2::zeros((number_qubits, number_qubits)));
+        crosstalk[[qubit_i, qubit_j]] = strength;
+        Ok(())
+    }
+
+    /// Returns the crosstalk strength between two qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_i` - The qubit causing the crosstalk.
+    /// * `qubit_j` - The qubit affected by the crosstalk.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The crosstalk strength.
+    /// * `None` - No crosstalk is set for the device or a qubit is not part of the device.
+    pub fn crosstalk(&self, qubit_i: usize, qubit_j: usize) -> Option<f64> {
+        self.crosstalk
+            .as_ref()
+            .and_then(|crosstalk| crosstalk.get([qubit_i, qubit_j]).copied())
+    }
+
+    /// Returns the crosstalk matrix of the device.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<Array2<f64>>` - The number_qubits x number_qubits matrix of crosstalk strengths.
+    /// * `None` - No crosstalk is set for the device.
+    pub fn crosstalk_matrix(&self) -> Option<Array2<f64>> {
+        self.crosstalk.clone()
+    }
+
+    /// Sets the error rate of the two-qubit gates acting on an edge.
+    ///
+    /// The error rate is stored per edge and applies to both directions of the edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    /// * `error_rate` - The depolarizing error probability of a two-qubit gate on the edge.
+    pub fn set_two_qubit_error_rate(
+        &mut self,
+        control: usize,
+        target: usize,
+        error_rate: f64,
+    ) -> Result<(), RoqoqoError> {
+        if !self
+            .two_qubit_edges()
+            .iter()
+            .any(|&(a, b)| (a, b) == (control, target) || (a, b) == (target, control))
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Qubits {} and {} are not connected in the device",
+                    control, target
+                ),
+            });
+        }
+        if !(0.0..=1.0).contains(&error_rate) {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Two-qubit error rate {} is not a probability between 0 and 1",
+                    error_rate
+                ),
+            });
+        }
+        self.two_qubit_error_rates
+            .insert((control.min(target), control.max(target)), error_rate);
+        Ok(())
+    }
+
+    /// Returns the error rate of the two-qubit gates acting on an edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The depolarizing error probability.
+    /// * `None` - No error rate is set for the edge.
+    pub fn two_qubit_error_rate(&self, control: usize, target: usize) -> Option<f64> {
+        self.two_qubit_error_rates
+            .get(&(control.min(target), control.max(target)))
+            .copied()
+    }
+
+    /// Sets the readout assignment error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the readout error is set.
+    /// * `prob_detect_0_as_1` - The probability to measure 1 when the qubit is in state 0.
+    /// * `prob_detect_1_as_0` - The probability to measure 0 when the qubit is in state 1.
+    pub fn set_readout_error(
+        &mut self,
+        qubit: usize,
+        prob_detect_0_as_1: f64,
+        prob_detect_1_as_0: f64,
+    ) -> Result<(), RoqoqoError> {
+        if qubit >= self.number_qubits {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Qubit {} larger than number qubits {}",
+                    qubit, self.number_qubits
+                ),
+            });
+        }
+        for probability in [prob_detect_0_as_1, prob_detect_1_as_0] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Readout error {} is not a probability between 0 and 1",
+                        probability
+                    ),
+                });
+            }
+        }
+        self.readout_errors
+            .insert(qubit, (prob_detect_0_as_1, prob_detect_1_as_0));
+        Ok(())
+    }
+
+    /// Returns the readout assignment error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the readout error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<(f64, f64)>` - The probabilities to detect 0 as 1 and to detect 1 as 0.
+    /// * `None` - No readout error is set for the qubit.
+    pub fn readout_error(&self, qubit: usize) -> Option<(f64, f64)> {
+        self.readout_errors.get(&qubit).copied()
+    }
+
+    /// Sets the state preparation error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the preparation error is set.
+    /// * `error` - The probability to prepare the qubit in state 1 instead of state 0.
+    pub fn set_preparation_error(&mut self, qubit: usize, error: f64) -> Result<(), RoqoqoError> {
+        if qubit >= self.number_qubits {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Qubit {} larger than number qubits {}",
+                    qubit, self.number_qubits
+                ),
+            });
+        }
+        if !(0.0..=1.0).contains(&error) {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Preparation error {} is not a probability between 0 and 1",
+                    error
+                ),
+            });
+        }
+        self.preparation_errors.insert(qubit, error);
+        Ok(())
+    }
+
+    /// Returns the state preparation error of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the preparation error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The probability to prepare the qubit in state 1 instead of state 0.
+    /// * `None` - No preparation error is set for the qubit.
+    pub fn preparation_error(&self, qubit: usize) -> Option<f64> {
+        self.preparation_errors.get(&qubit).copied()
+    }
+
+    /// Adds the same qubit damping to the noise rates of all qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `damping` - The damping rate added to every qubit.
+    pub fn add_damping_all(&mut self, damping: f64) -> Result<(), RoqoqoError> {
+        for qubit in 0..self.number_qubits {
+            self.add_damping(qubit, damping)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the same qubit dephasing to the noise rates of all qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `dephasing` - The dephasing rate added to every qubit.
+    pub fn add_dephasing_all(&mut self, dephasing: f64) -> Result<(), RoqoqoError> {
+        for qubit in 0..self.number_qubits {
+            self.add_dephasing(qubit, dephasing)?;
+        }
+        Ok(())
+    }
+
+    /// Adds qubit damping to the noise rates of several qubits.
+    ///
+    /// No rates are added if any of the qubits is not part of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `dampings` - The damping rates added to the qubits, keyed by qubit.
+    pub fn add_damping_rates(&mut self, dampings: &HashMap<usize, f64>) -> Result<(), RoqoqoError> {
+        self.check_qubits(dampings.keys())?;
+        for (qubit, damping) in dampings {
+            self.add_damping(*qubit, *damping)?;
+        }
+        Ok(())
+    }
+
+    /// Adds qubit dephasing to the noise rates of several qubits.
+    ///
+    /// No rates are added if any of the qubits is not part of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `dephasings` - The dephasing rates added to the qubits, keyed by qubit.
+    pub fn add_dephasing_rates(
+        &mut self,
+        dephasings: &HashMap<usize, f64>,
+    ) -> Result<(), RoqoqoError> {
+        self.check_qubits(dephasings.keys())?;
+        for (qubit, dephasing) in dephasings {
+            self.add_dephasing(*qubit, *dephasing)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that all qubits are part of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The qubits to check.
+    fn check_qubits<'a>(
+        &self,
+        qubits: impl IntoIterator<Item = &'a usize>,
+    ) -> Result<(), RoqoqoError> {
+        for qubit in qubits {
+            if *qubit >= self.number_qubits {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Qubit {} out of range for device of size {}",
+                        qubit, self.number_qubits
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the decoherence rates of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the decoherence rates are removed.
+    pub fn clear_decoherence(&mut self, qubit: usize) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        self.decoherence_rates.remove(&qubit);
+        Ok(())
+    }
+
+    /// Removes all noise from the device, keeping the gate times.
+    pub fn clear_all_noise(&mut self) {
+        self.decoherence_rates.clear();
+        self.crosstalk = None;
+        self.two_qubit_error_rates.clear();
+        self.readout_errors.clear();
+        self.preparation_errors.clear();
+        self.leakage_rates.clear();
+        self.thermal_populations.clear();
+    }
+
+    /// Sets the leakage rate of a qubit.
+    ///
+    /// The leakage rate is the rate with which population escapes the computational subspace of the qubit.
+    /// It is informational only: the noise models of roqoqo have no leakage channel, so the rate is
+    /// not part of the exported noise models, [crate::decorate_circuit_with_noise] or simulations.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the leakage rate is set.
+    /// * `leakage_rate` - The leakage rate.
+    pub fn set_leakage_rate(&mut self, qubit: usize, leakage_rate: f64) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        if leakage_rate < 0.0 {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Leakage rate {} is negative", leakage_rate),
+            });
+        }
+        self.leakage_rates.insert(qubit, leakage_rate);
+        Ok(())
+    }
+
+    /// Returns the leakage rate of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the leakage rate is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The leakage rate.
+    /// * `None` - No leakage rate is set for the qubit.
+    pub fn leakage_rate(&self, qubit: usize) -> Option<f64> {
+        self.leakage_rates.get(&qubit).copied()
+    }
+
+    /// Sets the thermal excited-state population of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the thermal population is set.
+    /// * `population` - The probability to find the qubit in state 1 after initialisation.
+    pub fn set_thermal_population(
+        &mut self,
+        qubit: usize,
+        population: f64,
+    ) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        if !(0.0..=1.0).contains(&population) {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Thermal population {} is not a probability between 0 and 1",
+                    population
+                ),
+            });
+        }
+        self.thermal_populations.insert(qubit, population);
+        Ok(())
+    }
+
+    /// Returns the thermal excited-state population of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the thermal population is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The probability to find the qubit in state 1 after initialisation.
+    /// * `None` - No thermal population is set for the qubit.
+    pub fn thermal_population(&self, qubit: usize) -> Option<f64> {
+        self.thermal_populations.get(&qubit).copied()
+    }
+
+    /// Sets the damping and dephasing rates of a qubit from its T1 and T2 times.
+    ///
+    /// The damping rate is 1/T1 and the dephasing rate is (1/T2 - 1/(2 T1)) / 2, following the
+    /// convention of qoqo where a dephasing rate decays the coherences with twice the rate.
+    /// The rates are in the inverse unit of the times, which should match the unit of the gate times.
+    /// Any other decoherence rate of the qubit is kept and the resulting rates are validated as in
+    /// [IonQAria1Device::set_qubit_decoherence_rates].
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the rates are set.
+    /// * `t1` - The energy relaxation time T1.
+    /// * `t2` - The coherence time T2, at most 2 T1.
+    pub fn set_coherence_times(
+        &mut self,
+        qubit: usize,
+        t1: f64,
+        t2: f64,
+    ) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        let rates = rates_from_coherence_times(self.decoherence_rates.get(&qubit), t1, t2)?;
+        self.set_qubit_decoherence_rates(qubit, rates)
+    }
+
+    /// Marks a qubit as unusable.
+    ///
+    /// The qubit is removed from the edges of the device and no gate times are returned for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit that is disabled.
+    pub fn disable_qubit(&mut self, qubit: usize) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        self.disabled_qubits.insert(qubit);
+        Ok(())
+    }
+
+    /// Marks an edge as unusable.
+    ///
+    /// The edge is removed from the edges of the device and no two-qubit gate times are returned for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    pub fn disable_edge(&mut self, control: usize, target: usize) -> Result<(), RoqoqoError> {
+        self.check_qubits([control, target].iter())?;
+        let edge = (control.min(target), control.max(target));
+        if !self
+            .native_two_qubit_edges()
+            .iter()
+            .any(|&(a, b)| (a.min(b), a.max(b)) == edge)
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Qubits {} and {} are not connected in the device",
+                    control, target
+                ),
+            });
+        }
+        self.disabled_edges.insert(edge);
+        Ok(())
+    }
+
+    /// Adds an edge to the device and sets the gate time of a two-qubit gate on it.
+    ///
+    /// The gate time is set for both directions of the edge. Adding an edge that already exists
+    /// re-enables it when it was disabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `gate` - hqslang name of the two-qubit-gate.
+    /// * `control` - The first qubit of the edge.
+    /// * `target` - The second qubit of the edge.
+    /// * `gate_time` - gate time for the given gate.
+    pub fn add_edge(
+        &mut self,
+        gate: &str,
+        control: usize,
+        target: usize,
+        gate_time: f64,
+    ) -> Result<(), RoqoqoError> {
+        self.check_qubits([control, target].iter())?;
+        if control == target {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Cannot add an edge from qubit {} to itself", control),
+            });
+        }
+        if !self.two_qubit_gate_names().iter().any(|name| name == gate) {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Gate {} is not a two-qubit gate of the device", gate),
+            });
+        }
+        if let Some(qubit) = [control, target]
+            .into_iter()
+            .find(|qubit| self.disabled_qubits.contains(qubit))
+        {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Qubit {} is disabled", qubit),
+            });
+        }
+        let edge = (control.min(target), control.max(target));
+        if !self
+            .native_two_qubit_edges()
+            .iter()
+            .any(|&(a, b)| (a.min(b), a.max(b)) == edge)
+        {
+            self.additional_edges.push(edge);
+        }
+        self.disabled_edges.remove(&edge);
+        self.set_two_qubit_gate_time(gate, control, target, gate_time)?;
+        self.set_two_qubit_gate_time(gate, target, control, gate_time)
+    }
+
+    /// Returns the qubits marked as unusable.
+    ///
+    /// # Returns
+    ///
+    /// The sorted list of disabled qubits.
+    pub fn disabled_qubits(&self) -> Vec<usize> {
+        let mut qubits: Vec<usize> = self.disabled_qubits.iter().copied().collect();
+        qubits.sort_unstable();
+        qubits
+    }
+
+    /// Returns the directed edges on which the native two-qubit gates are calibrated.
+    ///
+    /// The native two-qubit gates of the device are symmetric, so every edge is reported in both directions.
+    ///
+    /// # Returns
+    ///
+    /// The `(control, target)` pairs of the calibrated gates.
+    pub fn two_qubit_edges_directed(&self) -> Vec<(usize, usize)> {
+        self.two_qubit_edges()
+            .into_iter()
+            .flat_map(|(control, target)| [(control, target), (target, control)])
+            .collect()
+    }
+
+    /// Returns the layout coordinates of the qubits.
+    ///
+    /// The ions of the device form a linear chain, qubit `i` is placed at `(i, 0)`.
+    ///
+    /// # Returns
+    ///
+    /// The `(x, y)` coordinates of every qubit.
+    pub fn qubit_positions(&self) -> Vec<(f64, f64)> {
+        (0..self.number_qubits)
+            .map(|qubit| (qubit as f64, 0.0))
+            .collect()
+    }
+
+    /// Returns whether an edge and both of its qubits are usable.
+    fn is_edge_enabled(&self, control: usize, target: usize) -> bool {
+        !self.disabled_qubits.contains(&control)
+            && !self.disabled_qubits.contains(&target)
+            && !self
+                .disabled_edges
+                .contains(&(control.min(target), control.max(target)))
+    }
+
+    /// Returns the edges of the device including the disabled ones.
+    fn native_two_qubit_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = (0..self.number_qubits)
+            .combinations(2)
+            .map(|x| (x[0], x[1]))
+            .collect();
+        edges.extend(self.additional_edges.iter().copied());
+        edges
+    }
+
+    /// Sets the duration of the measurement of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the measurement time is set.
+    /// * `measurement_time` - The duration of the measurement.
+    pub fn set_measurement_time(
+        &mut self,
+        qubit: usize,
+        measurement_time: f64,
+    ) -> Result<(), RoqoqoError> {
+        self.check_qubits([qubit].iter())?;
+        if measurement_time < 0.0 {
+            return Err(RoqoqoError::GenericError {
+                msg: format!("Measurement time {} is negative", measurement_time),
+            });
+        }
+        self.measurement_times.insert(qubit, measurement_time);
         Ok(())
     }
+
+    /// Returns the duration of the measurement of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the measurement time is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Some<f64>` - The duration of the measurement.
+    /// * `None` - No measurement time is set for the qubit.
+    pub fn measurement_time(&self, qubit: usize) -> Option<f64> {
+        self.measurement_times.get(&qubit).copied()
+    }
 }
 
 /// Implements QoqoDevice trait for IonQAria1Device.
@@ -267,8 +975,8 @@ impl QoqoDevice for IonQAria1Device {
     #[allow(unused_variables)]
     fn single_qubit_gate_time(&self, hqslang: &str, qubit: &usize) -> Option<f64> {
         match self.single_qubit_gates.get(hqslang) {
-            Some(x) => x.get(qubit).copied(),
-            None => None,
+            Some(x) if !self.disabled_qubits.contains(qubit) => x.get(qubit).copied(),
+            _ => None,
         }
     }
 
@@ -298,8 +1006,10 @@ impl QoqoDevice for IonQAria1Device {
     #[allow(unused_variables)]
     fn two_qubit_gate_time(&self, hqslang: &str, control: &usize, target: &usize) -> Option<f64> {
         match self.two_qubit_gates.get(hqslang) {
-            Some(x) => x.get(&(*control, *target)).copied(),
-            None => None,
+            Some(x) if self.is_edge_enabled(*control, *target) => {
+                x.get(&(*control, *target)).copied()
+            }
+            _ => None,
         }
     }
 
@@ -405,10 +1115,18 @@ impl QoqoDevice for IonQAria1Device {
     /// * `Vec<Vec<usize>>` - A list of the longest chains given by vectors of qubits in the chain.
     ///
     fn longest_chains(&self) -> Vec<Vec<usize>> {
-        vec![vec![
-            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
-            24,
-        ]]
+        if self.disabled_edges.is_empty() {
+            // All usable qubits are connected with each other
+            vec![(0..self.number_qubits)
+                .filter(|qubit| !self.disabled_qubits.contains(qubit))
+                .collect()]
+        } else {
+            vec![longest_chain(
+                self.number_qubits,
+                &self.two_qubit_edges(),
+                false,
+            )]
+        }
     }
 
     /// Return a list of longest closed linear chains through the device.
@@ -424,10 +1142,18 @@ impl QoqoDevice for IonQAria1Device {
     /// * `Vec<Vec<usize>>` - A list of the longest chains given by vectors of qubits in the chain.
     ///
     fn longest_closed_chains(&self) -> Vec<Vec<usize>> {
-        vec![vec![
-            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
-            24,
-        ]]
+        if self.disabled_edges.is_empty() {
+            // All usable qubits are connected with each other
+            vec![(0..self.number_qubits)
+                .filter(|qubit| !self.disabled_qubits.contains(qubit))
+                .collect()]
+        } else {
+            vec![longest_chain(
+                self.number_qubits,
+                &self.two_qubit_edges(),
+                true,
+            )]
+        }
     }
 
     /// Returns the list of pairs of qubits linked with a native two-qubit-gate in the device.
@@ -448,10 +1174,9 @@ impl QoqoDevice for IonQAria1Device {
     /// A list (Vec) of pairs of qubits linked with a native two-qubit-gate in the device.
     ///
     fn two_qubit_edges(&self) -> Vec<(usize, usize)> {
-        let edges: Vec<(usize, usize)> = (0..self.number_qubits)
-            .combinations(2)
-            .map(|x| (x[0], x[1]))
-            .collect();
-        edges
+        self.native_two_qubit_edges()
+            .into_iter()
+            .filter(|(control, target)| self.is_edge_enabled(*control, *target))
+            .collect()
     }
 }
@@ -16,7 +16,36 @@
 //!
 //! Collection of AWS's Braket devices interfaces implementing roqoqo's Device trait.
 
+pub mod analysis;
+pub use analysis::{
+    gate_report, recommend_device, CircuitDuration, CircuitFidelity, DevicePrices, DeviceScore,
+    GateReport, PriceTable, ValidationError,
+};
+
+pub mod braket_ir;
+
+pub mod calibration;
+
+pub mod cutting;
+
 pub mod devices;
+
+pub mod noise_models;
 pub use devices::{
     AWSDevice, IonQAria1Device, IonQHarmonyDevice, OQCLucyDevice, RigettiAspenM3Device,
 };
+pub use noise_models::decorate_circuit_with_noise;
+
+pub mod pulse;
+pub use pulse::PulseInfo;
+
+pub mod routing;
+pub use routing::SwapOverhead;
+
+pub mod topology;
+pub use topology::{Connectivity, EdgeWeight, Topology};
+
+pub mod transpile;
+
+pub mod verification;
+pub use verification::{check_equivalence, EquivalenceCheck};
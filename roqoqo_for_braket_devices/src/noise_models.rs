@@ -0,0 +1,362 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Noise models
+//!
+//! Provides the conversion of the noise stored in AWS devices to qoqo noise models.
+
+use ndarray::Array2;
+use roqoqo::devices::{GenericDevice, QoqoDevice};
+use roqoqo::noise_models::{
+    ContinuousDecoherenceModel, DecoherenceOnGateModel, ImperfectReadoutModel, NoiseModel,
+};
+use roqoqo::operations::{Operate, PragmaDamping, PragmaDephasing, PragmaGeneralNoise};
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::{gate_qubits, gate_time};
+use crate::devices::check_decoherence_rates;
+use crate::AWSDevice;
+
+mod braket_noise_model;
+
+impl AWSDevice {
+    /// Validates the noise of the device.
+    ///
+    /// Checks that the decoherence rate matrix of every qubit is symmetric and positive semi-definite,
+    /// which is useful after loading decoherence rates from external calibration data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The noise of the device is physical.
+    /// * `Err(RoqoqoError)` - A decoherence rate matrix is unphysical.
+    pub fn validate_noise(&self) -> Result<(), RoqoqoError> {
+        for qubit in 0..self.number_qubits() {
+            if let Some(rates) = self.qubit_decoherence_rates(&qubit) {
+                check_decoherence_rates(qubit, &rates)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Converts the decoherence rates of the device to a qoqo ContinuousDecoherenceModel.
+    ///
+    /// The diagonal entries of the decoherence rate matrix of each qubit are converted to
+    /// damping, excitation and dephasing rates respectively.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ContinuousDecoherenceModel)` - The noise model of the device.
+    /// * `Err(RoqoqoError)` - A decoherence rate matrix has off-diagonal entries.
+    pub fn to_continuous_decoherence_model(
+        &self,
+    ) -> Result<ContinuousDecoherenceModel, RoqoqoError> {
+        let mut model = ContinuousDecoherenceModel::new();
+        for qubit in 0..self.number_qubits() {
+            model = self.add_qubit_decoherence(model, qubit, 1.0)?;
+        }
+        Ok(model)
+    }
+
+    /// Converts the gate times and decoherence rates of the device to a qoqo DecoherenceOnGateModel.
+    ///
+    /// Every native gate is assigned the decoherence its qubits undergo during the gate time,
+    /// i.e. the decoherence rates of the qubits multiplied by the gate time.
+    /// Gates acting only on qubits without decoherence rates are not added to the model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DecoherenceOnGateModel)` - The noise model of the device.
+    /// * `Err(RoqoqoError)` - A decoherence rate matrix has off-diagonal entries.
+    pub fn to_error_on_gate_model(&self) -> Result<DecoherenceOnGateModel, RoqoqoError> {
+        let mut model = DecoherenceOnGateModel::new();
+        for gate in self.single_qubit_gate_names() {
+            for qubit in 0..self.number_qubits() {
+                if let Some(time) = self.single_qubit_gate_time(&gate, &qubit) {
+                    let noise =
+                        self.add_qubit_decoherence(ContinuousDecoherenceModel::new(), qubit, time)?;
+                    if noise != ContinuousDecoherenceModel::new() {
+                        model =
+                            model.set_single_qubit_gate_error(&gate, qubit, noise.lindblad_noise);
+                    }
+                }
+            }
+        }
+        for gate in self.two_qubit_gate_names() {
+            for (first, second) in self.two_qubit_edges() {
+                for (control, target) in [(first, second), (second, first)] {
+                    if let Some(time) = self.two_qubit_gate_time(&gate, &control, &target) {
+                        let mut noise = ContinuousDecoherenceModel::new();
+                        noise = self.add_qubit_decoherence(noise, control, time)?;
+                        noise = self.add_qubit_decoherence(noise, target, time)?;
+                        if noise != ContinuousDecoherenceModel::new() {
+                            model = model.set_two_qubit_gate_error(
+                                &gate,
+                                control,
+                                target,
+                                noise.lindblad_noise,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Ok(model)
+    }
+
+    /// Converts the state preparation and measurement (SPAM) errors of the device to a qoqo ImperfectReadoutModel.
+    ///
+    /// Preparation errors and thermal populations leave a qubit in state 1 instead of 0 before the
+    /// circuit, which for the purpose of the readout model is treated like an additional error of
+    /// detecting 0 as 1. All error probabilities are combined as independent events.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImperfectReadoutModel)` - The readout noise model of the device.
+    /// * `Err(RoqoqoError)` - The error probabilities could not be set in the model.
+    pub fn to_imperfect_readout_model(&self) -> Result<ImperfectReadoutModel, RoqoqoError> {
+        let mut model = ImperfectReadoutModel::new();
+        for qubit in 0..self.number_qubits() {
+            let (readout_0_as_1, readout_1_as_0) = self.readout_error(qubit).unwrap_or((0.0, 0.0));
+            let preparation = self.initialization_error(qubit);
+            if readout_0_as_1 == 0.0 && readout_1_as_0 == 0.0 && preparation == 0.0 {
+                continue;
+            }
+            let prob_detect_0_as_1 =
+                preparation + readout_0_as_1 - 2.0 * preparation * readout_0_as_1;
+            model = model.set_error_probabilites(qubit, prob_detect_0_as_1, readout_1_as_0)?;
+        }
+        Ok(model)
+    }
+
+    /// Converts the device to a qoqo GenericDevice together with the noise the GenericDevice cannot hold.
+    ///
+    /// The GenericDevice only stores gate times and decoherence rates. The readout and
+    /// preparation errors are returned as an ImperfectReadoutModel and the two-qubit error rates
+    /// as a DecoherenceOnGateModel, where every native two-qubit gate on an edge is followed by
+    /// depolarising noise of the strength of the error rate of the edge on both qubits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((GenericDevice, ImperfectReadoutModel, DecoherenceOnGateModel))` - The converted device and noise models.
+    /// * `Err(RoqoqoError)` - The device could not be converted.
+    pub fn to_generic_device_with_noise(
+        &self,
+    ) -> Result<(GenericDevice, ImperfectReadoutModel, DecoherenceOnGateModel), RoqoqoError> {
+        Ok((
+            self.to_generic_device()?,
+            self.to_imperfect_readout_model()?,
+            self.to_two_qubit_error_model(),
+        ))
+    }
+
+    /// Converts the two-qubit error rates of the device to a qoqo DecoherenceOnGateModel.
+    ///
+    /// Every native two-qubit gate on an edge with an error rate is followed by depolarising
+    /// noise of the strength of the error rate on both qubits.
+    ///
+    /// # Returns
+    ///
+    /// The noise model of the two-qubit gate errors.
+    pub fn to_two_qubit_error_model(&self) -> DecoherenceOnGateModel {
+        let mut gate_model = DecoherenceOnGateModel::new();
+        for gate in self.two_qubit_gate_names() {
+            for (first, second) in self.two_qubit_edges() {
+                let error_rate = match self.two_qubit_error_rate(first, second) {
+                    Some(error_rate) if error_rate != 0.0 => error_rate,
+                    _ => continue,
+                };
+                for (control, target) in [(first, second), (second, first)] {
+                    if self.two_qubit_gate_time(&gate, &control, &target).is_some() {
+                        let noise = ContinuousDecoherenceModel::new()
+                            .add_depolarising_rate(&[control, target], error_rate);
+                        gate_model = gate_model.set_two_qubit_gate_error(
+                            &gate,
+                            control,
+                            target,
+                            noise.lindblad_noise,
+                        );
+                    }
+                }
+            }
+        }
+        gate_model
+    }
+
+    /// Converts all noise of the device to the qoqo noise models used by noisy simulators like qoqo-quest.
+    ///
+    /// The returned models are the decoherence during the gates ([AWSDevice::to_error_on_gate_model]),
+    /// the two-qubit gate errors ([AWSDevice::to_two_qubit_error_model]) and the state preparation and
+    /// measurement errors ([AWSDevice::to_imperfect_readout_model]). Models without any noise are left out.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<NoiseModel>)` - The noise models of the device.
+    /// * `Err(RoqoqoError)` - The noise of the device could not be converted.
+    pub fn to_noise_models(&self) -> Result<Vec<NoiseModel>, RoqoqoError> {
+        let mut noise_models: Vec<NoiseModel> = Vec::new();
+        let gate_model = self.to_error_on_gate_model()?;
+        if gate_model != DecoherenceOnGateModel::new() {
+            noise_models.push(gate_model.into());
+        }
+        let two_qubit_error_model = self.to_two_qubit_error_model();
+        if two_qubit_error_model != DecoherenceOnGateModel::new() {
+            noise_models.push(two_qubit_error_model.into());
+        }
+        let readout_model = self.to_imperfect_readout_model()?;
+        if readout_model != ImperfectReadoutModel::new() {
+            noise_models.push(readout_model.into());
+        }
+        Ok(noise_models)
+    }
+
+    /// Returns the decoherence the qubits of a native gate undergo during the gate.
+    ///
+    /// The decoherence is the rate matrix of each qubit multiplied by the gate time, in the units
+    /// of the stored rates and gate times.
+    ///
+    /// # Arguments
+    ///
+    /// * `gate` - The hqslang name of the gate.
+    /// * `qubits` - The qubits the gate acts on, `[qubit]` or `[control, target]` for single and two qubit gates.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(usize, Array2<f64>)>)` - The integrated decoherence rate matrix of each qubit with decoherence.
+    /// * `Err(RoqoqoError)` - The gate is not available on the device for the given qubits.
+    pub fn noise_during_gate(
+        &self,
+        gate: &str,
+        qubits: &[usize],
+    ) -> Result<Vec<(usize, Array2<f64>)>, RoqoqoError> {
+        let time = gate_time(self, gate, qubits).ok_or_else(|| RoqoqoError::GenericError {
+            msg: format!("Gate {} is not available on qubits {:?}", gate, qubits),
+        })?;
+        Ok(qubits
+            .iter()
+            .filter_map(|qubit| {
+                self.qubit_decoherence_rates(qubit)
+                    .map(|rates| (*qubit, rates * time))
+            })
+            .collect())
+    }
+
+    /// Returns the probability that a qubit is in state 1 instead of 0 after initialisation.
+    ///
+    /// The preparation error and the thermal population are combined as independent bit flips.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the error is returned.
+    ///
+    /// # Returns
+    ///
+    /// The initialisation error probability.
+    fn initialization_error(&self, qubit: usize) -> f64 {
+        let preparation = self.preparation_error(qubit).unwrap_or(0.0);
+        let thermal = self.thermal_population(qubit).unwrap_or(0.0);
+        preparation + thermal - 2.0 * preparation * thermal
+    }
+
+    /// Adds the decoherence rates of a qubit, scaled by a factor, to a ContinuousDecoherenceModel.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The model the rates are added to.
+    /// * `qubit` - The qubit whose rates are added.
+    /// * `scale` - The factor the rates are multiplied with.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ContinuousDecoherenceModel)` - The model with the added rates.
+    /// * `Err(RoqoqoError)` - The decoherence rate matrix has off-diagonal entries.
+    fn add_qubit_decoherence(
+        &self,
+        mut model: ContinuousDecoherenceModel,
+        qubit: usize,
+        scale: f64,
+    ) -> Result<ContinuousDecoherenceModel, RoqoqoError> {
+        let rates = match self.qubit_decoherence_rates(&qubit) {
+            Some(rates) => rates,
+            None => return Ok(model),
+        };
+        for ((row, column), rate) in rates.indexed_iter() {
+            if row != column && *rate != 0.0 {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Off-diagonal decoherence rate {} of qubit {} cannot be converted to a noise model",
+                        rate, qubit
+                    ),
+                });
+            }
+        }
+        if rates[[0, 0]] != 0.0 {
+            model = model.add_damping_rate(&[qubit], rates[[0, 0]] * scale);
+        }
+        if rates[[1, 1]] != 0.0 {
+            model = model.add_excitation_rate(&[qubit], rates[[1, 1]] * scale);
+        }
+        if rates[[2, 2]] != 0.0 {
+            model = model.add_dephasing_rate(&[qubit], rates[[2, 2]] * scale);
+        }
+        Ok(model)
+    }
+}
+
+/// Adds the decoherence of the device to a circuit as noise pragmas.
+///
+/// After every gate that is available on the device, the decoherence its qubits undergo during
+/// the gate time is inserted. Qubits with only damping and dephasing rates get a PragmaDamping and a
+/// PragmaDephasing, qubits with other decoherence rates a PragmaGeneralNoise with their full rate matrix.
+/// Gates that are not available on the device are kept without noise.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to decorate.
+/// * `device` - The device whose decoherence is added.
+///
+/// # Returns
+///
+/// The circuit with the added noise pragmas.
+pub fn decorate_circuit_with_noise(circuit: &Circuit, device: &AWSDevice) -> Circuit {
+    let mut decorated = Circuit::new();
+    for operation in circuit.iter() {
+        decorated += operation.clone();
+        let qubits = match gate_qubits(operation) {
+            Some(qubits) => qubits,
+            None => continue,
+        };
+        let time = match gate_time(device, operation.hqslang(), &qubits) {
+            Some(time) => time,
+            None => continue,
+        };
+        for qubit in qubits {
+            let rates = match device.qubit_decoherence_rates(&qubit) {
+                Some(rates) => rates,
+                None => continue,
+            };
+            let only_damping_and_dephasing = rates.indexed_iter().all(|((row, column), rate)| {
+                *rate == 0.0 || (row, column) == (0, 0) || (row, column) == (2, 2)
+            });
+            if only_damping_and_dephasing {
+                if rates[[0, 0]] != 0.0 {
+                    decorated += PragmaDamping::new(qubit, time.into(), rates[[0, 0]].into());
+                }
+                if rates[[2, 2]] != 0.0 {
+                    decorated += PragmaDephasing::new(qubit, time.into(), rates[[2, 2]].into());
+                }
+            } else {
+                decorated += PragmaGeneralNoise::new(qubit, time.into(), rates);
+            }
+        }
+    }
+    decorated
+}
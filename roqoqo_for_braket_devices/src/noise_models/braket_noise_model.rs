@@ -0,0 +1,202 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::RoqoqoError;
+use serde_json::{json, Value};
+
+use crate::AWSDevice;
+
+/// Returns the name of the Braket gate corresponding to a qoqo gate.
+///
+/// # Arguments
+///
+/// * `hqslang` - The hqslang name of the qoqo gate.
+///
+/// # Returns
+///
+/// * `Some(&str)` - The name of the gate class in the Braket SDK.
+/// * `None` - The gate has no Braket counterpart.
+fn braket_gate_name(hqslang: &str) -> Option<&'static str> {
+    match hqslang {
+        "Hadamard" => Some("H"),
+        "PauliX" => Some("X"),
+        "PauliY" => Some("Y"),
+        "PauliZ" => Some("Z"),
+        "SqrtPauliX" => Some("V"),
+        "InvSqrtPauliX" => Some("Vi"),
+        "SGate" => Some("S"),
+        "TGate" => Some("T"),
+        "RotateX" => Some("Rx"),
+        "RotateY" => Some("Ry"),
+        "RotateZ" => Some("Rz"),
+        "PhaseShiftState1" => Some("PhaseShift"),
+        "GPi" => Some("GPi"),
+        "GPi2" => Some("GPi2"),
+        "CNOT" => Some("CNot"),
+        "ControlledPauliY" => Some("CY"),
+        "ControlledPauliZ" => Some("CZ"),
+        "ControlledPhaseShift" => Some("CPhaseShift"),
+        "SWAP" => Some("Swap"),
+        "ISwap" => Some("ISwap"),
+        "XY" => Some("XY"),
+        "EchoCrossResonance" => Some("ECR"),
+        "MolmerSorensenXX" => Some("MS"),
+        _ => None,
+    }
+}
+
+/// Creates the dictionary of a single-parameter Braket noise channel.
+fn noise_channel(
+    class: &str,
+    parameter: &str,
+    value: f64,
+    symbol: &str,
+    qubit_count: usize,
+) -> Value {
+    json!({
+        "__class__": class,
+        parameter: value,
+        "qubit_count": qubit_count,
+        "ascii_symbols": vec![format!("{}({})", symbol, value); qubit_count],
+    })
+}
+
+/// Returns the probability of a decay with a given rate happening within a given time.
+fn decay_probability(rate: f64, time: f64) -> f64 {
+    1.0 - (-rate * time).exp()
+}
+
+/// Returns the gamma of the Braket PhaseDamping channel for a qoqo dephasing rate.
+///
+/// The dephasing rate of qoqo decays the coherences as `exp(-2 * rate * t)`, while PhaseDamping
+/// scales them by `sqrt(1 - gamma)`, so `gamma = 1 - exp(-4 * rate * t)`.
+fn dephasing_probability(rate: f64, time: f64) -> f64 {
+    1.0 - (-4.0 * rate * time).exp()
+}
+
+impl AWSDevice {
+    /// Converts the noise of the device to a Braket SDK NoiseModel for the DM1 simulator.
+    ///
+    /// The result is the dictionary representation of the NoiseModel, which can be loaded in python
+    /// with `braket.circuits.noise_model.NoiseModel.from_dict`. It contains
+    ///
+    /// * amplitude and phase damping after every native single-qubit gate, derived from the damping
+    ///   and dephasing rates of the qubit and the gate time,
+    /// * two-qubit depolarizing noise after every native two-qubit gate on edges with a two-qubit error rate,
+    /// * bit-flip initialisation noise combining the preparation error and thermal population of each qubit,
+    /// * bit-flip readout noise with the mean of the two readout error probabilities of each qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The JSON representation of the Braket NoiseModel.
+    /// * `Err(RoqoqoError)` - A native gate of the device has no Braket counterpart.
+    pub fn to_braket_noise_model(&self) -> Result<String, RoqoqoError> {
+        let mut instructions: Vec<Value> = Vec::new();
+
+        for gate in self.single_qubit_gate_names() {
+            let braket_gate = braket_gate_name(&gate).ok_or_else(|| RoqoqoError::GenericError {
+                msg: format!("Gate {} has no Braket counterpart", gate),
+            })?;
+            for qubit in 0..self.number_qubits() {
+                let (time, rates) = match (
+                    self.single_qubit_gate_time(&gate, &qubit),
+                    self.qubit_decoherence_rates(&qubit),
+                ) {
+                    (Some(time), Some(rates)) => (time, rates),
+                    _ => continue,
+                };
+                let criteria = json!({
+                    "__class__": "GateCriteria",
+                    "gates": [braket_gate],
+                    "qubits": [qubit],
+                });
+                if rates[[0, 0]] != 0.0 {
+                    let gamma = decay_probability(rates[[0, 0]], time);
+                    instructions.push(json!({
+                        "noise": noise_channel("AmplitudeDamping", "gamma", gamma, "AD", 1),
+                        "criteria": criteria,
+                    }));
+                }
+                if rates[[2, 2]] != 0.0 {
+                    let gamma = dephasing_probability(rates[[2, 2]], time);
+                    instructions.push(json!({
+                        "noise": noise_channel("PhaseDamping", "gamma", gamma, "PD", 1),
+                        "criteria": criteria,
+                    }));
+                }
+            }
+        }
+
+        for gate in self.two_qubit_gate_names() {
+            let braket_gate = braket_gate_name(&gate).ok_or_else(|| RoqoqoError::GenericError {
+                msg: format!("Gate {} has no Braket counterpart", gate),
+            })?;
+            for (first, second) in self.two_qubit_edges() {
+                let probability = match self.two_qubit_error_rate(first, second) {
+                    Some(probability) if probability != 0.0 => probability,
+                    _ => continue,
+                };
+                let qubits: Vec<[usize; 2]> = [(first, second), (second, first)]
+                    .into_iter()
+                    .filter(|(control, target)| {
+                        self.two_qubit_gate_time(&gate, control, target).is_some()
+                    })
+                    .map(|(control, target)| [control, target])
+                    .collect();
+                if qubits.is_empty() {
+                    continue;
+                }
+                instructions.push(json!({
+                    "noise": noise_channel("TwoQubitDepolarizing", "probability", probability, "DEPO", 2),
+                    "criteria": {
+                        "__class__": "GateCriteria",
+                        "gates": [braket_gate],
+                        "qubits": qubits,
+                    },
+                }));
+            }
+        }
+
+        for qubit in 0..self.number_qubits() {
+            let probability = self.initialization_error(qubit);
+            if probability != 0.0 {
+                instructions.push(json!({
+                    "noise": noise_channel("BitFlip", "probability", probability, "BF", 1),
+                    "criteria": {
+                        "__class__": "QubitInitializationCriteria",
+                        "qubits": [qubit],
+                    },
+                }));
+            }
+        }
+
+        for qubit in 0..self.number_qubits() {
+            if let Some((prob_detect_0_as_1, prob_detect_1_as_0)) = self.readout_error(qubit) {
+                let probability = (prob_detect_0_as_1 + prob_detect_1_as_0) / 2.0;
+                if probability == 0.0 {
+                    continue;
+                }
+                instructions.push(json!({
+                    "noise": noise_channel("BitFlip", "probability", probability, "BF", 1),
+                    "criteria": {
+                        "__class__": "ObservableCriteria",
+                        "observables": null,
+                        "qubits": [qubit],
+                    },
+                }));
+            }
+        }
+
+        Ok(json!({ "instructions": instructions }).to_string())
+    }
+}
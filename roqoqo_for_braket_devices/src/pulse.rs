@@ -0,0 +1,262 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pulse
+//!
+//! Provides the pulse capabilities (ports and frames) of the AWS devices supporting Braket
+//! pulse control.
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::RoqoqoError;
+use serde_json::Value;
+
+use crate::{AWSDevice, RigettiAspenM3Device};
+
+/// Time between two samples of a waveform on the ports of the Rigetti devices in seconds.
+const RIGETTI_SAMPLE_TIME: f64 = 1e-9;
+
+/// Two-qubit gates of the Rigetti devices that have their own frame on every edge.
+const RIGETTI_EDGE_FRAMES: [&str; 3] = ["cz", "cphase", "xy"];
+
+/// Direction of the signals on a port of a device.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum PortDirection {
+    /// Signals are sent to the device.
+    Tx,
+    /// Signals are received from the device.
+    Rx,
+}
+
+/// A hardware port of a device on which waveforms are played or captured.
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PulsePort {
+    /// The Braket identifier of the port.
+    pub id: String,
+    /// The direction of the signals on the port.
+    pub direction: PortDirection,
+    /// The time between two samples of a waveform in seconds.
+    pub dt: f64,
+    /// The qubits the port is connected to.
+    pub qubits: Vec<usize>,
+}
+
+/// A frame tracking the frequency and phase of the signals on a port.
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PulseFrame {
+    /// The Braket identifier of the frame.
+    pub id: String,
+    /// The identifier of the port the frame is defined on.
+    pub port_id: String,
+    /// The frequency of the frame in Hz, `None` when it is only known from the calibration.
+    pub frequency: Option<f64>,
+    /// The initial phase of the frame in radians.
+    pub phase: f64,
+    /// The qubits the frame acts on.
+    pub qubits: Vec<usize>,
+}
+
+/// The pulse capabilities of a device.
+#[derive(Debug, PartialEq, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct PulseInfo {
+    /// The ports of the device.
+    pub ports: Vec<PulsePort>,
+    /// The frames of the device.
+    pub frames: Vec<PulseFrame>,
+}
+
+impl PulseInfo {
+    /// Reads the pulse capabilities from Braket device properties.
+    ///
+    /// # Arguments
+    ///
+    /// * `properties` - The JSON of the device properties, either the full properties or only
+    ///   their `pulse` entry.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PulseInfo)` - The ports and frames of the device, sorted by their identifiers.
+    /// * `Err(RoqoqoError)` - The properties could not be parsed.
+    pub fn from_properties(properties: &str) -> Result<PulseInfo, RoqoqoError> {
+        let properties: Value =
+            serde_json::from_str(properties).map_err(|err| RoqoqoError::GenericError {
+                msg: format!("Could not parse device properties: {}", err),
+            })?;
+        let pulse = properties.get("pulse").unwrap_or(&properties);
+        let invalid = |id: &str| RoqoqoError::GenericError {
+            msg: format!("Invalid pulse properties of {}", id),
+        };
+        let qubits = |entry: &Value| -> Vec<usize> {
+            entry["qubitMappings"]
+                .as_array()
+                .map(|qubits| {
+                    qubits
+                        .iter()
+                        .filter_map(|qubit| qubit.as_u64().map(|qubit| qubit as usize))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut info = PulseInfo::default();
+        if let Some(ports) = pulse["ports"].as_object() {
+            for (id, port) in ports {
+                let direction = match port["direction"].as_str() {
+                    Some("tx") => PortDirection::Tx,
+                    Some("rx") => PortDirection::Rx,
+                    _ => return Err(invalid(id)),
+                };
+                info.ports.push(PulsePort {
+                    id: id.clone(),
+                    direction,
+                    dt: port["dt"].as_f64().ok_or_else(|| invalid(id))?,
+                    qubits: qubits(port),
+                });
+            }
+        }
+        if let Some(frames) = pulse["frames"].as_object() {
+            for (id, frame) in frames {
+                info.frames.push(PulseFrame {
+                    id: id.clone(),
+                    port_id: frame["portId"]
+                        .as_str()
+                        .ok_or_else(|| invalid(id))?
+                        .to_string(),
+                    frequency: frame["frequency"].as_f64(),
+                    phase: frame["phase"].as_f64().unwrap_or(0.0),
+                    qubits: qubits(frame),
+                });
+            }
+        }
+        info.ports.sort_by(|a, b| a.id.cmp(&b.id));
+        info.frames.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(info)
+    }
+
+    /// Returns the port with the given identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Braket identifier of the port.
+    ///
+    /// # Returns
+    ///
+    /// The port, `None` if the device has no such port.
+    pub fn port(&self, id: &str) -> Option<&PulsePort> {
+        self.ports.iter().find(|port| port.id == id)
+    }
+
+    /// Returns the frame with the given identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The Braket identifier of the frame.
+    ///
+    /// # Returns
+    ///
+    /// The frame, `None` if the device has no such frame.
+    pub fn frame(&self, id: &str) -> Option<&PulseFrame> {
+        self.frames.iter().find(|frame| frame.id == id)
+    }
+
+    /// Returns the frames acting on a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit of the device.
+    ///
+    /// # Returns
+    ///
+    /// The frames whose qubits contain the qubit.
+    pub fn qubit_frames(&self, qubit: usize) -> Vec<&PulseFrame> {
+        self.frames
+            .iter()
+            .filter(|frame| frame.qubits.contains(&qubit))
+            .collect()
+    }
+}
+
+impl RigettiAspenM3Device {
+    /// Returns the pulse capabilities of the device.
+    ///
+    /// The ports and frames follow the naming of the Braket Rigetti devices: every qubit has a
+    /// flux port `q{n}_ff`, a drive port `q{n}_rf` and the readout ports `q{n}_ro_tx` and
+    /// `q{n}_ro_rx`, with a frame on each of the last three. Every enabled edge has a frame per
+    /// two-qubit gate on the flux port of its smaller qubit, e.g. `q0_q1_cz_frame`.
+    ///
+    /// The frequencies of the frames are set by the calibration of the device and are not
+    /// known here, see [PulseInfo::from_properties] to read them from the device properties.
+    ///
+    /// # Returns
+    ///
+    /// The ports and frames of the device.
+    pub fn pulse_info(&self) -> PulseInfo {
+        let mut info = PulseInfo::default();
+        let mut add_frame = |id: String, port_id: String, qubits: Vec<usize>| {
+            info.frames.push(PulseFrame {
+                id,
+                port_id,
+                frequency: None,
+                phase: 0.0,
+                qubits,
+            })
+        };
+        for qubit in 0..self.number_qubits() {
+            for port in ["rf", "ro_tx", "ro_rx"] {
+                add_frame(
+                    format!("q{}_{}_frame", qubit, port),
+                    format!("q{}_{}", qubit, port),
+                    vec![qubit],
+                );
+            }
+        }
+        for (first, second) in self.two_qubit_edges() {
+            let (first, second) = (first.min(second), first.max(second));
+            for gate in RIGETTI_EDGE_FRAMES {
+                add_frame(
+                    format!("q{}_q{}_{}_frame", first, second, gate),
+                    format!("q{}_ff", first),
+                    vec![first, second],
+                );
+            }
+        }
+        for qubit in 0..self.number_qubits() {
+            for (port, direction) in [
+                ("ff", PortDirection::Tx),
+                ("rf", PortDirection::Tx),
+                ("ro_tx", PortDirection::Tx),
+                ("ro_rx", PortDirection::Rx),
+            ] {
+                info.ports.push(PulsePort {
+                    id: format!("q{}_{}", qubit, port),
+                    direction,
+                    dt: RIGETTI_SAMPLE_TIME,
+                    qubits: vec![qubit],
+                });
+            }
+        }
+        info
+    }
+}
+
+impl AWSDevice {
+    /// Returns the pulse capabilities of the device.
+    ///
+    /// # Returns
+    ///
+    /// The ports and frames of the device, `None` for devices without pulse control.
+    pub fn pulse_info(&self) -> Option<PulseInfo> {
+        match self {
+            AWSDevice::RigettiAspenM3Device(x) => Some(x.pulse_info()),
+            _ => None,
+        }
+    }
+}
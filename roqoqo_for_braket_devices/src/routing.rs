@@ -0,0 +1,565 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Routing
+//!
+//! Provides the mapping of qoqo circuits onto the connectivity of the AWS devices.
+
+use std::collections::{BTreeSet, HashMap};
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::{
+    InvolveQubits, InvolvedQubits, Operate, Operation, PragmaRepeatedMeasurement, Substitute, SWAP,
+};
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::{circuit_number_qubits, gate_qubits};
+use crate::topology::adjacency;
+use crate::{AWSDevice, EdgeWeight};
+
+/// Chooses the device qubit of every qubit of a circuit before routing.
+///
+/// If the interaction graph of the circuit can be placed on the device, that placement is used and
+/// no SWAP gates are needed. Otherwise the circuit qubits are placed in ascending order on the
+/// largest connected part of the device. Qubits without two-qubit gates are placed on the
+/// remaining available qubits.
+///
+/// This is the placement [route_circuit] starts from, together with the layout returned by the
+/// routing it relates the qubits of the routed circuit to the original circuit, see
+/// [crate::verification::check_equivalence].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to place.
+/// * `device` - The device the circuit is placed on.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, usize>)` - The device qubit of every circuit qubit.
+/// * `Err(RoqoqoError)` - The device does not have enough available qubits.
+pub fn initial_layout(
+    circuit: &Circuit,
+    device: &AWSDevice,
+) -> Result<HashMap<usize, usize>, RoqoqoError> {
+    let mut circuit_qubits: BTreeSet<usize> = BTreeSet::new();
+    let mut interaction_edges: Vec<(usize, usize)> = Vec::new();
+    for operation in circuit.iter() {
+        if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+            circuit_qubits.extend(qubits);
+        }
+        if let Some(qubits) = gate_qubits(operation) {
+            if let [control, target] = qubits[..] {
+                interaction_edges.push((control, target));
+            }
+        }
+    }
+
+    let components = device.connected_components();
+    let mut layout = match device.find_placement(&interaction_edges) {
+        Some(placement) => placement,
+        None => {
+            let mut interacting: Vec<usize> = interaction_edges
+                .iter()
+                .flat_map(|(control, target)| [*control, *target])
+                .collect();
+            interacting.sort_unstable();
+            interacting.dedup();
+            let largest = components.first().map_or(0, |component| component.len());
+            if interacting.len() > largest {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Circuit has two-qubit gates on {} qubits but the largest connected part \
+                         of the device only has {}",
+                        interacting.len(),
+                        largest
+                    ),
+                });
+            }
+            interacting
+                .into_iter()
+                .zip(components[0].iter().copied())
+                .collect()
+        }
+    };
+
+    let mut free: Vec<usize> = components.into_iter().flatten().collect();
+    free.sort_unstable();
+    free.retain(|qubit| !layout.values().any(|placed| placed == qubit));
+    let unplaced: Vec<usize> = circuit_qubits
+        .into_iter()
+        .filter(|qubit| !layout.contains_key(qubit))
+        .collect();
+    if unplaced.len() > free.len() {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit uses {} qubits but the device only has {} available qubits",
+                layout.len() + unplaced.len(),
+                layout.len() + free.len()
+            ),
+        });
+    }
+    layout.extend(unplaced.into_iter().zip(free));
+    Ok(layout)
+}
+
+/// Additional cost of running a circuit on a device caused by routing.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwapOverhead {
+    /// Number of SWAP gates inserted by [route_circuit].
+    pub number_swaps: usize,
+    /// Number of native two-qubit gates of the SWAP gates, three per SWAP.
+    pub extra_two_qubit_gates: usize,
+    /// Duration of the SWAP gates, the time of three native two-qubit gates on each of their edges.
+    pub extra_duration: f64,
+}
+
+/// Runs the routing of a circuit without building the routed circuit.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to route.
+/// * `device` - The device the circuit is run on.
+/// * `visit` - Called for every operation of the circuit with the SWAP gates inserted before it
+///   and the device qubit of every circuit qubit after the SWAP gates.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<usize, usize>)` - The device qubit every circuit qubit ends up on.
+/// * `Err(RoqoqoError)` - The circuit cannot be routed or `visit` failed.
+fn route<F>(
+    circuit: &Circuit,
+    device: &AWSDevice,
+    mut visit: F,
+) -> Result<HashMap<usize, usize>, RoqoqoError>
+where
+    F: FnMut(&[(usize, usize)], &Operation, &HashMap<usize, usize>) -> Result<(), RoqoqoError>,
+{
+    let topology = device.topology();
+    let mut layout = initial_layout(circuit, device)?;
+    let mut occupants: HashMap<usize, usize> = layout
+        .iter()
+        .map(|(circuit_qubit, device_qubit)| (*device_qubit, *circuit_qubit))
+        .collect();
+
+    for operation in circuit.iter() {
+        let qubits = gate_qubits(operation).unwrap_or_default();
+        if qubits.len() > 2 {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Gate {} acts on more than two qubits and cannot be routed",
+                    operation.hqslang()
+                ),
+            });
+        }
+        let mut swaps: Vec<(usize, usize)> = Vec::new();
+        if let [control, target] = qubits[..] {
+            let mut first = layout[&control];
+            let mut second = layout[&target];
+            loop {
+                let distance =
+                    topology
+                        .distance(first, second)
+                        .ok_or_else(|| RoqoqoError::GenericError {
+                            msg: format!(
+                                "Qubits {} and {} of gate {} are not connected on the device",
+                                control,
+                                target,
+                                operation.hqslang()
+                            ),
+                        })?;
+                if distance <= 1 {
+                    break;
+                }
+                let mut best: Option<(usize, usize, f64)> = None;
+                for (from, towards) in [(first, second), (second, first)] {
+                    for &neighbour in topology.neighbours(from) {
+                        if topology.distance(neighbour, towards) != Some(distance - 1) {
+                            continue;
+                        }
+                        let fidelity = device.edge_weight(from, neighbour, EdgeWeight::Fidelity);
+                        match best {
+                            Some((_, _, best_fidelity)) if best_fidelity >= fidelity => (),
+                            _ => best = Some((from, neighbour, fidelity)),
+                        }
+                    }
+                }
+                let (from, to) = match best {
+                    Some((from, to, _)) => (from, to),
+                    None => break,
+                };
+                swaps.push((from, to));
+                let moved_from = occupants.remove(&from);
+                let moved_to = occupants.remove(&to);
+                if let Some(circuit_qubit) = moved_from {
+                    occupants.insert(to, circuit_qubit);
+                    layout.insert(circuit_qubit, to);
+                }
+                if let Some(circuit_qubit) = moved_to {
+                    occupants.insert(from, circuit_qubit);
+                    layout.insert(circuit_qubit, from);
+                }
+                if from == first {
+                    first = to;
+                } else {
+                    second = to;
+                }
+            }
+        }
+        visit(&swaps, operation, &layout)?;
+    }
+    Ok(layout)
+}
+
+/// Maps a circuit onto the connectivity of a device by inserting SWAP gates.
+///
+/// The circuit qubits are first placed on the device, see [AWSDevice::find_placement]. Before
+/// every two-qubit gate acting on qubits that are not connected, SWAP gates move the qubits
+/// towards each other along a shortest path of the device. Among the shortest paths, the SWAPs on
+/// the edges with the highest two-qubit gate fidelity are chosen.
+///
+/// All operations are relabeled to the device qubits they act on at that point of the circuit,
+/// readout registers are left unchanged so the measurement results keep their meaning.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to route.
+/// * `device` - The device the circuit is run on.
+///
+/// # Returns
+///
+/// * `Ok((Circuit, HashMap<usize, usize>))` - The routed circuit and the device qubit every
+///   circuit qubit ends up on.
+/// * `Err(RoqoqoError)` - The circuit does not fit on the device, contains gates on more than
+///   two qubits or acts on qubits that are not connected by any path.
+pub fn route_circuit(
+    circuit: &Circuit,
+    device: &AWSDevice,
+) -> Result<(Circuit, HashMap<usize, usize>), RoqoqoError> {
+    let number_qubits = circuit_number_qubits(circuit);
+    let mut routed = Circuit::new();
+    let layout = route(circuit, device, |swaps, operation, layout| {
+        for (from, to) in swaps {
+            routed.add_operation(SWAP::new(*from, *to));
+        }
+        routed.add_operation(with_explicit_readout(operation, number_qubits).remap_qubits(layout)?);
+        Ok(())
+    })?;
+    Ok((routed, layout))
+}
+
+impl AWSDevice {
+    /// Estimates the SWAP gates needed to run a circuit on the device.
+    ///
+    /// The SWAP gates are the ones inserted by [route_circuit], but the routed circuit is not
+    /// built, so devices can be compared quickly. Every SWAP gate is counted as three native
+    /// two-qubit gates.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The circuit to route.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapOverhead)` - The number of SWAP gates and their cost.
+    /// * `Err(RoqoqoError)` - The circuit cannot be routed on the device.
+    pub fn estimate_swap_overhead(&self, circuit: &Circuit) -> Result<SwapOverhead, RoqoqoError> {
+        let mut overhead = SwapOverhead {
+            number_swaps: 0,
+            extra_two_qubit_gates: 0,
+            extra_duration: 0.0,
+        };
+        route(circuit, self, |swaps, _, _| {
+            for (from, to) in swaps {
+                overhead.number_swaps += 1;
+                overhead.extra_two_qubit_gates += 3;
+                overhead.extra_duration += 3.0 * self.edge_weight(*from, *to, EdgeWeight::GateTime);
+            }
+            Ok(())
+        })?;
+        Ok(overhead)
+    }
+}
+
+/// Returns the order in which the circuit qubits are placed by [remap_to_best_qubits].
+///
+/// Every qubit is preceded by as many of its interaction partners as possible, so that its
+/// placement is constrained by already placed qubits. Qubits with more two-qubit gates come first.
+///
+/// # Arguments
+///
+/// * `circuit_qubits` - The qubits of the circuit.
+/// * `interactions` - The number of two-qubit gates on every pair of circuit qubits.
+///
+/// # Returns
+///
+/// The circuit qubits in placement order.
+fn placement_order(
+    circuit_qubits: &BTreeSet<usize>,
+    interactions: &HashMap<(usize, usize), usize>,
+) -> Vec<usize> {
+    let gates = |qubit: usize, partners: &dyn Fn(usize) -> bool| -> usize {
+        interactions
+            .iter()
+            .filter_map(|((first, second), count)| {
+                if (*first == qubit && partners(*second)) || (*second == qubit && partners(*first))
+                {
+                    Some(*count)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    };
+    let mut order: Vec<usize> = Vec::new();
+    let mut remaining: Vec<usize> = circuit_qubits.iter().copied().collect();
+    while !remaining.is_empty() {
+        let (position, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(position, qubit)| {
+                let placed = gates(*qubit, &|partner| order.contains(&partner));
+                let total = gates(*qubit, &|_| true);
+                (position, (placed, total))
+            })
+            .fold((0, (0, 0)), |best, candidate| {
+                if candidate.1 > best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            });
+        order.push(remaining.remove(position));
+    }
+    order
+}
+
+/// Places circuit qubits one by one on the best available device qubit.
+///
+/// # Arguments
+///
+/// * `device` - The device the circuit is placed on.
+/// * `order` - The circuit qubits in placement order.
+/// * `interactions` - The number of two-qubit gates on every pair of circuit qubits.
+/// * `layout` - The already placed circuit qubits, extended by the placed qubits.
+///
+/// # Returns
+///
+/// * `Some(f64)` - The logarithm of the fidelity of the placed qubits and their gates.
+/// * `None` - A qubit could not be placed next to all of its placed interaction partners.
+fn place_greedily(
+    device: &AWSDevice,
+    order: &[usize],
+    interactions: &HashMap<(usize, usize), usize>,
+    layout: &mut HashMap<usize, usize>,
+) -> Option<f64> {
+    let adjacency = adjacency(device.number_qubits(), &device.two_qubit_edges());
+    let available: Vec<usize> = device
+        .connected_components()
+        .into_iter()
+        .flatten()
+        .collect();
+    let mut score = 0.0;
+    for &qubit in order {
+        if layout.contains_key(&qubit) {
+            continue;
+        }
+        let partners: Vec<(usize, usize)> = interactions
+            .iter()
+            .filter_map(|((first, second), count)| {
+                let partner = if *first == qubit {
+                    *second
+                } else if *second == qubit {
+                    *first
+                } else {
+                    return None;
+                };
+                layout.get(&partner).map(|placed| (*placed, *count))
+            })
+            .collect();
+        let mut best: Option<(usize, f64)> = None;
+        for &candidate in &available {
+            if layout.values().any(|placed| *placed == candidate)
+                || partners
+                    .iter()
+                    .any(|(placed, _)| !adjacency[candidate].contains(placed))
+            {
+                continue;
+            }
+            let candidate_score = device.qubit_fidelity(candidate).ln()
+                + partners
+                    .iter()
+                    .map(|(placed, count)| {
+                        *count as f64
+                            * device
+                                .edge_weight(candidate, *placed, EdgeWeight::Fidelity)
+                                .ln()
+                    })
+                    .sum::<f64>();
+            match best {
+                Some((_, best_score)) if best_score >= candidate_score => (),
+                _ => best = Some((candidate, candidate_score)),
+            }
+        }
+        let (placed, placed_score) = best?;
+        layout.insert(qubit, placed);
+        score += placed_score;
+    }
+    Some(score)
+}
+
+/// Maps the qubits of a circuit onto the device qubits with the best calibration data.
+///
+/// Every circuit qubit is placed on an available device qubit so that all two-qubit gates act on
+/// connected qubits and no SWAP gates are needed. Among the placements found, the one with the
+/// highest product of the [AWSDevice::qubit_fidelity] of the used qubits and the
+/// [EdgeWeight::Fidelity] of the edges of all two-qubit gates is chosen. Circuits whose two-qubit
+/// gates cannot be placed on the device have to be routed with [route_circuit] instead.
+///
+/// All operations are relabeled to the device qubits, readout registers are left unchanged so
+/// the measurement results keep their meaning.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to remap.
+/// * `device` - The device the circuit is run on.
+///
+/// # Returns
+///
+/// * `Ok((Circuit, HashMap<usize, usize>))` - The remapped circuit and the device qubit of every
+///   circuit qubit.
+/// * `Err(RoqoqoError)` - The circuit does not fit on the device, contains gates on more than
+///   two qubits or cannot be placed without SWAP gates.
+pub fn remap_to_best_qubits(
+    circuit: &Circuit,
+    device: &AWSDevice,
+) -> Result<(Circuit, HashMap<usize, usize>), RoqoqoError> {
+    let mut circuit_qubits: BTreeSet<usize> = BTreeSet::new();
+    let mut interactions: HashMap<(usize, usize), usize> = HashMap::new();
+    for operation in circuit.iter() {
+        if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+            circuit_qubits.extend(qubits);
+        }
+        match gate_qubits(operation).as_deref() {
+            Some([first, second]) => {
+                *interactions
+                    .entry((*first.min(second), *first.max(second)))
+                    .or_insert(0) += 1;
+            }
+            Some(qubits) if qubits.len() > 2 => {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Gate {} acts on more than two qubits and cannot be remapped",
+                        operation.hqslang()
+                    ),
+                })
+            }
+            _ => (),
+        }
+    }
+    let available = device.connected_components().into_iter().flatten().count();
+    if circuit_qubits.len() > available {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Circuit uses {} qubits but the device only has {} available qubits",
+                circuit_qubits.len(),
+                available
+            ),
+        });
+    }
+
+    let order = placement_order(&circuit_qubits, &interactions);
+    let mut best: Option<(f64, HashMap<usize, usize>)> = None;
+    let mut starts: Vec<HashMap<usize, usize>> = device
+        .connected_components()
+        .into_iter()
+        .flatten()
+        .filter_map(|start| order.first().map(|first| HashMap::from([(*first, start)])))
+        .collect();
+    if starts.is_empty() {
+        starts.push(HashMap::new());
+    }
+    let edges: Vec<(usize, usize)> = interactions.keys().copied().collect();
+    if let Some(placement) = device.find_placement(&edges) {
+        starts.push(placement);
+    }
+    for mut layout in starts {
+        let mut score: f64 = layout
+            .values()
+            .map(|placed| device.qubit_fidelity(*placed).ln())
+            .sum();
+        score += interactions
+            .iter()
+            .filter_map(|((first, second), count)| {
+                Some(
+                    *count as f64
+                        * device
+                            .edge_weight(
+                                *layout.get(first)?,
+                                *layout.get(second)?,
+                                EdgeWeight::Fidelity,
+                            )
+                            .ln(),
+                )
+            })
+            .sum::<f64>();
+        if let Some(placed_score) = place_greedily(device, &order, &interactions, &mut layout) {
+            score += placed_score;
+            match &best {
+                Some((best_score, _)) if *best_score >= score => (),
+                _ => best = Some((score, layout)),
+            }
+        }
+    }
+    let (_, layout) = best.ok_or_else(|| RoqoqoError::GenericError {
+        msg: "The two-qubit gates of the circuit cannot be placed on the device without SWAP \
+              gates"
+            .to_string(),
+    })?;
+
+    let number_qubits = circuit_number_qubits(circuit);
+    let mut remapped = Circuit::new();
+    for operation in circuit.iter() {
+        remapped
+            .add_operation(with_explicit_readout(operation, number_qubits).remap_qubits(&layout)?);
+    }
+    Ok((remapped, layout))
+}
+
+/// Gives a PragmaRepeatedMeasurement without qubit mapping the identity mapping of the circuit.
+///
+/// Without a mapping, qubit `i` is read out into index `i` of the register. After remapping the
+/// qubits onto device qubits this would read the device qubit indices, so the mapping is made
+/// explicit before the remapping to keep the readout register unchanged.
+///
+/// # Arguments
+///
+/// * `operation` - The operation to remap.
+/// * `number_qubits` - The number of qubits of the circuit.
+///
+/// # Returns
+///
+/// The operation with an explicit readout mapping, other operations are returned unchanged.
+fn with_explicit_readout(operation: &Operation, number_qubits: usize) -> Operation {
+    match operation {
+        Operation::PragmaRepeatedMeasurement(measurement)
+            if measurement.qubit_mapping().is_none() =>
+        {
+            PragmaRepeatedMeasurement::new(
+                measurement.readout().clone(),
+                *measurement.number_measurements(),
+                Some((0..number_qubits).map(|qubit| (qubit, qubit)).collect()),
+            )
+            .into()
+        }
+        _ => operation.clone(),
+    }
+}
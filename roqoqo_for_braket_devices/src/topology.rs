@@ -0,0 +1,1052 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Topology
+//!
+//! Provides graph algorithms on the connectivity of the AWS devices.
+
+use std::collections::{HashMap, VecDeque};
+
+use petgraph::graph::{NodeIndex, UnGraph};
+use roqoqo::devices::{GenericDevice, QoqoDevice};
+use roqoqo::RoqoqoError;
+
+use crate::analysis::total_decoherence_rate;
+use crate::AWSDevice;
+
+/// Quantity used as the weight of the edges of a device's connectivity graph.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum EdgeWeight {
+    /// The shortest gate time of the native two-qubit gates on the edge.
+    GateTime,
+    /// The fidelity of the two-qubit gates on the edge, one minus the two-qubit error rate.
+    Fidelity,
+    /// The two-qubit error rate of the edge.
+    ErrorRate,
+}
+
+impl std::str::FromStr for EdgeWeight {
+    type Err = RoqoqoError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "gate_time" => Ok(EdgeWeight::GateTime),
+            "fidelity" => Ok(EdgeWeight::Fidelity),
+            "error_rate" => Ok(EdgeWeight::ErrorRate),
+            _ => Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Unknown edge weight {}, expected gate_time, fidelity or error_rate",
+                    name
+                ),
+            }),
+        }
+    }
+}
+
+/// Maximal number of search steps used when looking for the longest chain of a device.
+const CHAIN_SEARCH_BUDGET: usize = 100_000;
+
+/// Maximal number of search steps used when looking for a placement of an interaction graph.
+const PLACEMENT_SEARCH_BUDGET: usize = 1_000_000;
+
+/// Type of the connectivity of a device.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Connectivity {
+    /// Every qubit is connected to every other qubit.
+    AllToAll,
+    /// The qubits form a single chain or ring.
+    Linear,
+    /// The qubits form a connected lattice where every qubit has at most four neighbours.
+    Lattice,
+    /// Any other connectivity, e.g. a device split into several parts.
+    Arbitrary,
+}
+
+impl std::fmt::Display for Connectivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Connectivity::AllToAll => "AllToAll",
+            Connectivity::Linear => "Linear",
+            Connectivity::Lattice => "Lattice",
+            Connectivity::Arbitrary => "Arbitrary",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Builds the adjacency lists of an undirected graph.
+///
+/// # Arguments
+///
+/// * `number_qubits` - The number of qubits (vertices) of the graph.
+/// * `edges` - The edges of the graph, duplicates and both directions are allowed.
+///
+/// # Returns
+///
+/// The sorted list of neighbours of every qubit.
+pub(crate) fn adjacency(number_qubits: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); number_qubits];
+    for &(first, second) in edges {
+        if first == second || first >= number_qubits || second >= number_qubits {
+            continue;
+        }
+        adjacency[first].push(second);
+        adjacency[second].push(first);
+    }
+    for neighbours in adjacency.iter_mut() {
+        neighbours.sort_unstable();
+        neighbours.dedup();
+    }
+    adjacency
+}
+
+/// Breadth-first search from a qubit.
+///
+/// # Arguments
+///
+/// * `adjacency` - The adjacency lists of the graph.
+/// * `start` - The qubit the search starts from.
+///
+/// # Returns
+///
+/// The distance of every qubit from the start and its predecessor on a shortest path,
+/// `None` for qubits that cannot be reached.
+pub(crate) fn breadth_first_search(
+    adjacency: &[Vec<usize>],
+    start: usize,
+) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+    let mut distances = vec![None; adjacency.len()];
+    let mut predecessors = vec![None; adjacency.len()];
+    let mut queue = VecDeque::from([start]);
+    distances[start] = Some(0);
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[current].map(|distance| distance + 1);
+        for &neighbour in &adjacency[current] {
+            if distances[neighbour].is_none() {
+                distances[neighbour] = distance;
+                predecessors[neighbour] = Some(current);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    (distances, predecessors)
+}
+
+/// Follows the predecessors of a breadth-first search back to its start.
+///
+/// # Arguments
+///
+/// * `predecessors` - The predecessors returned by [breadth_first_search].
+/// * `end` - The qubit the path ends on, which has to be reachable from the start.
+///
+/// # Returns
+///
+/// The qubits of the path, starting with the start of the search and ending with `end`.
+fn path_to(predecessors: &[Option<usize>], end: usize) -> Vec<usize> {
+    let mut path = vec![end];
+    while let Some(predecessor) = predecessors[path[path.len() - 1]] {
+        path.push(predecessor);
+    }
+    path.reverse();
+    path
+}
+
+/// Shortest paths between all pairs of qubits of a device.
+///
+/// The breadth-first searches from every qubit are run once when the topology is created, so
+/// repeated distance and path queries, e.g. while routing a circuit, only look up the results.
+/// The topology is a snapshot and does not follow later changes of the device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Topology {
+    /// The sorted neighbours of every qubit
+    adjacency: Vec<Vec<usize>>,
+    /// The distances from every qubit to every other qubit
+    distances: Vec<Vec<Option<usize>>>,
+    /// The predecessors on a shortest path from every qubit to every other qubit
+    predecessors: Vec<Vec<Option<usize>>>,
+}
+
+impl Topology {
+    /// Creates the topology of an undirected graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `number_qubits` - The number of qubits (vertices) of the graph.
+    /// * `edges` - The edges of the graph, duplicates and both directions are allowed.
+    ///
+    /// # Returns
+    ///
+    /// The topology with the shortest paths between all pairs of qubits.
+    pub fn new(number_qubits: usize, edges: &[(usize, usize)]) -> Self {
+        let adjacency = adjacency(number_qubits, edges);
+        let (distances, predecessors): (Vec<_>, Vec<_>) = (0..number_qubits)
+            .map(|qubit| breadth_first_search(&adjacency, qubit))
+            .unzip();
+        Self {
+            adjacency,
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Returns the number of qubits of the topology.
+    ///
+    /// # Returns
+    ///
+    /// The number of qubits.
+    pub fn number_qubits(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Returns the neighbours of a qubit.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit.
+    ///
+    /// # Returns
+    ///
+    /// The sorted qubits connected to `qubit`, empty if the qubit is not part of the topology.
+    pub fn neighbours(&self, qubit: usize) -> &[usize] {
+        self.adjacency
+            .get(qubit)
+            .map(|neighbours| neighbours.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of edges on a shortest path between two qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_a` - The first qubit.
+    /// * `qubit_b` - The second qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The distance between the qubits.
+    /// * `None` - A qubit is not part of the topology or the qubits are not connected.
+    pub fn distance(&self, qubit_a: usize, qubit_b: usize) -> Option<usize> {
+        *self.distances.get(qubit_a)?.get(qubit_b)?
+    }
+
+    /// Returns a shortest path between two qubits.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_a` - The first qubit.
+    /// * `qubit_b` - The second qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<usize>)` - The qubits of the path, starting with `qubit_a` and ending with `qubit_b`.
+    /// * `None` - A qubit is not part of the topology or the qubits are not connected.
+    pub fn shortest_path(&self, qubit_a: usize, qubit_b: usize) -> Option<Vec<usize>> {
+        self.distance(qubit_a, qubit_b)?;
+        Some(path_to(&self.predecessors[qubit_a], qubit_b))
+    }
+
+    /// Returns the distances between all pairs of qubits.
+    ///
+    /// # Returns
+    ///
+    /// The matrix of distances, `None` for pairs of qubits that are not connected.
+    pub fn distance_matrix(&self) -> &[Vec<Option<usize>>] {
+        &self.distances
+    }
+}
+
+/// State of the depth-first search for the longest chain.
+struct ChainSearch<'a> {
+    /// The neighbours of every qubit of the device.
+    adjacency: &'a [Vec<usize>],
+    /// Whether the last qubit of a chain must be connected to its first qubit.
+    closed: bool,
+    /// Whether a qubit is part of the current path.
+    visited: Vec<bool>,
+    /// The chain currently being extended.
+    path: Vec<usize>,
+    /// The longest chain found so far.
+    best: Vec<usize>,
+    /// The number of search steps taken, the search stops after [CHAIN_SEARCH_BUDGET] steps.
+    steps: usize,
+}
+
+impl ChainSearch<'_> {
+    /// Returns the unvisited qubits reachable from a qubit, including the qubit itself.
+    fn reachable(&self, qubit: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.adjacency.len()];
+        seen[qubit] = true;
+        let mut stack = vec![qubit];
+        let mut reachable = Vec::new();
+        while let Some(current) = stack.pop() {
+            reachable.push(current);
+            for &neighbour in &self.adjacency[current] {
+                if !self.visited[neighbour] && !seen[neighbour] {
+                    seen[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Returns the number of unvisited neighbours of a qubit.
+    fn free_degree(&self, qubit: usize) -> usize {
+        self.adjacency[qubit]
+            .iter()
+            .filter(|neighbour| !self.visited[**neighbour])
+            .count()
+    }
+
+    /// Extends the current path by a qubit, returns true when the search is finished.
+    fn visit(&mut self, qubit: usize) -> bool {
+        self.steps += 1;
+        if self.steps > CHAIN_SEARCH_BUDGET {
+            return true;
+        }
+        self.visited[qubit] = true;
+        self.path.push(qubit);
+
+        let is_chain =
+            !self.closed || (self.path.len() >= 3 && self.adjacency[qubit].contains(&self.path[0]));
+        if is_chain && self.path.len() > self.best.len() {
+            self.best = self.path.clone();
+        }
+        if self.best.len() == self.adjacency.len() {
+            return true;
+        }
+
+        // The chain can at most be extended by the unvisited qubits reachable from the current one
+        let reachable = self.reachable(qubit);
+        let can_close = !self.closed
+            || reachable
+                .iter()
+                .any(|reached| self.adjacency[*reached].contains(&self.path[0]));
+        if can_close && self.path.len() + reachable.len() - 1 > self.best.len() {
+            // Warnsdorff's rule: continue with the neighbours that are hardest to reach later
+            let mut neighbours: Vec<usize> = self.adjacency[qubit]
+                .iter()
+                .copied()
+                .filter(|neighbour| !self.visited[*neighbour])
+                .collect();
+            neighbours.sort_by_key(|neighbour| (self.free_degree(*neighbour), *neighbour));
+            for neighbour in neighbours {
+                if self.visit(neighbour) {
+                    return true;
+                }
+            }
+        }
+
+        self.visited[qubit] = false;
+        self.path.pop();
+        false
+    }
+}
+
+/// Searches the longest chain of connected qubits.
+///
+/// The search is a depth-first search with pruning and Warnsdorff's heuristic, which finds chains
+/// through all qubits of lattice-like devices quickly. For large graphs without such a chain the
+/// search is stopped after a fixed number of steps and returns the longest chain found so far.
+///
+/// # Arguments
+///
+/// * `number_qubits` - The number of qubits of the device.
+/// * `edges` - The two-qubit edges of the device.
+/// * `closed` - Whether the chain must be closed, i.e. its last qubit connected to its first.
+///
+/// # Returns
+///
+/// The qubits of the longest chain found, empty if a closed chain was requested and none exists.
+pub(crate) fn longest_chain(
+    number_qubits: usize,
+    edges: &[(usize, usize)],
+    closed: bool,
+) -> Vec<usize> {
+    let adjacency = adjacency(number_qubits, edges);
+    let mut search = ChainSearch {
+        adjacency: &adjacency,
+        closed,
+        visited: vec![false; number_qubits],
+        path: Vec::new(),
+        best: Vec::new(),
+        steps: 0,
+    };
+    for start in 0..number_qubits {
+        if search.visit(start) {
+            break;
+        }
+    }
+    search.best
+}
+
+/// State of the branch-and-bound search for the chain with the highest fidelity.
+struct WeightedChainSearch<'a, F: Fn(usize, usize) -> f64> {
+    /// The neighbours of every qubit of the device.
+    adjacency: &'a [Vec<usize>],
+    /// The number of qubits of the chain.
+    length: usize,
+    /// The weight of every qubit, between zero and one.
+    qubit_weights: &'a [f64],
+    /// Returns the weight of the edge between two qubits, between zero and one.
+    edge_weight: F,
+    /// Whether a qubit is part of the current path.
+    visited: Vec<bool>,
+    /// The chain currently being extended.
+    path: Vec<usize>,
+    /// The chain with the highest score found so far and its score.
+    best: Option<(Vec<usize>, f64)>,
+    /// The number of search steps taken, the search stops after [CHAIN_SEARCH_BUDGET] steps.
+    steps: usize,
+}
+
+impl<F: Fn(usize, usize) -> f64> WeightedChainSearch<'_, F> {
+    /// Returns the score of the best chain found so far, zero if none was found.
+    fn best_score(&self) -> f64 {
+        self.best.as_ref().map_or(0.0, |(_, score)| *score)
+    }
+
+    /// Extends the current path by a qubit, returns true when the search is finished.
+    fn visit(&mut self, qubit: usize, score: f64) -> bool {
+        self.steps += 1;
+        if self.steps > CHAIN_SEARCH_BUDGET {
+            return true;
+        }
+        // All weights are at most one, so the score of an extended chain cannot increase
+        if self.best.is_some() && score <= self.best_score() {
+            return false;
+        }
+        self.visited[qubit] = true;
+        self.path.push(qubit);
+        if self.path.len() == self.length {
+            self.best = Some((self.path.clone(), score));
+        } else {
+            let mut neighbours: Vec<(usize, f64)> = self.adjacency[qubit]
+                .iter()
+                .filter(|neighbour| !self.visited[**neighbour])
+                .map(|neighbour| {
+                    let weight =
+                        (self.edge_weight)(qubit, *neighbour) * self.qubit_weights[*neighbour];
+                    (*neighbour, score * weight)
+                })
+                .collect();
+            neighbours.sort_by(|a, b| b.1.total_cmp(&a.1));
+            for (neighbour, neighbour_score) in neighbours {
+                if self.visit(neighbour, neighbour_score) {
+                    return true;
+                }
+            }
+        }
+        self.visited[qubit] = false;
+        self.path.pop();
+        // A chain with the highest possible score cannot be improved
+        self.best_score() >= 1.0
+    }
+}
+
+/// Searches the chain of connected qubits of a given length with the highest fidelity.
+///
+/// The fidelity of a chain is the product of the weights of its qubits and edges, which all have to
+/// be between zero and one. The branch-and-bound search is stopped after a fixed number of steps
+/// and returns the best chain found so far.
+///
+/// # Arguments
+///
+/// * `adjacency` - The adjacency lists of the device.
+/// * `length` - The number of qubits of the chain.
+/// * `qubit_weights` - The weight of every qubit.
+/// * `edge_weight` - The weight of the edge between two qubits.
+///
+/// # Returns
+///
+/// * `Some((Vec<usize>, f64))` - The qubits of the best chain found and its fidelity.
+/// * `None` - No chain of the requested length was found.
+pub(crate) fn best_chain(
+    adjacency: &[Vec<usize>],
+    length: usize,
+    qubit_weights: &[f64],
+    edge_weight: impl Fn(usize, usize) -> f64,
+) -> Option<(Vec<usize>, f64)> {
+    if length == 0 || length > adjacency.len() {
+        return None;
+    }
+    let mut search = WeightedChainSearch {
+        adjacency,
+        length,
+        qubit_weights,
+        edge_weight,
+        visited: vec![false; adjacency.len()],
+        path: Vec::new(),
+        best: None,
+        steps: 0,
+    };
+    let mut starts: Vec<usize> = (0..adjacency.len()).collect();
+    starts.sort_by(|a, b| qubit_weights[*b].total_cmp(&qubit_weights[*a]));
+    for start in starts {
+        if search.visit(start, qubit_weights[start]) {
+            break;
+        }
+    }
+    search.best
+}
+
+/// State of the backtracking search for an embedding of an interaction graph into a device.
+///
+/// The search follows VF2: the qubits of the interaction graph are matched one at a time in an
+/// order where every qubit has as many already matched neighbours as possible, and candidates are
+/// restricted to device neighbours of those matches.
+struct PlacementSearch<'a> {
+    /// The neighbours of every qubit of the interaction graph.
+    pattern: &'a [Vec<usize>],
+    /// The neighbours of every qubit of the device.
+    device: &'a [Vec<usize>],
+    /// The order in which the qubits of the interaction graph are matched.
+    order: Vec<usize>,
+    /// The device qubit matched to every qubit of the interaction graph, if any.
+    mapping: Vec<Option<usize>>,
+    /// Whether a device qubit is already matched.
+    used: Vec<bool>,
+    /// The number of search steps taken, the search stops after [PLACEMENT_SEARCH_BUDGET] steps.
+    steps: usize,
+}
+
+impl PlacementSearch<'_> {
+    /// Returns the order in which the qubits of the interaction graph are matched.
+    fn matching_order(pattern: &[Vec<usize>]) -> Vec<usize> {
+        let mut order: Vec<usize> = Vec::with_capacity(pattern.len());
+        let mut ordered = vec![false; pattern.len()];
+        let mut connections = vec![0; pattern.len()];
+        while order.len() < pattern.len() {
+            let next = (0..pattern.len())
+                .filter(|qubit| !ordered[*qubit])
+                .max_by_key(|qubit| {
+                    (
+                        connections[*qubit],
+                        pattern[*qubit].len(),
+                        usize::MAX - qubit,
+                    )
+                })
+                .unwrap_or_default();
+            ordered[next] = true;
+            order.push(next);
+            for &neighbour in &pattern[next] {
+                connections[neighbour] += 1;
+            }
+        }
+        order
+    }
+
+    /// Matches the qubits from position `depth` of the order on.
+    ///
+    /// Returns `Some(true)` when a placement was found, `Some(false)` when none exists and `None`
+    /// when the search budget is exhausted.
+    fn extend(&mut self, depth: usize) -> Option<bool> {
+        if depth == self.order.len() {
+            return Some(true);
+        }
+        let qubit = self.order[depth];
+        let matched_neighbour = self.pattern[qubit]
+            .iter()
+            .find_map(|neighbour| self.mapping[*neighbour]);
+        let candidates: Vec<usize> = match matched_neighbour {
+            Some(matched) => self.device[matched].clone(),
+            None => (0..self.device.len()).collect(),
+        };
+        for candidate in candidates {
+            self.steps += 1;
+            if self.steps > PLACEMENT_SEARCH_BUDGET {
+                return None;
+            }
+            if self.used[candidate] || self.device[candidate].len() < self.pattern[qubit].len() {
+                continue;
+            }
+            let consistent = self.pattern[qubit]
+                .iter()
+                .filter_map(|neighbour| self.mapping[*neighbour])
+                .all(|matched| self.device[candidate].contains(&matched));
+            if !consistent {
+                continue;
+            }
+            self.mapping[qubit] = Some(candidate);
+            self.used[candidate] = true;
+            if self.extend(depth + 1)? {
+                return Some(true);
+            }
+            self.mapping[qubit] = None;
+            self.used[candidate] = false;
+        }
+        Some(false)
+    }
+}
+
+impl AWSDevice {
+    /// Returns the weight of an edge of the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The first qubit of the edge.
+    /// * `second` - The second qubit of the edge.
+    /// * `weight` - The quantity used as weight.
+    ///
+    /// # Returns
+    ///
+    /// The weight of the edge. The gate time is infinite when no native two-qubit gate is
+    /// available on the edge, the fidelity is one when no error rate is set.
+    pub fn edge_weight(&self, first: usize, second: usize, weight: EdgeWeight) -> f64 {
+        match weight {
+            EdgeWeight::GateTime => self
+                .two_qubit_gate_names()
+                .iter()
+                .flat_map(|gate| {
+                    [
+                        self.two_qubit_gate_time(gate, &first, &second),
+                        self.two_qubit_gate_time(gate, &second, &first),
+                    ]
+                })
+                .flatten()
+                .fold(f64::INFINITY, f64::min),
+            EdgeWeight::Fidelity => 1.0 - self.two_qubit_error_rate(first, second).unwrap_or(0.0),
+            EdgeWeight::ErrorRate => self.two_qubit_error_rate(first, second).unwrap_or(0.0),
+        }
+    }
+
+    /// Returns the edges of the device together with their weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - The quantity used as weight of the edges.
+    ///
+    /// # Returns
+    ///
+    /// The `(control, target, weight)` triples of all edges of the device.
+    pub fn two_qubit_edges_weighted(&self, weight: EdgeWeight) -> Vec<(usize, usize, f64)> {
+        self.two_qubit_edges()
+            .into_iter()
+            .map(|(first, second)| (first, second, self.edge_weight(first, second, weight)))
+            .collect()
+    }
+
+    /// Returns the type of connectivity of the device derived from its edges.
+    ///
+    /// # Returns
+    ///
+    /// The connectivity type of the device.
+    pub fn connectivity(&self) -> Connectivity {
+        let number_qubits = self.number_qubits();
+        let adjacency = adjacency(number_qubits, &self.two_qubit_edges());
+        let number_edges = adjacency
+            .iter()
+            .map(|neighbours| neighbours.len())
+            .sum::<usize>()
+            / 2;
+        let max_degree = adjacency
+            .iter()
+            .map(|neighbours| neighbours.len())
+            .max()
+            .unwrap_or(0);
+        let connected = number_qubits == 0
+            || breadth_first_search(&adjacency, 0)
+                .0
+                .iter()
+                .all(|distance| distance.is_some());
+        if number_edges == number_qubits * number_qubits.saturating_sub(1) / 2 {
+            Connectivity::AllToAll
+        } else if !connected {
+            Connectivity::Arbitrary
+        } else if max_degree <= 2 {
+            Connectivity::Linear
+        } else if max_degree <= 4 {
+            Connectivity::Lattice
+        } else {
+            Connectivity::Arbitrary
+        }
+    }
+
+    /// Returns the number of edges on a shortest path between two qubits.
+    ///
+    /// Every call searches the connectivity graph again, use [AWSDevice::topology] for repeated
+    /// queries.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_a` - The first qubit.
+    /// * `qubit_b` - The second qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The distance between the qubits.
+    /// * `None` - A qubit is not part of the device or the qubits are not connected.
+    pub fn distance(&self, qubit_a: usize, qubit_b: usize) -> Option<usize> {
+        if qubit_a >= self.number_qubits() || qubit_b >= self.number_qubits() {
+            return None;
+        }
+        let adjacency = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        breadth_first_search(&adjacency, qubit_a).0[qubit_b]
+    }
+
+    /// Returns a shortest path between two qubits.
+    ///
+    /// Every call searches the connectivity graph again, use [AWSDevice::topology] for repeated
+    /// queries.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit_a` - The first qubit.
+    /// * `qubit_b` - The second qubit.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<usize>)` - The qubits of the path, starting with `qubit_a` and ending with `qubit_b`.
+    /// * `None` - A qubit is not part of the device or the qubits are not connected.
+    pub fn shortest_path(&self, qubit_a: usize, qubit_b: usize) -> Option<Vec<usize>> {
+        if qubit_a >= self.number_qubits() || qubit_b >= self.number_qubits() {
+            return None;
+        }
+        let adjacency = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        let (distances, predecessors) = breadth_first_search(&adjacency, qubit_a);
+        distances[qubit_b]?;
+        Some(path_to(&predecessors, qubit_b))
+    }
+
+    /// Returns the shortest paths between all pairs of qubits.
+    ///
+    /// Computing the topology once is cheaper than repeated calls of [AWSDevice::distance] and
+    /// [AWSDevice::shortest_path] when many paths are needed, e.g. when routing a circuit.
+    ///
+    /// # Returns
+    ///
+    /// The topology of the enabled edges of the device.
+    pub fn topology(&self) -> Topology {
+        Topology::new(self.number_qubits(), &self.two_qubit_edges())
+    }
+
+    /// Returns the distances between all pairs of qubits.
+    ///
+    /// See [AWSDevice::topology].
+    ///
+    /// # Returns
+    ///
+    /// The matrix of distances, `None` for pairs of qubits that are not connected.
+    pub fn distance_matrix(&self) -> Vec<Vec<Option<usize>>> {
+        self.topology().distances
+    }
+
+    /// Returns the connected components of the device.
+    ///
+    /// Disabled qubits are not part of any component.
+    ///
+    /// # Returns
+    ///
+    /// The sorted qubits of every component, largest components first.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let adjacency = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        let mut assigned = vec![false; self.number_qubits()];
+        for qubit in self.disabled_qubits() {
+            assigned[qubit] = true;
+        }
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for start in 0..self.number_qubits() {
+            if assigned[start] {
+                continue;
+            }
+            let component: Vec<usize> = breadth_first_search(&adjacency, start)
+                .0
+                .iter()
+                .enumerate()
+                .filter_map(|(qubit, distance)| distance.map(|_| qubit))
+                .collect();
+            for &qubit in &component {
+                assigned[qubit] = true;
+            }
+            components.push(component);
+        }
+        components.sort_by(|a, b| b.len().cmp(&a.len()));
+        components
+    }
+
+    /// Restricts the device to a subset of its qubits.
+    ///
+    /// The qubits are relabeled by their position in `qubits`, so `qubits[i]` becomes qubit `i`
+    /// of the returned device. Only gate times between the chosen qubits and their decoherence
+    /// rates are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubits` - The qubits of the device to keep.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GenericDevice)` - The restricted device.
+    /// * `Err(RoqoqoError)` - A qubit is not part of the device or appears more than once.
+    pub fn sub_device(&self, qubits: &[usize]) -> Result<GenericDevice, RoqoqoError> {
+        for (index, qubit) in qubits.iter().enumerate() {
+            if *qubit >= self.number_qubits() {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!(
+                        "Qubit {} out of range for device of size {}",
+                        qubit,
+                        self.number_qubits()
+                    ),
+                });
+            }
+            if qubits[..index].contains(qubit) {
+                return Err(RoqoqoError::GenericError {
+                    msg: format!("Qubit {} appears more than once", qubit),
+                });
+            }
+        }
+        let mut sub_device = GenericDevice::new(qubits.len());
+        for (new_qubit, qubit) in qubits.iter().enumerate() {
+            for gate in self.single_qubit_gate_names() {
+                if let Some(time) = self.single_qubit_gate_time(&gate, qubit) {
+                    sub_device.set_single_qubit_gate_time(&gate, new_qubit, time)?;
+                }
+            }
+            if let Some(rates) = self.qubit_decoherence_rates(qubit) {
+                sub_device.set_qubit_decoherence_rates(new_qubit, rates)?;
+            }
+        }
+        for gate in self.two_qubit_gate_names() {
+            for (new_control, control) in qubits.iter().enumerate() {
+                for (new_target, target) in qubits.iter().enumerate() {
+                    if let Some(time) = self.two_qubit_gate_time(&gate, control, target) {
+                        sub_device.set_two_qubit_gate_time(&gate, new_control, new_target, time)?;
+                    }
+                }
+            }
+        }
+        Ok(sub_device)
+    }
+
+    /// Returns an estimate of the fidelity of a qubit.
+    ///
+    /// The estimate combines the mean readout error of the qubit with the decoherence during its
+    /// fastest single-qubit gate, `(1 - readout error) * exp(-total decoherence rate * gate time)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `qubit` - The qubit for which the fidelity is returned.
+    ///
+    /// # Returns
+    ///
+    /// The estimated fidelity, one for a qubit without noise.
+    pub fn qubit_fidelity(&self, qubit: usize) -> f64 {
+        let readout_error =
+            self.readout_error(qubit)
+                .map_or(0.0, |(prob_detect_0_as_1, prob_detect_1_as_0)| {
+                    (prob_detect_0_as_1 + prob_detect_1_as_0) / 2.0
+                });
+        let gate_time = self
+            .single_qubit_gate_names()
+            .iter()
+            .filter_map(|gate| self.single_qubit_gate_time(gate, &qubit))
+            .fold(f64::INFINITY, f64::min);
+        let gate_time = if gate_time.is_finite() {
+            gate_time
+        } else {
+            0.0
+        };
+        (1.0 - readout_error) * (-total_decoherence_rate(self, qubit) * gate_time).exp()
+    }
+
+    /// Finds a chain of connected qubits of a given length.
+    ///
+    /// Among the chains of the requested length the one whose qubits have the highest product of
+    /// [AWSDevice::qubit_fidelity] is preferred. For large devices with many candidate chains the
+    /// search is bounded and returns the best chain found.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The number of qubits of the chain.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<usize>)` - The qubits of the chain in order.
+    /// * `None` - The device has no chain of the requested length.
+    pub fn find_linear_chain(&self, length: usize) -> Option<Vec<usize>> {
+        let adjacency = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        let qubit_weights: Vec<f64> = (0..self.number_qubits())
+            .map(|qubit| self.qubit_fidelity(qubit))
+            .collect();
+        best_chain(&adjacency, length, &qubit_weights, |_, _| 1.0).map(|(chain, _)| chain)
+    }
+
+    /// Returns chains of connected qubits of a given length with the highest edge fidelity.
+    ///
+    /// Unlike [QoqoDevice::longest_chains] the chains are chosen to maximize the product of the
+    /// fidelities of their edges, avoiding the worst couplers of the device. For large devices the
+    /// search is bounded and returns the best chain found.
+    ///
+    /// # Arguments
+    ///
+    /// * `length` - The number of qubits of the chains.
+    ///
+    /// # Returns
+    ///
+    /// The qubits of the best chains in order, empty if the device has no chain of that length.
+    pub fn best_chains(&self, length: usize) -> Vec<Vec<usize>> {
+        let adjacency = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        let qubit_weights = vec![1.0; self.number_qubits()];
+        best_chain(&adjacency, length, &qubit_weights, |first, second| {
+            self.edge_weight(first, second, EdgeWeight::Fidelity)
+        })
+        .map(|(chain, _)| vec![chain])
+        .unwrap_or_default()
+    }
+
+    /// Searches an embedding of the two-qubit interaction graph of a circuit into the device.
+    ///
+    /// Every pair of interacting circuit qubits is mapped to a pair of connected device qubits,
+    /// so the circuit can be run without SWAP gates. The search is bounded and gives up on
+    /// interaction graphs for which no placement is found within a fixed number of steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `interaction_edges` - The pairs of circuit qubits acted on by two-qubit gates.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HashMap<usize, usize>)` - The device qubit of every circuit qubit in `interaction_edges`.
+    /// * `None` - No placement was found.
+    pub fn find_placement(
+        &self,
+        interaction_edges: &[(usize, usize)],
+    ) -> Option<HashMap<usize, usize>> {
+        let mut circuit_qubits: Vec<usize> = interaction_edges
+            .iter()
+            .flat_map(|(first, second)| [*first, *second])
+            .collect();
+        circuit_qubits.sort_unstable();
+        circuit_qubits.dedup();
+        let index: HashMap<usize, usize> = circuit_qubits
+            .iter()
+            .enumerate()
+            .map(|(index, qubit)| (*qubit, index))
+            .collect();
+        let mut pattern_edges: Vec<(usize, usize)> = interaction_edges
+            .iter()
+            .map(|(first, second)| (index[first], index[second]))
+            .map(|(first, second)| (first.min(second), first.max(second)))
+            .collect();
+        pattern_edges.sort_unstable();
+        pattern_edges.dedup();
+        let pattern = adjacency(circuit_qubits.len(), &pattern_edges);
+        let device = adjacency(self.number_qubits(), &self.two_qubit_edges());
+        if circuit_qubits.len() > device.len() {
+            return None;
+        }
+
+        let mut search = PlacementSearch {
+            pattern: &pattern,
+            device: &device,
+            order: PlacementSearch::matching_order(&pattern),
+            mapping: vec![None; pattern.len()],
+            used: vec![false; device.len()],
+            steps: 0,
+        };
+        if search.extend(0)? {
+            Some(
+                circuit_qubits
+                    .iter()
+                    .zip(search.mapping.iter())
+                    .filter_map(|(qubit, matched)| matched.map(|matched| (*qubit, matched)))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Returns the coupling map of the device in the format used by Qiskit.
+    ///
+    /// Every edge is reported in each direction in which a native two-qubit gate is available.
+    ///
+    /// # Returns
+    ///
+    /// The list of `[control, target]` pairs.
+    pub fn coupling_map(&self) -> Vec<[usize; 2]> {
+        let gates = self.two_qubit_gate_names();
+        let mut coupling_map = Vec::new();
+        for (first, second) in self.two_qubit_edges() {
+            for (control, target) in [(first, second), (second, first)] {
+                if gates
+                    .iter()
+                    .any(|gate| self.two_qubit_gate_time(gate, &control, &target).is_some())
+                {
+                    coupling_map.push([control, target]);
+                }
+            }
+        }
+        coupling_map
+    }
+
+    /// Returns a Graphviz DOT description of the connectivity of the device.
+    ///
+    /// Qubits are colored from green (no decoherence) to red (the largest total decoherence rate
+    /// of the device) and edges are labeled with the shortest gate time of their native two-qubit gates.
+    ///
+    /// # Returns
+    ///
+    /// The DOT description of the connectivity graph.
+    pub fn to_dot(&self) -> String {
+        let rates: Vec<f64> = (0..self.number_qubits())
+            .map(|qubit| total_decoherence_rate(self, qubit))
+            .collect();
+        let max_rate = rates.iter().copied().fold(0.0, f64::max);
+        let mut dot = format!("graph \"{}\" {{\n", self.clone().name());
+        for (qubit, rate) in rates.iter().enumerate() {
+            let relative_rate = if max_rate > 0.0 { rate / max_rate } else { 0.0 };
+            dot.push_str(&format!(
+                "    {} [style=filled, fillcolor=\"{:.3} 0.600 1.000\"];\n",
+                qubit,
+                (1.0 - relative_rate) / 3.0
+            ));
+        }
+        for (first, second) in self.two_qubit_edges() {
+            dot.push_str(&format!(
+                "    {} -- {} [label=\"{}\"];\n",
+                first,
+                second,
+                self.edge_weight(first, second, EdgeWeight::GateTime)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the connectivity graph of the device as a petgraph graph.
+    ///
+    /// The node with index `i` represents qubit `i` and carries the qubit index as weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - The quantity used as weight of the edges.
+    ///
+    /// # Returns
+    ///
+    /// The undirected connectivity graph of the device.
+    pub fn to_petgraph(&self, weight: EdgeWeight) -> UnGraph<usize, f64> {
+        let edges = self.two_qubit_edges();
+        let mut graph = UnGraph::with_capacity(self.number_qubits(), edges.len());
+        for qubit in 0..self.number_qubits() {
+            graph.add_node(qubit);
+        }
+        for (first, second) in edges {
+            graph.add_edge(
+                NodeIndex::new(first),
+                NodeIndex::new(second),
+                self.edge_weight(first, second, weight),
+            );
+        }
+        graph
+    }
+}
@@ -0,0 +1,1004 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transpile
+//!
+//! Provides the decomposition of qoqo circuits into the native gates of the AWS devices.
+//!
+//! All single-qubit gates acting on a qubit between two multi-qubit operations are merged into
+//! one unitary, which is emitted as RotateZ gates around at most two rotations by pi/2 (or one
+//! rotation by pi) around the x-axis. Two-qubit gates are decomposed into CNOTs and single-qubit
+//! gates, and the CNOTs are expressed with the native entangling gate of the device.
+//!
+//! The built-in decompositions can be overridden per gate with a [DecompositionRegistry] passed
+//! to [transpile_with_rules].
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use ndarray::{array, Array2};
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::gate_qubits;
+use crate::{AWSDevice, OQCLucyDevice};
+
+/// Tolerance used when comparing rotation angles.
+const ANGLE_TOLERANCE: f64 = 1e-9;
+
+/// Intermediate representation of a decomposed gate.
+enum Step {
+    /// A single-qubit unitary that is merged with its neighbouring single-qubit unitaries.
+    Single(usize, Array2<Complex64>),
+    /// A CNOT gate with control and target qubit.
+    Cnot(usize, usize),
+    /// An operation that is native to the device and kept as it is.
+    Native(Operation),
+}
+
+/// Native gates of a device family used by the transpiler.
+trait NativeGateSet {
+    /// Returns whether an operation is native to the device and can be kept as it is.
+    fn is_native(&self, operation: &Operation) -> bool;
+
+    /// Returns the rotation by pi/2 around the x-axis.
+    fn x90(&self, qubit: usize) -> Operation;
+
+    /// Returns the rotation by pi around the x-axis.
+    fn x180(&self, qubit: usize) -> Operation;
+
+    /// Adds the rotation by pi around the x-axis to a circuit.
+    fn emit_x180(&self, circuit: &mut Circuit, qubit: usize) {
+        circuit.add_operation(self.x180(qubit));
+    }
+
+    /// Expresses a CNOT with the native entangling gate of the device.
+    fn cnot(&self, control: usize, target: usize) -> Vec<Step>;
+}
+
+/// Native gates of the IonQ devices: GPi, GPi2, RotateZ and MolmerSorensenXX.
+struct IonQGates;
+
+impl NativeGateSet for IonQGates {
+    fn is_native(&self, operation: &Operation) -> bool {
+        matches!(operation, Operation::MolmerSorensenXX(_))
+    }
+
+    fn x90(&self, qubit: usize) -> Operation {
+        GPi2::new(qubit, 0.0.into()).into()
+    }
+
+    fn x180(&self, qubit: usize) -> Operation {
+        GPi::new(qubit, 0.0.into()).into()
+    }
+
+    fn cnot(&self, control: usize, target: usize) -> Vec<Step> {
+        vec![
+            Step::Single(control, hadamard()),
+            Step::Native(MolmerSorensenXX::new(control, target).into()),
+            Step::Single(control, rotate_z(-FRAC_PI_2).dot(&hadamard())),
+            Step::Single(target, rotate_x(-FRAC_PI_2)),
+        ]
+    }
+}
+
+/// Native gates of the OQC devices: RotateZ, SqrtPauliX, PauliX and EchoCrossResonance.
+struct OQCGates {
+    /// The `(control, target)` pairs on which the EchoCrossResonance is calibrated.
+    calibrated: HashSet<(usize, usize)>,
+}
+
+impl OQCGates {
+    /// Returns whether an EchoCrossResonance can be used in the given direction.
+    ///
+    /// Gates between qubits without a calibration in either direction are kept in the requested
+    /// direction, so that they are reported by the validation of the circuit.
+    fn is_calibrated(&self, control: usize, target: usize) -> bool {
+        self.calibrated.contains(&(control, target))
+            || !self.calibrated.contains(&(target, control))
+    }
+}
+
+impl NativeGateSet for OQCGates {
+    fn is_native(&self, operation: &Operation) -> bool {
+        match operation {
+            Operation::EchoCrossResonance(gate) => {
+                self.is_calibrated(*gate.control(), *gate.target())
+            }
+            _ => false,
+        }
+    }
+
+    fn x90(&self, qubit: usize) -> Operation {
+        SqrtPauliX::new(qubit).into()
+    }
+
+    fn x180(&self, qubit: usize) -> Operation {
+        PauliX::new(qubit).into()
+    }
+
+    fn cnot(&self, control: usize, target: usize) -> Vec<Step> {
+        if self.is_calibrated(control, target) {
+            let (after_control, after_target) = echo_cross_resonance_corrections();
+            vec![
+                Step::Single(control, hadamard()),
+                Step::Single(target, hadamard()),
+                Step::Native(EchoCrossResonance::new(control, target).into()),
+                Step::Single(control, after_control),
+                Step::Single(target, after_target),
+            ]
+        } else {
+            // Reverse the CNOT by conjugating it with Hadamard gates on both qubits
+            vec![
+                Step::Single(control, hadamard()),
+                Step::Single(target, hadamard()),
+                Step::Cnot(target, control),
+                Step::Single(control, hadamard()),
+                Step::Single(target, hadamard()),
+            ]
+        }
+    }
+}
+
+/// Native gates of the Rigetti devices: RotateX, RotateZ, ControlledPauliZ, ControlledPhaseShift
+/// and XY.
+struct RigettiGates {
+    /// Whether only rotations by pi/2 around the x-axis and ControlledPauliZ gates are emitted.
+    verbatim_safe: bool,
+}
+
+impl NativeGateSet for RigettiGates {
+    fn is_native(&self, operation: &Operation) -> bool {
+        match operation {
+            Operation::ControlledPauliZ(_) => true,
+            Operation::ControlledPhaseShift(_) | Operation::XY(_) => !self.verbatim_safe,
+            _ => false,
+        }
+    }
+
+    fn x90(&self, qubit: usize) -> Operation {
+        RotateX::new(qubit, FRAC_PI_2.into()).into()
+    }
+
+    fn x180(&self, qubit: usize) -> Operation {
+        RotateX::new(qubit, PI.into()).into()
+    }
+
+    fn emit_x180(&self, circuit: &mut Circuit, qubit: usize) {
+        if self.verbatim_safe {
+            circuit.add_operation(self.x90(qubit));
+            circuit.add_operation(self.x90(qubit));
+        } else {
+            circuit.add_operation(self.x180(qubit));
+        }
+    }
+
+    fn cnot(&self, control: usize, target: usize) -> Vec<Step> {
+        vec![
+            Step::Single(target, hadamard()),
+            Step::Native(ControlledPauliZ::new(control, target).into()),
+            Step::Single(target, hadamard()),
+        ]
+    }
+}
+
+/// A user-defined decomposition of a gate into a circuit.
+type DecompositionRule = Box<dyn Fn(&Operation) -> Result<Circuit, RoqoqoError> + Send + Sync>;
+
+/// Registry of user-defined decomposition rules overriding the built-in decompositions.
+///
+/// A rule replaces every gate with its hqslang name that is not native to the device by a
+/// circuit, which is transpiled in turn. Inside of the replacement the rule of the replaced gate
+/// is not applied again, so a rule may fall back to the built-in decomposition of its own gate.
+/// A rule for CNOT also replaces the CNOTs the built-in decompositions of other gates are made of.
+#[derive(Default)]
+pub struct DecompositionRegistry {
+    /// The rules by the hqslang name of the gate they decompose.
+    rules: HashMap<String, DecompositionRule>,
+}
+
+impl DecompositionRegistry {
+    /// Creates a new registry without rules.
+    ///
+    /// # Returns
+    ///
+    /// The empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule decomposing a gate, replacing a previous rule for the same gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the gate.
+    /// * `rule` - Returns the circuit replacing a gate.
+    pub fn add_rule(
+        &mut self,
+        hqslang: &str,
+        rule: impl Fn(&Operation) -> Result<Circuit, RoqoqoError> + Send + Sync + 'static,
+    ) {
+        self.rules.insert(hqslang.to_string(), Box::new(rule));
+    }
+
+    /// Adds a rule replacing a gate by a fixed circuit.
+    ///
+    /// The template acts on the qubits 0 to n-1, which are mapped onto the qubits of the
+    /// replaced gate, i.e. `(control, target)` for two-qubit gates.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the gate.
+    /// * `template` - The circuit replacing the gate.
+    pub fn add_template(&mut self, hqslang: &str, template: Circuit) {
+        self.add_rule(hqslang, move |operation| {
+            let qubits = gate_qubits(operation).unwrap_or_default();
+            let mapping: HashMap<usize, usize> = qubits.into_iter().enumerate().collect();
+            let mut replacement = Circuit::new();
+            for templated in template.iter() {
+                if let InvolvedQubits::Set(involved) = templated.involved_qubits() {
+                    if let Some(qubit) = involved.iter().find(|q| !mapping.contains_key(*q)) {
+                        return Err(RoqoqoError::GenericError {
+                            msg: format!(
+                                "Template for {} acts on qubit {} but the gate only has {} qubits",
+                                operation.hqslang(),
+                                qubit,
+                                mapping.len()
+                            ),
+                        });
+                    }
+                }
+                replacement.add_operation(templated.remap_qubits(&mapping)?);
+            }
+            Ok(replacement)
+        });
+    }
+
+    /// Removes the rule for a gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `hqslang` - The hqslang name of the gate.
+    ///
+    /// # Returns
+    ///
+    /// True if a rule for the gate was registered.
+    pub fn remove_rule(&mut self, hqslang: &str) -> bool {
+        self.rules.remove(hqslang).is_some()
+    }
+
+    /// Returns the hqslang names of the gates with a rule.
+    ///
+    /// # Returns
+    ///
+    /// The sorted gate names.
+    pub fn gates(&self) -> Vec<String> {
+        let mut gates: Vec<String> = self.rules.keys().cloned().collect();
+        gates.sort();
+        gates
+    }
+}
+
+/// Decomposes a circuit into the native gates of the IonQ devices.
+///
+/// The resulting circuit only contains RotateZ, GPi, GPi2 and MolmerSorensenXX gates besides
+/// the non-gate operations of the input, which are kept in place.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - The circuit contains a gate that cannot be decomposed, e.g. a gate
+///   with symbolic parameters or a gate acting on more than two qubits.
+pub fn to_ionq_native(circuit: &Circuit) -> Result<Circuit, RoqoqoError> {
+    transpile(circuit, &IonQGates, &DecompositionRegistry::new())
+}
+
+/// Decomposes a circuit into the native gates of the OQC devices.
+///
+/// The resulting circuit only contains RotateZ, SqrtPauliX, PauliX and EchoCrossResonance gates
+/// besides the non-gate operations of the input, which are kept in place. EchoCrossResonance
+/// gates are only applied in the directions calibrated on the OQC Lucy device.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - The circuit contains a gate that cannot be decomposed, e.g. a gate
+///   with symbolic parameters or a gate acting on more than two qubits.
+pub fn to_oqc_native(circuit: &Circuit) -> Result<Circuit, RoqoqoError> {
+    let gates = OQCGates {
+        calibrated: OQCLucyDevice::new()
+            .two_qubit_edges_directed()
+            .into_iter()
+            .collect(),
+    };
+    transpile(circuit, &gates, &DecompositionRegistry::new())
+}
+
+/// Decomposes a circuit into the native gates of the Rigetti devices.
+///
+/// The resulting circuit only contains RotateZ gates, RotateX gates by pi/2 or pi and
+/// ControlledPauliZ, ControlledPhaseShift and XY gates besides the non-gate operations of the
+/// input, which are kept in place. The angles of the RotateZ gates are normalized to (-pi, pi].
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - The circuit contains a gate that cannot be decomposed, e.g. a gate
+///   with symbolic parameters or a gate acting on more than two qubits.
+pub fn to_rigetti_native(circuit: &Circuit) -> Result<Circuit, RoqoqoError> {
+    transpile(
+        circuit,
+        &RigettiGates {
+            verbatim_safe: false,
+        },
+        &DecompositionRegistry::new(),
+    )
+}
+
+/// Decomposes a circuit into the native gates of the Rigetti devices that can be run verbatim.
+///
+/// In contrast to [to_rigetti_native] the resulting circuit only contains RotateZ gates,
+/// RotateX gates by pi/2 and ControlledPauliZ gates, so that it can be submitted in a verbatim
+/// box without being recompiled. ControlledPhaseShift and XY gates are decomposed as well.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - The circuit contains a gate that cannot be decomposed, e.g. a gate
+///   with symbolic parameters or a gate acting on more than two qubits.
+pub fn to_rigetti_native_verbatim(circuit: &Circuit) -> Result<Circuit, RoqoqoError> {
+    transpile(
+        circuit,
+        &RigettiGates {
+            verbatim_safe: true,
+        },
+        &DecompositionRegistry::new(),
+    )
+}
+
+/// Decomposes a circuit into the native gates of a device using user-defined rules.
+///
+/// The circuit is transpiled like with [to_ionq_native], [to_oqc_native] or [to_rigetti_native],
+/// but gates with a rule in the registry are decomposed with the rule instead of the built-in
+/// decomposition. For the OQC devices the EchoCrossResonance gates are applied in the directions
+/// calibrated on the given device.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+/// * `device` - The device whose native gates are used.
+/// * `rules` - The rules overriding the built-in decompositions.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - A rule failed or the circuit contains a gate that cannot be decomposed.
+pub fn transpile_with_rules(
+    circuit: &Circuit,
+    device: &AWSDevice,
+    rules: &DecompositionRegistry,
+) -> Result<Circuit, RoqoqoError> {
+    match device {
+        AWSDevice::IonQAria1Device(_) | AWSDevice::IonQHarmonyDevice(_) => {
+            transpile(circuit, &IonQGates, rules)
+        }
+        AWSDevice::OQCLucyDevice(_) => {
+            let gates = OQCGates {
+                calibrated: device.two_qubit_edges_directed().into_iter().collect(),
+            };
+            transpile(circuit, &gates, rules)
+        }
+        AWSDevice::RigettiAspenM3Device(_) => transpile(
+            circuit,
+            &RigettiGates {
+                verbatim_safe: false,
+            },
+            rules,
+        ),
+    }
+}
+
+/// Commutes RotateZ gates through a circuit and merges them.
+///
+/// Rotations around the z-axis are implemented virtually on all supported devices, by shifting the
+/// phase of the subsequent pulses. The pass moves every RotateZ towards the end of the circuit
+/// until it reaches a gate it does not commute with and merges it with the RotateZ gates it
+/// passes. Diagonal gates are passed unchanged, GPi and GPi2 gates are passed by shifting their
+/// phase, and RotateZ gates directly before a measurement of their qubit are removed since they
+/// do not change the measurement result.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to optimize.
+///
+/// # Returns
+///
+/// The optimized circuit.
+pub fn optimize_virtual_z(circuit: &Circuit) -> Circuit {
+    let mut optimized = Circuit::new();
+    let mut pending: HashMap<usize, RotateZ> = HashMap::new();
+    for operation in circuit.iter() {
+        match operation {
+            Operation::RotateZ(gate) => {
+                let qubit = *gate.qubit();
+                let merged = match pending.remove(&qubit) {
+                    Some(previous) => RotateZ::new(qubit, previous.theta().clone() + gate.theta()),
+                    None => gate.clone(),
+                };
+                pending.insert(qubit, merged);
+            }
+            // RotateZ(theta) followed by GPi(phi) is equal to GPi(phi - theta) followed by RotateZ(theta)
+            Operation::GPi(gate) => match pending.get(gate.qubit()) {
+                Some(rotation) => optimized.add_operation(GPi::new(
+                    *gate.qubit(),
+                    gate.theta().clone() - rotation.theta(),
+                )),
+                None => optimized.add_operation(operation.clone()),
+            },
+            Operation::GPi2(gate) => match pending.get(gate.qubit()) {
+                Some(rotation) => optimized.add_operation(GPi2::new(
+                    *gate.qubit(),
+                    gate.theta().clone() - rotation.theta(),
+                )),
+                None => optimized.add_operation(operation.clone()),
+            },
+            Operation::PauliZ(_)
+            | Operation::SGate(_)
+            | Operation::TGate(_)
+            | Operation::PhaseShiftState0(_)
+            | Operation::PhaseShiftState1(_)
+            | Operation::ControlledPauliZ(_)
+            | Operation::ControlledPhaseShift(_)
+            | Operation::PhaseShiftedControlledZ(_)
+            | Operation::PhaseShiftedControlledPhase(_) => {
+                optimized.add_operation(operation.clone())
+            }
+            Operation::MeasureQubit(measurement) => {
+                pending.remove(measurement.qubit());
+                optimized.add_operation(operation.clone());
+            }
+            Operation::PragmaRepeatedMeasurement(_) => {
+                pending.clear();
+                optimized.add_operation(operation.clone());
+            }
+            _ => {
+                let mut qubits: Vec<usize> = match operation.involved_qubits() {
+                    InvolvedQubits::Set(qubits) => qubits.into_iter().collect(),
+                    InvolvedQubits::None => Vec::new(),
+                    _ => pending.keys().copied().collect(),
+                };
+                qubits.sort_unstable();
+                for qubit in qubits {
+                    if let Some(rotation) = pending.remove(&qubit) {
+                        add_virtual_z(&mut optimized, rotation);
+                    }
+                }
+                optimized.add_operation(operation.clone());
+            }
+        }
+    }
+    let mut qubits: Vec<usize> = pending.keys().copied().collect();
+    qubits.sort_unstable();
+    for qubit in qubits {
+        if let Some(rotation) = pending.remove(&qubit) {
+            add_virtual_z(&mut optimized, rotation);
+        }
+    }
+    optimized
+}
+
+/// Adds a merged RotateZ gate to a circuit, leaving out rotations by multiples of 2 pi.
+fn add_virtual_z(circuit: &mut Circuit, rotation: RotateZ) {
+    match rotation.theta().float() {
+        Ok(theta) => add_rotate_z(circuit, *rotation.qubit(), *theta),
+        Err(_) => circuit.add_operation(rotation),
+    }
+}
+
+/// Decomposes a circuit into a native gate set.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to transpile.
+/// * `gates` - The native gates of the device.
+/// * `rules` - The rules overriding the built-in decompositions.
+///
+/// # Returns
+///
+/// * `Ok(Circuit)` - The transpiled circuit.
+/// * `Err(RoqoqoError)` - A rule failed or the circuit contains a gate that cannot be decomposed.
+fn transpile(
+    circuit: &Circuit,
+    gates: &impl NativeGateSet,
+    rules: &DecompositionRegistry,
+) -> Result<Circuit, RoqoqoError> {
+    let mut transpiled = Circuit::new();
+    let mut pending: HashMap<usize, Array2<Complex64>> = HashMap::new();
+    let mut applied: Vec<String> = Vec::new();
+    let mut state = TranspileState {
+        circuit: &mut transpiled,
+        pending: &mut pending,
+        applied: &mut applied,
+    };
+    for operation in circuit.iter() {
+        add_operation(&mut state, operation, gates, rules)?;
+    }
+    let mut qubits: Vec<usize> = pending.keys().copied().collect();
+    qubits.sort_unstable();
+    for qubit in qubits {
+        if let Some(unitary) = pending.remove(&qubit) {
+            emit_single_qubit(&mut transpiled, qubit, &unitary, gates);
+        }
+    }
+    Ok(transpiled)
+}
+
+/// The circuit under construction by the transpiler.
+struct TranspileState<'a> {
+    /// The transpiled circuit.
+    circuit: &'a mut Circuit,
+    /// The merged single-qubit unitaries that have not been emitted yet.
+    pending: &'a mut HashMap<usize, Array2<Complex64>>,
+    /// The hqslang names of the gates whose rule is currently being applied.
+    applied: &'a mut Vec<String>,
+}
+
+/// Decomposes an operation of the input circuit and adds it to the transpiled circuit.
+///
+/// # Arguments
+///
+/// * `state` - The circuit under construction.
+/// * `operation` - The operation to add.
+/// * `gates` - The native gates of the device.
+/// * `rules` - The rules overriding the built-in decompositions.
+///
+/// # Returns
+///
+/// * `Ok(())` - The operation was added.
+/// * `Err(RoqoqoError)` - A rule failed or the operation cannot be decomposed.
+fn add_operation(
+    state: &mut TranspileState,
+    operation: &Operation,
+    gates: &impl NativeGateSet,
+    rules: &DecompositionRegistry,
+) -> Result<(), RoqoqoError> {
+    let is_gate = operation.tags().contains(&"GateOperation");
+    if gates.is_native(operation) || !is_gate {
+        return add_step(state, Step::Native(operation.clone()), gates, rules);
+    }
+    let hqslang = operation.hqslang();
+    if let Some(rule) = rules.rules.get(hqslang) {
+        if !state.applied.iter().any(|applied| applied == hqslang) {
+            let replacement = rule(operation)?;
+            state.applied.push(hqslang.to_string());
+            for replaced in replacement.iter() {
+                add_operation(state, replaced, gates, rules)?;
+            }
+            state.applied.pop();
+            return Ok(());
+        }
+    }
+    for step in gate_steps(operation)? {
+        add_step(state, step, gates, rules)?;
+    }
+    Ok(())
+}
+
+/// Adds a single-qubit unitary or a native operation to the transpiled circuit.
+///
+/// Single-qubit unitaries are merged into the pending unitary of their qubit, native operations
+/// are added after the pending unitaries of their qubits have been emitted and CNOTs are
+/// expressed with the native entangling gate of the device, or with the rule for CNOT.
+///
+/// # Arguments
+///
+/// * `state` - The circuit under construction.
+/// * `step` - The step to add.
+/// * `gates` - The native gates of the device.
+/// * `rules` - The rules overriding the built-in decompositions.
+///
+/// # Returns
+///
+/// * `Ok(())` - The step was added.
+/// * `Err(RoqoqoError)` - The rule for CNOT failed.
+fn add_step(
+    state: &mut TranspileState,
+    step: Step,
+    gates: &impl NativeGateSet,
+    rules: &DecompositionRegistry,
+) -> Result<(), RoqoqoError> {
+    match step {
+        Step::Single(qubit, unitary) => {
+            let merged = match state.pending.remove(&qubit) {
+                Some(previous) => unitary.dot(&previous),
+                None => unitary,
+            };
+            state.pending.insert(qubit, merged);
+        }
+        Step::Native(operation) => {
+            flush(state.circuit, state.pending, &operation, gates);
+            state.circuit.add_operation(operation);
+        }
+        Step::Cnot(control, target) => {
+            let cnot: Operation = CNOT::new(control, target).into();
+            let has_rule = rules.rules.contains_key("CNOT")
+                && !state.applied.iter().any(|applied| applied == "CNOT");
+            if has_rule && !gates.is_native(&cnot) {
+                add_operation(state, &cnot, gates, rules)?;
+            } else {
+                for step in gates.cnot(control, target) {
+                    add_step(state, step, gates, rules)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Emits the pending single-qubit unitaries of the qubits an operation acts on.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit the gates are added to.
+/// * `pending` - The merged single-qubit unitaries that have not been emitted yet.
+/// * `operation` - The operation that is added next.
+/// * `gates` - The native gates of the device.
+fn flush(
+    circuit: &mut Circuit,
+    pending: &mut HashMap<usize, Array2<Complex64>>,
+    operation: &Operation,
+    gates: &impl NativeGateSet,
+) {
+    let mut qubits: Vec<usize> = match operation.involved_qubits() {
+        InvolvedQubits::Set(qubits) => qubits.into_iter().collect(),
+        InvolvedQubits::None => Vec::new(),
+        _ => pending.keys().copied().collect(),
+    };
+    qubits.sort_unstable();
+    for qubit in qubits {
+        if let Some(unitary) = pending.remove(&qubit) {
+            emit_single_qubit(circuit, qubit, &unitary, gates);
+        }
+    }
+}
+
+/// Decomposes a gate into single-qubit unitaries and CNOTs.
+///
+/// # Arguments
+///
+/// * `operation` - The gate to decompose.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Step>)` - The decomposed gate.
+/// * `Err(RoqoqoError)` - The gate has symbolic parameters or acts on more than two qubits.
+fn gate_steps(operation: &Operation) -> Result<Vec<Step>, RoqoqoError> {
+    // Symbolic rotations around the z-axis are native to all devices and cannot be merged
+    if let Operation::RotateZ(gate) = operation {
+        if !gate.theta().is_float() {
+            return Ok(vec![Step::Native(operation.clone())]);
+        }
+    }
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        return Ok(vec![Step::Single(*gate.qubit(), gate.unitary_matrix()?)]);
+    }
+    let steps = match operation {
+        Operation::CNOT(gate) => vec![Step::Cnot(*gate.control(), *gate.target())],
+        Operation::ControlledPauliZ(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            vec![
+                Step::Single(target, hadamard()),
+                Step::Cnot(control, target),
+                Step::Single(target, hadamard()),
+            ]
+        }
+        Operation::ControlledPauliY(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            vec![
+                Step::Single(target, phase(-FRAC_PI_2)),
+                Step::Cnot(control, target),
+                Step::Single(target, phase(FRAC_PI_2)),
+            ]
+        }
+        Operation::SWAP(gate) => swap_steps(*gate.control(), *gate.target()),
+        Operation::FSwap(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let mut steps = swap_steps(control, target);
+            steps.extend([
+                Step::Single(target, hadamard()),
+                Step::Cnot(control, target),
+                Step::Single(target, hadamard()),
+            ]);
+            steps
+        }
+        Operation::ControlledPhaseShift(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let theta = *gate.theta().float()?;
+            vec![
+                Step::Single(target, rotate_z(theta / 2.0)),
+                Step::Cnot(control, target),
+                Step::Single(target, rotate_z(-theta / 2.0)),
+                Step::Cnot(control, target),
+                Step::Single(control, rotate_z(theta / 2.0)),
+            ]
+        }
+        Operation::PhaseShiftedControlledZ(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let phi = *gate.phi().float()?;
+            vec![
+                Step::Single(target, hadamard()),
+                Step::Cnot(control, target),
+                Step::Single(target, hadamard()),
+                Step::Single(control, phase(phi)),
+                Step::Single(target, phase(phi)),
+            ]
+        }
+        Operation::ControlledRotateX(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let theta = *gate.theta().float()?;
+            vec![
+                Step::Single(target, rotate_z(theta / 2.0).dot(&hadamard())),
+                Step::Cnot(control, target),
+                Step::Single(target, rotate_z(-theta / 2.0)),
+                Step::Cnot(control, target),
+                Step::Single(target, hadamard()),
+            ]
+        }
+        Operation::MolmerSorensenXX(gate) => {
+            pauli_rotation(*gate.control(), *gate.target(), Pauli::X, FRAC_PI_2)
+        }
+        Operation::VariableMSXX(gate) => pauli_rotation(
+            *gate.control(),
+            *gate.target(),
+            Pauli::X,
+            *gate.theta().float()?,
+        ),
+        Operation::SpinInteraction(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let mut steps = pauli_rotation(control, target, Pauli::X, 2.0 * gate.x().float()?);
+            steps.extend(pauli_rotation(
+                control,
+                target,
+                Pauli::Y,
+                2.0 * gate.y().float()?,
+            ));
+            steps.extend(pauli_rotation(
+                control,
+                target,
+                Pauli::Z,
+                2.0 * gate.z().float()?,
+            ));
+            steps
+        }
+        Operation::PMInteraction(gate) => {
+            xy_steps(*gate.control(), *gate.target(), -2.0 * gate.t().float()?)
+        }
+        Operation::XY(gate) => xy_steps(*gate.control(), *gate.target(), *gate.theta().float()?),
+        Operation::ISwap(gate) => xy_steps(*gate.control(), *gate.target(), PI),
+        Operation::SqrtISwap(gate) => xy_steps(*gate.control(), *gate.target(), FRAC_PI_2),
+        Operation::InvSqrtISwap(gate) => xy_steps(*gate.control(), *gate.target(), -FRAC_PI_2),
+        Operation::EchoCrossResonance(gate) => {
+            let (control, target) = (*gate.control(), *gate.target());
+            let (after_control, after_target) = echo_cross_resonance_corrections();
+            vec![
+                Step::Single(control, hadamard()),
+                Step::Single(target, hadamard()),
+                Step::Cnot(control, target),
+                Step::Single(control, adjoint(&after_control)),
+                Step::Single(target, adjoint(&after_target)),
+            ]
+        }
+        _ => {
+            return Err(RoqoqoError::GenericError {
+                msg: format!(
+                    "Gate {} cannot be decomposed into native gates",
+                    operation.hqslang()
+                ),
+            })
+        }
+    };
+    Ok(steps)
+}
+
+/// Returns the single-qubit gates after an EchoCrossResonance that together with Hadamard gates
+/// on both qubits before it implement a CNOT.
+///
+/// # Returns
+///
+/// The unitaries on the control and on the target qubit.
+fn echo_cross_resonance_corrections() -> (Array2<Complex64>, Array2<Complex64>) {
+    (
+        rotate_z(-FRAC_PI_2)
+            .dot(&rotate_y(FRAC_PI_2))
+            .dot(&rotate_z(PI)),
+        rotate_z(PI)
+            .dot(&rotate_y(FRAC_PI_2))
+            .dot(&rotate_z(-FRAC_PI_2)),
+    )
+}
+
+/// Pauli operator of a two-qubit Pauli rotation.
+#[derive(Clone, Copy)]
+enum Pauli {
+    X,
+    Y,
+    Z,
+}
+
+/// Decomposes the rotation exp(-i theta/2 P⊗P) into CNOTs and single-qubit gates.
+fn pauli_rotation(control: usize, target: usize, pauli: Pauli, theta: f64) -> Vec<Step> {
+    // Change of basis mapping the Pauli operator to Z
+    let basis = match pauli {
+        Pauli::X => hadamard(),
+        Pauli::Y => rotate_x(FRAC_PI_2),
+        Pauli::Z => Array2::eye(2),
+    };
+    vec![
+        Step::Single(control, basis.clone()),
+        Step::Single(target, basis.clone()),
+        Step::Cnot(control, target),
+        Step::Single(target, rotate_z(theta)),
+        Step::Cnot(control, target),
+        Step::Single(control, adjoint(&basis)),
+        Step::Single(target, adjoint(&basis)),
+    ]
+}
+
+/// Decomposes the XY gate exp(i theta/4 (XX + YY)) into CNOTs and single-qubit gates.
+fn xy_steps(control: usize, target: usize, theta: f64) -> Vec<Step> {
+    let mut steps = pauli_rotation(control, target, Pauli::X, -theta / 2.0);
+    steps.extend(pauli_rotation(control, target, Pauli::Y, -theta / 2.0));
+    steps
+}
+
+/// Decomposes a SWAP into three CNOTs.
+fn swap_steps(control: usize, target: usize) -> Vec<Step> {
+    vec![
+        Step::Cnot(control, target),
+        Step::Cnot(target, control),
+        Step::Cnot(control, target),
+    ]
+}
+
+/// Adds a single-qubit unitary to a circuit as native gates.
+///
+/// The unitary is decomposed as RotateZ(phi) RotateY(theta) RotateZ(lambda) up to a global phase
+/// and the rotation around the y-axis is expressed with rotations around the x-axis.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit the gates are added to.
+/// * `qubit` - The qubit the unitary acts on.
+/// * `unitary` - The single-qubit unitary.
+/// * `gates` - The native gates of the device.
+fn emit_single_qubit(
+    circuit: &mut Circuit,
+    qubit: usize,
+    unitary: &Array2<Complex64>,
+    gates: &impl NativeGateSet,
+) {
+    let (phi, theta, lambda) = zyz_angles(unitary);
+    if theta.abs() < ANGLE_TOLERANCE {
+        add_rotate_z(circuit, qubit, phi + lambda);
+    } else if (theta - FRAC_PI_2).abs() < ANGLE_TOLERANCE {
+        add_rotate_z(circuit, qubit, lambda - FRAC_PI_2);
+        circuit.add_operation(gates.x90(qubit));
+        add_rotate_z(circuit, qubit, phi + FRAC_PI_2);
+    } else if (theta - PI).abs() < ANGLE_TOLERANCE {
+        add_rotate_z(circuit, qubit, lambda - FRAC_PI_2);
+        gates.emit_x180(circuit, qubit);
+        add_rotate_z(circuit, qubit, phi + FRAC_PI_2);
+    } else {
+        add_rotate_z(circuit, qubit, lambda);
+        circuit.add_operation(gates.x90(qubit));
+        add_rotate_z(circuit, qubit, theta + PI);
+        circuit.add_operation(gates.x90(qubit));
+        add_rotate_z(circuit, qubit, phi + PI);
+    }
+}
+
+/// Adds a RotateZ gate to a circuit unless it is the identity up to a global phase.
+fn add_rotate_z(circuit: &mut Circuit, qubit: usize, theta: f64) {
+    // Normalize the angle to (-pi, pi], a rotation by 2 pi only changes the global phase
+    let mut theta = theta.rem_euclid(2.0 * PI);
+    if theta > PI {
+        theta -= 2.0 * PI;
+    }
+    if theta.abs() > ANGLE_TOLERANCE {
+        circuit.add_operation(RotateZ::new(qubit, theta.into()));
+    }
+}
+
+/// Returns the angles (phi, theta, lambda) of a single-qubit unitary
+/// U = exp(i alpha) RotateZ(phi) RotateY(theta) RotateZ(lambda), with theta in [0, pi].
+fn zyz_angles(unitary: &Array2<Complex64>) -> (f64, f64, f64) {
+    let determinant = unitary[[0, 0]] * unitary[[1, 1]] - unitary[[0, 1]] * unitary[[1, 0]];
+    let special = unitary.mapv(|entry| entry / determinant.sqrt());
+    let theta = 2.0 * special[[1, 0]].norm().atan2(special[[0, 0]].norm());
+    if special[[1, 0]].norm() < ANGLE_TOLERANCE {
+        (2.0 * special[[1, 1]].arg(), theta, 0.0)
+    } else if special[[0, 0]].norm() < ANGLE_TOLERANCE {
+        (2.0 * special[[1, 0]].arg(), theta, 0.0)
+    } else {
+        let sum = 2.0 * special[[1, 1]].arg();
+        let difference = 2.0 * special[[1, 0]].arg();
+        ((sum + difference) / 2.0, theta, (sum - difference) / 2.0)
+    }
+}
+
+/// Returns the conjugate transpose of a single-qubit unitary.
+fn adjoint(unitary: &Array2<Complex64>) -> Array2<Complex64> {
+    unitary.t().mapv(|entry| entry.conj())
+}
+
+/// Returns the matrix of the Hadamard gate.
+fn hadamard() -> Array2<Complex64> {
+    let entry = Complex64::new(std::f64::consts::FRAC_1_SQRT_2, 0.0);
+    array![[entry, entry], [entry, -entry]]
+}
+
+/// Returns the matrix of a phase shift on the state 1.
+fn phase(theta: f64) -> Array2<Complex64> {
+    array![
+        [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+        [Complex64::new(0.0, 0.0), Complex64::from_polar(1.0, theta)]
+    ]
+}
+
+/// Returns the matrix of a rotation exp(-i theta/2 X) around the x-axis.
+fn rotate_x(theta: f64) -> Array2<Complex64> {
+    let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+    array![
+        [Complex64::new(cos, 0.0), Complex64::new(0.0, -sin)],
+        [Complex64::new(0.0, -sin), Complex64::new(cos, 0.0)]
+    ]
+}
+
+/// Returns the matrix of a rotation exp(-i theta/2 Y) around the y-axis.
+fn rotate_y(theta: f64) -> Array2<Complex64> {
+    let (cos, sin) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+    array![
+        [Complex64::new(cos, 0.0), Complex64::new(-sin, 0.0)],
+        [Complex64::new(sin, 0.0), Complex64::new(cos, 0.0)]
+    ]
+}
+
+/// Returns the matrix of a rotation exp(-i theta/2 Z) around the z-axis.
+fn rotate_z(theta: f64) -> Array2<Complex64> {
+    array![
+        [
+            Complex64::from_polar(1.0, -theta / 2.0),
+            Complex64::new(0.0, 0.0)
+        ],
+        [
+            Complex64::new(0.0, 0.0),
+            Complex64::from_polar(1.0, theta / 2.0)
+        ]
+    ]
+}
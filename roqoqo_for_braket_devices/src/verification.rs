@@ -0,0 +1,394 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification
+//!
+//! Provides the equivalence check of transpiled and routed circuits against the circuits they
+//! were created from, by simulating both circuits.
+
+use std::collections::{BTreeSet, HashMap};
+use std::f64::consts::PI;
+
+use ndarray::Array2;
+use num_complex::Complex64;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+
+use crate::analysis::circuit_number_qubits;
+
+/// Maximum number of simulated qubits for which the circuits are compared as unitaries.
+const EXACT_QUBITS: usize = 8;
+
+/// Maximum number of simulated qubits of an equivalence check.
+const SIMULATION_QUBITS: usize = 20;
+
+/// Default number of random input states used when the circuits are too large to compare as
+/// unitaries.
+pub const DEFAULT_NUMBER_SAMPLES: usize = 16;
+
+/// Seed of the random input states, fixed so that checks are reproducible.
+const SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Result of the equivalence check of two circuits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EquivalenceCheck {
+    /// The fidelity between the two circuits, estimated by sampling for large circuits.
+    ///
+    /// For exact checks this is the process fidelity |Tr(U^† V)|² / d² of the unitaries, which is
+    /// one if and only if the circuits agree up to a global phase. Otherwise it is the smallest
+    /// state fidelity found for the random input states. This estimates the worst-case fidelity
+    /// but is not a bound on it: input states that were not sampled can have a lower fidelity,
+    /// and more samples make missing them less likely.
+    pub fidelity_estimate: f64,
+    /// Whether the circuits were compared as unitaries.
+    pub exact: bool,
+    /// Number of input states the circuits were simulated with.
+    pub number_states: usize,
+}
+
+impl EquivalenceCheck {
+    /// Returns whether the circuits are equivalent within a tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `tolerance` - The accepted deviation of the fidelity from one.
+    ///
+    /// # Returns
+    ///
+    /// True if the estimated fidelity is at least one minus the tolerance.
+    pub fn is_equivalent(&self, tolerance: f64) -> bool {
+        self.fidelity_estimate >= 1.0 - tolerance
+    }
+}
+
+/// Checks that a transpiled or routed circuit implements the same operation as the original.
+///
+/// The transpiled circuit acts on device qubits: circuit qubit `i` starts on device qubit
+/// `initial_layout[i]` and ends up on device qubit `final_layout[i]`. For a circuit created with
+/// [crate::routing::route_circuit] these are [crate::routing::initial_layout] and the layout
+/// returned by the routing, for transpiled circuits both are the identity. Device qubits that
+/// are not in the initial layout start in the state |0> and have to end up in |0> again.
+///
+/// Circuits on up to eight simulated qubits are compared as unitaries, larger circuits are
+/// simulated with random product states, [DEFAULT_NUMBER_SAMPLES] unless `number_samples` is
+/// given. The sampled comparison can miss differences, see [EquivalenceCheck]. Definitions,
+/// measurements and the pragmas setting the number of measurements are ignored, so only the
+/// gates before the measurements are compared.
+///
+/// # Arguments
+///
+/// * `original` - The circuit before transpilation.
+/// * `transpiled` - The transpiled circuit.
+/// * `initial_layout` - The device qubit of every circuit qubit at the start, `None` for the
+///   identity.
+/// * `final_layout` - The device qubit of every circuit qubit at the end, `None` for the identity.
+/// * `number_samples` - The number of random input states of large circuits, `None` for
+///   [DEFAULT_NUMBER_SAMPLES].
+///
+/// # Returns
+///
+/// * `Ok(EquivalenceCheck)` - The fidelity between the circuits.
+/// * `Err(RoqoqoError)` - A layout misses a qubit, the number of samples is zero, the circuits are
+///   too large to be simulated or contain operations that cannot be simulated, e.g. gates with
+///   symbolic parameters.
+pub fn check_equivalence(
+    original: &Circuit,
+    transpiled: &Circuit,
+    initial_layout: Option<&HashMap<usize, usize>>,
+    final_layout: Option<&HashMap<usize, usize>>,
+    number_samples: Option<usize>,
+) -> Result<EquivalenceCheck, RoqoqoError> {
+    let number_samples = number_samples.unwrap_or(DEFAULT_NUMBER_SAMPLES);
+    if number_samples == 0 {
+        return Err(RoqoqoError::GenericError {
+            msg: "Equivalence check needs at least one sample".to_string(),
+        });
+    }
+    let number_qubits = circuit_number_qubits(original);
+    let identity: HashMap<usize, usize> = (0..number_qubits).map(|qubit| (qubit, qubit)).collect();
+    let initial_layout = initial_layout.unwrap_or(&identity);
+    let final_layout = final_layout.unwrap_or(&identity);
+
+    // The device qubits involved in the check are relabeled to 0..n
+    let mut device_qubits: BTreeSet<usize> = BTreeSet::new();
+    for operation in transpiled.iter() {
+        if let InvolvedQubits::Set(qubits) = operation.involved_qubits() {
+            device_qubits.extend(qubits);
+        }
+    }
+    for layout in [initial_layout, final_layout] {
+        for qubit in 0..number_qubits {
+            let device_qubit = layout
+                .get(&qubit)
+                .ok_or_else(|| RoqoqoError::GenericError {
+                    msg: format!("Layout does not contain circuit qubit {}", qubit),
+                })?;
+            device_qubits.insert(*device_qubit);
+        }
+    }
+    let relabeling: HashMap<usize, usize> = device_qubits
+        .iter()
+        .enumerate()
+        .map(|(index, qubit)| (*qubit, index))
+        .collect();
+    let simulated_qubits = relabeling.len().max(number_qubits);
+    if simulated_qubits > SIMULATION_QUBITS {
+        return Err(RoqoqoError::GenericError {
+            msg: format!(
+                "Equivalence check needs {} qubits but at most {} can be simulated",
+                simulated_qubits, SIMULATION_QUBITS
+            ),
+        });
+    }
+    let initial_positions: Vec<usize> = (0..number_qubits)
+        .map(|qubit| relabeling[&initial_layout[&qubit]])
+        .collect();
+    let final_positions: Vec<usize> = (0..number_qubits)
+        .map(|qubit| relabeling[&final_layout[&qubit]])
+        .collect();
+
+    // Overlap <U psi| V psi> of the two circuits for an input state psi of the original circuit
+    let overlap = |state: Vec<Complex64>| -> Result<Complex64, RoqoqoError> {
+        let mut expected = state.clone();
+        simulate(original, &identity, number_qubits, &mut expected)?;
+        let mut actual = embed(&state, &initial_positions, relabeling.len());
+        simulate(transpiled, &relabeling, relabeling.len(), &mut actual)?;
+        Ok(expected
+            .iter()
+            .enumerate()
+            .map(|(index, amplitude)| {
+                amplitude.conj() * actual[embed_index(index, &final_positions)]
+            })
+            .sum())
+    };
+
+    let dimension = 1_usize << number_qubits;
+    if simulated_qubits <= EXACT_QUBITS {
+        let mut trace = Complex64::new(0.0, 0.0);
+        for index in 0..dimension {
+            let mut basis_state = vec![Complex64::new(0.0, 0.0); dimension];
+            basis_state[index] = Complex64::new(1.0, 0.0);
+            trace += overlap(basis_state)?;
+        }
+        Ok(EquivalenceCheck {
+            fidelity_estimate: trace.norm_sqr() / (dimension as f64).powi(2),
+            exact: true,
+            number_states: dimension,
+        })
+    } else {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut fidelity: f64 = 1.0;
+        for _ in 0..number_samples {
+            let state = random_product_state(number_qubits, &mut rng);
+            fidelity = fidelity.min(overlap(state)?.norm_sqr());
+        }
+        Ok(EquivalenceCheck {
+            fidelity_estimate: fidelity,
+            exact: false,
+            number_states: number_samples,
+        })
+    }
+}
+
+/// Simulates the gates of a circuit on a state vector.
+///
+/// Qubit `i` of the state vector is bit `i` of the index of the amplitudes.
+///
+/// # Arguments
+///
+/// * `circuit` - The circuit to simulate.
+/// * `relabeling` - The state vector qubit of every qubit of the circuit.
+/// * `number_qubits` - The number of qubits of the state vector.
+/// * `state` - The state vector the gates are applied to.
+///
+/// # Returns
+///
+/// * `Ok(())` - The circuit was simulated.
+/// * `Err(RoqoqoError)` - The circuit contains an operation that cannot be simulated.
+fn simulate(
+    circuit: &Circuit,
+    relabeling: &HashMap<usize, usize>,
+    number_qubits: usize,
+    state: &mut [Complex64],
+) -> Result<(), RoqoqoError> {
+    for operation in circuit.iter() {
+        match operation {
+            Operation::DefinitionBit(_)
+            | Operation::DefinitionFloat(_)
+            | Operation::DefinitionComplex(_)
+            | Operation::DefinitionUsize(_)
+            | Operation::InputSymbolic(_)
+            | Operation::MeasureQubit(_)
+            | Operation::PragmaSetNumberOfMeasurements(_)
+            | Operation::PragmaRepeatedMeasurement(_)
+            | Operation::PragmaGlobalPhase(_) => continue,
+            _ => (),
+        }
+        let (matrix, qubits) = gate_matrix(operation)?;
+        let qubits: Vec<usize> = qubits
+            .iter()
+            .map(|qubit| {
+                relabeling
+                    .get(qubit)
+                    .copied()
+                    .filter(|qubit| *qubit < number_qubits)
+                    .ok_or_else(|| RoqoqoError::GenericError {
+                        msg: format!("Qubit {} is outside of the simulated qubits", qubit),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        apply_gate(state, &matrix, &qubits);
+    }
+    Ok(())
+}
+
+/// Returns the unitary matrix of a gate and the qubits it acts on.
+///
+/// The first qubit corresponds to the most significant bit of the rows and columns of the matrix,
+/// e.g. the control qubit of a two-qubit gate.
+///
+/// # Arguments
+///
+/// * `operation` - The gate.
+///
+/// # Returns
+///
+/// * `Ok((Array2<Complex64>, Vec<usize>))` - The unitary matrix and the qubits.
+/// * `Err(RoqoqoError)` - The operation is not a gate or has symbolic parameters.
+pub fn gate_matrix(operation: &Operation) -> Result<(Array2<Complex64>, Vec<usize>), RoqoqoError> {
+    if let Ok(gate) = SingleQubitGateOperation::try_from(operation.clone()) {
+        return Ok((gate.unitary_matrix()?, vec![*gate.qubit()]));
+    }
+    if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+        return Ok((
+            gate.unitary_matrix()?,
+            vec![*gate.control(), *gate.target()],
+        ));
+    }
+    if let Ok(gate) = ThreeQubitGateOperation::try_from(operation.clone()) {
+        return Ok((
+            gate.unitary_matrix()?,
+            vec![*gate.control_0(), *gate.control_1(), *gate.target()],
+        ));
+    }
+    if let Ok(gate) = MultiQubitGateOperation::try_from(operation.clone()) {
+        return Ok((gate.unitary_matrix()?, gate.qubits().clone()));
+    }
+    Err(RoqoqoError::GenericError {
+        msg: format!(
+            "Operation {} is not a gate and cannot be simulated",
+            operation.hqslang()
+        ),
+    })
+}
+
+/// Applies a gate to a state vector.
+///
+/// # Arguments
+///
+/// * `state` - The state vector.
+/// * `matrix` - The unitary matrix of the gate.
+/// * `qubits` - The state vector qubits of the gate, the first one being the most significant.
+pub fn apply_gate(state: &mut [Complex64], matrix: &Array2<Complex64>, qubits: &[usize]) {
+    let size = 1_usize << qubits.len();
+    let offsets: Vec<usize> = (0..size)
+        .map(|local| {
+            qubits
+                .iter()
+                .enumerate()
+                .filter(|(position, _)| (local >> (qubits.len() - 1 - position)) & 1 == 1)
+                .map(|(_, qubit)| 1_usize << qubit)
+                .sum()
+        })
+        .collect();
+    let mask: usize = qubits.iter().map(|qubit| 1_usize << qubit).sum();
+    let mut amplitudes = vec![Complex64::new(0.0, 0.0); size];
+    for base in (0..state.len()).filter(|index| index & mask == 0) {
+        for (local, offset) in offsets.iter().enumerate() {
+            amplitudes[local] = state[base + offset];
+        }
+        for (row, offset) in offsets.iter().enumerate() {
+            state[base + offset] = (0..size)
+                .map(|column| matrix[[row, column]] * amplitudes[column])
+                .sum();
+        }
+    }
+}
+
+/// Returns the index of a basis state after moving its qubits to new positions.
+///
+/// # Arguments
+///
+/// * `index` - The index of the basis state.
+/// * `positions` - The new position of every qubit.
+///
+/// # Returns
+///
+/// The index of the moved basis state.
+fn embed_index(index: usize, positions: &[usize]) -> usize {
+    positions
+        .iter()
+        .enumerate()
+        .filter(|(qubit, _)| (index >> qubit) & 1 == 1)
+        .map(|(_, position)| 1_usize << position)
+        .sum()
+}
+
+/// Embeds a state vector into a larger state vector with the remaining qubits in |0>.
+///
+/// # Arguments
+///
+/// * `state` - The state vector to embed.
+/// * `positions` - The position of every qubit of the state in the larger state vector.
+/// * `number_qubits` - The number of qubits of the larger state vector.
+///
+/// # Returns
+///
+/// The larger state vector.
+fn embed(state: &[Complex64], positions: &[usize], number_qubits: usize) -> Vec<Complex64> {
+    let mut embedded = vec![Complex64::new(0.0, 0.0); 1 << number_qubits];
+    for (index, amplitude) in state.iter().enumerate() {
+        embedded[embed_index(index, positions)] = *amplitude;
+    }
+    embedded
+}
+
+/// Returns a random product state in which every qubit is uniformly distributed on the Bloch
+/// sphere.
+///
+/// # Arguments
+///
+/// * `number_qubits` - The number of qubits.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The state vector.
+fn random_product_state(number_qubits: usize, rng: &mut StdRng) -> Vec<Complex64> {
+    let mut state = vec![Complex64::new(1.0, 0.0)];
+    for _ in 0..number_qubits {
+        let theta = (1.0 - 2.0 * rng.gen::<f64>()).acos();
+        let phi = 2.0 * PI * rng.gen::<f64>();
+        let zero = Complex64::new((theta / 2.0).cos(), 0.0);
+        let one = Complex64::from_polar((theta / 2.0).sin(), phi);
+        // The new qubit is the most significant one
+        let mut extended = state
+            .iter()
+            .map(|amplitude| amplitude * zero)
+            .collect::<Vec<_>>();
+        extended.extend(state.iter().map(|amplitude| amplitude * one));
+        state = extended;
+    }
+    state
+}
@@ -0,0 +1,508 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::measurements::PauliZProductInput;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::braket_ir::*;
+use roqoqo_for_braket_devices::*;
+
+#[test]
+fn test_circuit_to_openqasm3() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0);
+    circuit += RotateZ::new(1, 0.5.into());
+    circuit += CNOT::new(0, 1);
+    circuit += MolmerSorensenXX::new(1, 0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    let program = circuit_to_openqasm3(&circuit, &IonQAria1Device::new().into()).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\n\
+         bit[2] ro;\n\
+         qubit[2] q;\n\
+         h q[0];\n\
+         rz(0.5) q[1];\n\
+         cnot q[0], q[1];\n\
+         ms(0, 0, 1.5707963267948966) q[1], q[0];\n\
+         ro[1] = measure q[0];\n\
+         ro[0] = measure q[1];\n"
+    );
+}
+
+#[test]
+fn test_circuit_to_openqasm3_repeated_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PauliX::new(2);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let program = circuit_to_openqasm3(&circuit, &OQCLucyDevice::new().into()).unwrap();
+    assert!(program.ends_with(
+        "x q[2];\nro[0] = measure q[0];\nro[1] = measure q[1];\nro[2] = measure q[2];\n"
+    ));
+}
+
+#[test]
+fn test_circuit_to_openqasm3_errors() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(8);
+    assert!(circuit_to_openqasm3(&circuit, &device).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaDamping::new(0, 1.0.into(), 0.1.into());
+    assert!(circuit_to_openqasm3(&circuit, &device).is_err());
+}
+
+#[test]
+fn test_circuit_to_openqasm3_free_parameters() {
+    let mut circuit = Circuit::new();
+    circuit += InputSymbolic::new("theta".to_string(), 0.5);
+    circuit += RotateX::new(0, "theta".into());
+    circuit += RotateZ::new(1, "2 * phi + theta".into());
+    let program = circuit_to_openqasm3(&circuit, &OQCLucyDevice::new().into()).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\n\
+         input float phi;\n\
+         input float theta;\n\
+         qubit[2] q;\n\
+         rx(theta) q[0];\n\
+         rz(2 * phi + theta) q[1];\n"
+    );
+    assert_eq!(free_parameters(&circuit), vec!["phi", "theta"]);
+}
+
+#[test]
+fn test_circuit_to_openqasm3_delay() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += PragmaSleep::new(vec![0, 1], 0.5.into());
+    let program = circuit_to_openqasm3(&circuit, &OQCLucyDevice::new().into()).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\nqubit[2] q;\nx q[0];\ndelay[0.5s] q[0], q[1];\n"
+    );
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+    assert!(circuit_to_openqasm3(&circuit, &IonQAria1Device::new().into()).is_err());
+
+    let program = "qubit[1] q;\ndelay[1ms] q;";
+    let mut expected = Circuit::new();
+    expected += PragmaSleep::new(vec![0], 1e-3.into());
+    assert_eq!(circuit_from_openqasm3(program).unwrap(), expected);
+}
+
+#[test]
+fn test_circuit_to_openqasm3_conditional() {
+    let mut conditioned = Circuit::new();
+    conditioned += PauliX::new(1);
+    conditioned += RotateZ::new(1, "theta".into());
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditioned);
+    let program = circuit_to_openqasm3(&circuit, &RigettiAspenM3Device::new().into()).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\nbit[1] ro;\ninput float theta;\nqubit[2] q;\nro[0] = measure q[0];\nif (ro[0]) {\nx q[1];\nrz(theta) q[1];\n}\n"
+    );
+    assert!(circuit_to_openqasm3(&circuit, &OQCLucyDevice::new().into()).is_err());
+    assert!(circuit_to_openqasm3_simulator(&circuit).is_err());
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+
+    let mut conditioned = Circuit::new();
+    conditioned += MeasureQubit::new(1, "ro".to_string(), 0);
+    let mut circuit = Circuit::new();
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditioned);
+    assert!(circuit_to_openqasm3(&circuit, &RigettiAspenM3Device::new().into()).is_err());
+}
+
+#[test]
+fn test_circuit_to_openqasm3_controlled_gates() {
+    let mut circuit = Circuit::new();
+    circuit += ControlledControlledPauliZ::new(0, 1, 2);
+    circuit += ControlledControlledPhaseShift::new(2, 1, 0, 1.0.into());
+    circuit += ControlledRotateX::new(1, 0, "theta".into());
+
+    let program = circuit_to_openqasm3_simulator(&circuit).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\n\
+         input float theta;\n\
+         qubit[3] q;\n\
+         ctrl @ ctrl @ z q[0], q[1], q[2];\n\
+         ctrl @ ctrl @ phaseshift(1) q[2], q[1], q[0];\n\
+         ctrl @ rx(theta) q[1], q[0];\n"
+    );
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+
+    let program = circuit_to_openqasm3(&circuit, &IonQAria1Device::new().into()).unwrap();
+    assert!(!program.contains("ctrl @"));
+    assert!(program.contains("h q[2];\nccnot q[0], q[1], q[2];\nh q[2];\n"));
+    assert!(program.contains("cphaseshift(0.5) q[1], q[0];\ncnot q[2], q[1];\n"));
+    let mut circuit = Circuit::new();
+    circuit += ControlledControlledPauliZ::new(0, 1, 2);
+    let jaqcd: serde_json::Value =
+        serde_json::from_str(&circuit_to_jaqcd(&circuit).unwrap()).unwrap();
+    assert_eq!(jaqcd["instructions"][1]["type"], "ccnot");
+
+    let program = "qubit[2] q;\nctrl @ x q[0], q[1];\npow(2) @ h q[1];";
+    let mut expected = Circuit::new();
+    expected += CNOT::new(0, 1);
+    expected += Hadamard::new(1);
+    expected += Hadamard::new(1);
+    assert_eq!(circuit_from_openqasm3(program).unwrap(), expected);
+    assert!(circuit_from_openqasm3("qubit[2] q;\nctrl @ h q[0], q[1];").is_err());
+    assert!(circuit_from_openqasm3("qubit[1] q;\npow(0.5) @ x q[0];").is_err());
+}
+
+#[test]
+fn test_braket_inputs() {
+    let mut circuit = Circuit::new();
+    circuit += InputSymbolic::new("theta".to_string(), 0.5);
+    circuit += RotateX::new(0, "sin(theta) * phi".into());
+    circuit += RotateY::new(0, 1.0.into());
+
+    let values: HashMap<String, f64> = [("phi".to_string(), 2.0)].into_iter().collect();
+    let expected: HashMap<String, f64> = [("phi".to_string(), 2.0), ("theta".to_string(), 0.5)]
+        .into_iter()
+        .collect();
+    assert_eq!(braket_inputs(&circuit, &values).unwrap(), expected);
+
+    let values: HashMap<String, f64> = [("phi".to_string(), 2.0), ("theta".to_string(), 1.0)]
+        .into_iter()
+        .collect();
+    assert_eq!(braket_inputs(&circuit, &values).unwrap(), values);
+
+    assert!(braket_inputs(&circuit, &HashMap::new()).is_err());
+    let values: HashMap<String, f64> = [("phi".to_string(), 2.0), ("gamma".to_string(), 1.0)]
+        .into_iter()
+        .collect();
+    assert!(braket_inputs(&circuit, &values).is_err());
+}
+
+#[test]
+fn test_circuit_to_jaqcd() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += Hadamard::new(0);
+    circuit += RotateX::new(1, 0.5.into());
+    circuit += CNOT::new(0, 1);
+    circuit += SWAP::new(1, 2);
+    circuit += MolmerSorensenXX::new(0, 2);
+    circuit += Toffoli::new(0, 1, 2);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let program: serde_json::Value =
+        serde_json::from_str(&circuit_to_jaqcd(&circuit).unwrap()).unwrap();
+    assert_eq!(
+        program,
+        serde_json::json!({
+            "braketSchemaHeader": {"name": "braket.ir.jaqcd.program", "version": "1"},
+            "instructions": [
+                {"type": "h", "target": 0},
+                {"type": "rx", "target": 1, "angle": 0.5},
+                {"type": "cnot", "control": 0, "target": 1},
+                {"type": "swap", "targets": [1, 2]},
+                {"type": "xx", "targets": [0, 2], "angle": std::f64::consts::FRAC_PI_2},
+                {"type": "ccnot", "controls": [0, 1], "target": 2},
+            ],
+            "results": [],
+            "basis_rotation_instructions": [],
+        })
+    );
+}
+
+#[test]
+fn test_circuit_to_jaqcd_errors() {
+    let mut circuit = Circuit::new();
+    circuit += GPi::new(0, 0.5.into());
+    assert!(circuit_to_jaqcd(&circuit).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    assert!(circuit_to_jaqcd(&circuit).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += PragmaDamping::new(0, 1.0.into(), 0.1.into());
+    assert!(circuit_to_jaqcd(&circuit).is_err());
+}
+
+#[test]
+fn test_circuit_from_openqasm3_round_trip() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0);
+    circuit += RotateZ::new(1, 0.5.into());
+    circuit += CNOT::new(0, 1);
+    circuit += MolmerSorensenXX::new(1, 0);
+    circuit += VariableMSXX::new(0, 1, 0.25.into());
+    circuit += MeasureQubit::new(0, "ro".to_string(), 1);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 0);
+    let program = circuit_to_openqasm3(&circuit, &IonQAria1Device::new().into()).unwrap();
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_openqasm3_syntax() {
+    let program = "OPENQASM 3.0;\n\
+                   // comment\n\
+                   bit[2] b;\n\
+                   qubit[1] a;\n\
+                   qubit[2] q;\n\
+                   #pragma braket verbatim\n\
+                   box {\n\
+                   rx(-pi / 2) $2;\n\
+                   cz q[0], q[1];\n\
+                   }\n\
+                   si a[0]; b = measure q;\n";
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("b".to_string(), 2, true);
+    expected += RotateX::new(2, (-std::f64::consts::FRAC_PI_2).into());
+    expected += ControlledPauliZ::new(1, 2);
+    expected += PhaseShiftState1::new(0, (-std::f64::consts::FRAC_PI_2).into());
+    expected += MeasureQubit::new(1, "b".to_string(), 0);
+    expected += MeasureQubit::new(2, "b".to_string(), 1);
+    assert_eq!(circuit_from_openqasm3(program).unwrap(), expected);
+}
+
+#[test]
+fn test_circuit_from_openqasm3_conditional_verbatim() {
+    let program = "OPENQASM 3.0;\n\
+                   bit[1] ro;\n\
+                   ro[0] = measure $0;\n\
+                   if (ro[0]) {\n\
+                   #pragma braket verbatim\n\
+                   box{\n\
+                   rx(0.5) $1;\n\
+                   }\n\
+                   }\n\
+                   ro[0] = measure $1;\n";
+    let mut conditioned = Circuit::new();
+    conditioned += RotateX::new(1, 0.5.into());
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 1, true);
+    expected += MeasureQubit::new(0, "ro".to_string(), 0);
+    expected += PragmaConditional::new("ro".to_string(), 0, conditioned);
+    expected += MeasureQubit::new(1, "ro".to_string(), 0);
+    assert_eq!(circuit_from_openqasm3(program).unwrap(), expected);
+}
+
+#[test]
+fn test_circuit_from_openqasm3_free_parameters() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    circuit += CNOT::new(0, 1);
+    circuit += RotateZ::new(1, "theta / 2 + phi".into());
+    let program = circuit_to_openqasm3(&circuit, &OQCLucyDevice::new().into()).unwrap();
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_openqasm3_errors() {
+    assert!(circuit_from_openqasm3("qubit[1] q;\nfoo q[0];").is_err());
+    assert!(circuit_from_openqasm3("qubit[1] q;\nrx(theta) q[0];").is_err());
+    assert!(circuit_from_openqasm3("qubit[1] q;\nh r[0];").is_err());
+    assert!(circuit_from_openqasm3("qubit[2] q;\nh q;").is_err());
+    assert!(circuit_from_openqasm3("qubit[1] q;\nh q[0];\n}").is_err());
+    assert!(circuit_from_openqasm3("bit[1] b;\nqubit[1] q;\nif (b[0]) {\nx q[0];").is_err());
+    assert!(circuit_from_openqasm3("bit[1] b;\nqubit[1] q;\nif (b[1]) {\nx q[0];\n}").is_err());
+    assert!(circuit_from_openqasm3("bit[1] b;\nqubit[1] q;\nif (b == 1) {\nx q[0];\n}").is_err());
+    assert!(
+        circuit_from_openqasm3("bit[1] b;\nqubit[1] q;\nif (b[0]) {\nb[0] = measure q[0];\n}")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_circuit_from_jaqcd_round_trip() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += RotateX::new(1, 0.5.into());
+    circuit += CNOT::new(0, 1);
+    circuit += SWAP::new(1, 2);
+    circuit += VariableMSXX::new(0, 2, 0.3.into());
+    circuit += Toffoli::new(0, 1, 2);
+    let program = circuit_to_jaqcd(&circuit).unwrap();
+    assert_eq!(circuit_from_jaqcd(&program).unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_from_jaqcd_errors() {
+    assert!(circuit_from_jaqcd("not json").is_err());
+    assert!(circuit_from_jaqcd(r#"{"instructions": [{"type": "foo", "target": 0}]}"#).is_err());
+    assert!(circuit_from_jaqcd(r#"{"instructions": [{"type": "h", "target": -1}]}"#).is_err());
+}
+
+#[test]
+fn test_circuit_to_openqasm3_verbatim() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateX::new(0, std::f64::consts::FRAC_PI_2.into());
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let program =
+        circuit_to_openqasm3_verbatim(&circuit, &RigettiAspenM3Device::new().into()).unwrap();
+    assert_eq!(
+        program,
+        "OPENQASM 3.0;\n\
+         bit[2] ro;\n\
+         #pragma braket verbatim\n\
+         box{\n\
+         rx(1.5707963267948966) $0;\n\
+         cz $0, $1;\n\
+         }\n\
+         ro[0] = measure $0;\n\
+         ro[1] = measure $1;\n"
+    );
+    assert_eq!(circuit_from_openqasm3(&program).unwrap(), circuit);
+}
+
+#[test]
+fn test_circuit_to_openqasm3_verbatim_invalid() {
+    let device: AWSDevice = RigettiAspenM3Device::new().into();
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(circuit_to_openqasm3_verbatim(&circuit, &device).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += ControlledPauliZ::new(0, 4);
+    assert!(circuit_to_openqasm3_verbatim(&circuit, &device).is_err());
+}
+
+#[test]
+fn test_pauli_z_product_result_types() {
+    let mut input = PauliZProductInput::new(3, false);
+    input
+        .add_pauliz_product("ro".to_string(), vec![2, 0])
+        .unwrap();
+    input.add_pauliz_product("ro".to_string(), vec![1]).unwrap();
+    assert_eq!(
+        pauli_z_product_result_types(&input, "ro", false).unwrap(),
+        vec![
+            ResultType::Expectation(vec![0, 2]),
+            ResultType::Expectation(vec![1])
+        ]
+    );
+    let results = pauli_z_product_result_types(&input, "ro", true).unwrap();
+    assert_eq!(
+        results,
+        vec![ResultType::Sample(vec![0, 2]), ResultType::Sample(vec![1])]
+    );
+    assert!(pauli_z_product_result_types(&input, "other", false).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 2);
+    let program = circuit_to_openqasm3(&circuit, &IonQAria1Device::new().into()).unwrap();
+    assert_eq!(
+        add_openqasm3_result_types(&program, &results),
+        "OPENQASM 3.0;\n\
+         qubit[3] q;\n\
+         h q[0];\n\
+         cnot q[0], q[2];\n\
+         #pragma braket result sample z(q[0]) @ z(q[2])\n\
+         #pragma braket result sample z(q[1])\n"
+    );
+
+    let program = add_jaqcd_result_types(&circuit_to_jaqcd(&circuit).unwrap(), &results).unwrap();
+    let program: serde_json::Value = serde_json::from_str(&program).unwrap();
+    assert_eq!(
+        program["results"],
+        serde_json::json!([
+            {"type": "sample", "observable": ["z", "z"], "targets": [0, 2]},
+            {"type": "sample", "observable": ["z"], "targets": [1]},
+        ])
+    );
+}
+
+#[test]
+fn test_measurement_mapping() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += DefinitionBit::new("flag".to_string(), 2, true);
+    circuit += MeasureQubit::new(3, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += MeasureQubit::new(1, "flag".to_string(), 0);
+    let mapping = MeasurementMapping::from_circuit(&circuit).unwrap();
+    assert_eq!(mapping.measured_qubits(), vec![1, 3]);
+    assert_eq!(mapping.register("flag"), Some(&vec![Some(1), None]));
+
+    let results = mapping
+        .map_results(
+            &[0, 1, 3],
+            &[vec![true, false, true], vec![false, true, false]],
+        )
+        .unwrap();
+    assert_eq!(results["ro"], vec![vec![true, false], vec![false, true]]);
+    assert_eq!(results["flag"], vec![vec![false, false], vec![true, false]]);
+
+    assert!(mapping.map_results(&[0, 1], &[vec![true, false]]).is_err());
+    assert!(mapping.map_results(&[1, 3], &[vec![true]]).is_err());
+
+    let remapped = mapping.remap_qubits(&[(3, 5), (1, 0)].into_iter().collect());
+    assert_eq!(remapped.measured_qubits(), vec![0, 5]);
+    assert_eq!(remapped.register("ro"), Some(&vec![Some(5), Some(0)]));
+    assert_eq!(remapped.register("flag"), Some(&vec![Some(0), None]));
+    let unchanged = mapping.remap_qubits(&[(3, 4)].into_iter().collect());
+    assert_eq!(unchanged.register("flag"), Some(&vec![Some(1), None]));
+}
+
+#[test]
+fn test_measurement_mapping_repeated_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += PauliX::new(2);
+    circuit += PragmaRepeatedMeasurement::new(
+        "ro".to_string(),
+        10,
+        Some([(0, 2), (2, 0)].into_iter().collect()),
+    );
+    let mapping = MeasurementMapping::from_circuit(&circuit).unwrap();
+    assert_eq!(mapping.register("ro"), Some(&vec![Some(2), None, Some(0)]));
+
+    let mut circuit = Circuit::new();
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    assert!(MeasurementMapping::from_circuit(&circuit).is_err());
+}
+
+#[test]
+fn test_braket_shots() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += DefinitionBit::new("flag".to_string(), 1, true);
+    assert_eq!(braket_shots(&circuit).unwrap(), None);
+
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "flag".to_string(), 0);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    circuit += PragmaSetNumberOfMeasurements::new(100, "flag".to_string());
+    assert_eq!(braket_shots(&circuit).unwrap(), Some(100));
+
+    let mut mixed = circuit.clone();
+    mixed += PragmaSetNumberOfMeasurements::new(10, "flag".to_string());
+    assert!(braket_shots(&mixed).is_err());
+
+    let mut mixed = circuit;
+    mixed += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    assert!(braket_shots(&mixed).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 20, None);
+    assert_eq!(braket_shots(&circuit).unwrap(), Some(20));
+}
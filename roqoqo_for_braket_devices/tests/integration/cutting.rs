@@ -0,0 +1,279 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_complex::Complex64;
+use roqoqo::operations::*;
+use roqoqo::{Circuit, RoqoqoError};
+use roqoqo_for_braket_devices::cutting::{cut_circuit, CircuitCut, MAX_CUTS};
+use roqoqo_for_braket_devices::transpile::{to_oqc_native, to_rigetti_native};
+use roqoqo_for_braket_devices::verification::{apply_gate, gate_matrix};
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+/// Returns a circuit preparing a GHZ state on a chain of qubits.
+fn ghz_circuit(number_qubits: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), number_qubits, true);
+    circuit += Hadamard::new(0);
+    for qubit in 1..number_qubits {
+        circuit += CNOT::new(qubit - 1, qubit);
+    }
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    circuit
+}
+
+/// Returns a Rigetti device with only the given qubits available.
+fn rigetti_device(available: &[usize]) -> AWSDevice {
+    let mut device: AWSDevice = RigettiAspenM3Device::new().into();
+    for qubit in (0..80).filter(|qubit| !available.contains(qubit)) {
+        device.disable_qubit(qubit).unwrap();
+    }
+    device
+}
+
+/// Returns a Rigetti device with only the eight qubits of its first octagon available.
+fn octagon_device() -> AWSDevice {
+    rigetti_device(&(0..8).collect::<Vec<usize>>())
+}
+
+/// Returns the exact expectation value of a product of PauliZ operators after a circuit.
+///
+/// Mid-circuit measurements are simulated by following both outcomes, the contribution of an
+/// outcome `true` of a register in `sign_readouts` is multiplied by -1.
+fn expectation_value(
+    circuit: &Circuit,
+    number_qubits: usize,
+    sign_readouts: &[String],
+    observable: &[usize],
+) -> f64 {
+    let mut initial = vec![Complex64::new(0.0, 0.0); 1 << number_qubits];
+    initial[0] = Complex64::new(1.0, 0.0);
+    let mut branches: Vec<(Vec<Complex64>, f64)> = vec![(initial, 1.0)];
+    for operation in circuit.iter() {
+        match operation {
+            Operation::DefinitionBit(_)
+            | Operation::PragmaRepeatedMeasurement(_)
+            | Operation::PragmaSetNumberOfMeasurements(_) => {}
+            Operation::MeasureQubit(measurement) => {
+                let qubit = *measurement.qubit();
+                let flip = sign_readouts.contains(measurement.readout());
+                branches = branches
+                    .into_iter()
+                    .flat_map(|(state, sign)| {
+                        [false, true].into_iter().map(move |outcome| {
+                            let projected: Vec<Complex64> = state
+                                .iter()
+                                .enumerate()
+                                .map(|(index, amplitude)| {
+                                    if (index >> qubit) & 1 == usize::from(outcome) {
+                                        *amplitude
+                                    } else {
+                                        Complex64::new(0.0, 0.0)
+                                    }
+                                })
+                                .collect();
+                            let sign = if outcome && flip { -sign } else { sign };
+                            (projected, sign)
+                        })
+                    })
+                    .collect();
+            }
+            _ => {
+                let (matrix, qubits) = gate_matrix(operation).unwrap();
+                for (state, _) in branches.iter_mut() {
+                    apply_gate(state, &matrix, &qubits);
+                }
+            }
+        }
+    }
+    branches
+        .iter()
+        .map(|(state, sign)| {
+            sign * state
+                .iter()
+                .enumerate()
+                .map(|(index, amplitude)| {
+                    let parity = observable
+                        .iter()
+                        .filter(|qubit| (index >> *qubit) & 1 == 1)
+                        .count();
+                    let eigenvalue = if parity % 2 == 0 { 1.0 } else { -1.0 };
+                    eigenvalue * amplitude.norm_sqr()
+                })
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+/// Checks that every fragment of every variant can be run on the device after transpilation.
+fn assert_fragments_valid(
+    cut: &CircuitCut,
+    device: &AWSDevice,
+    to_native: fn(&Circuit) -> Result<Circuit, RoqoqoError>,
+) {
+    for variant in &cut.variants {
+        for circuit in &variant.circuits {
+            let native = to_native(circuit).unwrap();
+            assert_eq!(device.validate_circuit(&native), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn test_cut_circuit() {
+    let device = octagon_device();
+    let cut = cut_circuit(&ghz_circuit(10), &device).unwrap();
+    assert_eq!(
+        cut.fragments,
+        vec![(0..8).collect::<Vec<usize>>(), vec![8, 9]]
+    );
+    assert_eq!(cut.variants.len(), 6);
+    let coefficients: Vec<f64> = cut
+        .variants
+        .iter()
+        .map(|variant| variant.coefficient)
+        .collect();
+    assert_eq!(coefficients, vec![0.5, 0.5, 0.5, -0.5, 0.5, -0.5]);
+    assert_eq!(
+        cut.variants[2].sign_readouts,
+        vec![vec!["cut_0".to_string()], vec![]]
+    );
+    assert_eq!(
+        cut.variants[4].sign_readouts,
+        vec![vec![], vec!["cut_0".to_string()]]
+    );
+    for variant in &cut.variants {
+        assert_eq!(variant.circuits.len(), 2);
+        assert!(variant.circuits[1]
+            .iter()
+            .all(|operation| match operation.involved_qubits() {
+                InvolvedQubits::Set(qubits) => qubits.iter().all(|qubit| *qubit < 2),
+                _ => true,
+            }));
+    }
+    match cut.variants[0].circuits[1].iter().last() {
+        Some(Operation::PragmaRepeatedMeasurement(measurement)) => {
+            let mapping = measurement.qubit_mapping().clone().unwrap();
+            assert_eq!(mapping[&0], 8);
+            assert_eq!(mapping[&1], 9);
+        }
+        _ => panic!("Last operation is not the measurement"),
+    }
+    let measured: Vec<usize> = cut.variants[2].circuits[0]
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::MeasureQubit(measurement) if measurement.readout() == "ro" => {
+                Some(*measurement.qubit())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(measured, (0..8).collect::<Vec<usize>>());
+    assert!(matches!(
+        cut.variants[2].circuits[0].iter().last(),
+        Some(Operation::PragmaSetNumberOfMeasurements(_))
+    ));
+
+    assert_fragments_valid(&cut, &device, to_rigetti_native);
+
+    let results = vec![vec![1.0, 1.0]; 6];
+    assert_eq!(cut.recombine(&results).unwrap(), 1.0);
+    assert!(cut.recombine(&results[..5]).is_err());
+}
+
+#[test_case(vec![2]; "second fragment")]
+#[test_case(vec![0, 2]; "both fragments")]
+#[test_case(vec![0, 1, 2]; "all qubits")]
+fn test_cut_circuit_recombination(observable: Vec<usize>) {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateY::new(0, 0.7.into());
+    circuit += RotateX::new(1, 1.1.into());
+    circuit += Hadamard::new(2);
+    circuit += CNOT::new(0, 1);
+    circuit += CNOT::new(1, 2);
+    circuit += RotateY::new(2, 0.4.into());
+    circuit += ControlledPauliZ::new(2, 1);
+    circuit += RotateX::new(1, 0.3.into());
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+
+    let cut = cut_circuit(&circuit, &rigetti_device(&[0, 1])).unwrap();
+    assert_eq!(cut.fragments, vec![vec![0, 1], vec![2]]);
+    assert_eq!(cut.variants.len(), 36);
+
+    let results: Vec<Vec<f64>> = cut
+        .variants
+        .iter()
+        .map(|variant| {
+            cut.fragments
+                .iter()
+                .enumerate()
+                .map(|(fragment, qubits)| {
+                    let local_observable: Vec<usize> = qubits
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, qubit)| observable.contains(qubit))
+                        .map(|(index, _)| index)
+                        .collect();
+                    expectation_value(
+                        &variant.circuits[fragment],
+                        qubits.len(),
+                        &variant.sign_readouts[fragment],
+                        &local_observable,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+    let expected = expectation_value(&circuit, 3, &[], &observable);
+    assert!((cut.recombine(&results).unwrap() - expected).abs() < 1e-10);
+}
+
+#[test]
+fn test_cut_circuit_largest_component() {
+    let device = rigetti_device(&[0, 1, 4, 5]);
+    let cut = cut_circuit(&ghz_circuit(3), &device).unwrap();
+    assert_eq!(cut.fragments, vec![vec![0, 1], vec![2]]);
+    assert_fragments_valid(&cut, &device, to_rigetti_native);
+}
+
+#[test]
+fn test_cut_circuit_without_mid_circuit_measurement() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    assert!(!device.supports_mid_circuit_measurement());
+    assert!(cut_circuit(&ghz_circuit(10), &device).is_err());
+}
+
+#[test]
+fn test_cut_circuit_fitting() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let cut = cut_circuit(&ghz_circuit(4), &device).unwrap();
+    assert_eq!(cut.fragments, vec![vec![0, 1, 2, 3]]);
+    assert_eq!(cut.variants.len(), 1);
+    assert_eq!(cut.variants[0].coefficient, 1.0);
+    assert_eq!(cut.variants[0].circuits[0].len(), ghz_circuit(4).len());
+    assert_fragments_valid(&cut, &device, to_oqc_native);
+}
+
+#[test]
+fn test_cut_circuit_errors() {
+    let device = octagon_device();
+    let mut circuit = ghz_circuit(10);
+    for _ in 0..MAX_CUTS {
+        circuit += CNOT::new(0, 9);
+    }
+    assert!(cut_circuit(&circuit, &device).is_err());
+
+    let mut circuit = ghz_circuit(10);
+    circuit += SWAP::new(0, 9);
+    assert!(cut_circuit(&circuit, &device).is_err());
+}
@@ -10,6 +10,8 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use ndarray::array;
 use roqoqo::devices::{Device, QoqoDevice};
 use roqoqo_for_braket_devices::*;
@@ -362,6 +364,66 @@ fn test_add_dephasing(mut device: AWSDevice) {
     assert!(device.add_dephasing(200, 0.2).is_err());
 }
 
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_set_qubit_decoherence_rates(mut device: AWSDevice) {
+    device.add_damping(0, 0.5).unwrap();
+    let rates = array![[0.1, 0.0, 0.0], [0.0, 0.2, 0.0], [0.0, 0.0, 0.3]];
+    device
+        .set_qubit_decoherence_rates(0, rates.clone())
+        .unwrap();
+    assert_eq!(device.qubit_decoherence_rates(&0).unwrap(), rates);
+
+    assert!(device.set_qubit_decoherence_rates(200, rates).is_err());
+    assert!(device
+        .set_qubit_decoherence_rates(0, array![[0.1, 0.0], [0.0, 0.2]])
+        .is_err());
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_crosstalk(mut device: AWSDevice) {
+    assert_eq!(device.crosstalk(0, 1), None);
+    assert_eq!(device.crosstalk_matrix(), None);
+
+    device.set_crosstalk(0, 1, 0.05).unwrap();
+    assert_eq!(device.crosstalk(0, 1), Some(0.05));
+    assert_eq!(device.crosstalk(1, 0), Some(0.0));
+    let matrix = device.crosstalk_matrix().unwrap();
+    assert_eq!(
+        matrix.shape(),
+        [device.number_qubits(), device.number_qubits()]
+    );
+
+    assert!(device.set_crosstalk(0, 200, 0.1).is_err());
+    assert!(device.set_crosstalk(1, 1, 0.1).is_err());
+    assert_eq!(device.crosstalk(0, 200), None);
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_two_qubit_error_rate(mut device: AWSDevice) {
+    let (control, target) = device.two_qubit_edges()[0];
+    assert_eq!(device.two_qubit_error_rate(control, target), None);
+
+    device
+        .set_two_qubit_error_rate(control, target, 0.02)
+        .unwrap();
+    assert_eq!(device.two_qubit_error_rate(control, target), Some(0.02));
+    assert_eq!(device.two_qubit_error_rate(target, control), Some(0.02));
+
+    assert!(device
+        .set_two_qubit_error_rate(control, target, 1.5)
+        .is_err());
+    assert!(device.set_two_qubit_error_rate(0, 200, 0.01).is_err());
+}
+
 #[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
 #[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
 #[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
@@ -370,6 +432,66 @@ fn test_longest_chain(device: AWSDevice) {
     assert!(!device.longest_chains().is_empty());
 }
 
+/// Checks that a chain is a path through distinct connected qubits
+fn is_valid_chain(device: &AWSDevice, chain: &[usize], closed: bool) -> bool {
+    let edges = device.two_qubit_edges();
+    let connected = |a: usize, b: usize| edges.contains(&(a, b)) || edges.contains(&(b, a));
+    let distinct = chain.iter().collect::<std::collections::HashSet<_>>().len() == chain.len();
+    let linked = chain.windows(2).all(|pair| connected(pair[0], pair[1]));
+    let closing = !closed || connected(chain[chain.len() - 1], chain[0]);
+    distinct && linked && closing
+}
+
+#[test]
+fn test_rigetti_longest_chains() {
+    // The search on the full lattice is stopped after a fixed number of steps, so only a lower
+    // bound on the length of the chains is checked
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    let chains = device.longest_chains();
+    assert_eq!(chains.len(), 1);
+    assert!(chains[0].len() >= 70);
+    assert!(is_valid_chain(&device, &chains[0], false));
+
+    let closed_chains = device.longest_closed_chains();
+    assert_eq!(closed_chains.len(), 1);
+    assert!(closed_chains[0].len() >= 70);
+    assert!(is_valid_chain(&device, &closed_chains[0], true));
+}
+
+#[test]
+fn test_longest_chains_exact() {
+    // The graphs are small enough for the search to finish within its budget
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(device.longest_chains()[0].len(), 8);
+    assert_eq!(device.longest_closed_chains()[0].len(), 8);
+    assert!(is_valid_chain(
+        &device,
+        &device.longest_closed_chains()[0],
+        true
+    ));
+
+    // Without edge (3, 4) the only chain through all qubits starts and ends on it
+    device.disable_edge(3, 4).unwrap();
+    let chains = device.longest_chains();
+    let chain = &chains[0];
+    assert_eq!(chain.len(), 8);
+    assert!(is_valid_chain(&device, chain, false));
+    let ends = [chain[0], chain[7]];
+    assert!(ends.contains(&3) && ends.contains(&4));
+    assert!(device.longest_closed_chains()[0].is_empty());
+
+    device.disable_qubit(0).unwrap();
+    let mut chain = device.longest_chains()[0].clone();
+    chain.sort_unstable();
+    assert_eq!(chain, vec![4, 5, 6, 7]);
+
+    let mut device = AWSDevice::from(IonQHarmonyDevice::new());
+    device.disable_edge(0, 1).unwrap();
+    let closed_chains = device.longest_closed_chains();
+    assert_eq!(closed_chains[0].len(), device.number_qubits());
+    assert!(is_valid_chain(&device, &closed_chains[0], true));
+}
+
 #[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
 #[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
 #[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
@@ -446,3 +568,319 @@ fn test_to_generic_device(device: AWSDevice) {
     created_edges.sort();
     assert_eq!(device_edges, created_edges);
 }
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_bulk_noise_setters(mut device: AWSDevice) {
+    device.add_damping_all(0.1).unwrap();
+    device.add_dephasing_all(0.2).unwrap();
+    for qubit in 0..device.number_qubits() {
+        let rates = device.qubit_decoherence_rates(&qubit).unwrap();
+        assert_eq!(rates[[0, 0]], 0.1);
+        assert_eq!(rates[[2, 2]], 0.2);
+    }
+
+    let rates: HashMap<usize, f64> = [(0, 0.1), (1, 0.3)].into_iter().collect();
+    device.add_damping_rates(&rates).unwrap();
+    device.add_dephasing_rates(&rates).unwrap();
+    assert_eq!(device.qubit_decoherence_rates(&1).unwrap()[[0, 0]], 0.4);
+    assert_eq!(device.qubit_decoherence_rates(&1).unwrap()[[2, 2]], 0.5);
+
+    let wrong_rates: HashMap<usize, f64> = [(0, 0.1), (200, 0.1)].into_iter().collect();
+    assert!(device.add_damping_rates(&wrong_rates).is_err());
+    assert_eq!(device.qubit_decoherence_rates(&0).unwrap()[[0, 0]], 0.2);
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_clear_noise(mut device: AWSDevice) {
+    let gate = device.single_qubit_gate_names()[0].clone();
+    device.set_single_qubit_gate_time(&gate, 0, 2.0).unwrap();
+    device.add_damping(0, 0.1).unwrap();
+    device.add_damping(1, 0.1).unwrap();
+
+    device.clear_decoherence(0).unwrap();
+    assert_eq!(device.qubit_decoherence_rates(&0), None);
+    assert!(device.qubit_decoherence_rates(&1).is_some());
+    assert!(device.clear_decoherence(200).is_err());
+
+    let (control, target) = device.two_qubit_edges()[0];
+    device.set_crosstalk(0, 1, 0.1).unwrap();
+    device
+        .set_two_qubit_error_rate(control, target, 0.01)
+        .unwrap();
+    device.set_readout_error(0, 0.1, 0.1).unwrap();
+    device.set_preparation_error(0, 0.1).unwrap();
+    device.set_leakage_rate(0, 0.1).unwrap();
+    device.clear_all_noise();
+    assert_eq!(device.qubit_decoherence_rates(&1), None);
+    assert_eq!(device.crosstalk_matrix(), None);
+    assert_eq!(device.two_qubit_error_rate(control, target), None);
+    assert_eq!(device.readout_error(0), None);
+    assert_eq!(device.preparation_error(0), None);
+    assert_eq!(device.leakage_rate(0), None);
+    assert_eq!(device.single_qubit_gate_time(&gate, &0), Some(2.0));
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_leakage_rate(mut device: AWSDevice) {
+    assert_eq!(device.leakage_rate(0), None);
+    device.set_leakage_rate(0, 0.001).unwrap();
+    assert_eq!(device.leakage_rate(0), Some(0.001));
+    assert!(device.set_leakage_rate(0, -0.1).is_err());
+    assert!(device.set_leakage_rate(200, 0.1).is_err());
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_set_coherence_times(mut device: AWSDevice) {
+    device.set_coherence_times(0, 2.0, 1.0).unwrap();
+    let rates = device.qubit_decoherence_rates(&0).unwrap();
+    assert_eq!(rates[[0, 0]], 0.5);
+    assert_eq!(rates[[2, 2]], 0.375);
+
+    assert!(device.set_coherence_times(0, 1.0, 3.0).is_err());
+    assert!(device.set_coherence_times(0, -1.0, 1.0).is_err());
+    assert!(device.set_coherence_times(200, 1.0, 1.0).is_err());
+
+    let correlated = array![[1.0, 1.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 0.0]];
+    device
+        .set_qubit_decoherence_rates(1, correlated.clone())
+        .unwrap();
+    assert!(device.set_coherence_times(1, 2.0, 1.0).is_err());
+    assert_eq!(device.qubit_decoherence_rates(&1), Some(correlated));
+
+    let properties = r#"{
+        "standardized": {
+            "oneQubitProperties": {
+                "1": {
+                    "T1": {"value": 20.0, "standardError": null, "unit": "US"},
+                    "T2": {"value": 10.0, "standardError": null, "unit": "US"}
+                },
+                "2": {"oneQubitFidelity": []}
+            }
+        }
+    }"#;
+    device
+        .set_coherence_times_from_properties(properties)
+        .unwrap();
+    let rates = device.qubit_decoherence_rates(&1).unwrap();
+    assert!((rates[[0, 0]] - 5e4).abs() < 1e-6);
+    assert!((rates[[2, 2]] - 3.75e4).abs() < 1e-6);
+    assert_eq!(device.qubit_decoherence_rates(&2), None);
+
+    assert!(device.set_coherence_times_from_properties("{").is_err());
+
+    let (t1, t2) = device.coherence_times(1);
+    assert!((t1.unwrap() - 20e-6).abs() < 1e-15);
+    assert!((t2.unwrap() - 10e-6).abs() < 1e-15);
+    assert_eq!(device.coherence_times(2), (None, None));
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_validate_noise(mut device: AWSDevice) {
+    device.add_damping(0, 0.1).unwrap();
+    device.validate_noise().unwrap();
+
+    assert!(device.add_damping(0, -0.2).is_err());
+    assert!(device.add_dephasing(1, -0.1).is_err());
+    assert_eq!(device.qubit_decoherence_rates(&0).unwrap()[[0, 0]], 0.1);
+    assert_eq!(device.qubit_decoherence_rates(&1), None);
+
+    let not_symmetric = array![[0.1, 0.1, 0.0], [0.0, 0.1, 0.0], [0.0, 0.0, 0.0]];
+    assert!(device
+        .set_qubit_decoherence_rates(0, not_symmetric)
+        .is_err());
+    let not_positive = array![[0.1, 0.2, 0.0], [0.2, 0.1, 0.0], [0.0, 0.0, 0.0]];
+    assert!(device.set_qubit_decoherence_rates(0, not_positive).is_err());
+    let valid = array![[0.1, 0.1, 0.0], [0.1, 0.1, 0.0], [0.0, 0.0, 0.2]];
+    device.set_qubit_decoherence_rates(0, valid).unwrap();
+    device.validate_noise().unwrap();
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_disable_qubit_and_edge(mut device: AWSDevice) {
+    let number_edges = device.two_qubit_edges().len();
+    let (control, target) = device.two_qubit_edges()[0];
+    let gate = device.two_qubit_gate_names()[0].clone();
+    assert!(device
+        .two_qubit_gate_time(&gate, &control, &target)
+        .is_some());
+
+    device.disable_edge(target, control).unwrap();
+    assert_eq!(device.two_qubit_edges().len(), number_edges - 1);
+    assert!(!device.two_qubit_edges().contains(&(control, target)));
+    assert!(device
+        .two_qubit_gate_time(&gate, &control, &target)
+        .is_none());
+    assert!(device
+        .two_qubit_gate_time(&gate, &target, &control)
+        .is_none());
+
+    let qubit = device.number_qubits() - 1;
+    let single_gate = device.single_qubit_gate_names()[0].clone();
+    device.disable_qubit(qubit).unwrap();
+    assert!(device
+        .single_qubit_gate_time(&single_gate, &qubit)
+        .is_none());
+    assert!(device
+        .two_qubit_edges()
+        .iter()
+        .all(|&(a, b)| a != qubit && b != qubit));
+
+    assert!(device.disable_qubit(device.number_qubits()).is_err());
+    assert!(device.disable_edge(0, device.number_qubits()).is_err());
+}
+
+#[test]
+fn test_disable_edge_unconnected() {
+    let mut device = OQCLucyDevice::new();
+    assert!(device.disable_edge(0, 4).is_err());
+}
+
+#[test]
+fn test_add_edge() {
+    let mut device = OQCLucyDevice::new();
+    let number_edges = device.two_qubit_edges().len();
+    device.add_edge("EchoCrossResonance", 0, 4, 2.0).unwrap();
+    assert_eq!(device.two_qubit_edges().len(), number_edges + 1);
+    assert!(device.two_qubit_edges().contains(&(0, 4)));
+    assert_eq!(
+        device.two_qubit_gate_time("EchoCrossResonance", &4, &0),
+        Some(2.0)
+    );
+
+    device.disable_edge(0, 4).unwrap();
+    assert!(!device.two_qubit_edges().contains(&(0, 4)));
+    device.add_edge("EchoCrossResonance", 4, 0, 3.0).unwrap();
+    assert_eq!(device.two_qubit_edges().len(), number_edges + 1);
+    assert_eq!(
+        device.two_qubit_gate_time("EchoCrossResonance", &0, &4),
+        Some(3.0)
+    );
+
+    assert!(device.add_edge("EchoCrossResonance", 0, 8, 1.0).is_err());
+    assert!(device.add_edge("EchoCrossResonance", 2, 2, 1.0).is_err());
+    assert!(device.add_edge("CNOT", 0, 5, 1.0).is_err());
+    device.disable_qubit(5).unwrap();
+    assert!(device.add_edge("EchoCrossResonance", 0, 5, 1.0).is_err());
+    assert!(!device.two_qubit_edges().contains(&(0, 5)));
+}
+
+#[test]
+fn test_oqc_longest_chains_with_disabled_edge() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(
+        device.longest_closed_chains(),
+        vec![vec![0, 1, 2, 3, 4, 5, 6, 7]]
+    );
+    device.disable_edge(3, 4).unwrap();
+    let chains = device.longest_chains();
+    assert_eq!(chains[0].len(), 8);
+    assert!(is_valid_chain(&device, &chains[0], false));
+    assert_eq!(device.longest_closed_chains(), vec![Vec::<usize>::new()]);
+
+    device.add_edge("EchoCrossResonance", 3, 7, 1.0).unwrap();
+    let closed_chains = device.longest_closed_chains();
+    assert_eq!(closed_chains[0].len(), 5);
+    assert!(is_valid_chain(&device, &closed_chains[0], true));
+}
+
+#[test]
+fn test_ionq_longest_chains_with_disabled_qubits() {
+    let mut device = AWSDevice::from(IonQHarmonyDevice::new());
+    device.disable_qubit(3).unwrap();
+    let closed_chains = device.longest_closed_chains();
+    assert_eq!(closed_chains[0].len(), device.number_qubits() - 1);
+    assert!(!closed_chains[0].contains(&3));
+    assert!(is_valid_chain(&device, &closed_chains[0], true));
+
+    device.disable_edge(0, 1).unwrap();
+    let closed_chains = device.longest_closed_chains();
+    assert_eq!(closed_chains[0].len(), device.number_qubits() - 1);
+    assert!(is_valid_chain(&device, &closed_chains[0], true));
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_two_qubit_edges_directed_symmetric(device: AWSDevice) {
+    let directed = device.two_qubit_edges_directed();
+    assert_eq!(directed.len(), 2 * device.two_qubit_edges().len());
+    for (control, target) in device.two_qubit_edges() {
+        assert!(directed.contains(&(control, target)));
+        assert!(directed.contains(&(target, control)));
+    }
+}
+
+#[test]
+fn test_oqc_two_qubit_edges_directed() {
+    let mut device = OQCLucyDevice::new();
+    let directed = device.two_qubit_edges_directed();
+    assert_eq!(directed.len(), device.two_qubit_edges().len());
+    assert!(directed.contains(&(4, 3)));
+    assert!(!directed.contains(&(3, 4)));
+    for (control, target) in device.two_qubit_edges() {
+        assert!(directed.contains(&(control, target)) ^ directed.contains(&(target, control)));
+    }
+    for (control, target) in device.two_qubit_edges() {
+        assert!(device
+            .two_qubit_gate_time("EchoCrossResonance", &control, &target)
+            .is_some());
+        assert!(device
+            .two_qubit_gate_time("EchoCrossResonance", &target, &control)
+            .is_some());
+    }
+
+    device
+        .set_two_qubit_gate_time("EchoCrossResonance", 3, 4, 2.0)
+        .unwrap();
+    assert!(!device.two_qubit_edges_directed().contains(&(3, 4)));
+
+    device.disable_edge(3, 4).unwrap();
+    device.add_edge("EchoCrossResonance", 2, 6, 1.0).unwrap();
+    let directed = device.two_qubit_edges_directed();
+    assert!(!directed.contains(&(4, 3)));
+    assert!(directed.contains(&(2, 6)));
+    assert!(directed.contains(&(6, 2)));
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_qubit_positions(device: AWSDevice) {
+    let positions = device.qubit_positions();
+    assert_eq!(positions.len(), device.number_qubits());
+    for (first, a) in positions.iter().enumerate() {
+        for b in positions.iter().skip(first + 1) {
+            assert!((a.0 - b.0).hypot(a.1 - b.1) > 0.1);
+        }
+    }
+}
+
+#[test]
+fn test_rigetti_qubit_positions_neighbours() {
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    let positions = device.qubit_positions();
+    // Connected qubits are drawn close to each other
+    for (first, second) in device.two_qubit_edges() {
+        let (a, b) = (positions[first], positions[second]);
+        assert!((a.0 - b.0).hypot(a.1 - b.1) < 1.5);
+    }
+}
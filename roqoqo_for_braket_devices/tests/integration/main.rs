@@ -10,5 +10,35 @@
 // express or implied. See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod braket_ir;
+
+#[cfg(test)]
+mod cutting;
+
 #[cfg(test)]
 mod device;
+
+#[cfg(test)]
+mod scoring;
+
+#[cfg(test)]
+mod validation;
+
+#[cfg(test)]
+mod noise_models;
+
+#[cfg(test)]
+mod pulse;
+
+#[cfg(test)]
+mod routing;
+
+#[cfg(test)]
+mod topology;
+
+#[cfg(test)]
+mod transpile;
+
+#[cfg(test)]
+mod verification;
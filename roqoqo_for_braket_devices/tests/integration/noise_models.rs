@@ -0,0 +1,285 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ndarray::array;
+use roqoqo::devices::QoqoDevice;
+use roqoqo::noise_models::{
+    ContinuousDecoherenceModel, DecoherenceOnGateModel, ImperfectReadoutModel, NoiseModel,
+};
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_to_continuous_decoherence_model(mut device: AWSDevice) {
+    assert_eq!(
+        device.to_continuous_decoherence_model().unwrap(),
+        ContinuousDecoherenceModel::new()
+    );
+
+    device.add_damping(0, 0.5).unwrap();
+    device.add_dephasing(0, 0.2).unwrap();
+    device
+        .set_qubit_decoherence_rates(1, array![[0.0, 0.0, 0.0], [0.0, 0.1, 0.0], [0.0, 0.0, 0.0]])
+        .unwrap();
+    let expected = ContinuousDecoherenceModel::new()
+        .add_damping_rate(&[0], 0.5)
+        .add_dephasing_rate(&[0], 0.2)
+        .add_excitation_rate(&[1], 0.1);
+    assert_eq!(device.to_continuous_decoherence_model().unwrap(), expected);
+
+    device
+        .set_qubit_decoherence_rates(2, array![[0.1, 0.1, 0.0], [0.1, 0.1, 0.0], [0.0, 0.0, 0.0]])
+        .unwrap();
+    assert!(device.to_continuous_decoherence_model().is_err());
+}
+
+#[test]
+fn test_to_error_on_gate_model() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(
+        device.to_error_on_gate_model().unwrap(),
+        DecoherenceOnGateModel::new()
+    );
+
+    device.add_damping(0, 0.5).unwrap();
+    device
+        .set_single_qubit_gate_time("RotateZ", 0, 2.0)
+        .unwrap();
+    device
+        .set_two_qubit_gate_time("EchoCrossResonance", 1, 0, 3.0)
+        .unwrap();
+    let model = device.to_error_on_gate_model().unwrap();
+
+    let rotatez_noise = ContinuousDecoherenceModel::new()
+        .add_damping_rate(&[0], 1.0)
+        .lindblad_noise;
+    assert_eq!(
+        model.get_single_qubit_gate_error("RotateZ", 0),
+        Some(&rotatez_noise)
+    );
+    assert_eq!(model.get_single_qubit_gate_error("RotateZ", 1), None);
+    let ecr_noise = ContinuousDecoherenceModel::new()
+        .add_damping_rate(&[0], 1.5)
+        .lindblad_noise;
+    assert_eq!(
+        model.get_two_qubit_gate_error("EchoCrossResonance", 1, 0),
+        Some(&ecr_noise)
+    );
+    assert_eq!(
+        model.get_two_qubit_gate_error("EchoCrossResonance", 1, 2),
+        None
+    );
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_to_imperfect_readout_model(mut device: AWSDevice) {
+    assert_eq!(
+        device.to_imperfect_readout_model().unwrap(),
+        ImperfectReadoutModel::new()
+    );
+
+    assert!(device.set_readout_error(0, 1.2, 0.0).is_err());
+    assert!(device.set_readout_error(200, 0.1, 0.0).is_err());
+    assert!(device.set_preparation_error(0, -0.1).is_err());
+
+    device.set_readout_error(0, 0.1, 0.2).unwrap();
+    device.set_preparation_error(1, 0.05).unwrap();
+    assert_eq!(device.readout_error(0), Some((0.1, 0.2)));
+    assert_eq!(device.preparation_error(1), Some(0.05));
+    assert_eq!(device.preparation_error(0), None);
+
+    let expected = ImperfectReadoutModel::new()
+        .set_error_probabilites(0, 0.1, 0.2)
+        .unwrap()
+        .set_error_probabilites(1, 0.05, 0.0)
+        .unwrap();
+    assert_eq!(device.to_imperfect_readout_model().unwrap(), expected);
+
+    assert!(device.set_thermal_population(2, 1.5).is_err());
+    device.set_thermal_population(2, 0.02).unwrap();
+    assert_eq!(device.thermal_population(2), Some(0.02));
+    let expected = expected.set_error_probabilites(2, 0.02, 0.0).unwrap();
+    assert_eq!(device.to_imperfect_readout_model().unwrap(), expected);
+}
+
+#[test]
+fn test_to_generic_device_with_noise() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device.add_damping(0, 0.1).unwrap();
+    device.set_readout_error(2, 0.1, 0.2).unwrap();
+    device.set_two_qubit_error_rate(1, 0, 0.01).unwrap();
+
+    let (generic_device, readout_model, gate_model) =
+        device.to_generic_device_with_noise().unwrap();
+    assert_eq!(generic_device, device.to_generic_device().unwrap());
+    assert_eq!(readout_model, device.to_imperfect_readout_model().unwrap());
+
+    let depolarising = ContinuousDecoherenceModel::new()
+        .add_depolarising_rate(&[0, 1], 0.01)
+        .lindblad_noise;
+    assert_eq!(
+        gate_model.get_two_qubit_gate_error("EchoCrossResonance", 0, 1),
+        Some(&depolarising)
+    );
+    assert_eq!(
+        gate_model.get_two_qubit_gate_error("EchoCrossResonance", 1, 2),
+        None
+    );
+}
+
+#[test]
+fn test_to_braket_noise_model() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    let noise_model: serde_json::Value =
+        serde_json::from_str(&device.to_braket_noise_model().unwrap()).unwrap();
+    assert_eq!(noise_model["instructions"], serde_json::json!([]));
+
+    device.add_damping(0, 0.1).unwrap();
+    device.set_two_qubit_error_rate(0, 1, 0.01).unwrap();
+    device.set_readout_error(2, 0.1, 0.3).unwrap();
+    let noise_model: serde_json::Value =
+        serde_json::from_str(&device.to_braket_noise_model().unwrap()).unwrap();
+    let instructions = noise_model["instructions"].as_array().unwrap();
+    // Damping after RotateZ, SqrtPauliX and PauliX on qubit 0, one ECR error and one readout error
+    assert_eq!(instructions.len(), 5);
+
+    let damping = &instructions[0];
+    assert_eq!(damping["noise"]["__class__"], "AmplitudeDamping");
+    let gamma = damping["noise"]["gamma"].as_f64().unwrap();
+    assert!((gamma - (1.0 - (-0.1_f64).exp())).abs() < 1e-10);
+    assert_eq!(damping["criteria"]["qubits"], serde_json::json!([0]));
+
+    let depolarizing = &instructions[3];
+    assert_eq!(depolarizing["noise"]["__class__"], "TwoQubitDepolarizing");
+    assert_eq!(
+        depolarizing["criteria"]["gates"],
+        serde_json::json!(["ECR"])
+    );
+    assert_eq!(
+        depolarizing["criteria"]["qubits"],
+        serde_json::json!([[0, 1], [1, 0]])
+    );
+
+    let readout = &instructions[4];
+    assert_eq!(readout["noise"]["__class__"], "BitFlip");
+    let probability = readout["noise"]["probability"].as_f64().unwrap();
+    assert!((probability - 0.2).abs() < 1e-10);
+    assert_eq!(readout["criteria"]["__class__"], "ObservableCriteria");
+}
+
+#[test]
+fn test_to_braket_noise_model_reproduces_coherence_times() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    let (t1, t2) = (50e-6, 30e-6);
+    device.set_coherence_times(0, t1, t2).unwrap();
+    let noise_model: serde_json::Value =
+        serde_json::from_str(&device.to_braket_noise_model().unwrap()).unwrap();
+    let instructions = noise_model["instructions"].as_array().unwrap();
+    let gamma = |class: &str| {
+        instructions
+            .iter()
+            .find(|instruction| {
+                instruction["noise"]["__class__"] == class
+                    && instruction["criteria"]["gates"] == serde_json::json!(["X"])
+            })
+            .and_then(|instruction| instruction["noise"]["gamma"].as_f64())
+            .unwrap()
+    };
+    let time = device.single_qubit_gate_time("PauliX", &0).unwrap();
+    // Amplitude damping keeps the population of state 1 as exp(-t / T1)
+    assert!(((1.0 - gamma("AmplitudeDamping")).ln() + time / t1).abs() < 1e-9);
+    // Both channels together scale the coherences as exp(-t / T2)
+    let coherence = ((1.0 - gamma("AmplitudeDamping")) * (1.0 - gamma("PhaseDamping"))).sqrt();
+    assert!((coherence.ln() + time / t2).abs() < 1e-9);
+}
+
+#[test]
+fn test_decorate_circuit_with_noise() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device.add_damping(0, 0.1).unwrap();
+    device.add_dephasing(0, 0.2).unwrap();
+    device
+        .set_qubit_decoherence_rates(1, array![[0.0, 0.0, 0.0], [0.0, 0.3, 0.0], [0.0, 0.0, 0.0]])
+        .unwrap();
+    device
+        .set_single_qubit_gate_time("RotateZ", 0, 2.0)
+        .unwrap();
+
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += EchoCrossResonance::new(0, 1);
+    circuit += CNOT::new(0, 1);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let mut expected = Circuit::new();
+    expected += RotateZ::new(0, 1.0.into());
+    expected += PragmaDamping::new(0, 2.0.into(), 0.1.into());
+    expected += PragmaDephasing::new(0, 2.0.into(), 0.2.into());
+    expected += EchoCrossResonance::new(0, 1);
+    expected += PragmaDamping::new(0, 1.0.into(), 0.1.into());
+    expected += PragmaDephasing::new(0, 1.0.into(), 0.2.into());
+    expected += PragmaGeneralNoise::new(
+        1,
+        1.0.into(),
+        array![[0.0, 0.0, 0.0], [0.0, 0.3, 0.0], [0.0, 0.0, 0.0]],
+    );
+    expected += CNOT::new(0, 1);
+    expected += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    assert_eq!(decorate_circuit_with_noise(&circuit, &device), expected);
+}
+
+#[test]
+fn test_to_noise_models() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(device.to_noise_models().unwrap(), vec![]);
+
+    device.add_damping(0, 0.1).unwrap();
+    device.set_two_qubit_error_rate(0, 1, 0.01).unwrap();
+    device.set_readout_error(2, 0.1, 0.2).unwrap();
+    let expected: Vec<NoiseModel> = vec![
+        device.to_error_on_gate_model().unwrap().into(),
+        device.to_two_qubit_error_model().into(),
+        device.to_imperfect_readout_model().unwrap().into(),
+    ];
+    assert_eq!(device.to_noise_models().unwrap(), expected);
+}
+
+#[test]
+fn test_noise_during_gate() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device.add_damping(0, 0.1).unwrap();
+    device
+        .set_two_qubit_gate_time("EchoCrossResonance", 0, 1, 2.0)
+        .unwrap();
+
+    let noise = device
+        .noise_during_gate("EchoCrossResonance", &[0, 1])
+        .unwrap();
+    assert_eq!(
+        noise,
+        vec![(0, array![[0.2, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]])]
+    );
+    assert_eq!(device.noise_during_gate("RotateZ", &[1]).unwrap(), vec![]);
+    assert!(device
+        .noise_during_gate("EchoCrossResonance", &[0, 2])
+        .is_err());
+    assert!(device.noise_during_gate("CNOT", &[0, 1]).is_err());
+}
@@ -0,0 +1,65 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo_for_braket_devices::pulse::*;
+use roqoqo_for_braket_devices::*;
+
+#[test]
+fn test_rigetti_pulse_info() {
+    let mut device = RigettiAspenM3Device::new();
+    let info = device.pulse_info();
+    assert_eq!(info.ports.len(), 4 * 80);
+    let port = info.port("q3_ro_rx").unwrap();
+    assert_eq!(port.direction, PortDirection::Rx);
+    assert_eq!(port.dt, 1e-9);
+    assert_eq!(port.qubits, vec![3]);
+
+    let frame = info.frame("q0_q1_cz_frame").unwrap();
+    assert_eq!(frame.port_id, "q0_ff");
+    assert_eq!(frame.qubits, vec![0, 1]);
+    assert_eq!(frame.frequency, None);
+    assert!(info.port(&frame.port_id).is_some());
+    assert_eq!(info.qubit_frames(5).len(), 3 + 3 * 2);
+
+    device.disable_edge(0, 1).unwrap();
+    assert!(device.pulse_info().frame("q0_q1_cz_frame").is_none());
+
+    assert_eq!(
+        AWSDevice::from(RigettiAspenM3Device::new()).pulse_info(),
+        Some(info)
+    );
+    assert_eq!(AWSDevice::from(OQCLucyDevice::new()).pulse_info(), None);
+}
+
+#[test]
+fn test_pulse_info_from_properties() {
+    let properties = r#"{"pulse": {
+        "ports": {
+            "q0_rf": {"portId": "q0_rf", "direction": "tx", "dt": 1e-9, "qubitMappings": [0]},
+            "q0_ro_rx": {"portId": "q0_ro_rx", "direction": "rx", "dt": 2e-9, "qubitMappings": null}
+        },
+        "frames": {
+            "q0_rf_frame": {"frameId": "q0_rf_frame", "portId": "q0_rf", "frequency": 4.5e9, "phase": 0.5, "qubitMappings": [0]}
+        }
+    }}"#;
+    let info = PulseInfo::from_properties(properties).unwrap();
+    assert_eq!(info.ports.len(), 2);
+    assert_eq!(info.ports[0].id, "q0_rf");
+    assert_eq!(info.port("q0_ro_rx").unwrap().qubits, Vec::<usize>::new());
+    let frame = info.frame("q0_rf_frame").unwrap();
+    assert_eq!(frame.frequency, Some(4.5e9));
+    assert_eq!(frame.phase, 0.5);
+    assert_eq!(info.qubit_frames(0).len(), 1);
+
+    assert!(PulseInfo::from_properties("{").is_err());
+    assert!(PulseInfo::from_properties(r#"{"ports": {"p": {"direction": "up"}}}"#).is_err());
+}
@@ -0,0 +1,178 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use roqoqo::devices::QoqoDevice;
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::routing::{remap_to_best_qubits, route_circuit};
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+/// Returns a circuit whose two-qubit gates form a triangle, which no supported QPU contains.
+fn triangle_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    circuit += CNOT::new(1, 2);
+    circuit += CNOT::new(2, 0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += MeasureQubit::new(2, "ro".to_string(), 2);
+    circuit
+}
+
+#[test_case(OQCLucyDevice::new().into(); "lucy")]
+#[test_case(RigettiAspenM3Device::new().into(); "aspen")]
+fn test_route_circuit_inserts_swaps(device: AWSDevice) {
+    let (routed, permutation) = route_circuit(&triangle_circuit(), &device).unwrap();
+    let edges: HashSet<(usize, usize)> = device.two_qubit_edges().into_iter().collect();
+    let mut number_swaps = 0;
+    for operation in routed.iter() {
+        if let Ok(gate) = TwoQubitGateOperation::try_from(operation.clone()) {
+            let (control, target) = (*gate.control(), *gate.target());
+            assert!(edges.contains(&(control, target)) || edges.contains(&(target, control)));
+            if operation.hqslang() == "SWAP" {
+                number_swaps += 1;
+            }
+        }
+    }
+    assert_eq!(number_swaps, 1);
+    assert_eq!(permutation.len(), 3);
+    let device_qubits: HashSet<usize> = permutation.values().copied().collect();
+    assert_eq!(device_qubits.len(), 3);
+    let readout: Vec<(usize, usize)> = routed
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::MeasureQubit(measurement) => {
+                Some((*measurement.qubit(), *measurement.readout_index()))
+            }
+            _ => None,
+        })
+        .collect();
+    for (qubit, readout_index) in readout {
+        assert_eq!(permutation[&readout_index], qubit);
+    }
+}
+
+#[test_case(OQCLucyDevice::new().into(); "lucy")]
+#[test_case(RigettiAspenM3Device::new().into(); "aspen")]
+fn test_route_circuit_repeated_measurement(device: AWSDevice) {
+    let mut circuit = triangle_circuit();
+    circuit = circuit
+        .iter()
+        .filter(|operation| operation.hqslang() != "MeasureQubit")
+        .cloned()
+        .collect();
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    let (routed, permutation) = route_circuit(&circuit, &device).unwrap();
+    match routed.iter().last() {
+        Some(Operation::PragmaRepeatedMeasurement(measurement)) => {
+            let mapping = measurement.qubit_mapping().clone().unwrap();
+            assert_eq!(mapping.len(), 3);
+            for qubit in 0..3 {
+                assert_eq!(mapping[&permutation[&qubit]], qubit);
+            }
+        }
+        _ => panic!("Last operation is not the measurement"),
+    }
+}
+
+#[test_case(OQCLucyDevice::new().into(); "lucy")]
+#[test_case(RigettiAspenM3Device::new().into(); "aspen")]
+fn test_estimate_swap_overhead(device: AWSDevice) {
+    let overhead = device.estimate_swap_overhead(&triangle_circuit()).unwrap();
+    assert_eq!(overhead.number_swaps, 1);
+    assert_eq!(overhead.extra_two_qubit_gates, 3);
+    assert!(overhead.extra_duration > 0.0);
+
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 1);
+    let overhead = device.estimate_swap_overhead(&circuit).unwrap();
+    assert_eq!(overhead.number_swaps, 0);
+    assert_eq!(overhead.extra_duration, 0.0);
+}
+
+#[test]
+fn test_route_circuit_placement_without_swaps() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(0, 4);
+    circuit += CNOT::new(4, 2);
+    circuit += RotateZ::new(5, 1.0.into());
+    let (routed, permutation) = route_circuit(&circuit, &device).unwrap();
+    assert!(routed.iter().all(|operation| operation.hqslang() != "SWAP"));
+    assert_eq!(routed.len(), 3);
+    assert_eq!(permutation.len(), 3);
+    assert_eq!(device.distance(permutation[&0], permutation[&4]), Some(1));
+    assert_eq!(device.distance(permutation[&4], permutation[&2]), Some(1));
+}
+
+#[test]
+fn test_route_circuit_all_to_all() {
+    let device: AWSDevice = IonQHarmonyDevice::new().into();
+    let (routed, _) = route_circuit(&triangle_circuit(), &device).unwrap();
+    assert!(routed.iter().all(|operation| operation.hqslang() != "SWAP"));
+}
+
+#[test]
+fn test_route_circuit_errors() {
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let mut circuit = Circuit::new();
+    circuit += Toffoli::new(0, 1, 2);
+    assert!(route_circuit(&circuit, &device).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(9, 1.0.into());
+    circuit += CNOT::new(0, 1);
+    for qubit in 2..9 {
+        circuit += Hadamard::new(qubit);
+    }
+    assert!(route_circuit(&circuit, &device).is_err());
+}
+
+#[test]
+fn test_remap_to_best_qubits() {
+    let mut device = OQCLucyDevice::new();
+    for qubit in 0..8 {
+        device.set_readout_error(qubit, 0.1, 0.1).unwrap();
+    }
+    device.set_readout_error(5, 0.01, 0.01).unwrap();
+    device.set_readout_error(6, 0.01, 0.01).unwrap();
+    let device: AWSDevice = device.into();
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    let (remapped, permutation) = remap_to_best_qubits(&circuit, &device).unwrap();
+    let mut placed: Vec<usize> = permutation.values().copied().collect();
+    placed.sort_unstable();
+    assert_eq!(placed, vec![5, 6]);
+    assert_eq!(device.distance(permutation[&0], permutation[&1]), Some(1));
+    assert!(remapped
+        .iter()
+        .all(|operation| operation.hqslang() != "SWAP"));
+    match remapped.iter().last() {
+        Some(Operation::PragmaRepeatedMeasurement(measurement)) => {
+            let mapping = measurement.qubit_mapping().clone().unwrap();
+            assert_eq!(mapping[&permutation[&0]], 0);
+            assert_eq!(mapping[&permutation[&1]], 1);
+        }
+        _ => panic!("Last operation is not the measurement"),
+    }
+
+    assert!(remap_to_best_qubits(&triangle_circuit(), &device).is_err());
+}
@@ -0,0 +1,243 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+fn ionq_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    circuit
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()), 0.3 + 3.0; "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()), 0.3 + 1.0; "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()), 0.3 + 0.035; "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()), 0.3 + 0.035; "RigettiAspenM3Device")]
+fn test_score_cost(device: AWSDevice, cost: f64) {
+    let score = device.score_for_circuit(&ionq_circuit());
+    assert!((score.estimated_cost - cost).abs() < 1e-10);
+    assert!(score.fits_qubits);
+}
+
+#[test]
+fn test_score_native_gates() {
+    let circuit = ionq_circuit();
+
+    let score = AWSDevice::from(IonQHarmonyDevice::new()).score_for_circuit(&circuit);
+    assert_eq!(score.native_gate_coverage, 1.0);
+    assert_eq!(score.connectivity_match, 1.0);
+    assert_eq!(score.estimated_fidelity, 1.0);
+
+    let score = AWSDevice::from(OQCLucyDevice::new()).score_for_circuit(&circuit);
+    assert_eq!(score.native_gate_coverage, 0.5);
+    assert_eq!(score.connectivity_match, 1.0);
+}
+
+#[test]
+fn test_score_fidelity_and_size() {
+    let mut device = IonQHarmonyDevice::new();
+    device.add_damping(0, 0.1).unwrap();
+    let score = AWSDevice::from(device).score_for_circuit(&ionq_circuit());
+    // RotateZ and MolmerSorensenXX both act on qubit 0 with a gate time of 1.0
+    assert!((score.estimated_fidelity - (-0.2_f64).exp()).abs() < 1e-10);
+
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(20, 1.0.into());
+    let score = AWSDevice::from(IonQHarmonyDevice::new()).score_for_circuit(&circuit);
+    assert!(!score.fits_qubits);
+    assert_eq!(score.total(), 0.0);
+}
+
+#[test]
+fn test_max_shots_within_budget() {
+    let device = AWSDevice::from(IonQHarmonyDevice::new());
+    // 0.3 USD per task and 0.01 USD per shot
+    assert_eq!(device.max_shots_within_budget(2.0 * 1.3, 2), Some(100));
+    assert_eq!(device.max_shots_within_budget(1.305, 1), Some(100));
+    assert_eq!(device.max_shots_within_budget(1e6, 1), Some(10000));
+    assert_eq!(device.max_shots_within_budget(0.3, 1), None);
+    assert_eq!(device.max_shots_within_budget(0.0, 0), Some(10000));
+
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    // Rigetti requires at least 10 shots
+    assert_eq!(device.max_shots_within_budget(0.3 + 9.0 * 0.00035, 1), None);
+    assert_eq!(
+        device.max_shots_within_budget(0.3 + 10.0 * 0.00035, 1),
+        Some(10)
+    );
+}
+
+#[test]
+fn test_recommend_device() {
+    let devices = vec![
+        AWSDevice::from(OQCLucyDevice::new()),
+        AWSDevice::from(IonQAria1Device::new()),
+        AWSDevice::from(IonQHarmonyDevice::new()),
+    ];
+    let ranking = recommend_device(&ionq_circuit(), &devices, &PriceTable::new());
+    assert_eq!(ranking.len(), 3);
+    assert_eq!(ranking[0].0, 2);
+    assert!(ranking[0].1.total() >= ranking[1].1.total());
+    assert!(ranking[1].1.total() >= ranking[2].1.total());
+
+    // Harmony drops to the last place with a higher price per shot
+    let mut prices = PriceTable::new();
+    prices.set_prices(
+        &devices[2],
+        DevicePrices {
+            per_task: 0.3,
+            per_shot: 0.1,
+        },
+    );
+    let ranking = recommend_device(&ionq_circuit(), &devices, &prices);
+    assert_eq!(ranking[2].0, 2);
+    assert!((ranking[2].1.estimated_cost - 10.3).abs() < 1e-10);
+}
+
+#[test]
+fn test_price_table() {
+    let device = AWSDevice::from(OQCLucyDevice::new());
+    let mut prices = PriceTable::new();
+    assert_eq!(
+        prices.prices(&device),
+        DevicePrices {
+            per_task: device.price_per_task(),
+            per_shot: device.price_per_shot(),
+        }
+    );
+    let reserved = DevicePrices {
+        per_task: 0.0,
+        per_shot: 0.0,
+    };
+    prices.set_prices(&device, reserved);
+    assert_eq!(prices.prices(&device), reserved);
+    let score = device.score_for_circuit_with_prices(&ionq_circuit(), &prices);
+    assert_eq!(score.estimated_cost, 0.0);
+    // Other devices keep their on-demand prices
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    assert_eq!(prices.prices(&device).per_shot, 0.00035);
+}
+
+#[test]
+fn test_score_qubit_headroom() {
+    let harmony = AWSDevice::from(IonQHarmonyDevice::new());
+    let aria = AWSDevice::from(IonQAria1Device::new());
+    let mut prices = PriceTable::new();
+    prices.set_prices(
+        &aria,
+        DevicePrices {
+            per_task: 0.3,
+            per_shot: 0.01,
+        },
+    );
+    let harmony_score = harmony.score_for_circuit_with_prices(&ionq_circuit(), &prices);
+    let aria_score = aria.score_for_circuit_with_prices(&ionq_circuit(), &prices);
+    assert!((harmony_score.qubit_headroom - 9.0 / 11.0).abs() < 1e-10);
+    assert!((aria_score.qubit_headroom - 23.0 / 25.0).abs() < 1e-10);
+    // With equal prices the larger device leaves more room for the circuit
+    assert!(aria_score.total() > harmony_score.total());
+    assert!(aria_score.total() <= 1.1 * harmony_score.total());
+
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(20, 1.0.into());
+    assert_eq!(harmony.score_for_circuit(&circuit).qubit_headroom, 0.0);
+}
+
+#[test]
+fn test_estimate_duration() {
+    let mut device = OQCLucyDevice::new();
+    device
+        .set_two_qubit_gate_time("EchoCrossResonance", 0, 1, 3.0)
+        .unwrap();
+    device.set_measurement_time(1, 5.0).unwrap();
+    let device = AWSDevice::from(device);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += EchoCrossResonance::new(0, 1);
+    circuit += PragmaSleep::new(vec![2], 0.5.into());
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let duration = device.estimate_duration(&circuit).unwrap();
+    assert_eq!(duration.qubit_durations, vec![5.0, 10.0, 0.5]);
+    assert_eq!(duration.total, 10.0);
+
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 10, None);
+    let duration = device.estimate_duration(&circuit).unwrap();
+    assert_eq!(duration.qubit_durations, vec![10.0, 15.0, 10.0]);
+
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    assert!(device.estimate_duration(&circuit).is_err());
+}
+
+#[test]
+fn test_estimate_fidelity() {
+    let mut device = OQCLucyDevice::new();
+    device.set_two_qubit_error_rate(0, 1, 0.1).unwrap();
+    device.add_damping(0, 0.1).unwrap();
+    device.set_readout_error(1, 0.02, 0.04).unwrap();
+    device.set_preparation_error(2, 0.5).unwrap();
+    let device = AWSDevice::from(device);
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += EchoCrossResonance::new(0, 1);
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    let fidelity = device.estimate_fidelity(&circuit).unwrap();
+    // Qubit 0 decoheres for two gate times of 1.0
+    let decoherence = (-0.2_f64).exp();
+    assert!((fidelity.qubit_fidelities[0] - decoherence * 0.9).abs() < 1e-10);
+    assert!((fidelity.qubit_fidelities[1] - 0.9 * 0.97).abs() < 1e-10);
+    assert!((fidelity.total - decoherence * 0.9 * 0.97).abs() < 1e-10);
+
+    circuit += PauliX::new(2);
+    let fidelity = device.estimate_fidelity(&circuit).unwrap();
+    assert!((fidelity.qubit_fidelities[2] - 0.5).abs() < 1e-10);
+    assert!((fidelity.total - decoherence * 0.9 * 0.97 * 0.5).abs() < 1e-10);
+}
+
+#[test]
+fn test_gate_report() {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += Hadamard::new(1);
+    circuit += EchoCrossResonance::new(0, 1);
+    circuit += EchoCrossResonance::new(1, 0);
+    circuit += CNOT::new(2, 3);
+    circuit += EchoCrossResonance::new(0, 4);
+    circuit += DefinitionBit::new("ro".to_string(), 5, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+
+    let report = gate_report(&circuit, &AWSDevice::from(OQCLucyDevice::new()));
+    assert_eq!(report.native_gates.get("RotateZ"), Some(&1));
+    assert_eq!(report.native_gates.get("EchoCrossResonance"), Some(&3));
+    assert_eq!(report.non_native_gates.get("Hadamard"), Some(&1));
+    assert_eq!(report.non_native_gates.get("CNOT"), Some(&1));
+    assert_eq!(report.two_qubit_depth, 3);
+    assert_eq!(report.edge_usage.get(&(0, 1)), Some(&2));
+    assert_eq!(report.edge_usage.get(&(2, 3)), Some(&1));
+    assert_eq!(report.edge_usage.get(&(0, 4)), Some(&1));
+    assert_eq!(report.unconnected_gates, 1);
+    assert!((report.native_fraction() - 4.0 / 6.0).abs() < 1e-10);
+
+    let report = gate_report(&Circuit::new(), &AWSDevice::from(OQCLucyDevice::new()));
+    assert_eq!(report.two_qubit_depth, 0);
+    assert_eq!(report.native_fraction(), 1.0);
+}
@@ -0,0 +1,316 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use petgraph::graph::NodeIndex;
+use roqoqo::devices::QoqoDevice;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_to_petgraph(device: AWSDevice) {
+    let graph = device.to_petgraph(EdgeWeight::GateTime);
+    assert_eq!(graph.node_count(), device.number_qubits());
+    assert_eq!(graph.edge_count(), device.two_qubit_edges().len());
+    for (first, second) in device.two_qubit_edges() {
+        assert!(graph
+            .find_edge(NodeIndex::new(first), NodeIndex::new(second))
+            .is_some());
+    }
+}
+
+#[test]
+fn test_to_petgraph_weights() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device
+        .set_two_qubit_gate_time("EchoCrossResonance", 1, 0, 0.5)
+        .unwrap();
+    device.set_two_qubit_error_rate(0, 1, 0.1).unwrap();
+
+    let graph = device.to_petgraph(EdgeWeight::GateTime);
+    let edge = graph
+        .find_edge(NodeIndex::new(0), NodeIndex::new(1))
+        .unwrap();
+    assert_eq!(graph[edge], 0.5);
+
+    let graph = device.to_petgraph(EdgeWeight::Fidelity);
+    let edge = graph
+        .find_edge(NodeIndex::new(0), NodeIndex::new(1))
+        .unwrap();
+    assert_eq!(graph[edge], 0.9);
+    let edge = graph
+        .find_edge(NodeIndex::new(1), NodeIndex::new(2))
+        .unwrap();
+    assert_eq!(graph[edge], 1.0);
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_coupling_map(device: AWSDevice) {
+    let coupling_map = device.coupling_map();
+    assert_eq!(coupling_map.len(), 2 * device.two_qubit_edges().len());
+    let (first, second) = device.two_qubit_edges()[0];
+    assert!(coupling_map.contains(&[first, second]));
+    assert!(coupling_map.contains(&[second, first]));
+}
+
+#[test]
+fn test_to_dot() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device.add_damping(3, 0.1).unwrap();
+    let dot = device.to_dot();
+    assert!(dot.starts_with("graph \"arn:aws:braket:eu-west-2::device/qpu/oqc/Lucy\" {\n"));
+    assert!(dot.contains("    0 [style=filled, fillcolor=\"0.333 0.600 1.000\"];\n"));
+    assert!(dot.contains("    3 [style=filled, fillcolor=\"0.000 0.600 1.000\"];\n"));
+    assert!(dot.contains("    0 -- 1 [label=\"1\"];\n"));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn test_distance_and_shortest_path() {
+    let device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(device.distance(0, 0), Some(0));
+    assert_eq!(device.distance(0, 1), Some(1));
+    assert_eq!(device.distance(0, 4), Some(4));
+    assert_eq!(device.distance(1, 6), Some(3));
+    assert_eq!(device.distance(0, 8), None);
+
+    assert_eq!(device.shortest_path(1, 6), Some(vec![1, 0, 7, 6]));
+    assert_eq!(device.shortest_path(2, 2), Some(vec![2]));
+    assert_eq!(device.shortest_path(8, 2), None);
+
+    let matrix = device.distance_matrix();
+    assert_eq!(matrix.len(), 8);
+    assert_eq!(matrix[1][6], Some(3));
+    assert_eq!(matrix[6][1], Some(3));
+
+    let device = AWSDevice::from(IonQHarmonyDevice::new());
+    assert_eq!(device.distance(0, 10), Some(1));
+}
+
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_topology(mut device: AWSDevice) {
+    device.disable_qubit(2).unwrap();
+    let topology = device.topology();
+    assert_eq!(topology.number_qubits(), device.number_qubits());
+    assert_eq!(
+        topology.distance_matrix(),
+        device.distance_matrix().as_slice()
+    );
+    assert!(topology.neighbours(2).is_empty());
+    assert!(topology.neighbours(device.number_qubits()).is_empty());
+    for qubit_a in 0..device.number_qubits() {
+        for qubit_b in 0..device.number_qubits() {
+            assert_eq!(
+                topology.distance(qubit_a, qubit_b),
+                device.distance(qubit_a, qubit_b)
+            );
+            match topology.shortest_path(qubit_a, qubit_b) {
+                Some(path) => {
+                    assert_eq!(path.first(), Some(&qubit_a));
+                    assert_eq!(path.last(), Some(&qubit_b));
+                    assert_eq!(Some(path.len() - 1), device.distance(qubit_a, qubit_b));
+                    for pair in path.windows(2) {
+                        assert!(topology.neighbours(pair[0]).contains(&pair[1]));
+                    }
+                }
+                None => assert_eq!(device.shortest_path(qubit_a, qubit_b), None),
+            }
+        }
+    }
+    assert_eq!(topology.distance(0, device.number_qubits()), None);
+    assert_eq!(topology.shortest_path(device.number_qubits(), 0), None);
+}
+
+#[test]
+fn test_sub_device() {
+    let mut device = AWSDevice::from(RigettiAspenM3Device::new());
+    device.add_damping(14, 0.1).unwrap();
+    device.set_two_qubit_gate_time("XY", 14, 1, 0.5).unwrap();
+
+    let sub_device = device.sub_device(&[14, 1, 2, 40]).unwrap();
+    assert_eq!(sub_device.number_qubits(), 4);
+    assert_eq!(sub_device.two_qubit_gate_time("XY", &0, &1), Some(0.5));
+    assert_eq!(sub_device.two_qubit_gate_time("XY", &1, &2), Some(1.0));
+    assert_eq!(sub_device.two_qubit_gate_time("XY", &0, &2), None);
+    assert_eq!(sub_device.two_qubit_gate_time("XY", &2, &3), None);
+    assert_eq!(sub_device.single_qubit_gate_time("RotateZ", &3), Some(1.0));
+    assert_eq!(
+        sub_device.qubit_decoherence_rates(&0),
+        device.qubit_decoherence_rates(&14)
+    );
+
+    assert!(device.sub_device(&[0, 80]).is_err());
+    assert!(device.sub_device(&[0, 1, 0]).is_err());
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_find_linear_chain(device: AWSDevice) {
+    let edges = device.two_qubit_edges();
+    let chain = device.find_linear_chain(5).unwrap();
+    assert_eq!(chain.len(), 5);
+    assert!(chain
+        .windows(2)
+        .all(|pair| edges.contains(&(pair[0], pair[1])) || edges.contains(&(pair[1], pair[0]))));
+    assert!(device.find_linear_chain(0).is_none());
+    assert!(device
+        .find_linear_chain(device.number_qubits() + 1)
+        .is_none());
+}
+
+#[test]
+fn test_find_linear_chain_prefers_fidelity() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    assert_eq!(device.find_linear_chain(3), Some(vec![0, 1, 2]));
+
+    device.set_readout_error(1, 0.2, 0.2).unwrap();
+    let chain = device.find_linear_chain(3).unwrap();
+    assert!(!chain.contains(&1));
+    assert!(device.qubit_fidelity(1) < device.qubit_fidelity(0));
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()), Connectivity::AllToAll; "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()), Connectivity::AllToAll; "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()), Connectivity::Linear; "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()), Connectivity::Lattice; "RigettiAspenM3Device")]
+fn test_connectivity(device: AWSDevice, connectivity: Connectivity) {
+    assert_eq!(device.connectivity(), connectivity);
+}
+
+#[test]
+fn test_two_qubit_edges_weighted() {
+    let mut device = OQCLucyDevice::new();
+    device.set_two_qubit_error_rate(0, 1, 0.02).unwrap();
+    let device = AWSDevice::from(device);
+    let edges = device.two_qubit_edges_weighted(EdgeWeight::ErrorRate);
+    assert_eq!(edges.len(), device.two_qubit_edges().len());
+    for (first, second, weight) in edges {
+        if (first, second) == (0, 1) {
+            assert_eq!(weight, 0.02);
+        } else {
+            assert_eq!(weight, 0.0);
+        }
+    }
+    let edges = device.two_qubit_edges_weighted(EdgeWeight::Fidelity);
+    assert!(edges.contains(&(0, 1, 0.98)));
+    let edges = device.two_qubit_edges_weighted(EdgeWeight::GateTime);
+    assert!(edges.iter().all(|(_, _, weight)| weight.is_finite()));
+    assert_eq!(
+        "error_rate".parse::<EdgeWeight>().unwrap(),
+        EdgeWeight::ErrorRate
+    );
+    assert!("unknown".parse::<EdgeWeight>().is_err());
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_connected_components(device: AWSDevice) {
+    let components = device.connected_components();
+    assert_eq!(components.len(), 1);
+    assert_eq!(
+        components[0],
+        (0..device.number_qubits()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_connected_components_fragmented() {
+    let mut device = AWSDevice::from(OQCLucyDevice::new());
+    device.disable_qubit(0).unwrap();
+    device.disable_edge(3, 4).unwrap();
+    let components = device.connected_components();
+    assert_eq!(components, vec![vec![4, 5, 6, 7], vec![1, 2, 3]]);
+}
+
+fn is_valid_placement(
+    device: &AWSDevice,
+    interaction_edges: &[(usize, usize)],
+    placement: &HashMap<usize, usize>,
+) -> bool {
+    let edges = device.two_qubit_edges();
+    let mut device_qubits: Vec<usize> = placement.values().copied().collect();
+    device_qubits.sort_unstable();
+    device_qubits.dedup();
+    device_qubits.len() == placement.len()
+        && interaction_edges.iter().all(|(first, second)| {
+            let (a, b) = (placement[first], placement[second]);
+            edges.contains(&(a, b)) || edges.contains(&(b, a))
+        })
+}
+
+#[test_case(AWSDevice::from(IonQAria1Device::new()); "IonQAria1Device")]
+#[test_case(AWSDevice::from(IonQHarmonyDevice::new()); "IonQHarmonyDevice")]
+#[test_case(AWSDevice::from(OQCLucyDevice::new()); "OQCLucyDevice")]
+#[test_case(AWSDevice::from(RigettiAspenM3Device::new()); "RigettiAspenM3Device")]
+fn test_find_placement_chain(device: AWSDevice) {
+    let interaction_edges = [(10, 11), (11, 12), (12, 13), (13, 14), (11, 12)];
+    let placement = device.find_placement(&interaction_edges).unwrap();
+    assert_eq!(placement.len(), 5);
+    assert!(is_valid_placement(&device, &interaction_edges, &placement));
+}
+
+#[test]
+fn test_find_placement_rings() {
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    for length in [4, 8] {
+        let interaction_edges: Vec<(usize, usize)> =
+            (0..length).map(|q| (q, (q + 1) % length)).collect();
+        let placement = device.find_placement(&interaction_edges).unwrap();
+        assert!(is_valid_placement(&device, &interaction_edges, &placement));
+    }
+}
+
+#[test]
+fn test_find_placement_impossible() {
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    // A triangle cannot be embedded into the octagonal lattice
+    assert!(device.find_placement(&[(0, 1), (1, 2), (2, 0)]).is_none());
+    // A qubit with four neighbours neither
+    assert!(device
+        .find_placement(&[(0, 1), (0, 2), (0, 3), (0, 4)])
+        .is_none());
+
+    let device = AWSDevice::from(IonQHarmonyDevice::new());
+    let star: Vec<(usize, usize)> = (1..device.number_qubits() + 1).map(|q| (0, q)).collect();
+    assert!(device.find_placement(&star).is_none());
+    let placement = device.find_placement(&star[1..]).unwrap();
+    assert!(is_valid_placement(&device, &star[1..], &placement));
+}
+
+#[test]
+fn test_best_chains_avoids_bad_edges() {
+    let mut device = OQCLucyDevice::new();
+    device.set_two_qubit_error_rate(0, 1, 0.5).unwrap();
+    device.set_two_qubit_error_rate(4, 5, 0.01).unwrap();
+    let device = AWSDevice::from(device);
+    let chains = device.best_chains(8);
+    assert_eq!(chains.len(), 1);
+    let chain = &chains[0];
+    assert_eq!(chain.len(), 8);
+    // The only chain through all qubits not using edge (0, 1) starts or ends on it
+    let ends = [chain[0], chain[7]];
+    assert!(ends.contains(&0) && ends.contains(&1));
+
+    assert!(device.best_chains(9).is_empty());
+}
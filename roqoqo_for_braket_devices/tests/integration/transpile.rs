@@ -0,0 +1,332 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::transpile::*;
+use roqoqo_for_braket_devices::*;
+use test_case::test_case;
+
+/// Returns a circuit on two qubits containing every gate the transpiler can decompose.
+fn decomposable_circuit(first: usize, second: usize) -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += Hadamard::new(first);
+    circuit += SGate::new(second);
+    circuit += RotateY::new(second, 0.3.into());
+    circuit += CNOT::new(first, second);
+    circuit += ControlledPauliZ::new(second, first);
+    circuit += ControlledPauliY::new(first, second);
+    circuit += SWAP::new(second, first);
+    circuit += FSwap::new(first, second);
+    circuit += ControlledPhaseShift::new(first, second, 0.4.into());
+    circuit += PhaseShiftedControlledZ::new(second, first, 0.5.into());
+    circuit += ControlledRotateX::new(first, second, 0.6.into());
+    circuit += MolmerSorensenXX::new(first, second);
+    circuit += VariableMSXX::new(second, first, 0.7.into());
+    circuit += SpinInteraction::new(first, second, 0.1.into(), 0.2.into(), 0.3.into());
+    circuit += PMInteraction::new(second, first, 0.8.into());
+    circuit += XY::new(first, second, 0.9.into());
+    circuit += ISwap::new(second, first);
+    circuit += SqrtISwap::new(first, second);
+    circuit += InvSqrtISwap::new(second, first);
+    circuit += EchoCrossResonance::new(first, second);
+    circuit += EchoCrossResonance::new(second, first);
+    circuit += PauliY::new(second);
+    circuit += MeasureQubit::new(first, "ro".to_string(), 0);
+    circuit += MeasureQubit::new(second, "ro".to_string(), 1);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    circuit
+}
+
+#[test_case(IonQAria1Device::new().into(); "aria")]
+#[test_case(IonQHarmonyDevice::new().into(); "harmony")]
+fn test_to_ionq_native_valid(device: AWSDevice) {
+    let transpiled = to_ionq_native(&decomposable_circuit(0, 1)).unwrap();
+    assert_eq!(device.validate_circuit(&transpiled), Ok(()));
+}
+
+#[test]
+fn test_to_ionq_native_gates() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    let transpiled = to_ionq_native(&circuit).unwrap();
+    let names: Vec<&str> = transpiled.iter().map(|op| op.hqslang()).collect();
+    assert!(names
+        .iter()
+        .all(|name| ["RotateZ", "GPi", "GPi2", "MolmerSorensenXX"].contains(name)));
+    assert_eq!(
+        names
+            .iter()
+            .filter(|name| **name == "MolmerSorensenXX")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_to_ionq_native_merges_single_qubit_gates() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += Hadamard::new(0);
+    circuit += RotateZ::new(1, 0.5.into());
+    circuit += RotateZ::new(1, (-0.5).into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    let transpiled = to_ionq_native(&circuit).unwrap();
+    let mut expected = Circuit::new();
+    expected += MolmerSorensenXX::new(0, 1);
+    assert_eq!(transpiled, expected);
+}
+
+#[test]
+fn test_to_ionq_native_keeps_order_of_measurements() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PauliX::new(0);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    let transpiled = to_ionq_native(&circuit).unwrap();
+    let names: Vec<&str> = transpiled.iter().map(|op| op.hqslang()).collect();
+    assert_eq!(names.last(), Some(&"MeasureQubit"));
+    assert!(names.contains(&"GPi"));
+}
+
+#[test]
+fn test_to_ionq_native_symbolic() {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, "theta".into());
+    assert_eq!(to_ionq_native(&circuit).unwrap(), circuit);
+
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, "theta".into());
+    assert!(to_ionq_native(&circuit).is_err());
+}
+
+#[test]
+fn test_to_ionq_native_unsupported_gate() {
+    let mut circuit = Circuit::new();
+    circuit += Toffoli::new(0, 1, 2);
+    assert!(to_ionq_native(&circuit).is_err());
+}
+
+#[test_case(0, 1; "calibrated")]
+#[test_case(3, 4; "reversed")]
+#[test_case(7, 0; "reversed wraparound")]
+fn test_to_oqc_native_valid(first: usize, second: usize) {
+    let transpiled = to_oqc_native(&decomposable_circuit(first, second)).unwrap();
+    assert_eq!(
+        AWSDevice::from(OQCLucyDevice::new()).validate_circuit(&transpiled),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_to_oqc_native_direction() {
+    let mut circuit = Circuit::new();
+    circuit += CNOT::new(1, 0);
+    circuit += EchoCrossResonance::new(1, 0);
+    let transpiled = to_oqc_native(&circuit).unwrap();
+    let echo_cross_resonances: Vec<&Operation> = transpiled
+        .iter()
+        .filter(|op| op.hqslang() == "EchoCrossResonance")
+        .collect();
+    assert_eq!(echo_cross_resonances.len(), 2);
+    for operation in echo_cross_resonances {
+        assert_eq!(operation, &Operation::from(EchoCrossResonance::new(0, 1)));
+    }
+    assert!(transpiled.iter().all(
+        |op| ["RotateZ", "SqrtPauliX", "PauliX", "EchoCrossResonance"].contains(&op.hqslang())
+    ));
+}
+
+#[test]
+fn test_to_oqc_native_keeps_calibrated_echo_cross_resonance() {
+    let mut circuit = Circuit::new();
+    circuit += EchoCrossResonance::new(4, 3);
+    assert_eq!(to_oqc_native(&circuit).unwrap(), circuit);
+}
+
+#[test_case(0, 1; "octagon")]
+#[test_case(43, 0; "between octagons")]
+fn test_to_rigetti_native_valid(first: usize, second: usize) {
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    let transpiled = to_rigetti_native(&decomposable_circuit(first, second)).unwrap();
+    assert_eq!(device.validate_circuit(&transpiled), Ok(()));
+    let transpiled = to_rigetti_native_verbatim(&decomposable_circuit(first, second)).unwrap();
+    assert_eq!(device.validate_circuit(&transpiled), Ok(()));
+}
+
+#[test]
+fn test_to_rigetti_native_angles() {
+    let mut circuit = Circuit::new();
+    circuit += RotateX::new(0, 0.3.into());
+    circuit += PauliX::new(1);
+    circuit += RotateZ::new(2, 7.0.into());
+    let transpiled = to_rigetti_native(&circuit).unwrap();
+    for operation in transpiled.iter() {
+        match operation {
+            Operation::RotateX(gate) => {
+                let theta = *gate.theta().float().unwrap();
+                assert!(
+                    (theta - std::f64::consts::FRAC_PI_2).abs() < 1e-12
+                        || (theta - std::f64::consts::PI).abs() < 1e-12
+                );
+            }
+            Operation::RotateZ(gate) => {
+                let theta = *gate.theta().float().unwrap();
+                assert!(theta.abs() <= std::f64::consts::PI + 1e-12);
+            }
+            _ => panic!("Unexpected operation {}", operation.hqslang()),
+        }
+    }
+}
+
+#[test]
+fn test_to_rigetti_native_verbatim() {
+    let mut circuit = Circuit::new();
+    circuit += PauliX::new(0);
+    circuit += ControlledPhaseShift::new(0, 1, 0.4.into());
+    circuit += XY::new(0, 1, 0.5.into());
+    let transpiled = to_rigetti_native(&circuit).unwrap();
+    assert!(transpiled
+        .iter()
+        .any(|op| op.hqslang() == "ControlledPhaseShift"));
+    let transpiled = to_rigetti_native_verbatim(&circuit).unwrap();
+    for operation in transpiled.iter() {
+        match operation {
+            Operation::RotateX(gate) => {
+                assert!((gate.theta().float().unwrap() - std::f64::consts::FRAC_PI_2).abs() < 1e-12)
+            }
+            Operation::RotateZ(_) | Operation::ControlledPauliZ(_) => (),
+            _ => panic!("Unexpected operation {}", operation.hqslang()),
+        }
+    }
+}
+
+#[test]
+fn test_transpile_with_rules_template() {
+    let mut circuit = Circuit::new();
+    circuit += ISwap::new(1, 0);
+    let device: AWSDevice = RigettiAspenM3Device::new().into();
+
+    let rules = DecompositionRegistry::new();
+    assert_eq!(
+        transpile_with_rules(&circuit, &device, &rules).unwrap(),
+        to_rigetti_native(&circuit).unwrap()
+    );
+
+    let mut rules = DecompositionRegistry::new();
+    let mut template = Circuit::new();
+    template += XY::new(0, 1, std::f64::consts::PI.into());
+    rules.add_template("ISwap", template);
+    assert_eq!(rules.gates(), vec!["ISwap".to_string()]);
+    let mut expected = Circuit::new();
+    expected += XY::new(1, 0, std::f64::consts::PI.into());
+    assert_eq!(
+        transpile_with_rules(&circuit, &device, &rules).unwrap(),
+        expected
+    );
+
+    let mut template = Circuit::new();
+    template += XY::new(0, 2, std::f64::consts::PI.into());
+    rules.add_template("ISwap", template);
+    assert!(transpile_with_rules(&circuit, &device, &rules).is_err());
+    assert!(rules.remove_rule("ISwap"));
+    assert!(!rules.remove_rule("ISwap"));
+}
+
+#[test]
+fn test_transpile_with_rules_cnot() {
+    // The rule replaces the CNOTs of the built-in SWAP decomposition as well
+    let mut rules = DecompositionRegistry::new();
+    rules.add_rule("CNOT", |operation| {
+        let qubits = match operation {
+            Operation::CNOT(gate) => (*gate.control(), *gate.target()),
+            _ => unreachable!(),
+        };
+        let mut replacement = Circuit::new();
+        replacement += Hadamard::new(qubits.1);
+        replacement += ControlledPauliZ::new(qubits.0, qubits.1);
+        replacement += Hadamard::new(qubits.1);
+        Ok(replacement)
+    });
+    let mut circuit = Circuit::new();
+    circuit += SWAP::new(0, 1);
+    let mut expected = Circuit::new();
+    for (control, target) in [(0, 1), (1, 0), (0, 1)] {
+        expected += Hadamard::new(target);
+        expected += ControlledPauliZ::new(control, target);
+        expected += Hadamard::new(target);
+    }
+    let device: AWSDevice = IonQAria1Device::new().into();
+    assert_eq!(
+        transpile_with_rules(&circuit, &device, &rules).unwrap(),
+        to_ionq_native(&expected).unwrap()
+    );
+
+    rules.add_rule("SWAP", |_| {
+        Err(roqoqo::RoqoqoError::GenericError {
+            msg: "no SWAP".to_string(),
+        })
+    });
+    assert!(transpile_with_rules(&circuit, &device, &rules).is_err());
+}
+
+#[test]
+fn test_optimize_virtual_z_merges_rotations() {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.5.into());
+    circuit += ControlledPauliZ::new(0, 1);
+    circuit += RotateZ::new(0, 0.25.into());
+    circuit += SqrtPauliX::new(0);
+    circuit += RotateZ::new(1, 1.0.into());
+    circuit += RotateZ::new(1, (-1.0).into());
+    let optimized = optimize_virtual_z(&circuit);
+    let mut expected = Circuit::new();
+    expected += ControlledPauliZ::new(0, 1);
+    expected += RotateZ::new(0, 0.75.into());
+    expected += SqrtPauliX::new(0);
+    assert_eq!(optimized, expected);
+}
+
+#[test]
+fn test_optimize_virtual_z_shifts_phases() {
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(0, 0.5.into());
+    circuit += GPi2::new(0, 0.25.into());
+    circuit += GPi::new(0, 0.0.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    let optimized = optimize_virtual_z(&circuit);
+    let mut expected = Circuit::new();
+    expected += GPi2::new(0, (-0.25).into());
+    expected += GPi::new(0, (-0.5).into());
+    expected += RotateZ::new(0, 0.5.into());
+    expected += MolmerSorensenXX::new(0, 1);
+    assert_eq!(optimized, expected);
+}
+
+#[test]
+fn test_optimize_virtual_z_measurement_and_symbolic() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateZ::new(0, "theta".into());
+    circuit += RotateZ::new(1, 0.3.into());
+    circuit += MeasureQubit::new(1, "ro".to_string(), 1);
+    circuit += RotateX::new(0, 0.1.into());
+    let optimized = optimize_virtual_z(&circuit);
+    let mut expected = Circuit::new();
+    expected += DefinitionBit::new("ro".to_string(), 2, true);
+    expected += MeasureQubit::new(1, "ro".to_string(), 1);
+    expected += RotateZ::new(0, "theta".into());
+    expected += RotateX::new(0, 0.1.into());
+    assert_eq!(optimized, expected);
+}
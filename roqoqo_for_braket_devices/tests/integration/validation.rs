@@ -0,0 +1,201 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::*;
+
+#[test]
+fn test_validate_circuit_valid() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 2, true);
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += SqrtPauliX::new(1);
+    circuit += EchoCrossResonance::new(0, 1);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    assert_eq!(
+        AWSDevice::from(OQCLucyDevice::new()).validate_circuit(&circuit),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_validate_circuit_errors() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += EchoCrossResonance::new(0, 4);
+    circuit += PragmaDamping::new(0, 1.0.into(), 0.1.into());
+    circuit += RotateZ::new(9, 1.0.into());
+    let errors = AWSDevice::from(OQCLucyDevice::new())
+        .validate_circuit(&circuit)
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            ValidationError::TooManyQubits {
+                number_qubits: 10,
+                device_qubits: 8
+            },
+            ValidationError::NonNativeGate {
+                index: 0,
+                gate: "Hadamard".to_string()
+            },
+            ValidationError::UnconnectedQubits {
+                index: 1,
+                gate: "EchoCrossResonance".to_string(),
+                control: 0,
+                target: 4
+            },
+            ValidationError::UnsupportedOperation {
+                index: 2,
+                operation: "PragmaDamping".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "Circuit uses 10 qubits but the device only has 8"
+    );
+}
+
+#[test]
+fn test_validate_circuit_disabled_qubit() {
+    let mut device = IonQHarmonyDevice::new();
+    device.disable_qubit(2).unwrap();
+    let mut circuit = Circuit::new();
+    circuit += RotateZ::new(2, 1.0.into());
+    circuit += MolmerSorensenXX::new(0, 1);
+    let errors = AWSDevice::from(device)
+        .validate_circuit(&circuit)
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![ValidationError::UnavailableGate {
+            index: 0,
+            gate: "RotateZ".to_string(),
+            qubits: vec![2]
+        }]
+    );
+}
+
+#[test]
+fn test_validate_circuit_sleep() {
+    let mut circuit = Circuit::new();
+    circuit += PragmaSleep::new(vec![0, 1], 1e-6.into());
+    assert_eq!(
+        AWSDevice::from(RigettiAspenM3Device::new()).validate_circuit(&circuit),
+        Ok(())
+    );
+    assert_eq!(
+        AWSDevice::from(IonQAria1Device::new()).validate_circuit(&circuit),
+        Err(vec![ValidationError::UnsupportedOperation {
+            index: 0,
+            operation: "PragmaSleep".to_string()
+        }])
+    );
+}
+
+#[test]
+fn test_validate_circuit_mid_circuit_measurement() {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += RotateZ::new(0, 1.0.into());
+    circuit += RotateZ::new(1, 1.0.into());
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    assert!(device.supports_mid_circuit_measurement());
+    assert_eq!(device.validate_circuit(&circuit), Ok(()));
+
+    let device = AWSDevice::from(OQCLucyDevice::new());
+    assert!(!device.supports_mid_circuit_measurement());
+    assert_eq!(
+        device.validate_circuit(&circuit),
+        Err(vec![ValidationError::MidCircuitMeasurement {
+            index: 2,
+            gate: "RotateZ".to_string(),
+            qubit: 0
+        }])
+    );
+}
+
+#[test]
+fn test_validate_circuit_conditional() {
+    let mut conditioned = Circuit::new();
+    conditioned += RotateX::new(1, 1.0.into());
+    conditioned += Hadamard::new(1);
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaConditional::new("ro".to_string(), 0, conditioned);
+
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    assert!(device.supports_feedforward());
+    assert_eq!(
+        device.validate_circuit(&circuit),
+        Err(vec![ValidationError::NonNativeGate {
+            index: 1,
+            gate: "Hadamard".to_string()
+        }])
+    );
+
+    let device = AWSDevice::from(IonQHarmonyDevice::new());
+    assert!(!device.supports_feedforward());
+    assert_eq!(
+        device.validate_circuit(&circuit),
+        Err(vec![ValidationError::UnsupportedOperation {
+            index: 2,
+            operation: "PragmaConditional".to_string()
+        }])
+    );
+}
+
+#[test]
+fn test_validate_shots() {
+    let device = AWSDevice::from(RigettiAspenM3Device::new());
+    assert_eq!(device.shots_range(), (10, 100000));
+    assert_eq!(device.validate_shots(10), Ok(()));
+    assert_eq!(
+        device.validate_shots(0),
+        Err(ValidationError::InvalidShots {
+            shots: 0,
+            minimum: 10,
+            maximum: 100000
+        })
+    );
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 200000, None);
+    assert_eq!(
+        device.validate_circuit(&circuit),
+        Err(vec![ValidationError::InvalidShots {
+            shots: 200000,
+            minimum: 10,
+            maximum: 100000
+        }])
+    );
+    assert!(roqoqo_for_braket_devices::braket_ir::circuit_to_openqasm3(&circuit, &device).is_err());
+
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 1, true);
+    circuit += MeasureQubit::new(0, "ro".to_string(), 0);
+    circuit += PragmaSetNumberOfMeasurements::new(100, "ro".to_string());
+    circuit += PragmaSetNumberOfMeasurements::new(200, "ro".to_string());
+    match device.validate_circuit(&circuit) {
+        Err(errors) => assert!(matches!(
+            errors.as_slice(),
+            [ValidationError::InvalidMeasurements { .. }]
+        )),
+        Ok(()) => panic!("Circuit with different numbers of shots is valid"),
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright © 2023 HQS Quantum Simulations GmbH. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except
+// in compliance with the License. You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the
+// License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either
+// express or implied. See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use roqoqo::operations::*;
+use roqoqo::Circuit;
+use roqoqo_for_braket_devices::routing::{initial_layout, route_circuit};
+use roqoqo_for_braket_devices::transpile::*;
+use roqoqo_for_braket_devices::verification::DEFAULT_NUMBER_SAMPLES;
+use roqoqo_for_braket_devices::*;
+
+fn test_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    circuit += DefinitionBit::new("ro".to_string(), 3, true);
+    circuit += Hadamard::new(0);
+    circuit += RotateY::new(1, 0.3.into());
+    circuit += CNOT::new(0, 1);
+    circuit += ControlledPhaseShift::new(1, 2, 0.4.into());
+    circuit += ISwap::new(2, 0);
+    circuit += SWAP::new(0, 1);
+    circuit += PragmaRepeatedMeasurement::new("ro".to_string(), 100, None);
+    circuit
+}
+
+#[test]
+fn test_check_equivalence_transpiled() {
+    let circuit = test_circuit();
+    for transpiled in [
+        to_ionq_native(&circuit).unwrap(),
+        to_oqc_native(&circuit).unwrap(),
+        to_rigetti_native_verbatim(&circuit).unwrap(),
+    ] {
+        let check = check_equivalence(&circuit, &transpiled, None, None, None).unwrap();
+        assert!(check.exact);
+        assert_eq!(check.number_states, 8);
+        assert!(check.is_equivalent(1e-10));
+    }
+
+    let mut transpiled = to_ionq_native(&circuit).unwrap();
+    transpiled += PauliX::new(2);
+    let check = check_equivalence(&circuit, &transpiled, None, None, None).unwrap();
+    assert!(check.fidelity_estimate < 1e-10);
+    assert!(!check.is_equivalent(1e-3));
+}
+
+#[test]
+fn test_check_equivalence_routed() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    circuit += CNOT::new(0, 1);
+    circuit += CNOT::new(1, 2);
+    circuit += RotateX::new(2, 0.7.into());
+    circuit += CNOT::new(2, 0);
+    let device: AWSDevice = OQCLucyDevice::new().into();
+    let initial = initial_layout(&circuit, &device).unwrap();
+    let (routed, layout) = route_circuit(&circuit, &device).unwrap();
+    assert!(routed.iter().any(|operation| operation.hqslang() == "SWAP"));
+
+    let check = check_equivalence(&circuit, &routed, Some(&initial), Some(&layout), None).unwrap();
+    assert!(check.is_equivalent(1e-10));
+    let check = check_equivalence(&circuit, &routed, Some(&initial), Some(&initial), None).unwrap();
+    assert!(!check.is_equivalent(1e-3));
+}
+
+#[test]
+fn test_check_equivalence_sampled() {
+    let mut circuit = Circuit::new();
+    circuit += Hadamard::new(0);
+    for qubit in 0..9 {
+        circuit += CNOT::new(qubit, qubit + 1);
+        circuit += RotateZ::new(qubit + 1, 0.1.into());
+    }
+    let transpiled = to_rigetti_native(&circuit).unwrap();
+    let check = check_equivalence(&circuit, &transpiled, None, None, None).unwrap();
+    assert!(!check.exact);
+    assert_eq!(check.number_states, DEFAULT_NUMBER_SAMPLES);
+    assert!(check.is_equivalent(1e-10));
+    let check = check_equivalence(&circuit, &transpiled, None, None, Some(4)).unwrap();
+    assert_eq!(check.number_states, 4);
+    assert!(check.is_equivalent(1e-10));
+    assert!(check_equivalence(&circuit, &transpiled, None, None, Some(0)).is_err());
+
+    let mut wrong = transpiled.clone();
+    wrong += RotateX::new(9, 1.0.into());
+    let check = check_equivalence(&circuit, &wrong, None, None, None).unwrap();
+    assert!(check.fidelity_estimate < 1.0 - 1e-6);
+}
+
+#[test]
+fn test_check_equivalence_errors() {
+    let circuit = test_circuit();
+    let transpiled = to_ionq_native(&circuit).unwrap();
+    let layout: HashMap<usize, usize> = [(0, 0), (1, 1)].into_iter().collect();
+    assert!(check_equivalence(&circuit, &transpiled, Some(&layout), None, None).is_err());
+
+    let mut symbolic = Circuit::new();
+    symbolic += RotateX::new(0, "theta".into());
+    assert!(check_equivalence(&symbolic, &symbolic, None, None, None).is_err());
+
+    let mut large = Circuit::new();
+    large += PauliX::new(30);
+    assert!(check_equivalence(&large, &large, None, None, None).is_err());
+}